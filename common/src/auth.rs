@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, get_current_timestamp};
+use serde::Deserialize;
+use tonic::{Request, Status};
+use uuid::Uuid;
+
+/// The authenticated user a request acts for, taken from the token the
+/// gateway forwards in the `authorization` metadata.
+#[derive(Debug, Clone)]
+pub struct Caller {
+    pub user_id: Uuid,
+    pub role: String,
+    /// The admin acting as `user_id`, on impersonation tokens only.
+    pub act_as: Option<Uuid>,
+    pub token_version: i32,
+}
+
+impl Caller {
+    pub fn is_admin(&self) -> bool {
+        self.role == "admin"
+    }
+
+    /// An admin acting with their own authority: impersonation tokens carry
+    /// the target's role, but never an admin's.
+    pub fn is_acting_admin(&self) -> bool {
+        self.is_admin() && self.act_as.is_none()
+    }
+}
+
+/// Allowed clock drift between the token issuer and this service, applied
+/// to `exp`, `nbf` and `iat`. Override with `JWT_LEEWAY_SECS`.
+const DEFAULT_JWT_LEEWAY_SECS: u64 = 60;
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    role: String,
+    #[serde(default)]
+    iat: Option<u64>,
+    #[serde(default)]
+    act_as: Option<String>,
+    #[serde(default)]
+    token_version: i32,
+}
+
+#[derive(Clone)]
+pub struct CallerVerifier {
+    decoding_key: Arc<DecodingKey>,
+    validation: Validation,
+}
+
+impl CallerVerifier {
+    pub fn new(secret: &str, leeway_secs: u64) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = leeway_secs;
+        validation.validate_nbf = true;
+
+        Self {
+            decoding_key: Arc::new(DecodingKey::from_secret(secret.as_bytes())),
+            validation,
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        let secret = std::env::var("JWT_SECRET")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| "JWT_SECRET must be set".to_string())?;
+        let leeway = match std::env::var("JWT_LEEWAY_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .map_err(|_| "JWT_LEEWAY_SECS must be a non-negative integer".to_string())?,
+            Err(_) => DEFAULT_JWT_LEEWAY_SECS,
+        };
+
+        Ok(Self::new(&secret, leeway))
+    }
+
+    /// Verifies the bearer token in the request metadata, if any, and stores
+    /// the resulting `Caller` in the request extensions. A missing token is
+    /// not an error here; handlers that need a caller use `require_caller`.
+    #[allow(clippy::result_large_err)]
+    pub fn authenticate<T>(&self, request: &mut Request<T>) -> Result<(), Status> {
+        let Some(value) = request.metadata().get("authorization") else {
+            return Ok(());
+        };
+
+        let token = value
+            .to_str()
+            .ok()
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("Malformed authorization metadata"))?;
+
+        let claims = decode::<Claims>(token, &self.decoding_key, &self.validation)
+            .map_err(|_| Status::unauthenticated("Invalid or expired token"))?
+            .claims;
+
+        if claims.iat.is_some_and(|iat| iat > get_current_timestamp() + self.validation.leeway) {
+            return Err(Status::unauthenticated("Invalid or expired token"));
+        }
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::unauthenticated("Token subject is not a valid user id"))?;
+        let act_as = claims
+            .act_as
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(|_| Status::unauthenticated("Token act_as is not a valid user id"))?;
+
+        request.extensions_mut().insert(Caller {
+            user_id,
+            role: claims.role,
+            act_as,
+            token_version: claims.token_version,
+        });
+
+        Ok(())
+    }
+}
+
+impl tonic::service::Interceptor for CallerVerifier {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        self.authenticate(&mut request)?;
+        Ok(request)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+pub fn require_caller<T>(request: &Request<T>) -> Result<Caller, Status> {
+    request
+        .extensions()
+        .get::<Caller>()
+        .cloned()
+        .ok_or_else(|| Status::unauthenticated("Missing caller token"))
+}
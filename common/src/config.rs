@@ -0,0 +1,12 @@
+//! Helpers for reading service settings from the environment.
+
+/// Parses `name` from the environment, falling back to `default` when it
+/// is unset.
+pub fn env_or<T: std::str::FromStr>(name: &str, default: T) -> Result<T, String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map_err(|_| format!("{} has an invalid value: {}", name, value)),
+        Err(_) => Ok(default),
+    }
+}
@@ -0,0 +1,92 @@
+//! Timing for repository calls. Each call runs in a `db` span named after
+//! the `db` function, nested under the `rpc` span `RpcLogLayer` opens for
+//! the request, and ends with one event carrying its duration. Both use
+//! the `db` target at debug level, so `RUST_LOG=info,db=debug` turns them
+//! on without touching the access log.
+
+use std::future::Future;
+use std::time::Instant;
+
+use tracing::Instrument;
+
+pub async fn timed<F: Future>(query: &'static str, call: F) -> F::Output {
+    let span = tracing::debug_span!(target: "db", "db", query);
+    async move {
+        let started = Instant::now();
+        let output = call.await;
+        let elapsed_us = started.elapsed().as_micros() as u64;
+        tracing::debug!(target: "db", query, elapsed_us, "query finished");
+        output
+    }
+    .instrument(span)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use tonic::codegen::http;
+    use tower_layer::Layer;
+    use tower_service::Service;
+
+    use super::*;
+    use crate::rpc_log::tests::CapturedLogs;
+    use crate::rpc_log::{RpcLogLayer, REQUEST_ID_KEY};
+
+    /// A handler making two timed queries, the way the repositories do.
+    #[derive(Clone)]
+    struct TwoQueries;
+
+    impl Service<http::Request<()>> for TwoQueries {
+        type Response = http::Response<()>;
+        type Error = Infallible;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: http::Request<()>) -> Self::Future {
+            Box::pin(async {
+                timed("get_game_by_id", async {}).await;
+                timed("list_top_reviews", async {}).await;
+                Ok(http::Response::new(()))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn timed_calls_pass_their_output_through_and_log_the_elapsed_time() {
+        let logs = CapturedLogs::start();
+        assert_eq!(timed("count_users", async { 7 }).await, 7);
+
+        let lines = logs.lines();
+        assert_eq!(lines.len(), 1, "{lines:?}");
+        let line = &lines[0];
+        assert!(line.contains(" DEBUG db{query=\"count_users\"}: db: query finished"), "{line}");
+        assert!(line.contains("elapsed_us="), "{line}");
+    }
+
+    #[tokio::test]
+    async fn each_query_is_timed_inside_the_request_span() {
+        let logs = CapturedLogs::start();
+        let request = http::Request::builder()
+            .uri("/game.GameService/GetGamePage")
+            .header(REQUEST_ID_KEY, "req-7")
+            .body(())
+            .unwrap();
+        RpcLogLayer.layer(TwoQueries).call(request).await.unwrap();
+
+        let lines = logs.lines();
+        assert_eq!(lines.len(), 3, "{lines:?}");
+        let rpc = "rpc{method=/game.GameService/GetGamePage request_id=req-7}";
+        for (line, query) in lines.iter().zip(["get_game_by_id", "list_top_reviews"]) {
+            assert!(line.contains(&format!("{rpc}:db{{query=\"{query}\"}}: db: query finished")), "{line}");
+            assert!(line.contains("elapsed_us="), "{line}");
+        }
+        assert!(lines[2].contains("rpc_access"), "{}", lines[2]);
+    }
+}
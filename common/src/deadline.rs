@@ -0,0 +1,153 @@
+//! The caller's deadline, read from the standard `grpc-timeout` metadata, so
+//! a handler can stop before expensive work once nobody is waiting for the
+//! answer any more.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use tonic::body::BoxBody;
+use tonic::codegen::http;
+use tonic::metadata::MetadataMap;
+use tonic::Status;
+use tower_layer::Layer;
+use tower_service::Service;
+
+const GRPC_TIMEOUT_KEY: &str = "grpc-timeout";
+
+/// When the caller gives up. Requests without a (parseable) `grpc-timeout`
+/// have no deadline and `check` always passes.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline(Option<Instant>);
+
+impl Deadline {
+    /// Measured from now, so call it as soon as the request arrives.
+    pub fn from_metadata(metadata: &MetadataMap) -> Self {
+        let timeout = metadata
+            .get(GRPC_TIMEOUT_KEY)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_grpc_timeout);
+        Deadline(timeout.map(|t| Instant::now() + t))
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn check(&self) -> Result<(), Status> {
+        match self.0 {
+            Some(deadline) if Instant::now() >= deadline => {
+                Err(Status::deadline_exceeded("Deadline exceeded before the request was processed"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Answers `DEADLINE_EXCEEDED` straight away when a call arrives with no time
+/// left. Without it tonic only notices once its own timer fires, after the
+/// request body has been read, and reports the call as `CANCELLED`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeadlineLayer;
+
+impl<S> Layer<S> for DeadlineLayer {
+    type Service = RejectExpired<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RejectExpired { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RejectExpired<S> {
+    inner: S,
+}
+
+impl<S, ReqBody> Service<http::Request<ReqBody>> for RejectExpired<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        let expired = req
+            .headers()
+            .get(GRPC_TIMEOUT_KEY)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_grpc_timeout)
+            .is_some_and(|timeout| timeout.is_zero());
+
+        if expired {
+            let response = Status::deadline_exceeded("Deadline expired before the request arrived").into_http();
+            return Box::pin(async move { Ok(response) });
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
+
+/// `grpc-timeout` is at most eight digits followed by a unit: H, M, S, m
+/// (millis), u (micros) or n (nanos).
+fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    if value.len() < 2 || value.len() > 9 {
+        return None;
+    }
+    let (amount, unit) = value.split_at(value.len() - 1);
+    if !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: u64 = amount.parse().ok()?;
+
+    match unit {
+        "H" => Some(Duration::from_secs(amount * 60 * 60)),
+        "M" => Some(Duration::from_secs(amount * 60)),
+        "S" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_millis(amount)),
+        "u" => Some(Duration::from_micros(amount)),
+        "n" => Some(Duration::from_nanos(amount)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(timeout: &str) -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        metadata.insert(GRPC_TIMEOUT_KEY, timeout.parse().unwrap());
+        metadata
+    }
+
+    #[test]
+    fn every_unit_parses() {
+        assert_eq!(parse_grpc_timeout("2H"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_grpc_timeout("3M"), Some(Duration::from_secs(180)));
+        assert_eq!(parse_grpc_timeout("5S"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_grpc_timeout("250m"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_grpc_timeout("7u"), Some(Duration::from_micros(7)));
+        assert_eq!(parse_grpc_timeout("99999999n"), Some(Duration::from_nanos(99_999_999)));
+    }
+
+    #[test]
+    fn malformed_timeouts_are_ignored() {
+        for value in ["", "S", "5", "5s", "-5S", "+5S", "123456789S", "1.5S"] {
+            assert_eq!(parse_grpc_timeout(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn check_fails_only_once_the_deadline_passes() {
+        assert!(Deadline::from_metadata(&MetadataMap::new()).check().is_ok());
+        assert!(Deadline::from_metadata(&metadata("junk")).check().is_ok());
+        assert!(Deadline::from_metadata(&metadata("1H")).check().is_ok());
+
+        let err = Deadline::from_metadata(&metadata("0m")).check().unwrap_err();
+        assert_eq!(err.code(), tonic::Code::DeadlineExceeded);
+    }
+}
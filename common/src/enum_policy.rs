@@ -0,0 +1,55 @@
+//! What the services do with a proto enum integer that has no variant.
+
+use crate::config::env_or;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnumPolicy {
+    /// Reject unknown values instead of falling back to the default variant.
+    pub strict: bool,
+}
+
+impl EnumPolicy {
+    pub fn from_env() -> Result<Self, String> {
+        Ok(Self {
+            strict: env_or("STRICT_ENUMS", false)?,
+        })
+    }
+
+    /// `parsed` is `value` looked up in the enum. An unknown value is an
+    /// error in strict mode; otherwise it becomes `fallback`, with a warning
+    /// so the bad value still shows up somewhere.
+    pub fn resolve<T>(&self, field: &str, value: i32, parsed: Option<T>, fallback: T) -> Result<T, String> {
+        if let Some(known) = parsed {
+            return Ok(known);
+        }
+        if self.strict {
+            return Err(format!("Unknown {} value: {}", field, value));
+        }
+        tracing::warn!(field, value, "unknown enum value; using the default");
+        Ok(fallback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LENIENT: EnumPolicy = EnumPolicy { strict: false };
+    const STRICT: EnumPolicy = EnumPolicy { strict: true };
+
+    #[test]
+    fn known_values_pass_in_either_mode() {
+        assert_eq!(LENIENT.resolve("role", 2, Some("developer"), "player"), Ok("developer"));
+        assert_eq!(STRICT.resolve("role", 2, Some("developer"), "player"), Ok("developer"));
+    }
+
+    #[test]
+    fn unknown_values_fall_back_unless_strict() {
+        assert_eq!(LENIENT.resolve("role", 42, None, "player"), Ok("player"));
+        assert_eq!(EnumPolicy::default().resolve("role", 42, None, "player"), Ok("player"));
+        assert_eq!(
+            STRICT.resolve("category", -1, None::<&str>, "unspecified"),
+            Err("Unknown category value: -1".to_string())
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Minimal implementation of the gRPC rich error model: an `ErrorInfo` packed
+//! into a `google.rpc.Status` and carried in `tonic::Status::details`.
+
+use std::collections::HashMap;
+
+use prost::Message;
+
+const ERROR_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.ErrorInfo";
+
+/// Mirrors `google.rpc.ErrorInfo`.
+#[derive(Clone, PartialEq, Message)]
+pub struct ErrorInfo {
+    #[prost(string, tag = "1")]
+    pub reason: String,
+    #[prost(string, tag = "2")]
+    pub domain: String,
+    #[prost(map = "string, string", tag = "3")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl ErrorInfo {
+    pub fn new(reason: impl Into<String>, domain: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            domain: domain.into(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Mirrors `google.rpc.Status`.
+#[derive(Clone, PartialEq, Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<prost_types::Any>,
+}
+
+pub fn status_with_error_info(
+    code: tonic::Code,
+    message: impl Into<String>,
+    info: ErrorInfo,
+) -> tonic::Status {
+    let message = message.into();
+    let details = RpcStatus {
+        code: code as i32,
+        message: message.clone(),
+        details: vec![prost_types::Any {
+            type_url: ERROR_INFO_TYPE_URL.to_string(),
+            value: info.encode_to_vec(),
+        }],
+    };
+
+    tonic::Status::with_details(code, message, details.encode_to_vec().into())
+}
+
+pub fn error_info(status: &tonic::Status) -> Option<ErrorInfo> {
+    if status.details().is_empty() {
+        return None;
+    }
+
+    RpcStatus::decode(status.details())
+        .ok()?
+        .details
+        .into_iter()
+        .find(|any| any.type_url == ERROR_INFO_TYPE_URL)
+        .and_then(|any| ErrorInfo::decode(any.value.as_slice()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> ErrorInfo {
+        ErrorInfo::new("EMAIL_TAKEN", "user-service").with_metadata("field", "email")
+    }
+
+    #[test]
+    fn error_info_round_trips_through_status_details() {
+        let status = status_with_error_info(tonic::Code::AlreadyExists, "Email already in use", info());
+
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+        assert_eq!(status.message(), "Email already in use");
+        assert_eq!(error_info(&status), Some(info()));
+    }
+
+    #[test]
+    fn error_info_survives_the_grpc_trailers() {
+        let status = status_with_error_info(tonic::Code::AlreadyExists, "Email already in use", info());
+
+        let response = status.into_http();
+        let received = tonic::Status::from_header_map(response.headers()).unwrap();
+
+        assert_eq!(received.code(), tonic::Code::AlreadyExists);
+        assert_eq!(error_info(&received), Some(info()));
+    }
+
+    #[test]
+    fn statuses_without_error_info_have_none() {
+        assert_eq!(error_info(&tonic::Status::not_found("missing")), None);
+
+        let garbage = tonic::Status::with_details(tonic::Code::Internal, "boom", vec![0xff, 0xff, 0xff].into());
+        assert_eq!(error_info(&garbage), None);
+
+        let other = RpcStatus {
+            code: tonic::Code::Internal as i32,
+            message: "boom".to_string(),
+            details: vec![prost_types::Any {
+                type_url: "type.googleapis.com/google.rpc.RetryInfo".to_string(),
+                value: vec![],
+            }],
+        };
+        let status = tonic::Status::with_details(tonic::Code::Internal, "boom", other.encode_to_vec().into());
+        assert_eq!(error_info(&status), None);
+    }
+}
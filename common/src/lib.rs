@@ -65,12 +65,140 @@ pub mod models {
         pub status: Option<GameStatus>,
     }
 
-    #[derive(Debug, Clone, Serialize, Deserialize)]
+    /// Canonical game status. The snake_case spelling is shared by the HTTP
+    /// API (serde), the `game_status` Postgres enum (strum) and the proto
+    /// integers via `to_proto`/`from_proto`.
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        Hash,
+        Serialize,
+        Deserialize,
+        strum::Display,
+        strum::EnumString,
+        strum::EnumIter,
+    )]
     #[serde(rename_all = "snake_case")]
+    #[strum(serialize_all = "snake_case")]
     pub enum GameStatus {
+        Unspecified,
         Draft,
+        UnderReview,
         Published,
-        Archived,
+        Suspended,
+    }
+
+    impl GameStatus {
+        /// Every status a stored game can be in. `Unspecified` is only the
+        /// proto's "not set" value and is never accepted as input, so asking
+        /// for "no status filter" means leaving the status out altogether.
+        pub const SELECTABLE: [GameStatus; 4] = [
+            GameStatus::Draft,
+            GameStatus::UnderReview,
+            GameStatus::Published,
+            GameStatus::Suspended,
+        ];
+
+        /// Parses a status a caller asked for, refusing `unspecified`.
+        pub fn parse_selectable(value: &str) -> Option<Self> {
+            value
+                .parse::<Self>()
+                .ok()
+                .filter(|status| *status != GameStatus::Unspecified)
+        }
+
+        /// `from_proto` for statuses a caller asked for: `None` for 0 as well
+        /// as for undefined values.
+        pub fn from_proto_selectable(value: i32) -> Option<Self> {
+            Self::from_proto(value).filter(|status| *status != GameStatus::Unspecified)
+        }
+
+        pub fn to_proto(self) -> i32 {
+            match self {
+                GameStatus::Unspecified => 0,
+                GameStatus::Draft => 1,
+                GameStatus::UnderReview => 2,
+                GameStatus::Published => 3,
+                GameStatus::Suspended => 4,
+            }
+        }
+
+        /// `None` for integers the proto enum doesn't define.
+        pub fn from_proto(value: i32) -> Option<Self> {
+            match value {
+                0 => Some(GameStatus::Unspecified),
+                1 => Some(GameStatus::Draft),
+                2 => Some(GameStatus::UnderReview),
+                3 => Some(GameStatus::Published),
+                4 => Some(GameStatus::Suspended),
+                _ => None,
+            }
+        }
+
+        /// Display name for a proto value, with `"unknown"` for values outside
+        /// the enum.
+        pub fn proto_name(value: i32) -> String {
+            Self::from_proto(value).map_or_else(|| "unknown".to_string(), |s| s.to_string())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use strum::IntoEnumIterator;
+
+        use super::*;
+
+        #[test]
+        fn every_status_round_trips_through_proto_text_and_json() {
+            for status in GameStatus::iter() {
+                assert_eq!(GameStatus::from_proto(status.to_proto()), Some(status));
+
+                let text = status.to_string();
+                assert_eq!(text.parse::<GameStatus>().unwrap(), status);
+
+                let json = serde_json::to_string(&status).unwrap();
+                assert_eq!(json, format!("\"{}\"", text));
+                assert_eq!(serde_json::from_str::<GameStatus>(&json).unwrap(), status);
+            }
+        }
+
+        #[test]
+        fn statuses_are_spelled_snake_case() {
+            let spelled: Vec<String> = GameStatus::iter().map(|s| s.to_string()).collect();
+            assert_eq!(spelled, ["unspecified", "draft", "under_review", "published", "suspended"]);
+        }
+
+        #[test]
+        fn proto_values_outside_the_enum_are_unknown() {
+            for value in [-1, 5, i32::MAX] {
+                assert_eq!(GameStatus::from_proto(value), None);
+                assert_eq!(GameStatus::from_proto_selectable(value), None);
+                assert_eq!(GameStatus::proto_name(value), "unknown");
+            }
+            assert_eq!(GameStatus::proto_name(2), "under_review");
+        }
+
+        #[test]
+        fn unspecified_exists_but_is_never_selectable() {
+            assert_eq!(GameStatus::from_proto(0), Some(GameStatus::Unspecified));
+            assert_eq!(GameStatus::from_proto_selectable(0), None);
+            assert_eq!(GameStatus::parse_selectable("unspecified"), None);
+            assert!(!GameStatus::SELECTABLE.contains(&GameStatus::Unspecified));
+            assert_eq!(GameStatus::SELECTABLE.len(), GameStatus::iter().count() - 1);
+        }
+
+        #[test]
+        fn other_spellings_are_refused() {
+            for text in ["unknown", "UnderReview", "under-review", "UNDER_REVIEW", ""] {
+                assert!(text.parse::<GameStatus>().is_err(), "{text}");
+                assert_eq!(GameStatus::parse_selectable(text), None);
+            }
+            assert!(serde_json::from_str::<GameStatus>("\"UnderReview\"").is_err());
+            assert!(serde_json::from_str::<GameStatus>("2").is_err());
+        }
     }
 }
 
@@ -88,6 +216,17 @@ pub mod utils {
     }
 }
 
+pub mod auth;
+pub mod config;
+pub mod db_timing;
+pub mod deadline;
+pub mod enum_policy;
+pub mod error_details;
+pub mod money;
+pub mod pagination;
+pub mod rpc_log;
+pub mod timestamp;
+
 pub mod errors {
     use std::fmt;
 
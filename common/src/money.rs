@@ -0,0 +1,251 @@
+//! Integer money amounts shared by the proto (`i64` minor units), the DB
+//! (`NUMERIC(10, 2)`) and the HTTP API (decimal strings such as `"9.99"`).
+
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Currency {
+    #[default]
+    Usd,
+}
+
+impl Currency {
+    pub const ALL: &'static [Currency] = &[Currency::Usd];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+        }
+    }
+
+    /// Number of digits after the decimal point, e.g. 2 for cents.
+    pub fn minor_unit_digits(&self) -> u32 {
+        match self {
+            Currency::Usd => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    TooPrecise(String),
+    OutOfRange(String),
+    Negative(String),
+    Invalid(String),
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::TooPrecise(value) => {
+                write!(f, "Amount {} has more than 2 decimal places", value)
+            }
+            MoneyError::OutOfRange(value) => write!(f, "Amount {} is out of range", value),
+            MoneyError::Negative(value) => write!(f, "Amount {} must not be negative", value),
+            MoneyError::Invalid(value) => write!(f, "Invalid amount: {}", value),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+/// An amount in integer minor units (cents). Every service stores USD, so the
+/// currency is fixed for now and is not part of the serialized form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Money {
+    minor_units: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub const fn from_minor_units(minor_units: i64) -> Self {
+        Self {
+            minor_units,
+            currency: Currency::Usd,
+        }
+    }
+
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn to_decimal(&self) -> Decimal {
+        Decimal::new(self.minor_units, self.currency.minor_unit_digits())
+    }
+}
+
+/// Proto representation: minor units.
+impl From<i64> for Money {
+    fn from(minor_units: i64) -> Self {
+        Self::from_minor_units(minor_units)
+    }
+}
+
+impl From<Money> for i64 {
+    fn from(money: Money) -> Self {
+        money.minor_units
+    }
+}
+
+/// DB representation. Fails rather than rounding when the value has more
+/// precision than the currency allows. Prices, sale prices, filters and facet
+/// bounds are never negative, so negative amounts are rejected here, which
+/// also covers parsing and deserializing.
+impl TryFrom<Decimal> for Money {
+    type Error = MoneyError;
+
+    fn try_from(value: Decimal) -> Result<Self, Self::Error> {
+        if value.is_sign_negative() && !value.is_zero() {
+            return Err(MoneyError::Negative(value.to_string()));
+        }
+
+        let scale = Decimal::from(10i64.pow(Currency::Usd.minor_unit_digits()));
+        let minor = value
+            .checked_mul(scale)
+            .ok_or_else(|| MoneyError::OutOfRange(value.to_string()))?;
+
+        if !minor.fract().is_zero() {
+            return Err(MoneyError::TooPrecise(value.to_string()));
+        }
+
+        minor
+            .to_i64()
+            .map(Self::from_minor_units)
+            .ok_or_else(|| MoneyError::OutOfRange(value.to_string()))
+    }
+}
+
+impl From<Money> for Decimal {
+    fn from(money: Money) -> Self {
+        money.to_decimal()
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_decimal())
+    }
+}
+
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = Decimal::from_str(s.trim()).map_err(|_| MoneyError::Invalid(s.to_string()))?;
+        Money::try_from(value)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Accepts `"9.99"` as well as bare JSON numbers (`9.99`, `10`), all in major
+/// units.
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MoneyVisitor;
+
+        impl de::Visitor<'_> for MoneyVisitor {
+            type Value = Money;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a decimal amount such as \"9.99\"")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Money, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Money, E> {
+                Money::try_from(Decimal::from(value)).map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Money, E> {
+                Money::try_from(Decimal::from(value)).map_err(E::custom)
+            }
+
+            // Goes through the shortest round-trip string so 9.99 stays 9.99
+            // instead of picking up binary noise.
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Money, E> {
+                value.to_string().parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dollars_and_cents_round_trip() {
+        for cents in [0, 1, 99, 100, 999, 1999, 99_999, 999_999] {
+            let money = Money::from_minor_units(cents);
+
+            assert_eq!(i64::from(money), cents);
+            assert_eq!(Money::try_from(Decimal::from(money)), Ok(money));
+            assert_eq!(money.to_string().parse::<Money>(), Ok(money));
+
+            let json = serde_json::to_string(&money).unwrap();
+            assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), money);
+        }
+    }
+
+    #[test]
+    fn formats_and_parses_major_units() {
+        assert_eq!(Money::from_minor_units(999).to_string(), "9.99");
+        assert_eq!(Money::from_minor_units(5).to_string(), "0.05");
+        assert_eq!("9.99".parse::<Money>(), Ok(Money::from_minor_units(999)));
+        assert_eq!(" 10 ".parse::<Money>(), Ok(Money::from_minor_units(1000)));
+        assert_eq!("9.990".parse::<Money>(), Ok(Money::from_minor_units(999)));
+        assert_eq!(serde_json::from_str::<Money>("9.99").unwrap(), Money::from_minor_units(999));
+        assert_eq!(serde_json::from_str::<Money>("10").unwrap(), Money::from_minor_units(1000));
+        assert_eq!(serde_json::to_string(&Money::from_minor_units(999)).unwrap(), "\"9.99\"");
+    }
+
+    #[test]
+    fn sub_cent_amounts_are_rejected_rather_than_rounded() {
+        assert_eq!("9.999".parse::<Money>(), Err(MoneyError::TooPrecise("9.999".to_string())));
+        assert_eq!("0.001".parse::<Money>(), Err(MoneyError::TooPrecise("0.001".to_string())));
+        assert!(Money::try_from(Decimal::new(12345, 3)).is_err());
+        assert!(serde_json::from_str::<Money>("9.995").is_err());
+    }
+
+    #[test]
+    fn malformed_amounts_are_rejected() {
+        for input in ["", " ", "abc", "9.99.9", "$9.99", "9,99", "1e3x", "--1"] {
+            assert_eq!(input.parse::<Money>(), Err(MoneyError::Invalid(input.to_string())), "{:?}", input);
+        }
+        assert!(serde_json::from_str::<Money>("true").is_err());
+        assert!(serde_json::from_str::<Money>("null").is_err());
+    }
+
+    #[test]
+    fn out_of_range_amounts_are_rejected() {
+        assert!(matches!(Money::try_from(Decimal::MAX), Err(MoneyError::OutOfRange(_))));
+        assert!(matches!("99999999999999999999".parse::<Money>(), Err(MoneyError::OutOfRange(_))));
+    }
+
+    #[test]
+    fn negative_amounts_are_rejected() {
+        assert_eq!("-1.00".parse::<Money>(), Err(MoneyError::Negative("-1.00".to_string())));
+        assert!(matches!(Money::try_from(Decimal::new(-1, 2)), Err(MoneyError::Negative(_))));
+        assert!(serde_json::from_str::<Money>("-5").is_err());
+        assert!(serde_json::from_str::<Money>("\"-0.99\"").is_err());
+        assert_eq!("-0".parse::<Money>(), Ok(Money::from_minor_units(0)));
+    }
+}
@@ -0,0 +1,108 @@
+//! Page size policy shared by the gateway and the services behind it.
+
+use crate::config::env_or;
+
+pub const DEFAULT_PAGE_SIZE: i32 = 50;
+pub const MAX_PAGE_SIZE: i32 = 100;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PageSizeConfig {
+    pub default_page_size: i32,
+    pub max_page_size: i32,
+    /// Reject out-of-range limits instead of clamping them.
+    pub strict: bool,
+}
+
+impl Default for PageSizeConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: DEFAULT_PAGE_SIZE,
+            max_page_size: MAX_PAGE_SIZE,
+            strict: false,
+        }
+    }
+}
+
+impl PageSizeConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let config = Self {
+            default_page_size: env_or("DEFAULT_PAGE_SIZE", DEFAULT_PAGE_SIZE)?,
+            max_page_size: env_or("MAX_PAGE_SIZE", MAX_PAGE_SIZE)?,
+            strict: env_or("PAGE_SIZE_STRICT", false)?,
+        };
+
+        if config.max_page_size < 1 {
+            return Err("MAX_PAGE_SIZE must be at least 1".to_string());
+        }
+        if !(1..=config.max_page_size).contains(&config.default_page_size) {
+            return Err("DEFAULT_PAGE_SIZE must be between 1 and MAX_PAGE_SIZE".to_string());
+        }
+
+        Ok(config)
+    }
+
+    /// Applies the policy to a client-supplied limit. `None` gets the default;
+    /// out-of-range values are clamped, or rejected in strict mode.
+    pub fn resolve(&self, requested: Option<i32>) -> Result<i32, String> {
+        let Some(limit) = requested else {
+            return Ok(self.default_page_size);
+        };
+
+        if self.strict && !(1..=self.max_page_size).contains(&limit) {
+            return Err(format!(
+                "limit must be between 1 and {}",
+                self.max_page_size
+            ));
+        }
+
+        Ok(limit.clamp(1, self.max_page_size))
+    }
+
+    /// Lenient variant for proto requests, where an unset `int32` arrives as 0.
+    pub fn clamp(&self, requested: i32) -> i32 {
+        if requested == 0 {
+            self.default_page_size
+        } else {
+            requested.clamp(1, self.max_page_size)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRICT: PageSizeConfig = PageSizeConfig {
+        default_page_size: DEFAULT_PAGE_SIZE,
+        max_page_size: MAX_PAGE_SIZE,
+        strict: true,
+    };
+
+    #[test]
+    fn missing_limits_get_the_default() {
+        assert_eq!(PageSizeConfig::default().resolve(None), Ok(DEFAULT_PAGE_SIZE));
+        assert_eq!(STRICT.resolve(None), Ok(DEFAULT_PAGE_SIZE));
+        assert_eq!(PageSizeConfig::default().clamp(0), DEFAULT_PAGE_SIZE);
+    }
+
+    #[test]
+    fn out_of_range_limits_are_clamped_by_default() {
+        let config = PageSizeConfig::default();
+
+        assert_eq!(config.resolve(Some(20)), Ok(20));
+        assert_eq!(config.resolve(Some(0)), Ok(1));
+        assert_eq!(config.resolve(Some(-5)), Ok(1));
+        assert_eq!(config.resolve(Some(1000)), Ok(MAX_PAGE_SIZE));
+        assert_eq!(config.clamp(-5), 1);
+        assert_eq!(config.clamp(1000), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn strict_mode_rejects_out_of_range_limits() {
+        assert_eq!(STRICT.resolve(Some(MAX_PAGE_SIZE)), Ok(MAX_PAGE_SIZE));
+        assert!(STRICT.resolve(Some(0)).is_err());
+        assert!(STRICT.resolve(Some(MAX_PAGE_SIZE + 1)).is_err());
+        // Proto requests stay lenient either way
+        assert_eq!(STRICT.clamp(1000), MAX_PAGE_SIZE);
+    }
+}
@@ -0,0 +1,257 @@
+//! Per-call access log for the gRPC services: one `rpc_access` event per
+//! RPC with its method, duration, status code and the request id the
+//! gateway forwards. Verbosity follows the service's `RUST_LOG` filter.
+//!
+//! The handler itself runs inside an `rpc` span with the same method and
+//! request id, so anything it logs (such as `db_timing`) is tied back to
+//! the request.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use tonic::codegen::http;
+use tonic::Code;
+use tower_layer::Layer;
+use tower_service::Service;
+use tracing::Instrument;
+
+/// Metadata key the gateway uses to pass its request id along.
+pub const REQUEST_ID_KEY: &str = "x-request-id";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpcLogLayer;
+
+impl<S> Layer<S> for RpcLogLayer {
+    type Service = RpcLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcLog { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcLog<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for RpcLog<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        let method = req.uri().path().to_string();
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_KEY)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("-")
+            .to_string();
+        let span = tracing::info_span!("rpc", %method, %request_id);
+        let started = Instant::now();
+        let call = self.inner.call(req).instrument(span);
+
+        Box::pin(async move {
+            let result = call.await;
+            let elapsed_ms = started.elapsed().as_millis() as u64;
+
+            match &result {
+                Ok(response) => {
+                    // Failed calls carry their status in the headers
+                    // (trailers-only). Successful unary calls send it in the
+                    // trailers, after this point, and are always OK.
+                    let code = tonic::Status::from_header_map(response.headers())
+                        .map_or(Code::Ok, |status| status.code());
+
+                    if is_server_error(code) {
+                        tracing::warn!(target: "rpc_access", %method, %request_id, code = ?code, elapsed_ms, "rpc failed");
+                    } else {
+                        tracing::info!(target: "rpc_access", %method, %request_id, code = ?code, elapsed_ms, "rpc");
+                    }
+                }
+                Err(_) => {
+                    tracing::error!(target: "rpc_access", %method, %request_id, elapsed_ms, "rpc transport error");
+                }
+            }
+
+            result
+        })
+    }
+}
+
+fn is_server_error(code: Code) -> bool {
+    matches!(
+        code,
+        Code::Unknown | Code::Internal | Code::Unavailable | Code::DataLoss | Code::DeadlineExceeded
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::convert::Infallible;
+    use std::future::{ready, Ready};
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::subscriber::DefaultGuard;
+
+    use super::*;
+
+    /// Everything logged on this thread while it is alive, as plain text.
+    pub(crate) struct CapturedLogs {
+        buffer: Arc<Mutex<Vec<u8>>>,
+        _guard: DefaultGuard,
+    }
+
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl CapturedLogs {
+        pub(crate) fn start() -> Self {
+            let buffer = Arc::new(Mutex::new(Vec::new()));
+            let sink = buffer.clone();
+            let subscriber = tracing_subscriber::fmt()
+                .with_writer(move || Sink(sink.clone()))
+                .with_ansi(false)
+                .with_max_level(tracing::Level::TRACE)
+                .finish();
+
+            Self {
+                buffer,
+                _guard: tracing::subscriber::set_default(subscriber),
+            }
+        }
+
+        pub(crate) fn lines(&self) -> Vec<String> {
+            String::from_utf8(self.buffer.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    /// Answers every call with `grpc-status` set to `code`, as tonic does
+    /// for a failed call, or with no status header for a successful one.
+    #[derive(Clone)]
+    struct Respond(Option<Code>);
+
+    impl Service<http::Request<()>> for Respond {
+        type Response = http::Response<()>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: http::Request<()>) -> Self::Future {
+            let mut response = http::Response::new(());
+            if let Some(code) = self.0 {
+                response.headers_mut().insert("grpc-status", (code as i32).into());
+            }
+            ready(Ok(response))
+        }
+    }
+
+    fn request(request_id: Option<&str>) -> http::Request<()> {
+        let mut builder = http::Request::builder().uri("/game.GameService/GetGame");
+        if let Some(request_id) = request_id {
+            builder = builder.header(REQUEST_ID_KEY, request_id);
+        }
+        builder.body(()).unwrap()
+    }
+
+    async fn logged(code: Option<Code>, request_id: Option<&str>) -> Vec<String> {
+        let logs = CapturedLogs::start();
+        RpcLogLayer.layer(Respond(code)).call(request(request_id)).await.unwrap();
+        logs.lines()
+    }
+
+    #[tokio::test]
+    async fn each_rpc_logs_its_method_request_id_code_and_duration() {
+        let lines = logged(None, Some("req-42")).await;
+
+        assert_eq!(lines.len(), 1, "{lines:?}");
+        let line = &lines[0];
+        assert!(line.contains(" INFO "), "{line}");
+        assert!(line.contains("rpc_access"), "{line}");
+        assert!(line.contains("method=/game.GameService/GetGame"), "{line}");
+        assert!(line.contains("request_id=req-42"), "{line}");
+        assert!(line.contains("code=Ok"), "{line}");
+        assert!(line.contains("elapsed_ms="), "{line}");
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_info_and_server_errors_warn() {
+        let line = logged(Some(Code::NotFound), Some("req-1")).await.remove(0);
+        assert!(line.contains(" INFO ") && line.contains("code=NotFound"), "{line}");
+
+        let line = logged(Some(Code::Internal), Some("req-2")).await.remove(0);
+        assert!(line.contains(" WARN ") && line.contains("code=Internal"), "{line}");
+        assert!(line.contains("rpc failed"), "{line}");
+    }
+
+    #[tokio::test]
+    async fn calls_without_a_request_id_log_a_dash() {
+        let line = logged(None, None).await.remove(0);
+        assert!(line.contains("request_id=-"), "{line}");
+    }
+
+    /// Logs one line from inside the handler.
+    #[derive(Clone)]
+    struct LogsInHandler;
+
+    impl Service<http::Request<()>> for LogsInHandler {
+        type Response = http::Response<()>;
+        type Error = Infallible;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: http::Request<()>) -> Self::Future {
+            Box::pin(async {
+                tracing::info!("inside the handler");
+                Ok(http::Response::new(()))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn handlers_run_in_an_rpc_span_but_the_access_log_does_not() {
+        let logs = CapturedLogs::start();
+        RpcLogLayer.layer(LogsInHandler).call(request(Some("req-9"))).await.unwrap();
+
+        let lines = logs.lines();
+        assert_eq!(lines.len(), 2, "{lines:?}");
+        assert!(
+            lines[0].contains("rpc{method=/game.GameService/GetGame request_id=req-9}: common::rpc_log::tests: inside the handler"),
+            "{}",
+            lines[0]
+        );
+        assert!(!lines[1].contains("rpc{"), "{}", lines[1]);
+        assert!(lines[1].contains("rpc_access"), "{}", lines[1]);
+    }
+}
@@ -0,0 +1,97 @@
+//! Conversions between chrono and `prost_types::Timestamp` that respect the
+//! protobuf range (0001-01-01 through 9999-12-31, UTC) instead of assuming
+//! every value fits.
+
+use chrono::{DateTime, Utc};
+use prost_types::Timestamp;
+
+/// 0001-01-01T00:00:00Z
+pub const MIN_SECONDS: i64 = -62_135_596_800;
+/// 9999-12-31T23:59:59Z
+pub const MAX_SECONDS: i64 = 253_402_300_799;
+
+const MAX_NANOS: u32 = 999_999_999;
+
+/// Clamps to the protobuf range. chrono reports leap seconds as nanos past
+/// one billion, which protobuf does not allow, so those are clamped too.
+pub fn datetime_to_timestamp(datetime: DateTime<Utc>) -> Timestamp {
+    let seconds = datetime.timestamp();
+    if seconds < MIN_SECONDS {
+        return Timestamp { seconds: MIN_SECONDS, nanos: 0 };
+    }
+    if seconds > MAX_SECONDS {
+        return Timestamp { seconds: MAX_SECONDS, nanos: MAX_NANOS as i32 };
+    }
+
+    Timestamp {
+        seconds,
+        nanos: datetime.timestamp_subsec_nanos().min(MAX_NANOS) as i32,
+    }
+}
+
+/// `None` for timestamps outside the protobuf range or with nanos outside
+/// `0..1_000_000_000`, rather than quietly turning them into the epoch.
+pub fn timestamp_to_datetime(timestamp: &Timestamp) -> Option<DateTime<Utc>> {
+    if !(MIN_SECONDS..=MAX_SECONDS).contains(&timestamp.seconds) {
+        return None;
+    }
+    let nanos = u32::try_from(timestamp.nanos).ok().filter(|n| *n <= MAX_NANOS)?;
+    DateTime::from_timestamp(timestamp.seconds, nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone};
+
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn in_range_datetimes_round_trip() {
+        for datetime in [
+            DateTime::from_timestamp(MIN_SECONDS, 0).unwrap(),
+            utc(1969, 7, 20),
+            DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap(),
+            DateTime::from_timestamp(MAX_SECONDS, MAX_NANOS).unwrap(),
+        ] {
+            let timestamp = datetime_to_timestamp(datetime);
+            assert_eq!(timestamp_to_datetime(&timestamp), Some(datetime));
+        }
+    }
+
+    #[test]
+    fn out_of_range_datetimes_are_clamped() {
+        assert_eq!(
+            datetime_to_timestamp(utc(-500, 1, 1)),
+            Timestamp { seconds: MIN_SECONDS, nanos: 0 }
+        );
+        assert_eq!(
+            datetime_to_timestamp(utc(10_000, 1, 1)),
+            Timestamp { seconds: MAX_SECONDS, nanos: MAX_NANOS as i32 }
+        );
+    }
+
+    #[test]
+    fn leap_second_nanos_are_clamped() {
+        let leap = DateTime::from_timestamp(1_483_228_799, 1_500_000_000).unwrap();
+        assert_eq!(datetime_to_timestamp(leap).nanos, MAX_NANOS as i32);
+    }
+
+    #[test]
+    fn out_of_range_seconds_are_rejected() {
+        for seconds in [MIN_SECONDS - 1, MAX_SECONDS + 1, i64::MIN, i64::MAX] {
+            assert_eq!(timestamp_to_datetime(&Timestamp { seconds, nanos: 0 }), None, "{seconds}");
+        }
+    }
+
+    #[test]
+    fn out_of_range_nanos_are_rejected() {
+        for nanos in [-1, 1_000_000_000, i32::MAX, i32::MIN] {
+            assert_eq!(timestamp_to_datetime(&Timestamp { seconds: 0, nanos }), None, "{nanos}");
+        }
+        assert!(timestamp_to_datetime(&Timestamp { seconds: 0, nanos: MAX_NANOS as i32 }).is_some());
+    }
+}
@@ -5,6 +5,11 @@ NC='\033[0m' # No Color
 
 echo -e "${GREEN}=== GameHub Services Launcher ===${NC}"
 
+if [ ! -f .env ]; then
+    echo -e "${RED}No .env found: copy .env.example to .env and set JWT_SECRET${NC}"
+    exit 1
+fi
+
 # Проверка и запуск PostgreSQL
 echo -e "${YELLOW}Checking PostgreSQL...${NC}"
 if ! docker ps | grep -q gamehub_postgres; then
@@ -3,4 +3,10 @@ fn main() {
         .file_descriptor_set_path("../../target/descriptor.bin")
         .compile_protos(&["../../proto/game.proto"], &["../../proto"])
         .unwrap_or_else(|e| panic!("Failed to compile protos {:?}", e));
+
+    // Only the client: callers' sessions are checked against the user service
+    tonic_build::configure()
+        .build_server(false)
+        .compile_protos(&["../../proto/user.proto"], &["../../proto"])
+        .unwrap_or_else(|e| panic!("Failed to compile protos {:?}", e));
 }
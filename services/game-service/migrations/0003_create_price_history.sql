@@ -0,0 +1,9 @@
+CREATE TABLE price_history (
+     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+     game_id UUID NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+     old_price DECIMAL(10, 2) NOT NULL,
+     new_price DECIMAL(10, 2) NOT NULL,
+     changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_price_history_game_id ON price_history(game_id, changed_at);
@@ -0,0 +1,2 @@
+-- Trigram similarity backs the typo-tolerant fallback for game name search.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
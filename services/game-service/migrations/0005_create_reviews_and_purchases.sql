@@ -0,0 +1,19 @@
+CREATE TABLE game_reviews (
+     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+     game_id UUID NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+     user_id UUID NOT NULL,
+     rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+     CONSTRAINT game_reviews_one_per_user UNIQUE (game_id, user_id)
+);
+
+CREATE TABLE game_purchases (
+     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+     game_id UUID NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+     user_id UUID NOT NULL,
+     price DECIMAL(10, 2) NOT NULL CHECK (price >= 0),
+     purchased_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_game_purchases_game_id ON game_purchases(game_id);
@@ -0,0 +1,4 @@
+-- Backs the search-suggest prefix match; text_pattern_ops lets LIKE 'abc%'
+-- use the index regardless of collation.
+CREATE INDEX idx_games_published_name_prefix ON games (lower(name) text_pattern_ops)
+     WHERE status = 'published' AND deleted_at IS NULL;
@@ -0,0 +1,6 @@
+-- Drafts may not have a release date yet; anything published must.
+ALTER TABLE games ALTER COLUMN release_date DROP NOT NULL;
+
+ALTER TABLE games
+     ADD CONSTRAINT games_published_release_date
+     CHECK (status <> 'published'::game_status OR release_date IS NOT NULL);
@@ -0,0 +1,21 @@
+-- Set the first time a game goes live. Draft cleanup uses it to leave alone
+-- anything that was ever published and later pulled back to a draft.
+ALTER TABLE games ADD COLUMN first_published_at TIMESTAMPTZ;
+
+UPDATE games
+     SET first_published_at = updated_at
+     WHERE status IN ('published'::game_status, 'suspended'::game_status);
+
+CREATE INDEX idx_games_stale_drafts ON games(updated_at)
+     WHERE status = 'draft'::game_status AND deleted_at IS NULL AND first_published_at IS NULL;
+
+-- Changes made to games by the system rather than by their developer.
+CREATE TABLE game_audit_log (
+     id BIGSERIAL PRIMARY KEY,
+     game_id UUID NOT NULL REFERENCES games(id),
+     action VARCHAR(50) NOT NULL,
+     detail TEXT NOT NULL,
+     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_game_audit_log_game_id ON game_audit_log(game_id);
@@ -0,0 +1,13 @@
+-- A time-boxed discount. Reads report sale_price instead of price while the
+-- current time is in [sale_starts_at, sale_ends_at); outside that window the
+-- columns are ignored, so an expired sale needs no cleanup to stop applying.
+ALTER TABLE games
+     ADD COLUMN sale_price DECIMAL(10, 2),
+     ADD COLUMN sale_starts_at TIMESTAMPTZ,
+     ADD COLUMN sale_ends_at TIMESTAMPTZ,
+     ADD CONSTRAINT games_sale_all_or_none CHECK (
+          (sale_price IS NULL) = (sale_starts_at IS NULL)
+          AND (sale_price IS NULL) = (sale_ends_at IS NULL)
+     ),
+     ADD CONSTRAINT games_sale_price_min CHECK (sale_price >= 0),
+     ADD CONSTRAINT games_sale_window CHECK (sale_ends_at > sale_starts_at);
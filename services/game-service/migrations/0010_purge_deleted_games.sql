@@ -0,0 +1,5 @@
+-- Purging hard-deletes games, but their audit entries (including the one
+-- recording the purge) have to stay behind.
+ALTER TABLE game_audit_log DROP CONSTRAINT game_audit_log_game_id_fkey;
+
+CREATE INDEX idx_games_purgeable ON games(deleted_at) WHERE deleted_at IS NOT NULL;
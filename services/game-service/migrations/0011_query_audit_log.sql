@@ -0,0 +1,14 @@
+-- Who made the change (NULL for background jobs) and the affected state
+-- before and after, so admins can search the log.
+ALTER TABLE game_audit_log
+     ADD COLUMN actor_id UUID,
+     ADD COLUMN before JSONB,
+     ADD COLUMN after JSONB;
+
+-- Listings are newest-first by id and page on it, so each filter's index
+-- ends in id.
+DROP INDEX idx_game_audit_log_game_id;
+CREATE INDEX idx_game_audit_log_game_id ON game_audit_log(game_id, id);
+CREATE INDEX idx_game_audit_log_actor_id ON game_audit_log(actor_id, id);
+CREATE INDEX idx_game_audit_log_action ON game_audit_log(action, id);
+CREATE INDEX idx_game_audit_log_created_at ON game_audit_log(created_at);
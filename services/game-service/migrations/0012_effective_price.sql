@@ -0,0 +1,17 @@
+-- What a game costs at the current time, as DbGame::effective_price computes
+-- it for reads: sale_price inside [sale_starts_at, sale_ends_at), price
+-- otherwise. Price filters, facets and stats go through this so they agree
+-- with the prices the API returns. STABLE, not IMMUTABLE: it reads now().
+CREATE FUNCTION effective_price(
+     base_price DECIMAL,
+     sale_price DECIMAL,
+     starts_at TIMESTAMPTZ,
+     ends_at TIMESTAMPTZ
+) RETURNS DECIMAL
+LANGUAGE sql STABLE
+AS $$
+     SELECT CASE
+          WHEN now() >= starts_at AND now() < ends_at THEN sale_price
+          ELSE base_price
+     END
+$$;
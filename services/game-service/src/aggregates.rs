@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::repository::GameRepository;
+
+/// How often the background job recomputes rating and purchase counters
+/// from the reviews and purchases tables. Off unless
+/// `GAME_STATS_RECOMPUTE_INTERVAL_SECS` is set.
+pub fn recompute_interval_from_env() -> Result<Option<Duration>, String> {
+    match std::env::var("GAME_STATS_RECOMPUTE_INTERVAL_SECS") {
+        Ok(value) => value
+            .parse::<u64>()
+            .ok()
+            .filter(|secs| *secs > 0)
+            .map(|secs| Some(Duration::from_secs(secs)))
+            .ok_or_else(|| format!("GAME_STATS_RECOMPUTE_INTERVAL_SECS has an invalid value: {}", value)),
+        Err(_) => Ok(None),
+    }
+}
+
+pub fn spawn_recompute_job(repo: Arc<dyn GameRepository>, every: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        // The first tick fires immediately; let startup settle first.
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            match repo.recompute_game_stats(None).await {
+                Ok(0) => {}
+                Ok(corrected) => println!("Recomputed stats: corrected {} game(s)", corrected),
+                Err(e) => eprintln!("Recomputing game stats failed: {}", e),
+            }
+        }
+    });
+}
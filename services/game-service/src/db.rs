@@ -1,32 +1,55 @@
-use chrono::{NaiveDate, Utc};
-use sqlx::postgres::PgPool;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::postgres::{PgPool, PgPoolOptions};
 use sqlx::types::Decimal;
+use tokio_stream::Stream;
 use uuid::Uuid;
 
-use crate::models::{DbGame, DbGameCategory, DbGameStatus};
+use crate::models::{round_rating, AuditFilter, CategoryPriceStats, DbAuditEntry, DbGame, DbGameCategory, DbGameReview, DbGameStatus, DbPriceHistory, GameChanges, GameFilter, GameSuggestion, NewGame, ReviewSummary, SaleSchedule, StaleDraft, StatusCounts};
 
-#[allow(dead_code)]
-pub async fn create_game(
-     pool: &PgPool,
-     name: String,
-     description: String,
-     developer_id: Uuid,
-     publisher_id: Option<Uuid>,
-     cover_image: Option<String>,
-     trailer_url: Option<String>,
-     release_date: NaiveDate,
-     categories: Vec<DbGameCategory>,
-     tags: Vec<String>,
-     platforms: Vec<String>,
-     price: Decimal,
-) -> Result<DbGame, sqlx::Error> {
+// Every statement that changes a game row also sets `updated_at` itself,
+// rather than relying on the `update_games_updated_at` trigger alone: cache
+// validators are derived from it, so a missed touch would serve stale data.
+
+/// Postgres `query_canceled`, raised when `statement_timeout` elapses.
+const QUERY_CANCELED: &str = "57014";
+
+/// Opens the pool with `statement_timeout` set on every connection, so a slow
+/// or blocked query fails instead of holding a handler forever. A zero
+/// timeout disables the limit.
+pub async fn connect(database_url: &str, statement_timeout: Duration) -> Result<PgPool, sqlx::Error> {
+     pool_options(statement_timeout).connect(database_url).await
+}
+
+fn pool_options(statement_timeout: Duration) -> PgPoolOptions {
+     let timeout_ms = statement_timeout.as_millis();
+
+     PgPoolOptions::new()
+          .after_connect(move |conn, _meta| {
+               Box::pin(async move {
+                    sqlx::query(&format!("SET statement_timeout = {}", timeout_ms))
+                         .execute(conn)
+                         .await?;
+                    Ok(())
+               })
+          })
+}
+
+pub fn is_statement_timeout(err: &sqlx::Error) -> bool {
+     err.as_database_error()
+          .and_then(|e| e.code())
+          .is_some_and(|code| code == QUERY_CANCELED)
+}
+
+pub async fn create_game(pool: &PgPool, game: NewGame) -> Result<DbGame, sqlx::Error> {
      let id = Uuid::new_v4();
      let now = Utc::now();
 
      // Convert categories to strings for database insertion
-     let category_strings: Vec<String> = categories.iter().map(|c| format!("{:?}", c).to_lowercase()).collect();
+     let category_strings: Vec<String> = game.categories.iter().map(|c| c.to_string()).collect();
      
-     let game = sqlx::query_as!(
+     let created = sqlx::query_as!(
           DbGame,
           r#"
           INSERT INTO games (
@@ -43,20 +66,21 @@ pub async fn create_game(
                categories as "categories: Vec<DbGameCategory>",
                tags, platforms, screenshots, 
                rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
                created_at, updated_at, deleted_at
           "#,
           id,
-          name,
-          description,
-          developer_id,
-          publisher_id,
-          cover_image,
-          trailer_url,
-          release_date,
-          price,
+          game.name,
+          game.description,
+          game.developer_id,
+          game.publisher_id,
+          game.cover_image,
+          game.trailer_url,
+          game.release_date,
+          game.price,
           &category_strings,
-          &tags,
-          &platforms,
+          &game.tags,
+          &game.platforms,
           &Vec::<String>::new(),
           now,
           now
@@ -64,10 +88,9 @@ pub async fn create_game(
      .fetch_one(pool)
      .await?;
 
-     Ok(game)
+     Ok(created)
 }
 
-#[allow(dead_code)]
 pub async fn get_game_by_id(pool: &PgPool, id: Uuid) -> Result<Option<DbGame>, sqlx::Error> {
      let record = sqlx::query_as!(
           DbGame,
@@ -79,6 +102,7 @@ pub async fn get_game_by_id(pool: &PgPool, id: Uuid) -> Result<Option<DbGame>, s
                categories as "categories: Vec<DbGameCategory>",
                tags, platforms, screenshots,
                rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
                created_at, updated_at, deleted_at
           FROM games
           WHERE id = $1 AND deleted_at IS NULL
@@ -91,27 +115,31 @@ pub async fn get_game_by_id(pool: &PgPool, id: Uuid) -> Result<Option<DbGame>, s
      Ok(record)
 }
 
-#[allow(dead_code)]
 pub async fn update_game(
      pool: &PgPool,
      id: Uuid,
-     name: Option<String>,
-     description: Option<String>,
-     price: Option<Decimal>,
-     cover_image: Option<String>,
-     trailer_url: Option<String>,
-     status: Option<DbGameStatus>,
-     categories: Option<Vec<DbGameCategory>>,
-     tags: Option<Vec<String>>,
-     platforms: Option<Vec<String>>,
-     screenshots: Option<Vec<String>>,
-) -> Result<DbGame, sqlx::Error> {
+     changes: GameChanges,
+) -> Result<Option<DbGame>, sqlx::Error> {
      let now = Utc::now();
 
      // Convert categories to strings if provided
-     let category_strings = categories.as_ref().map(|cats| {
-          cats.iter().map(|c| format!("{:?}", c).to_lowercase()).collect::<Vec<String>>()
+     let category_strings = changes.categories.as_ref().map(|cats| {
+          cats.iter().map(|c| c.to_string()).collect::<Vec<String>>()
      });
+
+     let mut tx = pool.begin().await?;
+
+     // Lock the row so the recorded old price can't be changed underneath us
+     let old_price = sqlx::query_scalar!(
+          "SELECT price FROM games WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+          id
+     )
+     .fetch_optional(&mut *tx)
+     .await?;
+
+     let Some(old_price) = old_price else {
+          return Ok(None);
+     };
      
      let record = sqlx::query_as!(
           DbGame,
@@ -123,11 +151,16 @@ pub async fn update_game(
                price = COALESCE($4, price),
                cover_image = COALESCE($5, cover_image),
                trailer_url = COALESCE($6, trailer_url),
-               status = CASE WHEN $7::int4 IS NOT NULL THEN (CASE $7 WHEN 1 THEN 'draft'::game_status WHEN 2 THEN 'under_review'::game_status WHEN 3 THEN 'published'::game_status WHEN 4 THEN 'suspended'::game_status END) ELSE status END,
+               status = COALESCE($7::text::game_status, status),
+               first_published_at = CASE
+                    WHEN $7::text = 'published' THEN COALESCE(first_published_at, $12)
+                    ELSE first_published_at
+               END,
                categories = COALESCE($8::text[]::game_category[], categories),
                tags = COALESCE($9, tags),
                platforms = COALESCE($10, platforms),
                screenshots = COALESCE($11, screenshots),
+               release_date = COALESCE($13, release_date),
                updated_at = $12
           WHERE id = $1 AND deleted_at IS NULL
           RETURNING 
@@ -137,34 +170,244 @@ pub async fn update_game(
                categories as "categories: Vec<DbGameCategory>",
                tags, platforms, screenshots,
                rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
                created_at, updated_at, deleted_at
           "#,
           id,
-          name,
-          description,
-          price,
-          cover_image,
-          trailer_url,
-          status.as_ref().map(|s| s.to_proto() as i32),
+          changes.name,
+          changes.description,
+          changes.price,
+          changes.cover_image,
+          changes.trailer_url,
+          changes.status.map(|s| s.to_string()),
           category_strings.as_deref(),
-          tags.as_deref(),
-          platforms.as_deref(),
-          screenshots.as_deref(),
-          now
+          changes.tags.as_deref(),
+          changes.platforms.as_deref(),
+          changes.screenshots.as_deref(),
+          now,
+          changes.release_date
      )
-     .fetch_one(pool)
+     .fetch_one(&mut *tx)
+     .await?;
+
+     if old_price != record.price {
+          sqlx::query!(
+               r#"
+               INSERT INTO price_history (game_id, old_price, new_price, changed_at)
+               VALUES ($1, $2, $3, $4)
+               "#,
+               id,
+               old_price,
+               record.price,
+               now
+          )
+          .execute(&mut *tx)
+          .await?;
+     }
+
+     tx.commit().await?;
+
+     Ok(Some(record))
+}
+
+/// Sets each game's price in one transaction, recording price history for
+/// the ones that actually change. Returns the old price and updated row per
+/// entry, in order; `None` for a game that no longer exists. With
+/// `rollback_on_missing` one missing game rolls back the whole batch.
+pub async fn bulk_set_prices(
+     pool: &PgPool,
+     updates: &[(Uuid, Decimal)],
+     rollback_on_missing: bool,
+) -> Result<Vec<Option<(Decimal, DbGame)>>, sqlx::Error> {
+     let now = Utc::now();
+     let mut tx = pool.begin().await?;
+
+     let mut outcomes = Vec::with_capacity(updates.len());
+     for (id, price) in updates {
+          let old_price = sqlx::query_scalar!(
+               "SELECT price FROM games WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+               id
+          )
+          .fetch_optional(&mut *tx)
+          .await?;
+
+          let Some(old_price) = old_price else {
+               outcomes.push(None);
+               continue;
+          };
+
+          let record = sqlx::query_as!(
+               DbGame,
+               r#"
+               UPDATE games
+               SET price = $2, updated_at = $3
+               WHERE id = $1
+               RETURNING 
+                    id, name, description, developer_id, publisher_id,
+                    cover_image, trailer_url, release_date, price, 
+                    status as "status: DbGameStatus",
+                    categories as "categories: Vec<DbGameCategory>",
+                    tags, platforms, screenshots,
+                    rating_count, average_rating, purchase_count,
+                    sale_price, sale_starts_at, sale_ends_at,
+                    created_at, updated_at, deleted_at
+               "#,
+               id,
+               price,
+               now
+          )
+          .fetch_one(&mut *tx)
+          .await?;
+
+          if old_price != record.price {
+               sqlx::query!(
+                    r#"
+                    INSERT INTO price_history (game_id, old_price, new_price, changed_at)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                    id,
+                    old_price,
+                    record.price,
+                    now
+               )
+               .execute(&mut *tx)
+               .await?;
+          }
+
+          outcomes.push(Some((old_price, record)));
+     }
+
+     if rollback_on_missing && outcomes.iter().any(Option::is_none) {
+          tx.rollback().await?;
+     } else {
+          tx.commit().await?;
+     }
+
+     Ok(outcomes)
+}
+
+/// Replaces the game's scheduled sale, or clears it with `None`. The base
+/// price and its history are left alone.
+pub async fn set_sale(pool: &PgPool, id: Uuid, sale: Option<SaleSchedule>) -> Result<Option<DbGame>, sqlx::Error> {
+     let record = sqlx::query_as!(
+          DbGame,
+          r#"
+          UPDATE games
+          SET sale_price = $2, sale_starts_at = $3, sale_ends_at = $4, updated_at = $5
+          WHERE id = $1 AND deleted_at IS NULL
+          RETURNING 
+               id, name, description, developer_id, publisher_id,
+               cover_image, trailer_url, release_date, price, 
+               status as "status: DbGameStatus",
+               categories as "categories: Vec<DbGameCategory>",
+               tags, platforms, screenshots,
+               rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
+               created_at, updated_at, deleted_at
+          "#,
+          id,
+          sale.map(|s| s.price),
+          sale.map(|s| s.starts_at),
+          sale.map(|s| s.ends_at),
+          Utc::now()
+     )
+     .fetch_optional(pool)
      .await?;
 
      Ok(record)
 }
 
-#[allow(dead_code)]
+pub async fn list_price_history(pool: &PgPool, game_id: Uuid) -> Result<Vec<DbPriceHistory>, sqlx::Error> {
+     let records = sqlx::query_as!(
+          DbPriceHistory,
+          r#"
+          SELECT id, game_id, old_price, new_price, changed_at
+          FROM price_history
+          WHERE game_id = $1
+          ORDER BY changed_at ASC
+          "#,
+          game_id
+     )
+     .fetch_all(pool)
+     .await?;
+
+     Ok(records)
+}
+
+pub async fn list_top_reviews(pool: &PgPool, game_id: Uuid, limit: i32) -> Result<Vec<DbGameReview>, sqlx::Error> {
+     let reviews = sqlx::query_as!(
+          DbGameReview,
+          r#"
+          SELECT id, game_id, user_id, rating, created_at
+          FROM game_reviews
+          WHERE game_id = $1
+          ORDER BY rating DESC, created_at DESC
+          LIMIT $2
+          "#,
+          game_id,
+          limit as i64
+     )
+     .fetch_all(pool)
+     .await?;
+
+     Ok(reviews)
+}
+
+/// Review counts per star rating for a live game, or `None` if there is no
+/// such game. The left join keeps one all-NULL row for a game without
+/// reviews, so an empty result only ever means "not found".
+pub async fn get_review_summary(pool: &PgPool, game_id: Uuid) -> Result<Option<ReviewSummary>, sqlx::Error> {
+     let rows = sqlx::query!(
+          r#"
+          SELECT r.rating AS "rating?", COUNT(r.id) AS "count!"
+          FROM games g
+          LEFT JOIN game_reviews r ON r.game_id = g.id
+          WHERE g.id = $1 AND g.deleted_at IS NULL
+          GROUP BY r.rating
+          "#,
+          game_id
+     )
+     .fetch_all(pool)
+     .await?;
+
+     if rows.is_empty() {
+          return Ok(None);
+     }
+     let mut summary = ReviewSummary::default();
+     for row in rows {
+          if let Some(rating) = row.rating {
+               summary.add(rating, row.count);
+          }
+     }
+     Ok(Some(summary))
+}
+
+pub async fn get_developer_status_counts(pool: &PgPool, developer_id: Uuid) -> Result<StatusCounts, sqlx::Error> {
+     let rows = sqlx::query!(
+          r#"
+          SELECT status AS "status: DbGameStatus", COUNT(*) AS "count!"
+          FROM games
+          WHERE developer_id = $1 AND deleted_at IS NULL
+          GROUP BY status
+          "#,
+          developer_id
+     )
+     .fetch_all(pool)
+     .await?;
+
+     let mut counts = StatusCounts::default();
+     for row in rows {
+          counts.add(&row.status, row.count);
+     }
+     Ok(counts)
+}
+
 pub async fn delete_game(pool: &PgPool, id: Uuid, developer_id: Uuid) -> Result<bool, sqlx::Error> {
      let now = Utc::now();
      let rows_affected = sqlx::query!(
           r#"
           UPDATE games 
-          SET deleted_at = $3
+          SET deleted_at = $3, updated_at = $3
           WHERE id = $1 AND developer_id = $2 AND deleted_at IS NULL
           "#,
           id,
@@ -190,6 +433,7 @@ pub async fn get_all_games(pool: &PgPool) -> Result<Vec<DbGame>, sqlx::Error> {
                categories as "categories: Vec<DbGameCategory>",
                tags, platforms, screenshots,
                rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
                created_at, updated_at, deleted_at
           FROM games
           WHERE deleted_at IS NULL
@@ -204,18 +448,14 @@ pub async fn get_all_games(pool: &PgPool) -> Result<Vec<DbGame>, sqlx::Error> {
 
 pub async fn list_games(
      pool: &PgPool,
-     developer_id: Option<Uuid>,
-     categories: Option<Vec<DbGameCategory>>,
-     min_price: Option<Decimal>,
-     max_price: Option<Decimal>,
-     status: Option<DbGameStatus>,
-     search_query: Option<String>,
+     filter: &GameFilter,
+     search_query: Option<&str>,
      limit: i32,
      offset: i32,
 ) -> Result<(Vec<DbGame>, i64), sqlx::Error> {
      // Convert categories to strings for query
-     let category_strings = categories.as_ref().map(|cats| {
-          cats.iter().map(|c| format!("{:?}", c).to_lowercase()).collect::<Vec<String>>()
+     let category_strings = filter.categories.as_ref().map(|cats| {
+          cats.iter().map(|c| c.to_string()).collect::<Vec<String>>()
      });
      
      let games = sqlx::query_as!(
@@ -228,26 +468,32 @@ pub async fn list_games(
                categories as "categories: Vec<DbGameCategory>",
                tags, platforms, screenshots,
                rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
                created_at, updated_at, deleted_at
           FROM games
-          WHERE deleted_at IS NULL
+          WHERE ($9::bool OR deleted_at IS NULL)
                AND ($1::uuid IS NULL OR developer_id = $1)
                AND ($2::text[] IS NULL OR categories && $2::text[]::game_category[])
-               AND ($3::decimal IS NULL OR price >= $3)
-               AND ($4::decimal IS NULL OR price <= $4)  
-               AND ($5::int4 IS NULL OR status = (CASE $5 WHEN 1 THEN 'draft'::game_status WHEN 2 THEN 'under_review'::game_status WHEN 3 THEN 'published'::game_status WHEN 4 THEN 'suspended'::game_status END))
+               AND ($3::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) >= $3)
+               AND ($4::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) <= $4)  
+               AND ($5::text IS NULL OR status = $5::text::game_status)
                AND ($6::text IS NULL OR to_tsvector('english', name) @@ plainto_tsquery('english', $6))
+               AND ($10::uuid IS NULL OR id <> $10)
+               AND ($11::uuid IS NULL OR publisher_id = $11)
           ORDER BY created_at DESC
           LIMIT $7 OFFSET $8
           "#,
-          developer_id,
+          filter.developer_id,
           category_strings.as_deref(),
-          min_price,
-          max_price,
-          status.as_ref().map(|s| s.to_proto() as i32),
+          filter.min_price,
+          filter.max_price,
+          filter.status.map(|s| s.to_string()),
           search_query,
           limit as i64,
-          offset as i64
+          offset as i64,
+          filter.include_deleted,
+          filter.exclude_id,
+          filter.publisher_id
      )
      .fetch_all(pool)
      .await?;
@@ -255,20 +501,161 @@ pub async fn list_games(
      let total = sqlx::query_scalar!(
           r#"
           SELECT COUNT(*) FROM games 
-          WHERE deleted_at IS NULL
+          WHERE ($7::bool OR deleted_at IS NULL)
                AND ($1::uuid IS NULL OR developer_id = $1)
                AND ($2::text[] IS NULL OR categories && $2::text[]::game_category[])
-               AND ($3::decimal IS NULL OR price >= $3)
-               AND ($4::decimal IS NULL OR price <= $4)  
-               AND ($5::int4 IS NULL OR status = (CASE $5 WHEN 1 THEN 'draft'::game_status WHEN 2 THEN 'under_review'::game_status WHEN 3 THEN 'published'::game_status WHEN 4 THEN 'suspended'::game_status END))
+               AND ($3::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) >= $3)
+               AND ($4::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) <= $4)  
+               AND ($5::text IS NULL OR status = $5::text::game_status)
                AND ($6::text IS NULL OR to_tsvector('english', name) @@ plainto_tsquery('english', $6))
+               AND ($8::uuid IS NULL OR id <> $8)
+               AND ($9::uuid IS NULL OR publisher_id = $9)
           "#,
-          developer_id,
+          filter.developer_id,
+          category_strings.as_deref(),
+          filter.min_price,
+          filter.max_price,
+          filter.status.map(|s| s.to_string()),
+          search_query,
+          filter.include_deleted,
+          filter.exclude_id,
+          filter.publisher_id
+     )
+     .fetch_one(pool)
+     .await?
+     .unwrap_or(0);
+
+     Ok((games, total))
+}
+
+/// Counts the games `list_games` would match per price bucket. Returns
+/// `bounds.len() + 1` counts; bucket `i` holds prices in
+/// `[bounds[i - 1], bounds[i])`, open-ended at both ends.
+pub async fn get_price_facets(
+     pool: &PgPool,
+     filter: &GameFilter,
+     search_query: Option<&str>,
+     bounds: &[Decimal],
+) -> Result<Vec<i64>, sqlx::Error> {
+     let category_strings = filter.categories.as_ref().map(|cats| {
+          cats.iter().map(|c| c.to_string()).collect::<Vec<String>>()
+     });
+
+     let rows = sqlx::query!(
+          r#"
+          SELECT width_bucket(effective_price(price, sale_price, sale_starts_at, sale_ends_at), $10::numeric[]) AS "bucket!", COUNT(*) AS "count!"
+          FROM games
+          WHERE ($7::bool OR deleted_at IS NULL)
+               AND ($1::uuid IS NULL OR developer_id = $1)
+               AND ($2::text[] IS NULL OR categories && $2::text[]::game_category[])
+               AND ($3::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) >= $3)
+               AND ($4::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) <= $4)
+               AND ($5::text IS NULL OR status = $5::text::game_status)
+               AND ($6::text IS NULL OR to_tsvector('english', name) @@ plainto_tsquery('english', $6))
+               AND ($8::uuid IS NULL OR id <> $8)
+               AND ($9::uuid IS NULL OR publisher_id = $9)
+          GROUP BY 1
+          "#,
+          filter.developer_id,
+          category_strings.as_deref(),
+          filter.min_price,
+          filter.max_price,
+          filter.status.map(|s| s.to_string()),
+          search_query,
+          filter.include_deleted,
+          filter.exclude_id,
+          filter.publisher_id,
+          bounds
+     )
+     .fetch_all(pool)
+     .await?;
+
+     let mut counts = vec![0; bounds.len() + 1];
+     for row in rows {
+          counts[row.bucket as usize] = row.count;
+     }
+
+     Ok(counts)
+}
+
+/// Typo-tolerant variant of the `list_games` name search: matches on trigram
+/// `word_similarity` instead of full-text search and ranks by closeness.
+pub async fn fuzzy_search_games(
+     pool: &PgPool,
+     filter: &GameFilter,
+     search_query: &str,
+     similarity_threshold: f32,
+     limit: i32,
+     offset: i32,
+) -> Result<(Vec<DbGame>, i64), sqlx::Error> {
+     let category_strings = filter.categories.as_ref().map(|cats| {
+          cats.iter().map(|c| c.to_string()).collect::<Vec<String>>()
+     });
+
+     let games = sqlx::query_as!(
+          DbGame,
+          r#"
+          SELECT 
+               id, name, description, developer_id, publisher_id,
+               cover_image, trailer_url, release_date, price, 
+               status as "status: DbGameStatus",
+               categories as "categories: Vec<DbGameCategory>",
+               tags, platforms, screenshots,
+               rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
+               created_at, updated_at, deleted_at
+          FROM games
+          WHERE ($10::bool OR deleted_at IS NULL)
+               AND ($1::uuid IS NULL OR developer_id = $1)
+               AND ($2::text[] IS NULL OR categories && $2::text[]::game_category[])
+               AND ($3::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) >= $3)
+               AND ($4::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) <= $4)
+               AND ($5::text IS NULL OR status = $5::text::game_status)
+               AND word_similarity($6, name) >= $7
+               AND ($11::uuid IS NULL OR id <> $11)
+               AND ($12::uuid IS NULL OR publisher_id = $12)
+          ORDER BY word_similarity($6, name) DESC, created_at DESC
+          LIMIT $8 OFFSET $9
+          "#,
+          filter.developer_id,
+          category_strings.as_deref(),
+          filter.min_price,
+          filter.max_price,
+          filter.status.map(|s| s.to_string()),
+          search_query,
+          similarity_threshold,
+          limit as i64,
+          offset as i64,
+          filter.include_deleted,
+          filter.exclude_id,
+          filter.publisher_id
+     )
+     .fetch_all(pool)
+     .await?;
+
+     let total = sqlx::query_scalar!(
+          r#"
+          SELECT COUNT(*) FROM games 
+          WHERE ($8::bool OR deleted_at IS NULL)
+               AND ($1::uuid IS NULL OR developer_id = $1)
+               AND ($2::text[] IS NULL OR categories && $2::text[]::game_category[])
+               AND ($3::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) >= $3)
+               AND ($4::decimal IS NULL OR effective_price(price, sale_price, sale_starts_at, sale_ends_at) <= $4)
+               AND ($5::text IS NULL OR status = $5::text::game_status)
+               AND word_similarity($6, name) >= $7
+               AND ($9::uuid IS NULL OR id <> $9)
+               AND ($10::uuid IS NULL OR publisher_id = $10)
+          "#,
+          filter.developer_id,
           category_strings.as_deref(),
-          min_price,
-          max_price,
-          status.as_ref().map(|s| s.to_proto() as i32),
-          search_query
+          filter.min_price,
+          filter.max_price,
+          filter.status.map(|s| s.to_string()),
+          search_query,
+          similarity_threshold,
+          filter.include_deleted,
+          filter.exclude_id,
+          filter.publisher_id
      )
      .fetch_one(pool)
      .await?
@@ -277,6 +664,33 @@ pub async fn list_games(
      Ok((games, total))
 }
 
+/// Streams a developer's games row by row, oldest first, so exports never
+/// hold the whole result in memory.
+pub fn stream_developer_games(
+     pool: &PgPool,
+     developer_id: Uuid,
+) -> impl Stream<Item = Result<DbGame, sqlx::Error>> + Send + '_ {
+     sqlx::query_as!(
+          DbGame,
+          r#"
+          SELECT 
+               id, name, description, developer_id, publisher_id,
+               cover_image, trailer_url, release_date, price, 
+               status as "status: DbGameStatus",
+               categories as "categories: Vec<DbGameCategory>",
+               tags, platforms, screenshots,
+               rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
+               created_at, updated_at, deleted_at
+          FROM games
+          WHERE developer_id = $1 AND deleted_at IS NULL
+          ORDER BY created_at ASC
+          "#,
+          developer_id
+     )
+     .fetch(pool)
+}
+
 #[allow(dead_code)]
 pub async fn get_games_by_category(
      pool: &PgPool,
@@ -284,7 +698,7 @@ pub async fn get_games_by_category(
      limit: i32,
      offset: i32,
 ) -> Result<Vec<DbGame>, sqlx::Error> {
-     let category_string = format!("{:?}", category).to_lowercase();
+     let category_string = category.to_string();
      
      let games = sqlx::query_as!(
           DbGame,
@@ -296,6 +710,7 @@ pub async fn get_games_by_category(
                categories as "categories: Vec<DbGameCategory>",
                tags, platforms, screenshots,
                rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
                created_at, updated_at, deleted_at
           FROM games
           WHERE $1::text::game_category = ANY(categories) 
@@ -314,7 +729,6 @@ pub async fn get_games_by_category(
      Ok(games)
 }
 
-#[allow(dead_code)]
 pub async fn get_popular_games(
      pool: &PgPool,
      limit: i32,
@@ -329,6 +743,7 @@ pub async fn get_popular_games(
                categories as "categories: Vec<DbGameCategory>",
                tags, platforms, screenshots,
                rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
                created_at, updated_at, deleted_at
           FROM games
           WHERE status = 'published'::game_status AND deleted_at IS NULL
@@ -343,29 +758,236 @@ pub async fn get_popular_games(
      Ok(games)
 }
 
+pub async fn get_category_price_stats(
+     pool: &PgPool,
+     category: DbGameCategory,
+) -> Result<CategoryPriceStats, sqlx::Error> {
+     let category_string = category.to_string();
+
+     let stats = sqlx::query_as!(
+          CategoryPriceStats,
+          r#"
+          SELECT
+               COUNT(*) AS "game_count!",
+               MIN(effective_price(price, sale_price, sale_starts_at, sale_ends_at)) AS min_price,
+               MAX(effective_price(price, sale_price, sale_starts_at, sale_ends_at)) AS max_price,
+               ROUND(AVG(effective_price(price, sale_price, sale_starts_at, sale_ends_at)), 2) AS avg_price,
+               ROUND((percentile_cont(0.5) WITHIN GROUP (ORDER BY effective_price(price, sale_price, sale_starts_at, sale_ends_at)::float8))::numeric, 2) AS median_price
+          FROM games
+          WHERE $1::text::game_category = ANY(categories)
+               AND status = 'published'::game_status
+               AND deleted_at IS NULL
+          "#,
+          category_string
+     )
+     .fetch_one(pool)
+     .await?;
+
+     Ok(stats)
+}
+
+/// Published games whose name starts with `prefix` (case-insensitively),
+/// most purchased first.
+pub async fn suggest_games(
+     pool: &PgPool,
+     prefix: &str,
+     limit: i32,
+) -> Result<Vec<GameSuggestion>, sqlx::Error> {
+     let pattern = format!(
+          "{}%",
+          prefix.to_lowercase().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+     );
+
+     let suggestions = sqlx::query_as!(
+          GameSuggestion,
+          r#"
+          SELECT id, name
+          FROM games
+          WHERE status = 'published'::game_status AND deleted_at IS NULL
+               AND lower(name) LIKE $1
+          ORDER BY purchase_count DESC, name
+          LIMIT $2
+          "#,
+          pattern,
+          limit as i64
+     )
+     .fetch_all(pool)
+     .await?;
+
+     Ok(suggestions)
+}
+
+/// The planner's row estimate for `games`, which unlike `COUNT(*)` costs
+/// nothing to read. `None` until the table has been analyzed.
+pub async fn estimate_game_rows(pool: &PgPool) -> Result<Option<i64>, sqlx::Error> {
+     let estimate = sqlx::query_scalar!(
+          r#"SELECT reltuples::bigint AS "estimate!" FROM pg_class WHERE oid = 'games'::regclass"#
+     )
+     .fetch_one(pool)
+     .await?;
+
+     Ok((estimate >= 0).then_some(estimate))
+}
+
+/// Returns the number of published games and of distinct developers behind them.
+pub async fn count_published_catalog(pool: &PgPool) -> Result<(i64, i64), sqlx::Error> {
+     let row = sqlx::query!(
+          r#"
+          SELECT
+               COUNT(*) as "games!",
+               COUNT(DISTINCT developer_id) as "developers!"
+          FROM games
+          WHERE status = 'published'::game_status AND deleted_at IS NULL
+          "#
+     )
+     .fetch_one(pool)
+     .await?;
+
+     Ok((row.games, row.developers))
+}
+
+/// The most recently released published game; future release dates don't count.
+pub async fn get_newest_release(pool: &PgPool) -> Result<Option<DbGame>, sqlx::Error> {
+     let game = sqlx::query_as!(
+          DbGame,
+          r#"
+          SELECT 
+               id, name, description, developer_id, publisher_id,
+               cover_image, trailer_url, release_date, price, 
+               status as "status: DbGameStatus",
+               categories as "categories: Vec<DbGameCategory>",
+               tags, platforms, screenshots,
+               rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
+               created_at, updated_at, deleted_at
+          FROM games
+          WHERE status = 'published'::game_status
+               AND deleted_at IS NULL
+               AND release_date <= CURRENT_DATE
+          ORDER BY release_date DESC, created_at DESC
+          LIMIT 1
+          "#
+     )
+     .fetch_optional(pool)
+     .await?;
+
+     Ok(game)
+}
+
+/// Published games released in the last `window_days` days, today
+/// included, most recent first.
+pub async fn list_new_releases(
+     pool: &PgPool,
+     window_days: i32,
+     limit: i32,
+) -> Result<Vec<DbGame>, sqlx::Error> {
+     let games = sqlx::query_as!(
+          DbGame,
+          r#"
+          SELECT 
+               id, name, description, developer_id, publisher_id,
+               cover_image, trailer_url, release_date, price, 
+               status as "status: DbGameStatus",
+               categories as "categories: Vec<DbGameCategory>",
+               tags, platforms, screenshots,
+               rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
+               created_at, updated_at, deleted_at
+          FROM games
+          WHERE status = 'published'::game_status
+               AND deleted_at IS NULL
+               AND release_date <= CURRENT_DATE
+               AND release_date > CURRENT_DATE - $1::int
+          ORDER BY release_date DESC, created_at DESC
+          LIMIT $2
+          "#,
+          window_days,
+          limit as i64
+     )
+     .fetch_all(pool)
+     .await?;
+
+     Ok(games)
+}
+
+/// Published games due out in the next `window_days` days, soonest first.
+pub async fn list_upcoming_games(
+     pool: &PgPool,
+     window_days: i32,
+     limit: i32,
+) -> Result<Vec<DbGame>, sqlx::Error> {
+     let games = sqlx::query_as!(
+          DbGame,
+          r#"
+          SELECT 
+               id, name, description, developer_id, publisher_id,
+               cover_image, trailer_url, release_date, price, 
+               status as "status: DbGameStatus",
+               categories as "categories: Vec<DbGameCategory>",
+               tags, platforms, screenshots,
+               rating_count, average_rating, purchase_count,
+               sale_price, sale_starts_at, sale_ends_at,
+               created_at, updated_at, deleted_at
+          FROM games
+          WHERE status = 'published'::game_status
+               AND deleted_at IS NULL
+               AND release_date > CURRENT_DATE
+               AND release_date <= CURRENT_DATE + $1::int
+          ORDER BY release_date, created_at
+          LIMIT $2
+          "#,
+          window_days,
+          limit as i64
+     )
+     .fetch_all(pool)
+     .await?;
+
+     Ok(games)
+}
+
+/// Folds one more rating into the running average. The row is locked
+/// first so concurrent ratings for the same game apply one after another,
+/// each starting from the count and average the previous one left.
 #[allow(dead_code)]
 pub async fn update_game_rating(
      pool: &PgPool,
      game_id: Uuid,
      new_rating: Decimal,
 ) -> Result<(), sqlx::Error> {
+     let mut tx = pool.begin().await?;
+
+     let current = sqlx::query!(
+          "SELECT rating_count, average_rating FROM games WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+          game_id
+     )
+     .fetch_optional(&mut *tx)
+     .await?;
+
+     let Some(current) = current else {
+          return Ok(());
+     };
+
+     let rating_count = current.rating_count + 1;
+     // Rounded the same way as recompute_game_stats
+     let average_rating = round_rating(
+          (current.average_rating * Decimal::from(current.rating_count) + new_rating) / Decimal::from(rating_count),
+     );
+
      sqlx::query!(
           r#"
           UPDATE games
-          SET 
-               average_rating = (
-                    (average_rating * rating_count + $2) / (rating_count + 1)
-               ),
-               rating_count = rating_count + 1,
-               updated_at = NOW()
-          WHERE id = $1 AND deleted_at IS NULL
+          SET average_rating = $2, rating_count = $3, updated_at = NOW()
+          WHERE id = $1
           "#,
           game_id,
-          new_rating
+          average_rating,
+          rating_count
      )
-     .execute(pool)
+     .execute(&mut *tx)
      .await?;
 
+     tx.commit().await?;
+
      Ok(())
 }
 
@@ -390,6 +1012,209 @@ pub async fn increment_purchase_count(
      Ok(())
 }
 
+/// Recalculates `rating_count`, `average_rating` and `purchase_count` from
+/// `game_reviews` and `game_purchases`, for one game or for every live game.
+/// It is a single statement, so a run either corrects all drifted rows or
+/// none. Only rows whose counters actually changed are written, and their
+/// number is returned.
+pub async fn recompute_game_stats(pool: &PgPool, game_id: Option<Uuid>) -> Result<u64, sqlx::Error> {
+     let result = sqlx::query!(
+          r#"
+          UPDATE games g
+          SET
+               rating_count = s.rating_count,
+               average_rating = s.average_rating,
+               purchase_count = s.purchase_count,
+               updated_at = NOW()
+          FROM (
+               SELECT
+                    games.id,
+                    (SELECT COUNT(*) FROM game_reviews r WHERE r.game_id = games.id)::int AS rating_count,
+                    COALESCE(
+                         (SELECT ROUND(AVG(r.rating), 2) FROM game_reviews r WHERE r.game_id = games.id),
+                         0
+                    ) AS average_rating,
+                    (SELECT COUNT(*) FROM game_purchases p WHERE p.game_id = games.id)::int AS purchase_count
+               FROM games
+               WHERE deleted_at IS NULL AND ($1::uuid IS NULL OR id = $1)
+          ) s
+          WHERE g.id = s.id
+               AND (g.rating_count, g.average_rating, g.purchase_count)
+                    IS DISTINCT FROM (s.rating_count, s.average_rating, s.purchase_count)
+          "#,
+          game_id
+     )
+     .execute(pool)
+     .await?;
+
+     Ok(result.rows_affected())
+}
+
+/// Drafts that were never published and have not been updated since `cutoff`.
+pub async fn find_stale_drafts(pool: &PgPool, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error> {
+     let drafts = sqlx::query_as!(
+          StaleDraft,
+          r#"
+          SELECT id, name, developer_id, updated_at
+          FROM games
+          WHERE status = 'draft'::game_status
+               AND deleted_at IS NULL
+               AND first_published_at IS NULL
+               AND updated_at < $1
+          ORDER BY updated_at
+          "#,
+          cutoff
+     )
+     .fetch_all(pool)
+     .await?;
+
+     Ok(drafts)
+}
+
+/// Soft-deletes what `find_stale_drafts` would return and records an audit
+/// entry for each, in one transaction.
+pub async fn expire_stale_drafts(pool: &PgPool, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error> {
+     let mut tx = pool.begin().await?;
+
+     let drafts = sqlx::query_as!(
+          StaleDraft,
+          r#"
+          WITH stale AS (
+               SELECT id, updated_at
+               FROM games
+               WHERE status = 'draft'::game_status
+                    AND deleted_at IS NULL
+                    AND first_published_at IS NULL
+                    AND updated_at < $1
+               FOR UPDATE
+          )
+          UPDATE games g
+          SET deleted_at = NOW(), updated_at = NOW()
+          FROM stale
+          WHERE g.id = stale.id
+          -- Report the updated_at from before this touched it.
+          RETURNING g.id, g.name, g.developer_id, stale.updated_at
+          "#,
+          cutoff
+     )
+     .fetch_all(&mut *tx)
+     .await?;
+
+     let ids: Vec<Uuid> = drafts.iter().map(|d| d.id).collect();
+     let details: Vec<String> = drafts
+          .iter()
+          .map(|d| format!("Draft expired; last updated {}", d.updated_at.to_rfc3339()))
+          .collect();
+     sqlx::query!(
+          r#"
+          INSERT INTO game_audit_log (game_id, action, detail, before, after)
+          SELECT game_id, 'draft_expired', detail,
+               '{"deleted_at": null}'::jsonb, jsonb_build_object('deleted_at', NOW())
+          FROM UNNEST($1::uuid[], $2::text[]) AS t(game_id, detail)
+          "#,
+          &ids,
+          &details
+     )
+     .execute(&mut *tx)
+     .await?;
+
+     tx.commit().await?;
+     Ok(drafts)
+}
+
+/// Hard-deletes up to `limit` games soft-deleted before `cutoff`, oldest
+/// deletion first, with an audit entry for each holding the row as it was.
+/// Price history, reviews and purchases go with them through their foreign
+/// keys. Rows another purge has already locked are skipped rather than
+/// waited for.
+pub async fn purge_deleted_games(
+     pool: &PgPool,
+     cutoff: DateTime<Utc>,
+     limit: i64,
+     actor_id: Option<Uuid>,
+) -> Result<u64, sqlx::Error> {
+     let mut tx = pool.begin().await?;
+
+     let purged = sqlx::query!(
+          r#"
+          WITH doomed AS (
+               SELECT id
+               FROM games
+               WHERE deleted_at < $1
+               ORDER BY deleted_at
+               LIMIT $2
+               FOR UPDATE SKIP LOCKED
+          )
+          DELETE FROM games g
+          USING doomed
+          WHERE g.id = doomed.id
+          RETURNING g.id, g.name, g.developer_id, g.deleted_at AS "deleted_at!", to_jsonb(g)::text AS "snapshot!"
+          "#,
+          cutoff,
+          limit
+     )
+     .fetch_all(&mut *tx)
+     .await?;
+
+     let ids: Vec<Uuid> = purged.iter().map(|g| g.id).collect();
+     let details: Vec<String> = purged
+          .iter()
+          .map(|g| format!("Purged '{}' by developer {}; deleted {}", g.name, g.developer_id, g.deleted_at.to_rfc3339()))
+          .collect();
+     let snapshots: Vec<String> = purged.iter().map(|g| g.snapshot.clone()).collect();
+     sqlx::query!(
+          r#"
+          INSERT INTO game_audit_log (game_id, actor_id, action, detail, before)
+          SELECT game_id, $4, 'purged', detail, before::jsonb
+          FROM UNNEST($1::uuid[], $2::text[], $3::text[]) AS t(game_id, detail, before)
+          "#,
+          &ids,
+          &details,
+          &snapshots,
+          actor_id
+     )
+     .execute(&mut *tx)
+     .await?;
+
+     tx.commit().await?;
+     Ok(purged.len() as u64)
+}
+
+/// Audit entries matching `filter`, newest first. Pages by id: pass the
+/// last id of one page as `before_id` to get the next.
+pub async fn list_audit(
+     pool: &PgPool,
+     filter: &AuditFilter,
+     limit: i64,
+     before_id: Option<i64>,
+) -> Result<Vec<DbAuditEntry>, sqlx::Error> {
+     sqlx::query_as!(
+          DbAuditEntry,
+          r#"
+          SELECT id, game_id, actor_id, action, detail,
+               before::text AS before, after::text AS after, created_at
+          FROM game_audit_log
+          WHERE ($1::uuid IS NULL OR actor_id = $1)
+               AND ($2::uuid IS NULL OR game_id = $2)
+               AND ($3::text IS NULL OR action = $3)
+               AND ($4::timestamptz IS NULL OR created_at >= $4)
+               AND ($5::timestamptz IS NULL OR created_at < $5)
+               AND ($6::bigint IS NULL OR id < $6)
+          ORDER BY id DESC
+          LIMIT $7
+          "#,
+          filter.actor_id,
+          filter.game_id,
+          filter.action.as_deref(),
+          filter.since,
+          filter.until,
+          before_id,
+          limit
+     )
+     .fetch_all(pool)
+     .await
+}
+
 #[allow(dead_code)]
 pub async fn add_screenshot(
      pool: &PgPool,
@@ -434,4 +1259,792 @@ pub async fn remove_screenshot(
      .await?;
 
      Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+     use chrono::{Days, TimeZone};
+     use common::models::GameStatus;
+     use sqlx::postgres::PgConnectOptions;
+
+     use super::*;
+
+     fn new_game(name: &str) -> NewGame {
+          NewGame {
+               name: name.to_string(),
+               description: "A game".to_string(),
+               developer_id: Uuid::new_v4(),
+               publisher_id: None,
+               cover_image: Some("https://example.com/cover.png".to_string()),
+               trailer_url: None,
+               release_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 1),
+               categories: vec![DbGameCategory::Action],
+               tags: vec![],
+               platforms: vec![],
+               price: Decimal::new(4000, 2),
+          }
+     }
+
+     /// A published action game priced at 40.00, on sale for 10.00 from
+     /// `starts_at` to `ends_at`.
+     async fn game_on_sale(pool: &PgPool, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> Uuid {
+          let game = create_game(pool, new_game("Sale Game")).await.unwrap();
+          update_game(pool, game.id, GameChanges { status: Some(GameStatus::Published), ..Default::default() })
+               .await
+               .unwrap();
+          set_sale(pool, game.id, Some(SaleSchedule { price: Decimal::new(1000, 2), starts_at, ends_at }))
+               .await
+               .unwrap();
+          game.id
+     }
+
+     async fn game_deleted_at(pool: &PgPool, deleted_at: Option<DateTime<Utc>>) -> Uuid {
+          let game = create_game(pool, new_game("Deleted Game")).await.unwrap();
+          sqlx::query("UPDATE games SET deleted_at = $2 WHERE id = $1")
+               .bind(game.id)
+               .bind(deleted_at)
+               .execute(pool)
+               .await
+               .unwrap();
+          game.id
+     }
+
+     /// Sets `updated_at`, which the update trigger would otherwise
+     /// overwrite with the current time.
+     async fn backdate(pool: &PgPool, ids: &[Uuid], updated_at: DateTime<Utc>) {
+          let mut tx = pool.begin().await.unwrap();
+          sqlx::query("ALTER TABLE games DISABLE TRIGGER update_games_updated_at")
+               .execute(&mut *tx)
+               .await
+               .unwrap();
+          sqlx::query("UPDATE games SET updated_at = $2 WHERE id = ANY($1)")
+               .bind(ids)
+               .bind(updated_at)
+               .execute(&mut *tx)
+               .await
+               .unwrap();
+          sqlx::query("ALTER TABLE games ENABLE TRIGGER update_games_updated_at")
+               .execute(&mut *tx)
+               .await
+               .unwrap();
+          tx.commit().await.unwrap();
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn every_status_round_trips_through_the_database_enum(pool: PgPool) {
+          use strum::IntoEnumIterator;
+
+          let labels: Vec<String> = sqlx::query_scalar("SELECT unnest(enum_range(NULL::game_status))::text")
+               .fetch_all(&pool)
+               .await
+               .unwrap();
+          let spelled: Vec<String> = GameStatus::iter().map(|s| s.to_string()).collect();
+          assert_eq!(labels, spelled);
+
+          for status in GameStatus::iter() {
+               let stored: DbGameStatus = sqlx::query_scalar("SELECT $1::text::game_status")
+                    .bind(status.to_string())
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+               assert_eq!(GameStatus::from(stored), status);
+          }
+     }
+
+     /// A game with `status` by `developer_id`, released on `release_date`
+     /// and bought `purchases` times.
+     async fn catalog_game(
+          pool: &PgPool,
+          name: &str,
+          developer_id: Uuid,
+          status: GameStatus,
+          release_date: chrono::NaiveDate,
+          purchases: i32,
+     ) -> Uuid {
+          let game = create_game(pool, NewGame { developer_id, release_date: Some(release_date), ..new_game(name) })
+               .await
+               .unwrap();
+          update_game(pool, game.id, GameChanges { status: Some(status), ..Default::default() })
+               .await
+               .unwrap();
+          sqlx::query("UPDATE games SET purchase_count = $2 WHERE id = $1")
+               .bind(game.id)
+               .bind(purchases)
+               .execute(pool)
+               .await
+               .unwrap();
+          game.id
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn catalog_stats_count_only_live_published_games(pool: PgPool) {
+          let today = Utc::now().date_naive();
+          let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+          let old_hit = catalog_game(&pool, "Old Hit", alice, GameStatus::Published, today - Days::new(900), 50).await;
+          let newest = catalog_game(&pool, "Newest", alice, GameStatus::Published, today - Days::new(1), 5).await;
+          catalog_game(&pool, "Preorder", bob, GameStatus::Published, today + Days::new(30), 0).await;
+          catalog_game(&pool, "Unreleased Draft", carol, GameStatus::Draft, today - Days::new(1), 999).await;
+          let deleted = catalog_game(&pool, "Delisted", carol, GameStatus::Published, today, 1000).await;
+          delete_game(&pool, deleted, carol).await.unwrap();
+
+          assert_eq!(count_published_catalog(&pool).await.unwrap(), (3, 2));
+          assert_eq!(get_newest_release(&pool).await.unwrap().map(|g| g.id), Some(newest));
+          let popular = get_popular_games(&pool, 1).await.unwrap();
+          assert_eq!(popular.iter().map(|g| g.id).collect::<Vec<_>>(), vec![old_hit]);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn catalog_stats_of_an_empty_catalog(pool: PgPool) {
+          assert_eq!(count_published_catalog(&pool).await.unwrap(), (0, 0));
+          assert!(get_newest_release(&pool).await.unwrap().is_none());
+          assert!(get_popular_games(&pool, 1).await.unwrap().is_empty());
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn releases_split_at_today_newest_and_soonest_first(pool: PgPool) {
+          let today = Utc::now().date_naive();
+          let dev = Uuid::new_v4();
+          let game = |name: &'static str, status: GameStatus, release_date: chrono::NaiveDate| {
+               let pool = pool.clone();
+               async move { catalog_game(&pool, name, dev, status, release_date, 0).await }
+          };
+          game("Last Quarter", GameStatus::Published, today - Days::new(60)).await;
+          let recent = game("Last Week", GameStatus::Published, today - Days::new(10)).await;
+          let yesterday = game("Yesterday", GameStatus::Published, today - Days::new(1)).await;
+          let launch = game("Launch Day", GameStatus::Published, today).await;
+          let launch_later = game("Launch Day Too", GameStatus::Published, today).await;
+          let tomorrow = game("Tomorrow", GameStatus::Published, today + Days::new(1)).await;
+          let soon = game("Next Month", GameStatus::Published, today + Days::new(20)).await;
+          let far = game("Next Year", GameStatus::Published, today + Days::new(200)).await;
+          game("Draft", GameStatus::Draft, today - Days::new(1)).await;
+          game("Draft Preorder", GameStatus::Draft, today + Days::new(1)).await;
+          let deleted = game("Delisted", GameStatus::Published, today).await;
+          delete_game(&pool, deleted, dev).await.unwrap();
+
+          let ids = |games: Vec<DbGame>| games.into_iter().map(|g| g.id).collect::<Vec<_>>();
+          assert_eq!(
+               ids(list_new_releases(&pool, 30, 10).await.unwrap()),
+               [launch_later, launch, yesterday, recent]
+          );
+          assert_eq!(ids(list_new_releases(&pool, 5, 10).await.unwrap()), [launch_later, launch, yesterday]);
+          assert_eq!(ids(list_new_releases(&pool, 30, 2).await.unwrap()), [launch_later, launch]);
+
+          assert_eq!(ids(list_upcoming_games(&pool, 90, 10).await.unwrap()), [tomorrow, soon]);
+          assert_eq!(ids(list_upcoming_games(&pool, 365, 10).await.unwrap()), [tomorrow, soon, far]);
+          assert_eq!(ids(list_upcoming_games(&pool, 365, 1).await.unwrap()), [tomorrow]);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn misspelled_names_are_found_by_the_trigram_fallback(pool: PgPool) {
+          let adventure = create_game(&pool, new_game("Grand Adventure")).await.unwrap().id;
+          create_game(&pool, new_game("Racing Stars")).await.unwrap();
+          let filter = GameFilter::default();
+
+          let (games, total) = list_games(&pool, &filter, Some("advntur"), 10, 0).await.unwrap();
+          assert!(games.is_empty() && total == 0);
+
+          let (games, total) = fuzzy_search_games(&pool, &filter, "advntur", 0.3, 10, 0).await.unwrap();
+          assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), vec![adventure]);
+          assert_eq!(total, 1);
+
+          let (games, total) = fuzzy_search_games(&pool, &filter, "advntur", 0.9, 10, 0).await.unwrap();
+          assert!(games.is_empty() && total == 0);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn closer_trigram_matches_rank_first(pool: PgPool) {
+          let close = create_game(&pool, new_game("Adventure")).await.unwrap().id;
+          let far = create_game(&pool, new_game("Advent Calendar")).await.unwrap().id;
+          create_game(&pool, new_game("Adrift")).await.unwrap();
+
+          let (games, _) = fuzzy_search_games(&pool, &GameFilter::default(), "advntur", 0.3, 10, 0).await.unwrap();
+          assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), vec![close, far]);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn slow_queries_are_cancelled_by_the_statement_timeout(_: PgPoolOptions, options: PgConnectOptions) {
+          let pool = pool_options(Duration::from_millis(100)).connect_with(options).await.unwrap();
+
+          let err = sqlx::query("SELECT pg_sleep(5)").execute(&pool).await.unwrap_err();
+          assert!(is_statement_timeout(&err), "{err}");
+
+          // The connection survives the cancellation.
+          let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&pool).await.unwrap();
+          assert_eq!(one, 1);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn a_zero_statement_timeout_means_no_limit(_: PgPoolOptions, options: PgConnectOptions) {
+          let pool = pool_options(Duration::ZERO).connect_with(options).await.unwrap();
+
+          sqlx::query("SELECT pg_sleep(0.2)").execute(&pool).await.unwrap();
+     }
+
+     #[test]
+     fn other_errors_are_not_timeouts() {
+          assert!(!is_statement_timeout(&sqlx::Error::RowNotFound));
+          assert!(!is_statement_timeout(&sqlx::Error::PoolTimedOut));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn deleted_games_are_listed_and_counted_only_when_asked_for(pool: PgPool) {
+          let live = create_game(&pool, new_game("Live")).await.unwrap().id;
+          let deleted = game_deleted_at(&pool, Some(Utc::now())).await;
+
+          let (games, total) = list_games(&pool, &GameFilter::default(), None, 10, 0).await.unwrap();
+          assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), vec![live]);
+          assert_eq!(total, 1);
+
+          let everything = GameFilter { include_deleted: true, ..Default::default() };
+          let (games, total) = list_games(&pool, &everything, None, 10, 0).await.unwrap();
+          let mut ids: Vec<Uuid> = games.iter().map(|g| g.id).collect();
+          ids.sort();
+          let mut expected = vec![live, deleted];
+          expected.sort();
+          assert_eq!(ids, expected);
+          assert_eq!(total, 2);
+          assert!(games.iter().find(|g| g.id == deleted).unwrap().deleted_at.is_some());
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn created_rows_come_back_with_their_stored_timestamps(pool: PgPool) {
+          let created = create_game(&pool, new_game("Timestamps")).await.unwrap();
+          let stored = get_game_by_id(&pool, created.id).await.unwrap().unwrap();
+
+          assert_eq!(created.created_at, stored.created_at);
+          assert_eq!(created.updated_at, stored.updated_at);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn suggestions_match_name_prefixes_most_purchased_first(pool: PgPool) {
+          let today = Utc::now().date_naive();
+          let dev = Uuid::new_v4();
+          let published = |name, purchases| catalog_game(&pool, name, dev, GameStatus::Published, today, purchases);
+          let quest = published("Space Quest", 10).await;
+          let race = published("Space Race", 50).await;
+          let rangers = published("space rangers", 10).await;
+          published("Lost in Space", 500).await;
+          catalog_game(&pool, "Space Draft", dev, GameStatus::Draft, today, 900).await;
+
+          let suggested = suggest_games(&pool, "SPACE", 10).await.unwrap();
+          assert_eq!(suggested.iter().map(|g| g.id).collect::<Vec<_>>(), vec![race, quest, rangers]);
+
+          let suggested = suggest_games(&pool, "space", 2).await.unwrap();
+          assert_eq!(suggested.iter().map(|g| g.name.as_str()).collect::<Vec<_>>(), ["Space Race", "Space Quest"]);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn suggestion_prefixes_are_matched_literally(pool: PgPool) {
+          let today = Utc::now().date_naive();
+          let dev = Uuid::new_v4();
+          for name in ["100% Fun", "1000 Fun", "A_B Game", "AXB Game", "C\\D", "CXD"] {
+               catalog_game(&pool, name, dev, GameStatus::Published, today, 0).await;
+          }
+          let names = |suggestions: Vec<GameSuggestion>| suggestions.into_iter().map(|g| g.name).collect::<Vec<_>>();
+
+          assert_eq!(names(suggest_games(&pool, "100%", 10).await.unwrap()), ["100% Fun"]);
+          assert_eq!(names(suggest_games(&pool, "a_b", 10).await.unwrap()), ["A_B Game"]);
+          assert_eq!(names(suggest_games(&pool, "c\\", 10).await.unwrap()), ["C\\D"]);
+          assert!(suggest_games(&pool, "%", 10).await.unwrap().is_empty());
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn price_queries_use_the_sale_price_only_during_the_sale(pool: PgPool) {
+          let now = Utc::now();
+          let before = game_on_sale(&pool, now + Days::new(1), now + Days::new(2)).await;
+          let during = game_on_sale(&pool, now - Days::new(1), now + Days::new(1)).await;
+          let after = game_on_sale(&pool, now - Days::new(2), now - Days::new(1)).await;
+
+          let cheap = GameFilter { max_price: Some(Decimal::new(2000, 2)), ..Default::default() };
+          let (games, total) = list_games(&pool, &cheap, None, 10, 0).await.unwrap();
+          assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), vec![during]);
+          assert_eq!(total, 1);
+
+          let full_price = GameFilter { min_price: Some(Decimal::new(3000, 2)), ..Default::default() };
+          let (games, total) = list_games(&pool, &full_price, None, 10, 0).await.unwrap();
+          let mut ids: Vec<Uuid> = games.iter().map(|g| g.id).collect();
+          ids.sort();
+          let mut expected = vec![before, after];
+          expected.sort();
+          assert_eq!(ids, expected);
+          assert_eq!(total, 2);
+
+          let (games, total) = fuzzy_search_games(&pool, &cheap, "Sale Game", 0.3, 10, 0).await.unwrap();
+          assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), vec![during]);
+          assert_eq!(total, 1);
+
+          let facets = get_price_facets(&pool, &GameFilter::default(), None, &[Decimal::new(2000, 2)])
+               .await
+               .unwrap();
+          assert_eq!(facets, vec![1, 2]);
+
+          let stats = get_category_price_stats(&pool, DbGameCategory::Action).await.unwrap();
+          assert_eq!(stats.game_count, 3);
+          assert_eq!(stats.min_price, Some(Decimal::new(1000, 2)));
+          assert_eq!(stats.max_price, Some(Decimal::new(4000, 2)));
+          assert_eq!(stats.avg_price, Some(Decimal::new(3000, 2)));
+          assert_eq!(stats.median_price, Some(Decimal::new(4000, 2)));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn purge_removes_only_games_deleted_before_the_cutoff(pool: PgPool) {
+          let now = Utc::now();
+          let old = game_deleted_at(&pool, Some(now - Days::new(30))).await;
+          let recent = game_deleted_at(&pool, Some(now - Days::new(2))).await;
+          let live = game_deleted_at(&pool, None).await;
+          let admin = Uuid::new_v4();
+
+          let purged = purge_deleted_games(&pool, now - Days::new(7), 10, Some(admin)).await.unwrap();
+
+          assert_eq!(purged, 1);
+          let mut remaining: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM games").fetch_all(&pool).await.unwrap();
+          remaining.sort();
+          let mut expected = vec![recent, live];
+          expected.sort();
+          assert_eq!(remaining, expected);
+
+          let audit = list_audit(&pool, &AuditFilter::default(), 10, None).await.unwrap();
+          assert_eq!(audit.len(), 1);
+          assert_eq!(audit[0].game_id, old);
+          assert_eq!(audit[0].action, "purged");
+          assert_eq!(audit[0].actor_id, Some(admin));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn each_price_change_is_recorded_in_order(pool: PgPool) {
+          let game = create_game(&pool, new_game("Priced Game")).await.unwrap();
+          let set_price = |cents| GameChanges { price: Some(Decimal::new(cents, 2)), ..Default::default() };
+
+          update_game(&pool, game.id, set_price(3000)).await.unwrap();
+          update_game(&pool, game.id, set_price(3000)).await.unwrap();
+          update_game(&pool, game.id, set_price(2000)).await.unwrap();
+
+          let history = list_price_history(&pool, game.id).await.unwrap();
+          let changes: Vec<(Decimal, Decimal)> = history.iter().map(|h| (h.old_price, h.new_price)).collect();
+          assert_eq!(
+               changes,
+               vec![(Decimal::new(4000, 2), Decimal::new(3000, 2)), (Decimal::new(3000, 2), Decimal::new(2000, 2))]
+          );
+          assert!(history[0].changed_at < history[1].changed_at);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn only_dated_games_can_be_stored_as_published(pool: PgPool) {
+          let draft = create_game(&pool, NewGame { release_date: None, ..new_game("Someday") }).await.unwrap();
+          assert_eq!(draft.release_date, None);
+
+          let publish = GameChanges { status: Some(GameStatus::Published), ..Default::default() };
+          assert!(update_game(&pool, draft.id, publish.clone()).await.is_err());
+
+          let dated = GameChanges { release_date: chrono::NaiveDate::from_ymd_opt(2025, 3, 1), ..publish };
+          let published = update_game(&pool, draft.id, dated).await.unwrap().unwrap();
+          assert!(matches!(published.status, DbGameStatus::Published));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn average_ratings_are_stored_and_read_back_exactly(pool: PgPool) {
+          let game = create_game(&pool, new_game("Rated")).await.unwrap();
+          for rating in [5, 4, 4] {
+               update_game_rating(&pool, game.id, Decimal::from(rating)).await.unwrap();
+          }
+
+          let stored = get_game_by_id(&pool, game.id).await.unwrap().unwrap();
+          assert_eq!(stored.rating_count, 3);
+          assert_eq!(stored.average_rating, Decimal::new(433, 2));
+          assert_eq!(crate::models::rating_to_f64(stored.average_rating), 4.33);
+
+          for cents in [0, 1, 99, 250, 499, 500] {
+               let rating = Decimal::new(cents, 2);
+               sqlx::query("UPDATE games SET average_rating = $2 WHERE id = $1")
+                    .bind(game.id)
+                    .bind(rating)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+               let stored = get_game_by_id(&pool, game.id).await.unwrap().unwrap();
+               assert_eq!(stored.average_rating, rating);
+          }
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn a_developers_other_published_games_leave_out_the_current_one(pool: PgPool) {
+          let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+          let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+          let current = catalog_game(&pool, "Current", alice, GameStatus::Published, date, 0).await;
+          let sequel = catalog_game(&pool, "Sequel", alice, GameStatus::Published, date, 0).await;
+          catalog_game(&pool, "Unfinished", alice, GameStatus::Draft, date, 0).await;
+          catalog_game(&pool, "Someone Else's", bob, GameStatus::Published, date, 0).await;
+
+          let filter = GameFilter {
+               developer_id: Some(alice),
+               status: Some(GameStatus::Published),
+               exclude_id: Some(current),
+               ..Default::default()
+          };
+          let (games, total) = list_games(&pool, &filter, None, 10, 0).await.unwrap();
+          assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), [sequel]);
+          assert_eq!(total, 1);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn the_row_estimate_is_unknown_until_the_table_is_analyzed(pool: PgPool) {
+          assert_eq!(estimate_game_rows(&pool).await.unwrap(), None);
+
+          for name in ["One", "Two", "Three"] {
+               create_game(&pool, new_game(name)).await.unwrap();
+          }
+          sqlx::query("ANALYZE games").execute(&pool).await.unwrap();
+
+          assert_eq!(estimate_game_rows(&pool).await.unwrap(), Some(3));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn only_never_published_drafts_older_than_the_cutoff_expire(pool: PgPool) {
+          let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+          let developer = Uuid::new_v4();
+          let stale = catalog_game(&pool, "Stale Draft", developer, GameStatus::Draft, date, 0).await;
+          let recent = catalog_game(&pool, "Recent Draft", developer, GameStatus::Draft, date, 0).await;
+          let unpublished = catalog_game(&pool, "Pulled Game", developer, GameStatus::Published, date, 0).await;
+          update_game(&pool, unpublished, GameChanges { status: Some(GameStatus::Draft), ..Default::default() })
+               .await
+               .unwrap();
+          let live = catalog_game(&pool, "Live Game", developer, GameStatus::Published, date, 0).await;
+          let deleted = catalog_game(&pool, "Deleted Draft", developer, GameStatus::Draft, date, 0).await;
+          delete_game(&pool, deleted, developer).await.unwrap();
+
+          let cutoff = Utc::now() - chrono::Duration::days(30);
+          backdate(&pool, &[stale, unpublished, live, deleted], cutoff - chrono::Duration::days(1)).await;
+
+          let found: Vec<Uuid> = find_stale_drafts(&pool, cutoff).await.unwrap().iter().map(|d| d.id).collect();
+          assert_eq!(found, [stale]);
+
+          let expired: Vec<Uuid> = expire_stale_drafts(&pool, cutoff).await.unwrap().iter().map(|d| d.id).collect();
+          assert_eq!(expired, [stale]);
+          let deleted: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM games WHERE deleted_at IS NOT NULL AND id <> $1")
+               .bind(deleted)
+               .fetch_all(&pool)
+               .await
+               .unwrap();
+          assert_eq!(deleted, [stale]);
+          assert!(get_game_by_id(&pool, recent).await.unwrap().is_some());
+
+          assert!(expire_stale_drafts(&pool, cutoff).await.unwrap().is_empty());
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn listings_filter_by_publisher(pool: PgPool) {
+          let (publisher, rival) = (Uuid::new_v4(), Uuid::new_v4());
+          let published_by = |name: &str, publisher_id: Option<Uuid>| NewGame { publisher_id, ..new_game(name) };
+          let first = create_game(&pool, published_by("First", Some(publisher))).await.unwrap().id;
+          let second = create_game(&pool, published_by("Second", Some(publisher))).await.unwrap().id;
+          create_game(&pool, published_by("Rival's", Some(rival))).await.unwrap();
+          create_game(&pool, published_by("Self-published", None)).await.unwrap();
+
+          let filter = GameFilter { publisher_id: Some(publisher), ..Default::default() };
+          let (games, total) = list_games(&pool, &filter, None, 10, 0).await.unwrap();
+          let mut ids: Vec<Uuid> = games.iter().map(|g| g.id).collect();
+          ids.sort();
+          let mut expected = vec![first, second];
+          expected.sort();
+          assert_eq!(ids, expected);
+          assert_eq!(total, 2);
+
+          let (_, everyone) = list_games(&pool, &GameFilter::default(), None, 10, 0).await.unwrap();
+          assert_eq!(everyone, 4);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn every_mutation_touches_updated_at_without_the_trigger(pool: PgPool) {
+          // With the trigger off, only the statements themselves can move it.
+          sqlx::query("ALTER TABLE games DISABLE TRIGGER update_games_updated_at")
+               .execute(&pool)
+               .await
+               .unwrap();
+          let id = create_game(&pool, new_game("Touched")).await.unwrap().id;
+          let long_ago = Utc::now() - chrono::Duration::days(365);
+
+          let reset = || async {
+               sqlx::query("UPDATE games SET updated_at = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(long_ago)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+          };
+          let touched = || async {
+               let updated_at: DateTime<Utc> = sqlx::query_scalar("SELECT updated_at FROM games WHERE id = $1")
+                    .bind(id)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+               updated_at > long_ago
+          };
+
+          reset().await;
+          update_game(&pool, id, GameChanges { name: Some("Renamed".to_string()), ..Default::default() })
+               .await
+               .unwrap();
+          assert!(touched().await, "update_game");
+
+          reset().await;
+          let sale = SaleSchedule {
+               price: Decimal::new(1000, 2),
+               starts_at: Utc::now(),
+               ends_at: Utc::now() + chrono::Duration::days(7),
+          };
+          set_sale(&pool, id, Some(sale)).await.unwrap();
+          assert!(touched().await, "set_sale");
+
+          reset().await;
+          bulk_set_prices(&pool, &[(id, Decimal::new(2500, 2))], true).await.unwrap();
+          assert!(touched().await, "bulk_set_prices");
+
+          reset().await;
+          update_game_rating(&pool, id, Decimal::from(4)).await.unwrap();
+          assert!(touched().await, "update_game_rating");
+
+          reset().await;
+          increment_purchase_count(&pool, id).await.unwrap();
+          assert!(touched().await, "increment_purchase_count");
+
+          // The rating and purchase above have no rows behind them, so this
+          // puts both counts back to zero.
+          reset().await;
+          assert_eq!(recompute_game_stats(&pool, Some(id)).await.unwrap(), 1);
+          assert!(touched().await, "recompute_game_stats");
+
+          reset().await;
+          add_screenshot(&pool, id, "https://example.com/shot.png".to_string()).await.unwrap();
+          assert!(touched().await, "add_screenshot");
+
+          reset().await;
+          remove_screenshot(&pool, id, "https://example.com/shot.png".to_string()).await.unwrap();
+          assert!(touched().await, "remove_screenshot");
+
+          let developer_id = get_game_by_id(&pool, id).await.unwrap().unwrap().developer_id;
+          reset().await;
+          assert!(delete_game(&pool, id, developer_id).await.unwrap());
+          assert!(touched().await, "delete_game");
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn category_price_stats_use_live_published_games_at_their_current_price(pool: PgPool) {
+          let game = |name: &str, cents: i64, category: DbGameCategory, status: GameStatus| {
+               let pool = pool.clone();
+               let name = name.to_string();
+               async move {
+                    let new = NewGame { price: Decimal::new(cents, 2), categories: vec![category], ..new_game(&name) };
+                    let id = create_game(&pool, new).await.unwrap().id;
+                    update_game(&pool, id, GameChanges { status: Some(status), ..Default::default() })
+                         .await
+                         .unwrap();
+                    id
+               }
+          };
+          game("Ten", 1000, DbGameCategory::Action, GameStatus::Published).await;
+          game("Twenty", 2000, DbGameCategory::Action, GameStatus::Published).await;
+          let on_sale = game("Forty", 4000, DbGameCategory::Action, GameStatus::Published).await;
+          game("Sixty", 6000, DbGameCategory::Action, GameStatus::Published).await;
+          game("Unreleased", 10000, DbGameCategory::Action, GameStatus::Draft).await;
+          let deleted = game("Removed", 100, DbGameCategory::Action, GameStatus::Published).await;
+          game("Other Category", 50000, DbGameCategory::Rpg, GameStatus::Published).await;
+
+          let sale = SaleSchedule {
+               price: Decimal::new(3000, 2),
+               starts_at: Utc::now() - chrono::Duration::hours(1),
+               ends_at: Utc::now() + chrono::Duration::days(1),
+          };
+          set_sale(&pool, on_sale, Some(sale)).await.unwrap();
+          let developer_id = get_game_by_id(&pool, deleted).await.unwrap().unwrap().developer_id;
+          delete_game(&pool, deleted, developer_id).await.unwrap();
+
+          // 10, 20, 30 (on sale from 40) and 60
+          let stats = get_category_price_stats(&pool, DbGameCategory::Action).await.unwrap();
+          assert_eq!(stats.game_count, 4);
+          assert_eq!(stats.min_price, Some(Decimal::new(1000, 2)));
+          assert_eq!(stats.max_price, Some(Decimal::new(6000, 2)));
+          assert_eq!(stats.avg_price, Some(Decimal::new(3000, 2)));
+          assert_eq!(stats.median_price, Some(Decimal::new(2500, 2)));
+
+          let empty = get_category_price_stats(&pool, DbGameCategory::Puzzle).await.unwrap();
+          assert_eq!(empty.game_count, 0);
+          assert_eq!((empty.min_price, empty.max_price, empty.avg_price, empty.median_price), (None, None, None, None));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn price_facets_count_each_bound_in_the_bucket_above_it(pool: PgPool) {
+          for (name, cents, category) in [
+               ("Free", 0, DbGameCategory::Action),
+               ("Almost Ten", 999, DbGameCategory::Action),
+               ("Ten", 1000, DbGameCategory::Action),
+               ("Almost Twenty", 1999, DbGameCategory::Rpg),
+               ("Twenty", 2000, DbGameCategory::Action),
+               ("Almost Sixty", 5999, DbGameCategory::Action),
+               ("Sixty", 6000, DbGameCategory::Rpg),
+               ("Collector's Edition", 25000, DbGameCategory::Action),
+          ] {
+               let new = NewGame { price: Decimal::new(cents, 2), categories: vec![category], ..new_game(name) };
+               create_game(&pool, new).await.unwrap();
+          }
+          let bounds = [1000, 2000, 4000, 6000].map(|cents| Decimal::new(cents, 2));
+
+          let all = get_price_facets(&pool, &GameFilter::default(), None, &bounds).await.unwrap();
+          assert_eq!(all, [2, 2, 1, 1, 2]);
+
+          let rpg = GameFilter { categories: Some(vec![DbGameCategory::Rpg]), ..Default::default() };
+          assert_eq!(get_price_facets(&pool, &rpg, None, &bounds).await.unwrap(), [0, 1, 0, 0, 1]);
+
+          let none = GameFilter { categories: Some(vec![DbGameCategory::Puzzle]), ..Default::default() };
+          assert_eq!(get_price_facets(&pool, &none, None, &bounds).await.unwrap(), [0, 0, 0, 0, 0]);
+
+          let single = get_price_facets(&pool, &GameFilter::default(), None, &[Decimal::new(2000, 2)]).await.unwrap();
+          assert_eq!(single, [4, 4]);
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn a_missing_game_rolls_back_a_strict_price_batch_only(pool: PgPool) {
+          let first = create_game(&pool, new_game("First")).await.unwrap().id;
+          let second = create_game(&pool, new_game("Second")).await.unwrap().id;
+          let updates = [(first, Decimal::new(999, 2)), (Uuid::new_v4(), Decimal::new(500, 2)), (second, Decimal::new(1499, 2))];
+          let price = |id: Uuid| {
+               let pool = pool.clone();
+               async move { get_game_by_id(&pool, id).await.unwrap().unwrap().price }
+          };
+
+          let strict = bulk_set_prices(&pool, &updates, true).await.unwrap();
+          assert!(strict[1].is_none());
+          assert_eq!((price(first).await, price(second).await), (Decimal::new(4000, 2), Decimal::new(4000, 2)));
+
+          let lenient = bulk_set_prices(&pool, &updates, false).await.unwrap();
+          assert!(lenient[1].is_none());
+          let (old_price, updated) = lenient[0].clone().unwrap();
+          assert_eq!((old_price, updated.price), (Decimal::new(4000, 2), Decimal::new(999, 2)));
+          assert_eq!((price(first).await, price(second).await), (Decimal::new(999, 2), Decimal::new(1499, 2)));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn review_histograms_count_each_star_rating(pool: PgPool) {
+          let game = create_game(&pool, new_game("Reviewed")).await.unwrap();
+          for rating in [5i16, 5, 5, 4, 2, 1, 1] {
+               sqlx::query("INSERT INTO game_reviews (game_id, user_id, rating) VALUES ($1, $2, $3)")
+                    .bind(game.id)
+                    .bind(Uuid::new_v4())
+                    .bind(rating)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+          }
+          let unreviewed = create_game(&pool, new_game("Unreviewed")).await.unwrap();
+
+          let summary = get_review_summary(&pool, game.id).await.unwrap().unwrap();
+          assert_eq!(summary.star_counts, [2, 1, 0, 1, 3]);
+          assert_eq!(summary.total(), 7);
+          assert_eq!(summary.star_counts.iter().sum::<i64>(), summary.total());
+          // 23 stars over 7 reviews
+          assert_eq!(summary.average(), Decimal::new(329, 2));
+
+          let empty = get_review_summary(&pool, unreviewed.id).await.unwrap().unwrap();
+          assert_eq!(empty.star_counts, [0; 5]);
+
+          assert!(get_review_summary(&pool, Uuid::new_v4()).await.unwrap().is_none());
+          delete_game(&pool, game.id, game.developer_id).await.unwrap();
+          assert!(get_review_summary(&pool, game.id).await.unwrap().is_none());
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn concurrent_ratings_are_all_counted(pool: PgPool) {
+          let game = create_game(&pool, new_game("Popular")).await.unwrap();
+
+          let ratings: Vec<_> = (0..50)
+               .map(|_| {
+                    let pool = pool.clone();
+                    tokio::spawn(async move { update_game_rating(&pool, game.id, Decimal::from(4)).await })
+               })
+               .collect();
+          for rating in ratings {
+               rating.await.unwrap().unwrap();
+          }
+
+          let stored = get_game_by_id(&pool, game.id).await.unwrap().unwrap();
+          assert_eq!(stored.rating_count, 50);
+          assert_eq!(stored.average_rating, Decimal::new(400, 2));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn status_counts_cover_only_the_developers_live_games(pool: PgPool) {
+          let (developer, rival) = (Uuid::new_v4(), Uuid::new_v4());
+          let released = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+          for status in [
+               GameStatus::Draft,
+               GameStatus::Draft,
+               GameStatus::UnderReview,
+               GameStatus::Published,
+               GameStatus::Published,
+               GameStatus::Published,
+               GameStatus::Suspended,
+          ] {
+               catalog_game(&pool, "Mine", developer, status, released, 0).await;
+          }
+          let deleted = catalog_game(&pool, "Removed", developer, GameStatus::Published, released, 0).await;
+          delete_game(&pool, deleted, developer).await.unwrap();
+          catalog_game(&pool, "Theirs", rival, GameStatus::Draft, released, 0).await;
+
+          let counts = get_developer_status_counts(&pool, developer).await.unwrap();
+          assert_eq!((counts.draft, counts.under_review, counts.published, counts.suspended), (2, 1, 3, 1));
+
+          let none = get_developer_status_counts(&pool, Uuid::new_v4()).await.unwrap();
+          assert_eq!((none.draft, none.under_review, none.published, none.suspended), (0, 0, 0, 0));
+     }
+
+     #[sqlx::test(migrations = "./migrations")]
+     async fn audit_entries_filter_and_page_newest_first(pool: PgPool) {
+          let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+          let (first_game, second_game) = (Uuid::new_v4(), Uuid::new_v4());
+          let day = |d: u32| Utc.with_ymd_and_hms(2024, 5, d, 12, 0, 0).unwrap();
+          let mut ids = Vec::new();
+          for (game_id, actor_id, action, created_at) in [
+               (first_game, Some(alice), "purged", day(1)),
+               (second_game, Some(bob), "purged", day(2)),
+               (first_game, None, "draft_expired", day(3)),
+               (second_game, Some(alice), "purged", day(4)),
+               (first_game, Some(alice), "draft_expired", day(5)),
+          ] {
+               let id: i64 = sqlx::query_scalar(
+                    "INSERT INTO game_audit_log (game_id, actor_id, action, detail, created_at) VALUES ($1, $2, $3, '', $4) RETURNING id",
+               )
+               .bind(game_id)
+               .bind(actor_id)
+               .bind(action)
+               .bind(created_at)
+               .fetch_one(&pool)
+               .await
+               .unwrap();
+               ids.push(id);
+          }
+          let listed = |filter: AuditFilter, limit: i64, before_id: Option<i64>| {
+               let pool = pool.clone();
+               async move {
+                    let entries = list_audit(&pool, &filter, limit, before_id).await.unwrap();
+                    entries.into_iter().map(|e| e.id).collect::<Vec<i64>>()
+               }
+          };
+
+          assert_eq!(listed(AuditFilter::default(), 10, None).await, [ids[4], ids[3], ids[2], ids[1], ids[0]]);
+          let by_alice = AuditFilter { actor_id: Some(alice), ..Default::default() };
+          assert_eq!(listed(by_alice, 10, None).await, [ids[4], ids[3], ids[0]]);
+          let first = AuditFilter { game_id: Some(first_game), ..Default::default() };
+          assert_eq!(listed(first, 10, None).await, [ids[4], ids[2], ids[0]]);
+          let purges = AuditFilter { action: Some("purged".to_string()), ..Default::default() };
+          assert_eq!(listed(purges, 10, None).await, [ids[3], ids[1], ids[0]]);
+          let window = AuditFilter { since: Some(day(2)), until: Some(day(4)), ..Default::default() };
+          assert_eq!(listed(window, 10, None).await, [ids[2], ids[1]]);
+          let combined = AuditFilter { actor_id: Some(alice), game_id: Some(first_game), ..Default::default() };
+          assert_eq!(listed(combined, 10, None).await, [ids[4], ids[0]]);
+
+          // Each page starts below the last id of the one before
+          assert_eq!(listed(AuditFilter::default(), 2, None).await, [ids[4], ids[3]]);
+          assert_eq!(listed(AuditFilter::default(), 2, Some(ids[3])).await, [ids[2], ids[1]]);
+          assert_eq!(listed(AuditFilter::default(), 2, Some(ids[1])).await, [ids[0]]);
+          let by_alice = AuditFilter { actor_id: Some(alice), ..Default::default() };
+          assert_eq!(listed(by_alice, 2, Some(ids[3])).await, [ids[0]]);
+     }
+}
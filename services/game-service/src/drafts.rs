@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::repository::GameRepository;
+
+/// Removes drafts nobody has touched for a while. Off unless
+/// `DRAFT_EXPIRY_DAYS` is set; `DRAFT_EXPIRY_CHECK_INTERVAL_SECS` controls
+/// how often the job looks (hourly by default).
+#[derive(Debug, Clone, Copy)]
+pub struct DraftExpiryConfig {
+    pub after_days: Option<u32>,
+    pub check_every: Duration,
+}
+
+impl DraftExpiryConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let after_days = match std::env::var("DRAFT_EXPIRY_DAYS") {
+            Ok(value) => Some(
+                value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|days| *days > 0)
+                    .ok_or_else(|| format!("DRAFT_EXPIRY_DAYS has an invalid value: {}", value))?,
+            ),
+            Err(_) => None,
+        };
+
+        let check_every = match std::env::var("DRAFT_EXPIRY_CHECK_INTERVAL_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs)
+                .ok_or_else(|| format!("DRAFT_EXPIRY_CHECK_INTERVAL_SECS has an invalid value: {}", value))?,
+            Err(_) => Duration::from_secs(60 * 60),
+        };
+
+        Ok(Self { after_days, check_every })
+    }
+}
+
+/// Drafts last updated before this are stale.
+pub fn cutoff(after_days: u32, now: DateTime<Utc>) -> DateTime<Utc> {
+    now - chrono::Duration::days(i64::from(after_days))
+}
+
+pub fn spawn_expiry_job(repo: Arc<dyn GameRepository>, after_days: u32, every: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            match repo.expire_stale_drafts(cutoff(after_days, Utc::now())).await {
+                Ok(expired) if expired.is_empty() => {}
+                Ok(expired) => println!("Expired {} stale draft(s)", expired.len()),
+                Err(e) => eprintln!("Expiring stale drafts failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_cutoff_is_whole_days_before_now() {
+        let now = DateTime::parse_from_rfc3339("2024-03-10T12:30:00Z").unwrap().with_timezone(&Utc);
+        let expected = DateTime::parse_from_rfc3339("2024-02-09T12:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(cutoff(30, now), expected);
+    }
+}
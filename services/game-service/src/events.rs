@@ -0,0 +1,42 @@
+use chrono::Utc;
+use common::timestamp::datetime_to_timestamp;
+use tokio::sync::broadcast;
+
+use crate::game;
+
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Fan-out point for game change notifications. Publishing never blocks and
+/// is a no-op when nobody is subscribed.
+#[derive(Clone)]
+pub struct EventSink {
+    sender: broadcast::Sender<game::GameEvent>,
+}
+
+impl EventSink {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event_type: game::GameEventType, game: &game::Game) {
+        let event = game::GameEvent {
+            game_id: game.id.clone(),
+            event_type: event_type as i32,
+            game: Some(game.clone()),
+            occurred_at: Some(datetime_to_timestamp(Utc::now())),
+        };
+
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<game::GameEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,17 +1,60 @@
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Instant;
+
 use tonic::{Request, Response, Status};
+use tokio_stream::{Stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{BroadcastStream, ReceiverStream};
 use uuid::Uuid;
-use chrono::Utc;
-use sqlx::PgPool;
-use num_traits::ToPrimitive;
+use chrono::{NaiveDate, Utc};
+use sqlx::types::Decimal;
+use common::auth::{self, CallerVerifier};
+use common::models::GameStatus;
+use common::deadline::Deadline;
+use common::enum_policy::EnumPolicy;
+use common::money::Money;
+use common::pagination::PageSizeConfig;
+use common::timestamp::{datetime_to_timestamp, timestamp_to_datetime};
 
 use crate::game;
 use crate::types::GameResponse;
-use crate::models::{DbGame, DbGameCategory, DbGameStatus};
+use crate::models::{rating_to_f64, AuditFilter, DbGame, DbGameCategory, DbGameStatus, GameChanges, GameFilter, NewGame, SaleSchedule};
+use crate::drafts::{self, DraftExpiryConfig};
+use crate::events::EventSink;
+use crate::list_guard::{self, ListGuardConfig};
+use crate::pricing::PricePolicy;
+use crate::publishing;
+use crate::purge::{self, PurgeConfig};
+use crate::search::SearchConfig;
+use crate::single_flight::SingleFlight;
+use crate::stats::CatalogStatsCache;
 use crate::db;
+use crate::repository::GameRepository;
+use crate::users::{self, UserDirectory};
+use crate::validation;
+
+type GameEventStream = Pin<Box<dyn Stream<Item = Result<game::GameEvent, Status>> + Send>>;
+type GameStream = Pin<Box<dyn Stream<Item = Result<game::Game, Status>> + Send>>;
+
+const EXPORT_CHANNEL_CAPACITY: usize = 32;
 
 #[derive(Clone)]
 pub struct GameServiceImpl {
-    pub pool: PgPool,
+    pub repo: Arc<dyn GameRepository>,
+    pub events: EventSink,
+    pub page_size: PageSizeConfig,
+    pub callers: CallerVerifier,
+    pub catalog_stats: CatalogStatsCache,
+    pub search: SearchConfig,
+    pub list_guard: ListGuardConfig,
+    pub draft_expiry: DraftExpiryConfig,
+    pub purge: PurgeConfig,
+    pub prices: PricePolicy,
+    pub enums: EnumPolicy,
+    pub game_reads: SingleFlight<Uuid, Result<Option<DbGame>, Status>>,
+    pub users: Arc<dyn UserDirectory>,
 }
 
 #[tonic::async_trait]
@@ -20,96 +63,342 @@ impl game::game_service_server::GameService for GameServiceImpl {
         &self,
         request: Request<game::CreateGameRequest>,
     ) -> Result<Response<game::Game>, Status> {
-        let req = request.into_inner();
-        
-        let game_msg = game::Game {
-            id: Uuid::new_v4().to_string(),
+        let caller = self.caller(&request).await?;
+        self.create_game_as(&caller, request.into_inner()).await.map(Response::new)
+    }
+
+    async fn get_game(
+        &self,
+        request: Request<game::GetGameRequest>,
+    ) -> Result<Response<game::GetGameResponse>, Status> {
+        let id = parse_id(&request.into_inner().id)?;
+
+        // Concurrent reads of the same game (e.g. right after a launch) share
+        // one query instead of each hitting Postgres.
+        let db_game = self
+            .game_reads
+            .run(id, || async move {
+                self.repo.get_game(id).await.map_err(db_error)
+            })
+            .await?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        Ok(Response::new(game::GetGameResponse {
+            game: Some(self.db_game_to_proto(db_game)),
+        }))
+    }
+
+    async fn update_game(
+        &self,
+        request: Request<game::UpdateGameRequest>,
+    ) -> Result<Response<game::Game>, Status> {
+        let caller = self.caller(&request).await?;
+        let mut req = request.into_inner();
+        let id = parse_id(&req.id)?;
+        req.name = req.name.as_deref().map(validation::normalize_name);
+        req.description = req.description.as_deref().map(validation::normalize_description);
+        req.release_date = req.release_date.filter(|d| !d.trim().is_empty());
+
+        if let Err(e) = validation::validate_update_game_request(&req, &self.prices) {
+            return Err(Status::invalid_argument(e));
+        }
+
+        let existing = self.repo.get_game(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        if !caller.is_admin() && existing.developer_id != caller.user_id {
+            return Err(Status::permission_denied("You can only update your own games"));
+        }
+
+        let status = req
+            .status
+            .map(parse_status)
+            .transpose()?;
+        let release_date = req.release_date.as_deref().map(parse_release_date).transpose()?;
+
+        let categories = if req.categories.is_empty() {
+            None
+        } else {
+            Some(parse_categories(req.categories, self.enums)?)
+        };
+
+        let changes = GameChanges {
             name: req.name,
-            description: Some(req.description),
-            developer_id: req.developer_id,
-            publisher_id: req.publisher_id,
-            cover_image: Some(req.cover_image),
+            description: req.description,
+            price: req.price.map(|p| Decimal::from(Money::from(p))),
+            cover_image: req.cover_image,
             trailer_url: req.trailer_url,
-            release_date: Some(req.release_date),
-            tags: req.tags,
-            platforms: req.platforms,
-            screenshots: vec![],
-            price: req.price,
-            created_at: Some(prost_types::Timestamp {
-                seconds: Utc::now().timestamp(),
-                nanos: 0,
-            }),
-            updated_at: Some(prost_types::Timestamp {
-                seconds: Utc::now().timestamp(),
-                nanos: 0,
-            }),
-            status: 1, // Draft
-            categories: req.categories,
-            rating_count: 0,
-            average_rating: 0.0,
-            purchase_count: 0,
+            release_date,
+            status,
+            categories,
+            tags: (!req.tags.is_empty()).then_some(req.tags),
+            platforms: (!req.platforms.is_empty()).then_some(req.platforms),
+            screenshots: (!req.screenshots.is_empty()).then_some(req.screenshots),
         };
 
+        // Checked against the game as it will be after this update, so the
+        // missing pieces can be supplied together with the status change.
+        if status == Some(GameStatus::Published) {
+            publishing::check_ready(&existing, &changes)?;
+        }
+
+        let updated = self.repo.update_game(id, changes)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        let price_changed = updated.price != existing.price;
+        let published = matches!(updated.status, DbGameStatus::Published)
+            && !matches!(existing.status, DbGameStatus::Published);
+
+        let game_msg = self.db_game_to_proto(updated);
+
+        self.events.publish(game::GameEventType::Updated, &game_msg);
+        if price_changed {
+            self.events.publish(game::GameEventType::PriceChanged, &game_msg);
+        }
+        if published {
+            self.events.publish(game::GameEventType::Published, &game_msg);
+        }
+
         Ok(Response::new(game_msg))
     }
 
-    async fn get_game(
+    async fn bulk_set_prices(
         &self,
-        _request: Request<game::GetGameRequest>,
-    ) -> Result<Response<game::GetGameResponse>, Status> {
-        Err(Status::unimplemented("GetGame not implemented yet"))
+        request: Request<game::BulkSetPricesRequest>,
+    ) -> Result<Response<game::BulkSetPricesResponse>, Status> {
+        let caller = self.caller(&request).await?;
+        let deadline = Deadline::from_metadata(request.metadata());
+        let req = request.into_inner();
+        let strict = req.mode() == game::BulkPriceMode::Strict;
+
+        if req.updates.is_empty() {
+            return Err(Status::invalid_argument("At least one price update is required"));
+        }
+        if req.updates.len() > MAX_BULK_PRICE_UPDATES {
+            return Err(Status::invalid_argument(format!(
+                "At most {} price updates are allowed per batch",
+                MAX_BULK_PRICE_UPDATES
+            )));
+        }
+
+        // Check every entry before touching anything so a strict batch with
+        // a bad entry never opens the transaction.
+        let mut results = Vec::with_capacity(req.updates.len());
+        let mut accepted = Vec::new();
+        let mut seen = HashSet::new();
+        for (index, update) in req.updates.iter().enumerate() {
+            deadline.check()?;
+            match self.check_price_update(&caller, update, &mut seen).await? {
+                Ok(entry) => accepted.push((index, entry)),
+                Err((error, code)) => results.push(price_update_failure(index, error, code)),
+            }
+        }
+
+        // In strict mode there is no point applying once an entry has failed
+        let already_failed = strict && !results.is_empty();
+        if already_failed {
+            for (index, _) in accepted {
+                results.push(price_update_failure(index, BATCH_ROLLED_BACK.to_string(), "BATCH_ROLLED_BACK"));
+            }
+        } else if !accepted.is_empty() {
+            deadline.check()?;
+            let (indices, updates): (Vec<usize>, Vec<(Uuid, Decimal)>) = accepted.into_iter().unzip();
+            let outcomes = self.repo.bulk_set_prices(&updates, strict)
+                .await
+                .map_err(db_error)?;
+
+            let mut changed = Vec::new();
+            for (index, outcome) in indices.into_iter().zip(outcomes) {
+                match outcome {
+                    Some((old_price, updated)) => {
+                        let price_changed = updated.price != old_price;
+                        let game_msg = self.db_game_to_proto(updated);
+                        if price_changed {
+                            changed.push(game_msg.clone());
+                        }
+                        results.push(game::PriceUpdateResult {
+                            index: index as u32,
+                            game: Some(game_msg),
+                            error: None,
+                            error_code: None,
+                        });
+                    }
+                    None => results.push(price_update_failure(index, "Game not found".to_string(), "NOT_FOUND")),
+                }
+            }
+
+            // A strict batch with a vanished game was rolled back, so there
+            // is nothing to announce.
+            if !(strict && results.iter().any(|r| r.error.is_some())) {
+                for game_msg in &changed {
+                    self.events.publish(game::GameEventType::Updated, game_msg);
+                    self.events.publish(game::GameEventType::PriceChanged, game_msg);
+                }
+            }
+        }
+
+        let failed = results.iter().any(|r| r.error.is_some());
+
+        let mut updated_count = 0;
+        for result in results.iter_mut() {
+            if result.game.is_some() {
+                if strict && failed {
+                    *result = price_update_failure(
+                        result.index as usize,
+                        BATCH_ROLLED_BACK.to_string(),
+                        "BATCH_ROLLED_BACK",
+                    );
+                } else {
+                    updated_count += 1;
+                }
+            }
+        }
+        results.sort_by_key(|r| r.index);
+
+        Ok(Response::new(game::BulkSetPricesResponse {
+            results,
+            updated_count,
+        }))
     }
 
-    async fn update_game(
+    async fn schedule_sale(
+        &self,
+        request: Request<game::ScheduleSaleRequest>,
+    ) -> Result<Response<game::Game>, Status> {
+        let caller = self.caller(&request).await?;
+        let req = request.into_inner();
+        let id = parse_id(&req.game_id)?;
+
+        let (Some(starts_at), Some(ends_at)) = (
+            req.starts_at.as_ref().and_then(timestamp_to_datetime),
+            req.ends_at.as_ref().and_then(timestamp_to_datetime),
+        ) else {
+            return Err(Status::invalid_argument("A sale needs a valid starts_at and ends_at"));
+        };
+        if ends_at <= starts_at {
+            return Err(Status::invalid_argument("A sale must end after it starts"));
+        }
+        if ends_at <= Utc::now() {
+            return Err(Status::invalid_argument("A sale cannot end in the past"));
+        }
+
+        let sale_price = Money::from(req.sale_price);
+        self.prices.check(sale_price).map_err(Status::invalid_argument)?;
+
+        let existing = self.editable_game(&caller, id).await?;
+        let sale_price = Decimal::from(sale_price);
+        if sale_price >= existing.price {
+            return Err(Status::invalid_argument("The sale price must be below the game's base price"));
+        }
+
+        let sale = SaleSchedule {
+            price: sale_price,
+            starts_at,
+            ends_at,
+        };
+        let updated = self.repo.set_sale(id, Some(sale))
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        Ok(Response::new(self.publish_sale_change(&existing, updated)))
+    }
+
+    async fn cancel_sale(
         &self,
-        _request: Request<game::UpdateGameRequest>,
+        request: Request<game::CancelSaleRequest>,
     ) -> Result<Response<game::Game>, Status> {
-        Err(Status::unimplemented("UpdateGame not implemented yet"))
+        let caller = self.caller(&request).await?;
+        let id = parse_id(&request.into_inner().game_id)?;
+
+        let existing = self.editable_game(&caller, id).await?;
+        if existing.sale().is_none() {
+            return Ok(Response::new(self.db_game_to_proto(existing)));
+        }
+
+        let updated = self.repo.set_sale(id, None)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        Ok(Response::new(self.publish_sale_change(&existing, updated)))
     }
 
     async fn delete_game(
         &self,
-        _request: Request<game::DeleteGameRequest>,
+        request: Request<game::DeleteGameRequest>,
     ) -> Result<Response<game::DeleteGameResponse>, Status> {
-        Err(Status::unimplemented("DeleteGame not implemented yet"))
+        let caller = self.caller(&request).await?;
+        let req = request.into_inner();
+        let id = parse_id(&req.id)?;
+        let developer_id = parse_id(&req.developer_id)?;
+
+        if !caller.is_admin() && caller.user_id != developer_id {
+            return Err(Status::permission_denied("You can only delete your own games"));
+        }
+
+        if let Some(game) = self.repo.get_game(id).await.map_err(db_error)?
+            && game.developer_id != developer_id
+        {
+            return Err(Status::permission_denied("You can only delete your own games"));
+        }
+
+        // A game that is already gone counts as deleted, so retries succeed.
+        let deleted = self.repo.delete_game(id, developer_id)
+            .await
+            .map_err(db_error)?;
+
+        Ok(Response::new(game::DeleteGameResponse {
+            success: true,
+            already_absent: !deleted,
+        }))
     }
 
     async fn list_games(
         &self,
         request: Request<game::ListGamesRequest>,
     ) -> Result<Response<game::ListGamesResponse>, Status> {
+        let deadline = Deadline::from_metadata(request.metadata());
+        let include_deleted = request.get_ref().include_deleted.unwrap_or(false);
+        if include_deleted && !self.caller(&request).await?.is_admin() {
+            return Err(Status::permission_denied("Only admins can list deleted games"));
+        }
         let req = request.into_inner();
 
-        let limit = req.page_size.max(1).min(100) as i32;
+        let limit = self.page_size.clamp(req.page_size);
         let offset = req.page_token.parse::<i32>().unwrap_or(0);
-        
-        let developer_id = if req.developer_id.as_deref().map_or(true, |s| s.is_empty()) {
-            None
-        } else {
-            Some(Uuid::parse_str(req.developer_id.as_deref().unwrap()).map_err(|_| Status::invalid_argument("Invalid developer_id"))?)
-        };
-        
-        let categories: Option<Vec<DbGameCategory>> = if req.categories.is_empty() {
-            None
-        } else {
-            Some(req.categories.into_iter().map(DbGameCategory::from_proto).collect())
-        };
-        
-        let status = req.status.filter(|&s| s != 0).map(DbGameStatus::from_proto);
-        
-        let search_query = req.search_query.filter(|s| !s.is_empty());
+        let (filter, search_query) = list_filter(req, include_deleted, self.enums)?;
 
-        let (db_games, total) = db::list_games(
-            &self.pool,
-            developer_id,
-            categories,
-            req.min_price.map(|p| sqlx::types::Decimal::new(p, 2)),
-            req.max_price.map(|p| sqlx::types::Decimal::new(p, 2)),
-            status,
-            search_query,
-            limit,
-            offset,
-        ).await.map_err(|e| Status::internal(format!("Database error: {}", e)))?;
+        if self.list_guard.needs_check(&filter, search_query.is_some(), offset) {
+            let estimated_rows = self.repo.estimate_game_rows().await.map_err(db_error)?;
+            self.list_guard.check(estimated_rows)?;
+        }
+
+        deadline.check()?;
+        let started = Instant::now();
+        let (mut db_games, mut total) = self
+            .repo
+            .list_games(&filter, search_query.as_deref(), limit, offset)
+            .await
+            .map_err(db_error)?;
+
+        // Full-text search has no typo tolerance, so fall back to trigram
+        // similarity on the name when it comes up short.
+        if let Some(query) = search_query.as_deref()
+            && total < self.search.fuzzy_min_results
+        {
+            deadline.check()?;
+            (db_games, total) = self
+                .repo
+                .fuzzy_search_games(&filter, query, self.search.similarity_threshold, limit, offset)
+                .await
+                .map_err(db_error)?;
+        }
+        let query_time = started.elapsed();
 
         let games: Vec<game::Game> = db_games.into_iter().map(|g| self.db_game_to_proto(g)).collect();
         
@@ -119,44 +408,738 @@ impl game::game_service_server::GameService for GameServiceImpl {
             String::new()
         };
 
-        let response = game::ListGamesResponse {
+        let mut response = Response::new(game::ListGamesResponse {
             games,
             total_count: total as u64,
             next_page_token,
+        });
+        if self.list_guard.debug_timing {
+            response
+                .metadata_mut()
+                .insert(list_guard::QUERY_TIME_KEY, (query_time.as_millis() as u64).into());
+        }
+
+        Ok(response)
+    }
+
+    async fn list_price_history(
+        &self,
+        request: Request<game::ListPriceHistoryRequest>,
+    ) -> Result<Response<game::ListPriceHistoryResponse>, Status> {
+        let game_id = parse_id(&request.into_inner().game_id)?;
+
+        self.repo.get_game(game_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        let entries = self.repo.list_price_history(game_id)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|entry| game::PriceHistoryEntry {
+                old_price: decimal_to_cents(entry.old_price),
+                new_price: decimal_to_cents(entry.new_price),
+                changed_at: Some(datetime_to_timestamp(entry.changed_at)),
+            })
+            .collect();
+
+        Ok(Response::new(game::ListPriceHistoryResponse { entries }))
+    }
+
+    async fn list_top_reviews(
+        &self,
+        request: Request<game::ListTopReviewsRequest>,
+    ) -> Result<Response<game::ListTopReviewsResponse>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let req = request.into_inner();
+        let game_id = parse_id(&req.game_id)?;
+        let limit = if req.limit <= 0 {
+            DEFAULT_TOP_REVIEWS
+        } else {
+            req.limit.min(MAX_TOP_REVIEWS)
+        };
+
+        let reviews = self.repo.list_top_reviews(game_id, limit)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|review| game::GameReview {
+                id: review.id.to_string(),
+                user_id: review.user_id.to_string(),
+                rating: i32::from(review.rating),
+                created_at: Some(datetime_to_timestamp(review.created_at)),
+            })
+            .collect();
+
+        Ok(Response::new(game::ListTopReviewsResponse { reviews }))
+    }
+
+    async fn get_review_summary(
+        &self,
+        request: Request<game::GetReviewSummaryRequest>,
+    ) -> Result<Response<game::ReviewSummary>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let game_id = parse_id(&request.into_inner().game_id)?;
+
+        let summary = self.repo.get_review_summary(game_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        Ok(Response::new(game::ReviewSummary {
+            average_rating: rating_to_f64(summary.average()),
+            total: summary.total() as i32,
+            star_counts: summary.star_counts.iter().map(|&count| count as i32).collect(),
+        }))
+    }
+
+    type WatchGameEventsStream = GameEventStream;
+
+    async fn watch_game_events(
+        &self,
+        request: Request<game::WatchGameEventsRequest>,
+    ) -> Result<Response<Self::WatchGameEventsStream>, Status> {
+        let game_id = request.into_inner().game_id.filter(|id| !id.is_empty());
+        if let Some(id) = &game_id {
+            parse_id(id)?;
+        }
+
+        let stream = BroadcastStream::new(self.events.subscribe())
+            // A lagging subscriber just misses events rather than being disconnected
+            .filter_map(|event| event.ok())
+            .filter(move |event| game_id.as_ref().is_none_or(|id| &event.game_id == id))
+            .map(Ok);
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    type ExportDeveloperGamesStream = GameStream;
+
+    async fn export_developer_games(
+        &self,
+        request: Request<game::ExportDeveloperGamesRequest>,
+    ) -> Result<Response<Self::ExportDeveloperGamesStream>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let caller = self.caller(&request).await?;
+        let developer_id = parse_id(&request.into_inner().developer_id)?;
+
+        if !caller.is_admin() && caller.user_id != developer_id {
+            return Err(Status::permission_denied("You can only export your own games"));
+        }
+
+        // The bounded channel applies backpressure to the DB cursor, so a slow
+        // client never makes us buffer the whole catalog.
+        let (sender, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+        let service = self.clone();
+
+        tokio::spawn(async move {
+            let mut rows = service.repo.stream_developer_games(developer_id);
+            while let Some(row) = rows.next().await {
+                let item = row
+                    .map(|g| service.db_game_to_proto(g))
+                    .map_err(db_error);
+                let failed = item.is_err();
+
+                // A closed channel means the client went away
+                if sender.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+
+    async fn get_catalog_stats(
+        &self,
+        request: Request<game::GetCatalogStatsRequest>,
+    ) -> Result<Response<game::CatalogStats>, Status> {
+        if let Some(stats) = self.catalog_stats.get() {
+            return Ok(Response::new(stats));
+        }
+        Deadline::from_metadata(request.metadata()).check()?;
+
+        let (published_games, developers) = self.repo.count_published_catalog()
+            .await
+            .map_err(db_error)?;
+
+        let newest_release = self.repo.get_newest_release()
+            .await
+            .map_err(db_error)?;
+
+        let most_purchased = self.repo.get_popular_games(1)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .next();
+
+        let stats = game::CatalogStats {
+            published_games: published_games as u64,
+            developers: developers as u64,
+            newest_release: newest_release.map(|g| self.db_game_to_proto(g)),
+            most_purchased: most_purchased.map(|g| self.db_game_to_proto(g)),
+        };
+
+        self.catalog_stats.put(stats.clone());
+
+        Ok(Response::new(stats))
+    }
+
+    async fn get_category_price_stats(
+        &self,
+        request: Request<game::GetCategoryPriceStatsRequest>,
+    ) -> Result<Response<game::CategoryPriceStats>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let req = request.into_inner();
+
+        let category = parse_category(req.category, self.enums)?;
+        if category == DbGameCategory::Unspecified {
+            return Err(Status::invalid_argument("A category is required"));
+        }
+
+        let stats = self.repo.get_category_price_stats(category)
+            .await
+            .map_err(db_error)?;
+
+        Ok(Response::new(game::CategoryPriceStats {
+            category: req.category,
+            game_count: stats.game_count as u64,
+            min_price: stats.min_price.map(decimal_to_cents),
+            max_price: stats.max_price.map(decimal_to_cents),
+            avg_price: stats.avg_price.map(decimal_to_cents),
+            median_price: stats.median_price.map(decimal_to_cents),
+        }))
+    }
+
+    async fn get_price_facets(
+        &self,
+        request: Request<game::GetPriceFacetsRequest>,
+    ) -> Result<Response<game::PriceFacetsResponse>, Status> {
+        let deadline = Deadline::from_metadata(request.metadata());
+        let include_deleted = request
+            .get_ref()
+            .filter
+            .as_ref()
+            .and_then(|f| f.include_deleted)
+            .unwrap_or(false);
+        if include_deleted && !self.caller(&request).await?.is_admin() {
+            return Err(Status::permission_denied("Only admins can list deleted games"));
+        }
+        let req = request.into_inner();
+
+        let (filter, search_query) = list_filter(req.filter.unwrap_or_default(), include_deleted, self.enums)?;
+        let bounds = price_facet_bounds(req.bounds)?;
+
+        deadline.check()?;
+        let counts = self.repo.get_price_facets(&filter, search_query.as_deref(), &bounds)
+            .await
+            .map_err(db_error)?;
+
+        let facets = counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| game::PriceFacet {
+                min_price: i.checked_sub(1).map(|lower| decimal_to_cents(bounds[lower])),
+                max_price: bounds.get(i).copied().map(decimal_to_cents),
+                count: count as u64,
+            })
+            .collect();
+
+        Ok(Response::new(game::PriceFacetsResponse { facets }))
+    }
+
+    async fn get_developer_status_counts(
+        &self,
+        request: Request<game::GetDeveloperStatusCountsRequest>,
+    ) -> Result<Response<game::DeveloperStatusCounts>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let caller = self.caller(&request).await?;
+        let developer_id = parse_id(&request.into_inner().developer_id)?;
+
+        if !caller.is_admin() && caller.user_id != developer_id {
+            return Err(Status::permission_denied("You can only view your own status counts"));
+        }
+
+        let counts = self.repo.get_developer_status_counts(developer_id)
+            .await
+            .map_err(db_error)?;
+
+        Ok(Response::new(game::DeveloperStatusCounts {
+            draft: counts.draft as u64,
+            under_review: counts.under_review as u64,
+            published: counts.published as u64,
+            suspended: counts.suspended as u64,
+        }))
+    }
+
+    async fn recompute_game_stats(
+        &self,
+        request: Request<game::RecomputeGameStatsRequest>,
+    ) -> Result<Response<game::RecomputeGameStatsResponse>, Status> {
+        let deadline = Deadline::from_metadata(request.metadata());
+        let caller = self.caller(&request).await?;
+        if !caller.is_admin() {
+            return Err(Status::permission_denied("Only admins can recompute game stats"));
+        }
+
+        let game_id = match request.into_inner().game_id.filter(|id| !id.is_empty()) {
+            Some(id) => Some(parse_id(&id)?),
+            None => None,
+        };
+
+        if let Some(id) = game_id {
+            self.repo.get_game(id)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| Status::not_found("Game not found"))?;
+        }
+
+        deadline.check()?;
+        let games_corrected = self.repo.recompute_game_stats(game_id)
+            .await
+            .map_err(db_error)?;
+
+        Ok(Response::new(game::RecomputeGameStatsResponse { games_corrected }))
+    }
+
+    async fn preview_draft_expiry(
+        &self,
+        request: Request<game::PreviewDraftExpiryRequest>,
+    ) -> Result<Response<game::PreviewDraftExpiryResponse>, Status> {
+        let caller = self.caller(&request).await?;
+        if !caller.is_admin() {
+            return Err(Status::permission_denied("Only admins can preview draft expiry"));
+        }
+
+        let after_days = request
+            .into_inner()
+            .older_than_days
+            .or(self.draft_expiry.after_days)
+            .filter(|days| *days > 0)
+            .ok_or_else(|| {
+                Status::invalid_argument("older_than_days must be positive (draft expiry is not configured)")
+            })?;
+
+        let cutoff = drafts::cutoff(after_days, chrono::Utc::now());
+        let drafts = self.repo.find_stale_drafts(cutoff)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|draft| game::StaleDraft {
+                id: draft.id.to_string(),
+                name: draft.name,
+                developer_id: draft.developer_id.to_string(),
+                updated_at: Some(datetime_to_timestamp(draft.updated_at)),
+            })
+            .collect();
+
+        Ok(Response::new(game::PreviewDraftExpiryResponse {
+            drafts,
+            cutoff: Some(datetime_to_timestamp(cutoff)),
+        }))
+    }
+
+    async fn purge_deleted_games(
+        &self,
+        request: Request<game::PurgeDeletedGamesRequest>,
+    ) -> Result<Response<game::PurgeDeletedGamesResponse>, Status> {
+        let caller = self.caller(&request).await?;
+        if !caller.is_admin() {
+            return Err(Status::permission_denied("Only admins can purge deleted games"));
+        }
+
+        let retention_days = request
+            .into_inner()
+            .older_than_days
+            .unwrap_or(self.purge.retention_days);
+        if retention_days == 0 {
+            return Err(Status::invalid_argument("older_than_days must be positive"));
+        }
+
+        let cutoff = purge::cutoff(retention_days, chrono::Utc::now());
+        let purged = purge::purge_deleted(self.repo.as_ref(), cutoff, self.purge.batch_size, Some(caller.user_id))
+            .await
+            .map_err(db_error)?;
+        tracing::info!(target: "audit", admin_id = %caller.user_id, purged, %cutoff, "deleted games purged");
+
+        Ok(Response::new(game::PurgeDeletedGamesResponse {
+            purged,
+            cutoff: Some(datetime_to_timestamp(cutoff)),
+        }))
+    }
+
+    async fn list_audit_log(
+        &self,
+        request: Request<game::ListAuditLogRequest>,
+    ) -> Result<Response<game::ListAuditLogResponse>, Status> {
+        let caller = self.caller(&request).await?;
+        if !caller.is_admin() {
+            return Err(Status::permission_denied("Only admins can read the audit log"));
+        }
+        let req = request.into_inner();
+
+        let filter = AuditFilter {
+            actor_id: req.actor_id.as_deref().map(parse_id).transpose()?,
+            game_id: req.game_id.as_deref().map(parse_id).transpose()?,
+            action: req.action.filter(|action| !action.is_empty()),
+            since: req.since.as_ref().and_then(timestamp_to_datetime),
+            until: req.until.as_ref().and_then(timestamp_to_datetime),
+        };
+        let before_id = match req.page_token.as_str() {
+            "" => None,
+            token => Some(token.parse::<i64>().map_err(|_| Status::invalid_argument("invalid page_token"))?),
+        };
+        let limit = self.page_size.clamp(req.page_size);
+
+        let entries = self.repo.list_audit(&filter, i64::from(limit), before_id)
+            .await
+            .map_err(db_error)?;
+        let next_page_token = match entries.last() {
+            Some(last) if entries.len() == limit as usize => last.id.to_string(),
+            _ => String::new(),
+        };
+
+        Ok(Response::new(game::ListAuditLogResponse {
+            entries: entries
+                .into_iter()
+                .map(|entry| game::AuditEntry {
+                    id: entry.id,
+                    game_id: entry.game_id.to_string(),
+                    actor_id: entry.actor_id.map(|id| id.to_string()),
+                    action: entry.action,
+                    detail: entry.detail,
+                    before: entry.before,
+                    after: entry.after,
+                    created_at: Some(datetime_to_timestamp(entry.created_at)),
+                })
+                .collect(),
+            next_page_token,
+        }))
+    }
+
+    async fn suggest_games(
+        &self,
+        request: Request<game::SuggestGamesRequest>,
+    ) -> Result<Response<game::SuggestGamesResponse>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let req = request.into_inner();
+        let prefix = req.prefix.trim();
+        if prefix.is_empty() {
+            return Ok(Response::new(game::SuggestGamesResponse { suggestions: vec![] }));
+        }
+
+        let limit = if req.limit <= 0 {
+            DEFAULT_SUGGESTIONS
+        } else {
+            req.limit.min(MAX_SUGGESTIONS)
         };
 
-        Ok(Response::new(response))
+        let suggestions = self.repo.suggest_games(prefix, limit)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|s| game::GameSuggestion {
+                id: s.id.to_string(),
+                name: s.name,
+            })
+            .collect();
+
+        Ok(Response::new(game::SuggestGamesResponse { suggestions }))
+    }
+
+    async fn list_new_releases(
+        &self,
+        request: Request<game::ListReleasesRequest>,
+    ) -> Result<Response<game::ListReleasesResponse>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let req = request.into_inner();
+        let window_days = release_window(req.window_days, DEFAULT_NEW_RELEASE_DAYS)?;
+        let limit = self.page_size.clamp(req.limit);
+
+        let games = self.repo.list_new_releases(window_days, limit)
+            .await
+            .map_err(db_error)?;
+
+        Ok(Response::new(game::ListReleasesResponse {
+            games: games.into_iter().map(|g| self.db_game_to_proto(g)).collect(),
+        }))
+    }
+
+    async fn list_upcoming_games(
+        &self,
+        request: Request<game::ListReleasesRequest>,
+    ) -> Result<Response<game::ListReleasesResponse>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let req = request.into_inner();
+        let window_days = release_window(req.window_days, DEFAULT_UPCOMING_DAYS)?;
+        let limit = self.page_size.clamp(req.limit);
+
+        let games = self.repo.list_upcoming_games(window_days, limit)
+            .await
+            .map_err(db_error)?;
+
+        Ok(Response::new(game::ListReleasesResponse {
+            games: games.into_iter().map(|g| self.db_game_to_proto(g)).collect(),
+        }))
+    }
+}
+
+/// `0` means `default`; anything negative or longer than a year is refused.
+#[allow(clippy::result_large_err)]
+fn release_window(window_days: i32, default: i32) -> Result<i32, Status> {
+    match window_days {
+        0 => Ok(default),
+        1..=MAX_RELEASE_WINDOW_DAYS => Ok(window_days),
+        _ => Err(Status::invalid_argument(format!(
+            "The release window must be between 1 and {} days",
+            MAX_RELEASE_WINDOW_DAYS
+        ))),
+    }
+}
+
+const DEFAULT_SUGGESTIONS: i32 = 8;
+const MAX_SUGGESTIONS: i32 = 20;
+const DEFAULT_NEW_RELEASE_DAYS: i32 = 30;
+const DEFAULT_UPCOMING_DAYS: i32 = 90;
+const MAX_RELEASE_WINDOW_DAYS: i32 = 365;
+const DEFAULT_TOP_REVIEWS: i32 = 5;
+const MAX_TOP_REVIEWS: i32 = 50;
+const MAX_BULK_PRICE_UPDATES: usize = 100;
+const BATCH_ROLLED_BACK: &str = "Not applied because another entry in the batch failed";
+/// In cents: under $10, $10-$20, $20-$40, $40-$60 and $60 or more.
+const DEFAULT_PRICE_FACET_BOUNDS: [i64; 4] = [1000, 2000, 4000, 6000];
+const MAX_PRICE_FACET_BOUNDS: usize = 20;
+
+/// Prices are `NUMERIC(10, 2)` in the DB, so the conversion is always exact.
+fn decimal_to_cents(price: Decimal) -> i64 {
+    Money::try_from(price).map(i64::from).unwrap_or_default()
+}
+
+fn db_error(e: sqlx::Error) -> Status {
+    if db::is_statement_timeout(&e) {
+        Status::deadline_exceeded("Database query timed out")
+    } else {
+        Status::internal(format!("Database error: {}", e))
     }
 }
 
+/// The `GameFilter` and trimmed search query a `ListGamesRequest` asks for.
+/// Paging and sorting fields are left to the caller.
+#[allow(clippy::result_large_err)]
+fn list_filter(
+    req: game::ListGamesRequest,
+    include_deleted: bool,
+    enums: EnumPolicy,
+) -> Result<(GameFilter, Option<String>), Status> {
+    let developer_id = match req.developer_id.as_deref().filter(|s| !s.is_empty()) {
+        Some(id) => Some(parse_id(id)?),
+        None => None,
+    };
+
+    let categories: Option<Vec<DbGameCategory>> = if req.categories.is_empty() {
+        None
+    } else {
+        Some(parse_categories(req.categories, enums)?)
+    };
+
+    let status = req
+        .status
+        .map(parse_status)
+        .transpose()?;
+
+    let search_query = req
+        .search_query
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let exclude_id = match req.exclude_id.as_deref().filter(|s| !s.is_empty()) {
+        Some(id) => Some(parse_id(id)?),
+        None => None,
+    };
+    let publisher_id = match req.publisher_id.as_deref().filter(|s| !s.is_empty()) {
+        Some(id) => Some(parse_id(id)?),
+        None => None,
+    };
+
+    let filter = GameFilter {
+        developer_id,
+        categories,
+        min_price: req.min_price.map(|p| Decimal::from(Money::from(p))),
+        max_price: req.max_price.map(|p| Decimal::from(Money::from(p))),
+        status,
+        include_deleted,
+        exclude_id,
+        publisher_id,
+    };
+
+    Ok((filter, search_query))
+}
+
+/// Checks facet bucket edges and converts them to prices, falling back to
+/// `DEFAULT_PRICE_FACET_BOUNDS` when none are given.
+#[allow(clippy::result_large_err)]
+fn price_facet_bounds(bounds: Vec<i64>) -> Result<Vec<Decimal>, Status> {
+    let bounds = if bounds.is_empty() {
+        DEFAULT_PRICE_FACET_BOUNDS.to_vec()
+    } else {
+        bounds
+    };
+
+    if bounds.len() > MAX_PRICE_FACET_BOUNDS {
+        return Err(Status::invalid_argument(format!(
+            "At most {} price bucket bounds are allowed",
+            MAX_PRICE_FACET_BOUNDS
+        )));
+    }
+    if bounds.iter().any(|b| *b < 0) {
+        return Err(Status::invalid_argument("Price bucket bounds cannot be negative"));
+    }
+    if bounds.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(Status::invalid_argument("Price bucket bounds must be strictly ascending"));
+    }
+
+    Ok(bounds.into_iter().map(|b| Decimal::from(Money::from(b))).collect())
+}
+
+fn price_update_failure(index: usize, error: String, code: &str) -> game::PriceUpdateResult {
+    game::PriceUpdateResult {
+        index: index as u32,
+        game: None,
+        error: Some(error),
+        error_code: Some(code.to_string()),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_id(value: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value).map_err(|_| Status::invalid_argument("invalid id"))
+}
+
+/// Already checked by the request validation; this yields the parsed date.
+#[allow(clippy::result_large_err)]
+fn parse_release_date(value: &str) -> Result<NaiveDate, Status> {
+    validation::parse_release_date(value).map_err(Status::invalid_argument)
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_status(value: i32) -> Result<GameStatus, Status> {
+    GameStatus::from_proto_selectable(value).ok_or_else(|| Status::invalid_argument("Invalid status"))
+}
+
+/// A category from a request. An undefined value becomes `Unspecified`, or
+/// is refused when `STRICT_ENUMS` is set.
+#[allow(clippy::result_large_err)]
+fn parse_category(value: i32, enums: EnumPolicy) -> Result<DbGameCategory, Status> {
+    enums
+        .resolve("category", value, DbGameCategory::try_from_proto(value), DbGameCategory::Unspecified)
+        .map_err(Status::invalid_argument)
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_categories(values: Vec<i32>, enums: EnumPolicy) -> Result<Vec<DbGameCategory>, Status> {
+    values.into_iter().map(|value| parse_category(value, enums)).collect()
+}
+
 impl GameServiceImpl {
+    /// Creates a game for `caller`. Both the gRPC method and the HTTP
+    /// handler come through here, so normalization, validation and the
+    /// stored row can't differ between them.
+    pub async fn create_game_as(&self, caller: &auth::Caller, mut req: game::CreateGameRequest) -> Result<game::Game, Status> {
+        // The developer comes from the verified token; only admins may create
+        // games on someone else's behalf.
+        if !caller.is_admin() {
+            req.developer_id = caller.user_id.to_string();
+        }
+
+        req.name = validation::normalize_name(&req.name);
+        req.description = validation::normalize_description(&req.description);
+        req.release_date = req.release_date.filter(|d| !d.trim().is_empty());
+
+        if let Err(e) = validation::validate_create_game_request(&req, &self.prices) {
+            return Err(Status::invalid_argument(e));
+        }
+        let publisher_id = match req.publisher_id.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(id) => Some(self.check_publisher(id).await?),
+            None => None,
+        };
+
+        let release_date = req.release_date.as_deref().map(parse_release_date).transpose()?;
+
+        // A dry run stops only after every check a real create makes, so
+        // "valid" means the create would succeed.
+        if req.dry_run {
+            parse_id(&req.developer_id)?;
+            parse_categories(req.categories.clone(), self.enums)?;
+            return Ok(game::Game {
+                name: req.name,
+                developer_id: req.developer_id,
+                price: req.price,
+                base_price: req.price,
+                status: GameStatus::Draft.to_proto(),
+                categories: req.categories,
+                ..Default::default()
+            });
+        }
+
+        let new_game = NewGame {
+            name: req.name,
+            description: req.description,
+            developer_id: parse_id(&req.developer_id)?,
+            publisher_id,
+            cover_image: Some(req.cover_image),
+            trailer_url: req.trailer_url,
+            release_date,
+            categories: parse_categories(req.categories, self.enums)?,
+            tags: req.tags,
+            platforms: req.platforms,
+            price: Decimal::from(Money::from(req.price)),
+        };
+
+        // Timestamps come from the stored row, so they carry the same
+        // precision as every later read of the game.
+        let created = self.repo.create_game(new_game)
+            .await
+            .map_err(db_error)?;
+
+        Ok(self.db_game_to_proto(created))
+    }
+
     pub fn db_game_to_proto(&self, db_game: DbGame) -> game::Game {
+        // Sales are applied on read, so one that has ended simply stops
+        // showing up in `price` without anything having to clear it.
+        let now = Utc::now();
+        let price = decimal_to_cents(db_game.effective_price(now));
+        let sale = db_game.sale().map(|sale| game::GameSale {
+            price: decimal_to_cents(sale.price),
+            starts_at: Some(datetime_to_timestamp(sale.starts_at)),
+            ends_at: Some(datetime_to_timestamp(sale.ends_at)),
+            active: sale.is_active(now),
+        });
+
         game::Game {
             id: db_game.id.to_string(),
             name: db_game.name,
-            description: Some(db_game.description),
+            description: db_game.description,
             developer_id: db_game.developer_id.to_string(),
             publisher_id: db_game.publisher_id.map(|p| p.to_string()),
             cover_image: db_game.cover_image,
             trailer_url: db_game.trailer_url,
-            release_date: Some(db_game.release_date.format("%Y-%m-%d").to_string()),
+            release_date: db_game.release_date.map(|d| d.format("%Y-%m-%d").to_string()),
             tags: db_game.tags,
             platforms: db_game.platforms,
             screenshots: db_game.screenshots,
-            price: (db_game.price.to_f64().unwrap_or(0.0) * 100.0) as i64,
-            created_at: Some(prost_types::Timestamp {
-                seconds: db_game.created_at.timestamp(),
-                nanos: (db_game.created_at.timestamp_subsec_nanos()) as i32,
-            }),
-            updated_at: Some(prost_types::Timestamp {
-                seconds: db_game.updated_at.timestamp(),
-                nanos: (db_game.updated_at.timestamp_subsec_nanos()) as i32,
-            }),
-            status: db_game.status.to_proto(),
+            price,
+            base_price: decimal_to_cents(db_game.price),
+            sale,
+            created_at: Some(datetime_to_timestamp(db_game.created_at)),
+            updated_at: Some(datetime_to_timestamp(db_game.updated_at)),
+            status: GameStatus::from(db_game.status).to_proto(),
             categories: db_game.categories.into_iter().map(|c| c.to_proto()).collect(),
             rating_count: db_game.rating_count,
-            average_rating: db_game.average_rating.to_string().parse::<f64>().unwrap_or(0.0),
+            average_rating: rating_to_f64(db_game.average_rating),
             purchase_count: db_game.purchase_count,
+            deleted_at: db_game.deleted_at.map(datetime_to_timestamp),
         }
     }
 
@@ -169,34 +1152,949 @@ impl GameServiceImpl {
             publisher_id: game.publisher_id,
             cover_image: game.cover_image.unwrap_or_default(),
             trailer_url: game.trailer_url,
-            release_date: game.release_date.unwrap_or_default(),
+            release_date: game.release_date,
             tags: game.tags,
             platforms: game.platforms,
             screenshots: game.screenshots,
-            price: game.price as f64,
-            status: match game.status {
-                1 => "draft".to_string(),
-                2 => "under_review".to_string(),
-                3 => "published".to_string(),
-                4 => "suspended".to_string(),
-                _ => "unknown".to_string(),
-            },
-            categories: game.categories.into_iter().map(|c| match c {
-                1 => "action".to_string(),
-                2 => "rpg".to_string(),
-                3 => "strategy".to_string(),
-                4 => "sports".to_string(),
-                5 => "racing".to_string(),
-                6 => "adventure".to_string(),
-                7 => "simulation".to_string(),
-                8 => "puzzle".to_string(),
-                _ => "unknown".to_string(),
-            }).collect(),
+            price: game.price.into(),
+            status: GameStatus::proto_name(game.status),
+            categories: game.categories.into_iter().map(|c| DbGameCategory::from_proto(c).to_string()).collect(),
             rating_count: game.rating_count,
             average_rating: game.average_rating,
             purchase_count: game.purchase_count,
-            created_at: game.created_at.map(|t| format!("{}Z", chrono::DateTime::from_timestamp(t.seconds, t.nanos as u32).unwrap_or_default().format("%Y-%m-%dT%H:%M:%S"))).unwrap_or_default(),
-            updated_at: game.updated_at.map(|t| format!("{}Z", chrono::DateTime::from_timestamp(t.seconds, t.nanos as u32).unwrap_or_default().format("%Y-%m-%dT%H:%M:%S"))).unwrap_or_default(),
+            // An unrepresentable timestamp is left empty rather than shown as
+            // the epoch.
+            created_at: game.created_at.as_ref().and_then(timestamp_to_datetime).map(|t| format!("{}Z", t.format("%Y-%m-%dT%H:%M:%S"))).unwrap_or_default(),
+            updated_at: game.updated_at.as_ref().and_then(timestamp_to_datetime).map(|t| format!("{}Z", t.format("%Y-%m-%dT%H:%M:%S"))).unwrap_or_default(),
+        }
+    }
+    /// The caller the interceptor verified, provided their sessions haven't
+    /// been revoked since the token was issued.
+    async fn caller<T: Sync>(&self, request: &Request<T>) -> Result<auth::Caller, Status> {
+        let caller = auth::require_caller(request)?;
+        users::check_session(self.users.as_ref(), &caller).await?;
+        Ok(caller)
+    }
+
+    /// Publishers are users until there is a publishers table, so a
+    /// `publisher_id` must name one that exists.
+    async fn check_publisher(&self, id: &str) -> Result<Uuid, Status> {
+        let publisher_id = Uuid::parse_str(id).map_err(|_| Status::invalid_argument("Invalid publisher_id format"))?;
+        if !self.users.user_exists(publisher_id).await? {
+            return Err(Status::invalid_argument("publisher_id does not reference an existing user"));
+        }
+        Ok(publisher_id)
+    }
+
+    /// The live game `id`, provided the caller owns it or is an admin.
+    async fn editable_game(&self, caller: &auth::Caller, id: Uuid) -> Result<DbGame, Status> {
+        let game = self.repo.get_game(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| Status::not_found("Game not found"))?;
+
+        if !caller.is_admin() && game.developer_id != caller.user_id {
+            return Err(Status::permission_denied("You can only update your own games"));
+        }
+
+        Ok(game)
+    }
+
+    /// Announces a sale being scheduled or cancelled, including a price
+    /// change when the game's current price moved as a result.
+    fn publish_sale_change(&self, before: &DbGame, updated: DbGame) -> game::Game {
+        let now = Utc::now();
+        let price_changed = updated.effective_price(now) != before.effective_price(now);
+
+        let game_msg = self.db_game_to_proto(updated);
+        self.events.publish(game::GameEventType::Updated, &game_msg);
+        if price_changed {
+            self.events.publish(game::GameEventType::PriceChanged, &game_msg);
+        }
+
+        game_msg
+    }
+
+    /// Validates one bulk price entry: a well-formed, not yet seen id of a
+    /// live game the caller may edit, and a price the policy allows. The
+    /// inner error is the message and code reported for that entry.
+    async fn check_price_update(
+        &self,
+        caller: &auth::Caller,
+        update: &game::PriceUpdate,
+        seen: &mut HashSet<Uuid>,
+    ) -> Result<Result<(Uuid, Decimal), (String, &'static str)>, Status> {
+        let Ok(id) = Uuid::parse_str(&update.game_id) else {
+            return Ok(Err(("invalid id".to_string(), "INVALID_ID")));
+        };
+        if !seen.insert(id) {
+            return Ok(Err(("Game appears more than once in the batch".to_string(), "DUPLICATE_GAME")));
+        }
+
+        let price = Money::from(update.price);
+        if let Err(e) = self.prices.check(price) {
+            return Ok(Err((e, "INVALID_PRICE")));
+        }
+
+        let Some(existing) = self.repo.get_game(id).await.map_err(db_error)? else {
+            return Ok(Err(("Game not found".to_string(), "NOT_FOUND")));
+        };
+        if !caller.is_admin() && existing.developer_id != caller.user_id {
+            return Ok(Err(("You can only update your own games".to_string(), "NOT_OWNER")));
+        }
+
+        Ok(Ok((id, Decimal::from(price))))
+    }
+}
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+
+    use jsonwebtoken::{EncodingKey, Header, encode, get_current_timestamp};
+
+    use super::*;
+    use crate::game::game_service_server::GameService;
+    use crate::memory_repository::InMemoryGameRepository;
+    use crate::users::tests::FakeUserDirectory;
+
+    const SECRET: &str = "test-secret";
+
+    pub(crate) fn service(repo: Arc<InMemoryGameRepository>) -> GameServiceImpl {
+        service_with(repo, Arc::new(FakeUserDirectory::default()))
+    }
+
+    pub(crate) fn service_with(repo: Arc<InMemoryGameRepository>, users: Arc<FakeUserDirectory>) -> GameServiceImpl {
+        GameServiceImpl {
+            repo,
+            events: EventSink::new(),
+            page_size: PageSizeConfig::default(),
+            callers: CallerVerifier::new(SECRET, 0),
+            catalog_stats: CatalogStatsCache::new(),
+            search: SearchConfig::default(),
+            list_guard: ListGuardConfig::default(),
+            draft_expiry: DraftExpiryConfig::from_env().unwrap(),
+            purge: PurgeConfig::from_env().unwrap(),
+            prices: PricePolicy::from_env().unwrap(),
+            enums: EnumPolicy::default(),
+            game_reads: SingleFlight::new(),
+            users,
+        }
+    }
+
+    pub(crate) fn token(user_id: Uuid, role: &str) -> String {
+        let claims = serde_json::json!({
+            "sub": user_id.to_string(),
+            "role": role,
+            "exp": get_current_timestamp() + 300,
+        });
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap()
+    }
+
+    /// `message` as the interceptor would hand it over for `user_id`.
+    pub(crate) fn request_as<T>(service: &GameServiceImpl, message: T, user_id: Uuid, role: &str) -> Request<T> {
+        let mut request = Request::new(message);
+        let value = format!("Bearer {}", token(user_id, role)).parse().unwrap();
+        request.metadata_mut().insert("authorization", value);
+        service.callers.authenticate(&mut request).unwrap();
+        request
+    }
+
+    fn new_game(developer_id: Uuid, price: i64) -> game::CreateGameRequest {
+        game::CreateGameRequest {
+            name: "Test Game".to_string(),
+            description: "A game for the handler tests".to_string(),
+            developer_id: developer_id.to_string(),
+            price,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_runs_validate_without_storing() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        let valid = game::CreateGameRequest { dry_run: true, ..new_game(developer, 1999) };
+        let request = request_as(&service, valid, developer, "developer");
+        let checked = service.create_game(request).await.unwrap().into_inner();
+        assert_eq!(checked.name, "Test Game");
+        assert!(checked.id.is_empty());
+
+        let invalid = [
+            game::CreateGameRequest { dry_run: true, name: String::new(), ..new_game(developer, 1999) },
+            game::CreateGameRequest { dry_run: true, ..new_game(developer, 100_000_000) },
+            game::CreateGameRequest {
+                dry_run: true,
+                release_date: Some("2025-02-30".to_string()),
+                ..new_game(developer, 1999)
+            },
+            game::CreateGameRequest {
+                dry_run: true,
+                publisher_id: Some("not-a-uuid".to_string()),
+                ..new_game(developer, 1999)
+            },
+        ];
+        for message in invalid {
+            let request = request_as(&service, message.clone(), developer, "developer");
+            let err = service.create_game(request).await.unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument, "{:?}", message);
+        }
+
+        let (stored, total) = repo.list_games(&GameFilter::default(), None, 10, 0).await.unwrap();
+        assert!(stored.is_empty());
+        assert_eq!(total, 0);
+        assert!(repo.audit_log().is_empty());
+    }
+
+    #[tokio::test]
+    async fn names_are_stored_normalized() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        let message = game::CreateGameRequest { name: "  Cool  Game  ".to_string(), ..new_game(developer, 1999) };
+        let request = request_as(&service, message, developer, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+
+        assert_eq!(created.name, "Cool Game");
+        let stored = repo.get_game(created.id.parse().unwrap()).await.unwrap().unwrap();
+        assert_eq!(stored.name, "Cool Game");
+    }
+
+    #[tokio::test]
+    async fn price_changes_are_listed_oldest_first() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let developer = Uuid::new_v4();
+        let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+        let id = service.create_game(request).await.unwrap().into_inner().id;
+
+        for price in [1499, 999] {
+            let update = game::UpdateGameRequest { id: id.clone(), price: Some(price), ..Default::default() };
+            let request = request_as(&service, update, developer, "developer");
+            service.update_game(request).await.unwrap();
+        }
+
+        let request = Request::new(game::ListPriceHistoryRequest { game_id: id });
+        let entries = service.list_price_history(request).await.unwrap().into_inner().entries;
+        let changes: Vec<(i64, i64)> = entries.iter().map(|e| (e.old_price, e.new_price)).collect();
+        assert_eq!(changes, vec![(1999, 1499), (1499, 999)]);
+        assert!(entries.iter().all(|e| e.changed_at.is_some()));
+    }
+
+    #[test]
+    fn common_statuses_match_the_generated_proto_enum() {
+        use strum::IntoEnumIterator;
+
+        for status in GameStatus::iter() {
+            let generated = game::GameStatus::try_from(status.to_proto()).unwrap();
+            let expected = format!("GAME_STATUS_{}", status.to_string().to_uppercase());
+            assert_eq!(generated.as_str_name(), expected);
+        }
+        for value in -1..16 {
+            assert_eq!(game::GameStatus::try_from(value).is_ok(), GameStatus::from_proto(value).is_some());
         }
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn empty_descriptions_come_back_empty() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        for description in ["", "  \n "] {
+            let message = game::CreateGameRequest { description: description.to_string(), ..new_game(developer, 1999) };
+            let created = service
+                .create_game(request_as(&service, message, developer, "developer"))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(created.description, "", "{description:?}");
+
+            let fetched = service
+                .get_game(Request::new(game::GetGameRequest { id: created.id }))
+                .await
+                .unwrap()
+                .into_inner()
+                .game
+                .unwrap();
+            assert_eq!(fetched.description, "", "{description:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn deep_unfiltered_pages_of_a_large_catalog_are_refused() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let mut service = service(repo.clone());
+        let developer = Uuid::new_v4();
+        for _ in 0..3 {
+            let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+            service.create_game(request).await.unwrap();
+        }
+
+        async fn list(
+            service: &GameServiceImpl,
+            developer_id: Option<String>,
+        ) -> Result<Response<game::ListGamesResponse>, Status> {
+            let caller = Uuid::new_v4();
+            let message = game::ListGamesRequest { page_token: "2".to_string(), developer_id, ..Default::default() };
+            service.list_games(request_as(service, message, caller, "player")).await
+        }
+
+        service.list_guard = ListGuardConfig { max_unfiltered_offset: 1, min_guarded_rows: 3, debug_timing: false };
+        assert_eq!(list(&service, None).await.unwrap().into_inner().games.len(), 1);
+
+        service.list_guard.min_guarded_rows = 2;
+        let err = list(&service, None).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(list(&service, Some(developer.to_string())).await.unwrap().into_inner().games.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn status_filters_pick_one_real_status_and_absent_means_any() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let admin = Uuid::new_v4();
+
+        let mut ids = HashMap::new();
+        for status in GameStatus::SELECTABLE {
+            let request = request_as(&service, new_game(Uuid::new_v4(), 1999), admin, "admin");
+            let created = service.create_game(request).await.unwrap().into_inner();
+            let id = Uuid::parse_str(&created.id).unwrap();
+            repo.update_game(id, GameChanges { status: Some(status), ..Default::default() }).await.unwrap();
+            ids.insert(status, created.id);
+        }
+
+        let list = |status: Option<i32>| {
+            let message = game::ListGamesRequest { status, page_size: 10, ..Default::default() };
+            service.list_games(request_as(&service, message, admin, "admin"))
+        };
+
+        for status in GameStatus::SELECTABLE {
+            let listed = list(Some(status.to_proto())).await.unwrap().into_inner();
+            let listed: Vec<String> = listed.games.into_iter().map(|g| g.id).collect();
+            assert_eq!(listed, [ids[&status].clone()], "{status}");
+        }
+
+        let everything = list(None).await.unwrap().into_inner();
+        assert_eq!(everything.total_count, GameStatus::SELECTABLE.len() as u64);
+
+        let err = list(Some(GameStatus::Unspecified.to_proto())).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn blank_search_queries_are_no_search_at_all() {
+        let search = |query: Option<&str>| {
+            let req = game::ListGamesRequest { search_query: query.map(String::from), ..Default::default() };
+            list_filter(req, false, EnumPolicy::default()).unwrap().1
+        };
+
+        for query in [None, Some(""), Some("   "), Some("\t\n")] {
+            assert_eq!(search(query), None, "{query:?}");
+        }
+        assert_eq!(search(Some("  space quest ")), Some("space quest".to_string()));
+    }
+
+    #[tokio::test]
+    async fn malformed_ids_are_invalid_arguments_in_every_rpc() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let admin = Uuid::new_v4();
+        let bad = || "not-a-uuid".to_string();
+        let some_id = || Uuid::new_v4().to_string();
+
+        // Calls `rpc` as an admin, so only the id can be what's wrong.
+        macro_rules! call {
+            ($rpc:ident, $message:expr) => {
+                service.$rpc(request_as(&service, $message, admin, "admin")).await.map(drop)
+            };
+        }
+
+        let outcomes: Vec<(&str, Result<(), Status>)> = vec![
+            ("CreateGame", call!(create_game, game::CreateGameRequest { developer_id: bad(), ..new_game(admin, 1999) })),
+            ("GetGame", call!(get_game, game::GetGameRequest { id: bad() })),
+            ("UpdateGame", call!(update_game, game::UpdateGameRequest { id: bad(), ..Default::default() })),
+            ("ScheduleSale", call!(schedule_sale, game::ScheduleSaleRequest { game_id: bad(), ..Default::default() })),
+            ("CancelSale", call!(cancel_sale, game::CancelSaleRequest { game_id: bad() })),
+            ("DeleteGame.id", call!(delete_game, game::DeleteGameRequest { id: bad(), developer_id: some_id() })),
+            ("DeleteGame.developer_id", call!(delete_game, game::DeleteGameRequest { id: some_id(), developer_id: bad() })),
+            ("ListGames.developer_id", call!(list_games, game::ListGamesRequest { developer_id: Some(bad()), ..Default::default() })),
+            ("ListGames.exclude_id", call!(list_games, game::ListGamesRequest { exclude_id: Some(bad()), ..Default::default() })),
+            ("ListGames.publisher_id", call!(list_games, game::ListGamesRequest { publisher_id: Some(bad()), ..Default::default() })),
+            ("ListPriceHistory", call!(list_price_history, game::ListPriceHistoryRequest { game_id: bad() })),
+            ("ListTopReviews", call!(list_top_reviews, game::ListTopReviewsRequest { game_id: bad(), ..Default::default() })),
+            ("GetReviewSummary", call!(get_review_summary, game::GetReviewSummaryRequest { game_id: bad() })),
+            ("WatchGameEvents", call!(watch_game_events, game::WatchGameEventsRequest { game_id: Some(bad()) })),
+            ("ExportDeveloperGames", call!(export_developer_games, game::ExportDeveloperGamesRequest { developer_id: bad() })),
+            ("GetDeveloperStatusCounts", call!(get_developer_status_counts, game::GetDeveloperStatusCountsRequest { developer_id: bad() })),
+            ("RecomputeGameStats", call!(recompute_game_stats, game::RecomputeGameStatsRequest { game_id: Some(bad()) })),
+            ("ListAuditLog.actor_id", call!(list_audit_log, game::ListAuditLogRequest { actor_id: Some(bad()), ..Default::default() })),
+            ("ListAuditLog.game_id", call!(list_audit_log, game::ListAuditLogRequest { game_id: Some(bad()), ..Default::default() })),
+        ];
+
+        for (rpc, outcome) in outcomes {
+            let err = outcome.expect_err(rpc);
+            assert_eq!(err.code(), tonic::Code::InvalidArgument, "{rpc}: {}", err.message());
+        }
+
+        // Bulk updates report a bad id per row rather than failing the call.
+        let bulk = game::BulkSetPricesRequest {
+            updates: vec![game::PriceUpdate { game_id: bad(), price: 1999 }],
+            mode: game::BulkPriceMode::SkipFailed as i32,
+        };
+        let response = service.bulk_set_prices(request_as(&service, bulk, admin, "admin")).await.unwrap().into_inner();
+        assert_eq!(response.results[0].error_code.as_deref(), Some("INVALID_ID"));
+    }
+
+    #[tokio::test]
+    async fn only_admins_can_list_deleted_games() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let developer = Uuid::new_v4();
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+            ids.push(service.create_game(request).await.unwrap().into_inner().id);
+        }
+        let delete = game::DeleteGameRequest { id: ids[0].clone(), developer_id: developer.to_string() };
+        service.delete_game(request_as(&service, delete, developer, "developer")).await.unwrap();
+
+        let list = |include_deleted| game::ListGamesRequest { include_deleted, ..Default::default() };
+        let listed = |response: game::ListGamesResponse| {
+            let mut ids: Vec<String> = response.games.into_iter().map(|g| g.id).collect();
+            ids.sort();
+            (ids, response.total_count)
+        };
+        let mut all = ids.clone();
+        all.sort();
+
+        let admin = Uuid::new_v4();
+        let response = service.list_games(request_as(&service, list(Some(true)), admin, "admin")).await.unwrap();
+        assert_eq!(listed(response.into_inner()), (all, 2));
+
+        for include_deleted in [None, Some(false)] {
+            let response = service.list_games(request_as(&service, list(include_deleted), admin, "admin")).await.unwrap();
+            assert_eq!(listed(response.into_inner()), (vec![ids[1].clone()], 1));
+        }
+
+        // Not even the developer who deleted it.
+        let err = service
+            .list_games(request_as(&service, list(Some(true)), developer, "developer"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let err = service.list_games(Request::new(list(Some(true)))).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn returned_timestamps_are_the_stored_ones() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+        let stored = repo.get_game(Uuid::parse_str(&created.id).unwrap()).await.unwrap().unwrap();
+
+        let created_at = created.created_at.unwrap();
+        assert_eq!(timestamp_to_datetime(&created_at), Some(stored.created_at));
+        assert_eq!(created_at.nanos as u32, stored.created_at.timestamp_subsec_nanos());
+        assert_eq!(timestamp_to_datetime(&created.updated_at.unwrap()), Some(stored.updated_at));
+
+        let fetched = service
+            .get_game(Request::new(game::GetGameRequest { id: created.id }))
+            .await
+            .unwrap()
+            .into_inner()
+            .game
+            .unwrap();
+        assert_eq!(fetched.created_at, Some(created_at));
+    }
+
+    #[tokio::test]
+    async fn create_game_requires_a_caller() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+
+        let err = service
+            .create_game(Request::new(new_game(Uuid::new_v4(), 1999)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn developers_create_games_only_for_themselves() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let developer = Uuid::new_v4();
+
+        let request = request_as(&service, new_game(Uuid::new_v4(), 1999), developer, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+
+        assert_eq!(created.developer_id, developer.to_string());
+    }
+
+    #[tokio::test]
+    async fn forged_developer_ids_are_overridden_or_rejected() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let victim = Uuid::new_v4();
+        let attacker = Uuid::new_v4();
+
+        let request = request_as(&service, new_game(victim, 1999), victim, "developer");
+        let victims_game = service.create_game(request).await.unwrap().into_inner();
+
+        // Creating: the token's subject wins over the body.
+        let request = request_as(&service, new_game(victim, 1999), attacker, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+        let stored = repo.get_game(Uuid::parse_str(&created.id).unwrap()).await.unwrap().unwrap();
+        assert_eq!(stored.developer_id, attacker);
+
+        // Deleting: naming the owner in the body doesn't make the caller the owner.
+        let delete = |developer_id: Uuid| game::DeleteGameRequest {
+            id: victims_game.id.clone(),
+            developer_id: developer_id.to_string(),
+        };
+        for developer_id in [victim, attacker] {
+            let request = request_as(&service, delete(developer_id), attacker, "developer");
+            let err = service.delete_game(request).await.unwrap_err();
+            assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        }
+        let victims_id = Uuid::parse_str(&victims_game.id).unwrap();
+        assert!(repo.get_game(victims_id).await.unwrap().is_some());
+
+        // Reading someone else's dashboard.
+        let counts = game::GetDeveloperStatusCountsRequest { developer_id: victim.to_string() };
+        let request = request_as(&service, counts, attacker, "developer");
+        let err = service.get_developer_status_counts(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+    }
+
+    #[test]
+    fn tokens_signed_with_another_secret_are_rejected() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let claims = serde_json::json!({
+            "sub": Uuid::new_v4().to_string(),
+            "role": "admin",
+            "exp": get_current_timestamp() + 300,
+        });
+        let forged = encode(&Header::default(), &claims, &EncodingKey::from_secret(b"guessed")).unwrap();
+
+        let mut request = Request::new(new_game(Uuid::new_v4(), 1999));
+        request.metadata_mut().insert("authorization", format!("Bearer {}", forged).parse().unwrap());
+        let err = service.callers.authenticate(&mut request).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn admins_create_games_for_any_developer() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let developer = Uuid::new_v4();
+
+        let request = request_as(&service, new_game(developer, 1999), Uuid::new_v4(), "admin");
+        let created = service.create_game(request).await.unwrap().into_inner();
+
+        assert_eq!(created.developer_id, developer.to_string());
+    }
+
+    #[tokio::test]
+    async fn price_outside_the_policy_is_rejected_before_storing() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        let request = request_as(&service, new_game(developer, 50), developer, "developer");
+        let err = service.create_game(request).await.unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        let (games, total) = repo.list_games(&GameFilter::default(), None, 10, 0).await.unwrap();
+        assert!(games.is_empty() && total == 0);
+    }
+
+    #[tokio::test]
+    async fn get_game_maps_the_stored_row() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let developer = Uuid::new_v4();
+        let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+
+        let fetched = service
+            .get_game(Request::new(game::GetGameRequest { id: created.id.clone() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .game
+            .unwrap();
+
+        assert_eq!(fetched.name, "Test Game");
+        assert_eq!(fetched.price, 1999);
+        assert_eq!(fetched.base_price, 1999);
+        assert_eq!(fetched.status, GameStatus::Draft.to_proto());
+        assert_eq!(fetched.publisher_id, None);
+    }
+
+    #[tokio::test]
+    async fn publishers_must_be_existing_users() {
+        let users = Arc::new(FakeUserDirectory::default());
+        let service = service_with(Arc::new(InMemoryGameRepository::new()), users.clone());
+        let developer = Uuid::new_v4();
+        let publisher = Uuid::new_v4();
+        let gone = Uuid::new_v4();
+        users.delete(gone);
+        let create = |publisher_id: Option<String>| {
+            let message = game::CreateGameRequest { publisher_id, ..new_game(developer, 1999) };
+            service.create_game(request_as(&service, message, developer, "developer"))
+        };
+
+        let created = create(Some(publisher.to_string())).await.unwrap().into_inner();
+        assert_eq!(created.publisher_id, Some(publisher.to_string()));
+        assert_eq!(create(None).await.unwrap().into_inner().publisher_id, None);
+        assert_eq!(create(Some(String::new())).await.unwrap().into_inner().publisher_id, None);
+
+        for publisher_id in ["not-a-uuid".to_string(), gone.to_string()] {
+            let err = create(Some(publisher_id)).await.unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        }
+    }
+
+    #[tokio::test]
+    async fn get_missing_game_is_not_found() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+
+        let err = service
+            .get_game(Request::new(game::GetGameRequest { id: Uuid::new_v4().to_string() }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn only_the_owner_or_an_admin_may_update() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let owner = Uuid::new_v4();
+        let request = request_as(&service, new_game(owner, 1999), owner, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+
+        let rename = |name: &str| game::UpdateGameRequest {
+            id: created.id.clone(),
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+
+        let request = request_as(&service, rename("Stolen"), Uuid::new_v4(), "developer");
+        let err = service.update_game(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let request = request_as(&service, rename("Renamed"), owner, "developer");
+        assert_eq!(service.update_game(request).await.unwrap().into_inner().name, "Renamed");
+
+        let request = request_as(&service, rename("Moderated"), Uuid::new_v4(), "admin");
+        assert_eq!(service.update_game(request).await.unwrap().into_inner().name, "Moderated");
+    }
+
+    #[tokio::test]
+    async fn deleting_twice_reports_the_game_already_absent() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let owner = Uuid::new_v4();
+        let request = request_as(&service, new_game(owner, 1999), owner, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+
+        let delete = || game::DeleteGameRequest {
+            id: created.id.clone(),
+            developer_id: owner.to_string(),
+        };
+
+        let first = service.delete_game(request_as(&service, delete(), owner, "developer")).await.unwrap();
+        assert!(!first.into_inner().already_absent);
+        let second = service.delete_game(request_as(&service, delete(), owner, "developer")).await.unwrap();
+        assert!(second.into_inner().already_absent);
+    }
+
+    #[tokio::test]
+    async fn recompute_corrects_counters_that_drifted() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+        let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+        let id = service.create_game(request).await.unwrap().into_inner().id;
+        let game_id = Uuid::parse_str(&id).unwrap();
+        repo.add_review(game_id, 4);
+        repo.add_review(game_id, 5);
+        repo.add_purchase(game_id);
+
+        let recompute = || game::RecomputeGameStatsRequest { game_id: Some(id.clone()) };
+        let err = service
+            .recompute_game_stats(request_as(&service, recompute(), developer, "developer"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let admin = Uuid::new_v4();
+        let response = service
+            .recompute_game_stats(request_as(&service, recompute(), admin, "admin"))
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().games_corrected, 1);
+
+        let stored = repo.get_game(game_id).await.unwrap().unwrap();
+        assert_eq!((stored.rating_count, stored.purchase_count), (2, 1));
+        assert_eq!(stored.average_rating, Decimal::new(450, 2));
+    }
+
+    #[tokio::test]
+    async fn purge_removes_old_deletions_and_audits_the_admin() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+        let mut ids = Vec::new();
+        for deleted_days_ago in [Some(30), Some(2), None] {
+            let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+            let id = service.create_game(request).await.unwrap().into_inner().id;
+            let game_id = Uuid::parse_str(&id).unwrap();
+            let mut stored = repo.get_game(game_id).await.unwrap().unwrap();
+            stored.deleted_at = deleted_days_ago.map(|days| chrono::Utc::now() - chrono::Duration::days(days));
+            repo.insert(stored);
+            ids.push(game_id);
+        }
+        let (game_id, recently_deleted, live) = (ids[0], ids[1], ids[2]);
+
+        let admin = Uuid::new_v4();
+        let request = request_as(
+            &service,
+            game::PurgeDeletedGamesRequest { older_than_days: Some(7) },
+            admin,
+            "admin",
+        );
+        assert_eq!(service.purge_deleted_games(request).await.unwrap().into_inner().purged, 1);
+
+        let everything = GameFilter { include_deleted: true, ..Default::default() };
+        let (remaining, _) = repo.list_games(&everything, None, 10, 0).await.unwrap();
+        let mut remaining: Vec<Uuid> = remaining.iter().map(|g| g.id).collect();
+        remaining.sort();
+        let mut expected = vec![recently_deleted, live];
+        expected.sort();
+        assert_eq!(remaining, expected);
+        let audit = repo.audit_log();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].game_id, game_id);
+        assert_eq!(audit[0].action, "purged");
+        assert_eq!(audit[0].actor_id, Some(admin));
+    }
+
+    #[tokio::test]
+    async fn tokens_issued_before_a_revocation_are_rejected() {
+        let users = Arc::new(FakeUserDirectory::default());
+        let service = service_with(Arc::new(InMemoryGameRepository::new()), users.clone());
+        let admin = Uuid::new_v4();
+        let developer = Uuid::new_v4();
+
+        let before = request_as(&service, new_game(developer, 1999), admin, "admin");
+        users.revoke_sessions(admin);
+
+        let err = service.create_game(before).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+        assert_eq!(err.message(), "Token has been revoked");
+
+        // Revoking one user's sessions leaves everyone else's alone
+        let request = request_as(&service, new_game(developer, 1999), developer, "developer");
+        assert!(service.create_game(request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn drafts_need_no_release_date_but_publishing_does() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        let draft = game::CreateGameRequest {
+            cover_image: "https://example.com/cover.png".to_string(),
+            ..new_game(developer, 1999)
+        };
+        let request = request_as(&service, draft, developer, "developer");
+        let created = service.create_game(request).await.unwrap().into_inner();
+        assert_eq!(created.status, GameStatus::Draft.to_proto());
+        assert_eq!(created.release_date, None);
+
+        let publish = |release_date: Option<&str>| game::UpdateGameRequest {
+            id: created.id.clone(),
+            status: Some(GameStatus::Published.to_proto()),
+            screenshots: vec!["https://example.com/shot.png".to_string()],
+            release_date: release_date.map(String::from),
+            ..Default::default()
+        };
+
+        let request = request_as(&service, publish(None), developer, "developer");
+        let err = service.update_game(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(err.message(), "Cannot publish until these are set: release_date");
+
+        let request = request_as(&service, publish(Some("2025-03-01")), developer, "developer");
+        let published = service.update_game(request).await.unwrap().into_inner();
+        assert_eq!(published.status, GameStatus::Published.to_proto());
+        assert_eq!(published.release_date.as_deref(), Some("2025-03-01"));
+    }
+
+    #[tokio::test]
+    async fn price_facets_cover_the_default_buckets_and_check_custom_bounds() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let developer = Uuid::new_v4();
+        for price in [0, 999, 1000, 1999, 2000, 5999, 6000, 25000] {
+            service
+                .create_game(request_as(&service, new_game(developer, price), developer, "developer"))
+                .await
+                .unwrap();
+        }
+        let facets = |bounds: Vec<i64>| {
+            service.get_price_facets(Request::new(game::GetPriceFacetsRequest { filter: None, bounds }))
+        };
+
+        let defaults = facets(vec![]).await.unwrap().into_inner().facets;
+        let buckets: Vec<(Option<i64>, Option<i64>, u64)> =
+            defaults.iter().map(|f| (f.min_price, f.max_price, f.count)).collect();
+        assert_eq!(
+            buckets,
+            [
+                (None, Some(1000), 2),
+                (Some(1000), Some(2000), 2),
+                (Some(2000), Some(4000), 1),
+                (Some(4000), Some(6000), 1),
+                (Some(6000), None, 2),
+            ]
+        );
+
+        let counts: Vec<u64> = facets(vec![1000]).await.unwrap().into_inner().facets.iter().map(|f| f.count).collect();
+        assert_eq!(counts, [2, 6]);
+
+        for (bounds, message) in [
+            (vec![2000, 1000], "Price bucket bounds must be strictly ascending"),
+            (vec![1000, 1000], "Price bucket bounds must be strictly ascending"),
+            (vec![-1], "Price bucket bounds cannot be negative"),
+            ((1..=21).collect(), "At most 20 price bucket bounds are allowed"),
+        ] {
+            let err = facets(bounds).await.unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument);
+            assert_eq!(err.message(), message);
+        }
+    }
+
+    #[tokio::test]
+    async fn strict_price_batches_are_all_or_nothing_and_skip_failed_applies_the_rest() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let (developer, rival) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut ids = Vec::new();
+        for owner in [developer, rival, developer] {
+            let request = request_as(&service, new_game(owner, 1999), owner, "developer");
+            ids.push(service.create_game(request).await.unwrap().into_inner().id);
+        }
+        let batch = |mode: game::BulkPriceMode| game::BulkSetPricesRequest {
+            updates: ids
+                .iter()
+                .zip([999, 500, 1499])
+                .map(|(id, price)| game::PriceUpdate { game_id: id.clone(), price })
+                .collect(),
+            mode: mode as i32,
+        };
+        async fn prices(repo: &InMemoryGameRepository, ids: &[String]) -> Vec<i64> {
+            let mut prices = Vec::new();
+            for id in ids {
+                let game = repo.get_game(id.parse().unwrap()).await.unwrap().unwrap();
+                prices.push(decimal_to_cents(game.price));
+            }
+            prices
+        }
+        let codes = |response: &game::BulkSetPricesResponse| -> Vec<Option<String>> {
+            response.results.iter().map(|r| r.error_code.clone()).collect()
+        };
+
+        let request = request_as(&service, batch(game::BulkPriceMode::Strict), developer, "developer");
+        let strict = service.bulk_set_prices(request).await.unwrap().into_inner();
+        assert_eq!(strict.updated_count, 0);
+        assert_eq!(
+            codes(&strict),
+            [Some("BATCH_ROLLED_BACK".to_string()), Some("NOT_OWNER".to_string()), Some("BATCH_ROLLED_BACK".to_string())]
+        );
+        assert_eq!(prices(&repo, &ids).await, [1999, 1999, 1999]);
+
+        let request = request_as(&service, batch(game::BulkPriceMode::SkipFailed), developer, "developer");
+        let skipped = service.bulk_set_prices(request).await.unwrap().into_inner();
+        assert_eq!(skipped.updated_count, 2);
+        assert_eq!(codes(&skipped), [None, Some("NOT_OWNER".to_string()), None]);
+        assert_eq!(skipped.results[0].game.as_ref().unwrap().price, 999);
+        assert_eq!(prices(&repo, &ids).await, [999, 1999, 1499]);
+
+        // Nothing fails for an admin, so strict mode applies everything
+        let request = request_as(&service, batch(game::BulkPriceMode::Strict), Uuid::new_v4(), "admin");
+        let admin = service.bulk_set_prices(request).await.unwrap().into_inner();
+        assert_eq!(admin.updated_count, 3);
+        assert_eq!(prices(&repo, &ids).await, [999, 500, 1499]);
+    }
+
+    #[tokio::test]
+    async fn malformed_release_dates_are_rejected_before_storing() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        let message = game::CreateGameRequest { release_date: Some("2024-02-30".to_string()), ..new_game(developer, 1999) };
+        let err = service.create_game(request_as(&service, message, developer, "developer")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.message(), "release_date '2024-02-30' is not a valid calendar date");
+        assert_eq!(repo.list_games(&GameFilter::default(), None, 10, 0).await.unwrap().1, 0);
+
+        let message = game::CreateGameRequest { release_date: Some("2024-03-01".to_string()), ..new_game(developer, 1999) };
+        let created = service.create_game(request_as(&service, message, developer, "developer")).await.unwrap().into_inner();
+
+        let update = game::UpdateGameRequest {
+            id: created.id.clone(),
+            release_date: Some("March 2024".to_string()),
+            ..Default::default()
+        };
+        let err = service.update_game(request_as(&service, update, developer, "developer")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.message(), "release_date must be in YYYY-MM-DD format, got 'March 2024'");
+    }
+
+    #[tokio::test]
+    async fn unknown_categories_become_unspecified_unless_enums_are_strict() {
+        let developer = Uuid::new_v4();
+        let message = || game::CreateGameRequest { categories: vec![1, 99], ..new_game(developer, 1999) };
+
+        let lenient = service(Arc::new(InMemoryGameRepository::new()));
+        let request = request_as(&lenient, message(), developer, "developer");
+        let created = lenient.create_game(request).await.unwrap().into_inner();
+        assert_eq!(created.categories, [1, 0]);
+
+        let mut strict = service(Arc::new(InMemoryGameRepository::new()));
+        strict.enums = EnumPolicy { strict: true };
+        let request = request_as(&strict, message(), developer, "developer");
+        let err = strict.create_game(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.message(), "Unknown category value: 99");
+    }
+
+    #[tokio::test]
+    async fn status_counts_are_for_the_developer_or_an_admin() {
+        let service = service(Arc::new(InMemoryGameRepository::new()));
+        let (developer, rival) = (Uuid::new_v4(), Uuid::new_v4());
+        for owner in [developer, developer, rival] {
+            let request = request_as(&service, new_game(owner, 1999), owner, "developer");
+            service.create_game(request).await.unwrap();
+        }
+        let counts_as = |caller: Uuid, role: &str| {
+            let message = game::GetDeveloperStatusCountsRequest { developer_id: developer.to_string() };
+            service.get_developer_status_counts(request_as(&service, message, caller, role))
+        };
+
+        let own = counts_as(developer, "developer").await.unwrap().into_inner();
+        assert_eq!((own.draft, own.under_review, own.published, own.suspended), (2, 0, 0, 0));
+        let admin = counts_as(Uuid::new_v4(), "admin").await.unwrap().into_inner();
+        assert_eq!(admin.draft, 2);
+
+        let err = counts_as(rival, "developer").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        let anonymous = game::GetDeveloperStatusCountsRequest { developer_id: developer.to_string() };
+        let err = service.get_developer_status_counts(Request::new(anonymous)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+}
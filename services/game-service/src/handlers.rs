@@ -1,42 +1,198 @@
 use axum::{
-    extract::{Json, State},
-    http::StatusCode,
+    extract::{Json, State, rejection::JsonRejection},
+    http::{HeaderMap, StatusCode, header},
     response::Json as ResponseJson,
 };
-use sqlx::PgPool;
 use tonic::Request;
 
+use common::auth;
+
 use crate::game;
 use crate::grpc_service::GameServiceImpl;
 use crate::types::{CreateGameRequest, GameResponse};
+use crate::users;
 
 pub async fn create_game_http(
-    State(pool): State<PgPool>,
-    Json(request): Json<CreateGameRequest>,
+    State(service): State<GameServiceImpl>,
+    headers: HeaderMap,
+    request: Result<Json<CreateGameRequest>, JsonRejection>,
 ) -> Result<ResponseJson<GameResponse>, StatusCode> {
-    use crate::game::game_service_server::GameService;
-    
-    let service = GameServiceImpl { pool };
-    
-    let grpc_request = game::CreateGameRequest {
+    // A malformed body, including a price that isn't an exact amount, is the
+    // client's mistake: 400 rather than axum's default 422.
+    let Ok(Json(request)) = request else {
+        return Err(StatusCode::BAD_REQUEST);
+    };
+
+    // Verified exactly as the gRPC interceptor does it.
+    let mut auth_request = Request::new(());
+    if let Some(value) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+    {
+        auth_request.metadata_mut().insert("authorization", value);
+    }
+    if service.callers.authenticate(&mut auth_request).is_err() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    let Ok(caller) = auth::require_caller(&auth_request) else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+    match users::check_session(service.users.as_ref(), &caller).await {
+        Ok(()) => {}
+        Err(status) if status.code() == tonic::Code::Unauthenticated => return Err(StatusCode::UNAUTHORIZED),
+        Err(_) => return Err(StatusCode::SERVICE_UNAVAILABLE),
+    }
+
+    let create = game::CreateGameRequest {
         name: request.name,
         description: request.description,
         developer_id: request.developer_id,
-        publisher_id: request.publisher_id,
-        cover_image: request.cover_image,
-        trailer_url: request.trailer_url,
-        release_date: request.release_date,
         categories: request.categories,
+        price: request.price.into(),
+        cover_image: request.cover_image,
         tags: request.tags,
         platforms: request.platforms,
-        price: request.price as i64,
+        publisher_id: request.publisher_id,
+        trailer_url: request.trailer_url,
+        release_date: request.release_date,
+        dry_run: false,
     };
 
-    match service.create_game(Request::new(grpc_request)).await {
-        Ok(response) => {
-            let game_response = service.convert_to_response(response.into_inner());
-            Ok(ResponseJson(game_response))
-        },
+    match service.create_game_as(&caller, create).await {
+        Ok(created) => Ok(ResponseJson(service.convert_to_response(created))),
+        Err(status) if status.code() == tonic::Code::InvalidArgument => Err(StatusCode::BAD_REQUEST),
+        Err(status) if status.code() == tonic::Code::Unauthenticated => Err(StatusCode::UNAUTHORIZED),
+        // The user service, asked about the publisher, is down
+        Err(status) if status.code() == tonic::Code::Unavailable => Err(StatusCode::SERVICE_UNAVAILABLE),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::HeaderValue;
+    use common::models::GameStatus;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::game::game_service_server::GameService;
+    use crate::grpc_service::tests::{request_as, service, service_with, token};
+    use crate::memory_repository::InMemoryGameRepository;
+    use crate::repository::GameRepository;
+    use crate::users::tests::FakeUserDirectory;
+
+    fn bearer(user_id: Uuid, role: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let bearer = format!("Bearer {}", token(user_id, role));
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(&bearer).unwrap());
+        headers
+    }
+
+    fn body(developer: Uuid) -> CreateGameRequest {
+        serde_json::from_value(serde_json::json!({
+            "name": "Test Game",
+            "description": "A game for the handler tests",
+            "developer_id": developer.to_string(),
+            "categories": [],
+            "tags": [],
+            "platforms": [],
+            "price": 19.99,
+            "cover_image": "",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn price_round_trips_in_dollars() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+
+        let ResponseJson(created) =
+            create_game_http(State(service), bearer(developer, "developer"), Ok(Json(body(developer))))
+                .await
+                .unwrap();
+
+        assert_eq!(serde_json::to_value(&created).unwrap()["price"], "19.99");
+        let stored = repo.get_game(created.id.parse().unwrap()).await.unwrap().unwrap();
+        assert_eq!(stored.price, Decimal::new(1999, 2));
+    }
+
+    #[tokio::test]
+    async fn revoked_tokens_are_unauthorized() {
+        let users = Arc::new(FakeUserDirectory::default());
+        let service = service_with(Arc::new(InMemoryGameRepository::new()), users.clone());
+        let developer = Uuid::new_v4();
+        let headers = bearer(developer, "developer");
+        users.revoke_sessions(developer);
+
+        let err = create_game_http(State(service), headers, Ok(Json(body(developer))))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn http_and_grpc_creates_store_the_same_row() {
+        let repo = Arc::new(InMemoryGameRepository::new());
+        let service = service(repo.clone());
+        let developer = Uuid::new_v4();
+        let fields = serde_json::json!({
+            "name": "Same Game",
+            "description": "Created through both APIs",
+            "developer_id": developer.to_string(),
+            "categories": [1, 2],
+            "tags": ["co-op", "pixel art"],
+            "platforms": ["windows", "linux"],
+            "price": "24.99",
+            "cover_image": "https://example.com/cover.png",
+            "trailer_url": "https://example.com/trailer.mp4",
+            "release_date": "2025-03-01",
+        });
+
+        let http_body: CreateGameRequest = serde_json::from_value(fields).unwrap();
+        let grpc_body = game::CreateGameRequest {
+            name: http_body.name.clone(),
+            description: http_body.description.clone(),
+            developer_id: http_body.developer_id.clone(),
+            categories: http_body.categories.clone(),
+            price: http_body.price.into(),
+            cover_image: http_body.cover_image.clone(),
+            tags: http_body.tags.clone(),
+            platforms: http_body.platforms.clone(),
+            publisher_id: http_body.publisher_id.clone(),
+            trailer_url: http_body.trailer_url.clone(),
+            release_date: http_body.release_date.clone(),
+            dry_run: false,
+        };
+
+        let ResponseJson(via_http) =
+            create_game_http(State(service.clone()), bearer(developer, "developer"), Ok(Json(http_body)))
+                .await
+                .unwrap();
+        let request = request_as(&service, grpc_body, developer, "developer");
+        let via_grpc = service.create_game(request).await.unwrap().into_inner();
+
+        let http_row = repo.get_game(via_http.id.parse().unwrap()).await.unwrap().unwrap();
+        let grpc_row = repo.get_game(via_grpc.id.parse().unwrap()).await.unwrap().unwrap();
+        assert_ne!(http_row.id, grpc_row.id);
+        assert_eq!(http_row.name, grpc_row.name);
+        assert_eq!(http_row.description, grpc_row.description);
+        assert_eq!(http_row.developer_id, grpc_row.developer_id);
+        assert_eq!(http_row.publisher_id, grpc_row.publisher_id);
+        assert_eq!(http_row.cover_image, grpc_row.cover_image);
+        assert_eq!(http_row.trailer_url, grpc_row.trailer_url);
+        assert_eq!(http_row.release_date, grpc_row.release_date);
+        assert_eq!(http_row.categories, grpc_row.categories);
+        assert_eq!(http_row.tags, grpc_row.tags);
+        assert_eq!(http_row.platforms, grpc_row.platforms);
+        assert_eq!(http_row.price, grpc_row.price);
+        assert_eq!(http_row.price, Decimal::new(2499, 2));
+        assert_eq!(GameStatus::from(http_row.status), GameStatus::from(grpc_row.status));
+    }
+}
@@ -0,0 +1,154 @@
+use tonic::Status;
+
+use crate::models::GameFilter;
+
+/// Name of the response metadata carrying the listing query time when
+/// `LIST_DEBUG_TIMING` is on.
+pub const QUERY_TIME_KEY: &str = "x-query-time-ms";
+
+/// Guards `ListGames` against deep pages that no filter narrows down: Postgres
+/// has to produce and throw away every row before the offset, so on a big
+/// catalog each such page is close to a full scan.
+#[derive(Debug, Clone, Copy)]
+pub struct ListGuardConfig {
+    /// Listings without a selective filter may not start past this offset.
+    pub max_unfiltered_offset: i32,
+    /// The guard only applies once the catalog is estimated to hold more
+    /// rows than this.
+    pub min_guarded_rows: i64,
+    /// Reports how long the listing queries took in the response metadata.
+    pub debug_timing: bool,
+}
+
+impl Default for ListGuardConfig {
+    fn default() -> Self {
+        Self {
+            max_unfiltered_offset: 1_000,
+            min_guarded_rows: 10_000,
+            debug_timing: false,
+        }
+    }
+}
+
+impl ListGuardConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let defaults = Self::default();
+
+        let max_unfiltered_offset = match std::env::var("LIST_MAX_UNFILTERED_OFFSET") {
+            Ok(value) => value
+                .parse::<i32>()
+                .ok()
+                .filter(|offset| *offset >= 0)
+                .ok_or_else(|| format!("LIST_MAX_UNFILTERED_OFFSET has an invalid value: {}", value))?,
+            Err(_) => defaults.max_unfiltered_offset,
+        };
+
+        let min_guarded_rows = match std::env::var("LIST_GUARD_MIN_ROWS") {
+            Ok(value) => value
+                .parse::<i64>()
+                .map_err(|_| format!("LIST_GUARD_MIN_ROWS has an invalid value: {}", value))?,
+            Err(_) => defaults.min_guarded_rows,
+        };
+
+        let debug_timing = match std::env::var("LIST_DEBUG_TIMING") {
+            Ok(value) => value
+                .trim()
+                .parse::<bool>()
+                .map_err(|_| "LIST_DEBUG_TIMING must be true or false".to_string())?,
+            Err(_) => defaults.debug_timing,
+        };
+
+        Ok(Self {
+            max_unfiltered_offset,
+            min_guarded_rows,
+            debug_timing,
+        })
+    }
+
+    /// Whether the page is deep enough, and the filter loose enough, that the
+    /// catalog size has to be checked before running the query.
+    pub fn needs_check(&self, filter: &GameFilter, has_search: bool, offset: i32) -> bool {
+        offset > self.max_unfiltered_offset && !is_selective(filter, has_search)
+    }
+
+    /// `estimated_rows` is `None` when the size is unknown, which is treated
+    /// as large.
+    #[allow(clippy::result_large_err)]
+    pub fn check(&self, estimated_rows: Option<i64>) -> Result<(), Status> {
+        if estimated_rows.is_some_and(|rows| rows <= self.min_guarded_rows) {
+            return Ok(());
+        }
+        Err(Status::invalid_argument(format!(
+            "Filter by developer, publisher, category, price or search query to page past offset {}",
+            self.max_unfiltered_offset
+        )))
+    }
+}
+
+/// Status and `exclude_id` don't count: nearly every game matches them.
+fn is_selective(filter: &GameFilter, has_search: bool) -> bool {
+    has_search
+        || filter.developer_id.is_some()
+        || filter.publisher_id.is_some()
+        || filter.categories.is_some()
+        || filter.min_price.is_some()
+        || filter.max_price.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::models::DbGameCategory;
+
+    fn guard() -> ListGuardConfig {
+        ListGuardConfig { max_unfiltered_offset: 100, min_guarded_rows: 1_000, debug_timing: false }
+    }
+
+    #[test]
+    fn only_pages_past_the_cap_need_a_check() {
+        let unfiltered = GameFilter::default();
+        assert!(!guard().needs_check(&unfiltered, false, 0));
+        assert!(!guard().needs_check(&unfiltered, false, 100));
+        assert!(guard().needs_check(&unfiltered, false, 101));
+    }
+
+    #[test]
+    fn selective_filters_lift_the_cap() {
+        let selective = [
+            GameFilter { developer_id: Some(Uuid::new_v4()), ..Default::default() },
+            GameFilter { publisher_id: Some(Uuid::new_v4()), ..Default::default() },
+            GameFilter { categories: Some(vec![DbGameCategory::Rpg]), ..Default::default() },
+            GameFilter { min_price: Some(Decimal::from(10)), ..Default::default() },
+            GameFilter { max_price: Some(Decimal::from(10)), ..Default::default() },
+        ];
+        for filter in &selective {
+            assert!(!guard().needs_check(filter, false, 5_000), "{filter:?}");
+        }
+        assert!(!guard().needs_check(&GameFilter::default(), true, 5_000));
+
+        let loose = GameFilter {
+            status: Some(common::models::GameStatus::Published),
+            exclude_id: Some(Uuid::new_v4()),
+            ..Default::default()
+        };
+        assert!(guard().needs_check(&loose, false, 5_000));
+    }
+
+    #[test]
+    fn deep_pages_are_refused_once_the_catalog_is_estimated_large() {
+        assert!(guard().check(Some(0)).is_ok());
+        assert!(guard().check(Some(1_000)).is_ok());
+
+        for estimate in [Some(1_001), None] {
+            let err = guard().check(estimate).unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument);
+            assert_eq!(
+                err.message(),
+                "Filter by developer, publisher, category, price or search query to page past offset 100"
+            );
+        }
+    }
+}
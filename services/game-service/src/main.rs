@@ -1,35 +1,112 @@
 use tonic::transport::Server;
 use dotenv::dotenv;
-use sqlx::postgres::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub mod game {
     tonic::include_proto!("game");
 }
 
+pub mod user {
+    tonic::include_proto!("user");
+}
+
+mod aggregates;
 mod types;
 mod grpc_service;
+mod list_guard;
 mod handlers;
 mod routes;
 mod db;
+mod drafts;
 mod models;
+mod events;
+mod validation;
+mod repository;
+mod retry;
+#[cfg(test)]
+mod memory_repository;
+mod pricing;
+mod publishing;
+mod purge;
+mod search;
+mod single_flight;
+mod stats;
+mod users;
+
+use common::auth::CallerVerifier;
+use common::pagination::PageSizeConfig;
+use common::deadline::DeadlineLayer;
+use common::enum_policy::EnumPolicy;
+use common::rpc_log::RpcLogLayer;
+use tracing_subscriber::EnvFilter;
 
+use crate::drafts::DraftExpiryConfig;
+use crate::events::EventSink;
+use crate::list_guard::ListGuardConfig;
 use crate::grpc_service::GameServiceImpl;
+use crate::repository::PgGameRepository;
+use crate::retry::RetryPolicy;
+use crate::users::UserServiceDirectory;
+use crate::pricing::PricePolicy;
+use crate::purge::PurgeConfig;
+use crate::search::SearchConfig;
+use crate::single_flight::SingleFlight;
+use crate::stats::CatalogStatsCache;
 use crate::routes::create_routes;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
 
     let database_url = std::env::var("DATABASE_URL")
         .expect("DATABASE_URL must be set");
-    let pool = PgPool::connect(&database_url).await?;
+    let statement_timeout_ms = match std::env::var("DB_STATEMENT_TIMEOUT_MS") {
+        Ok(value) => value.parse().expect("Invalid DB_STATEMENT_TIMEOUT_MS configuration"),
+        Err(_) => 30_000,
+    };
+    let pool = db::connect(&database_url, Duration::from_millis(statement_timeout_ms)).await?;
 
     let grpc_addr = "[::1]:50052".parse()?;
     let http_addr = "0.0.0.0:8080".parse::<std::net::SocketAddr>()?;
     
-    let game_service = GameServiceImpl { pool: pool.clone() };
+    let game_service = GameServiceImpl {
+        repo: Arc::new(PgGameRepository::new(
+            pool,
+            RetryPolicy::from_env().expect("Invalid DB retry configuration"),
+        )),
+        events: EventSink::new(),
+        page_size: PageSizeConfig::from_env().expect("Invalid page size configuration"),
+        callers: CallerVerifier::from_env().expect("Invalid JWT configuration"),
+        catalog_stats: CatalogStatsCache::new(),
+        search: SearchConfig::from_env().expect("Invalid search configuration"),
+        list_guard: ListGuardConfig::from_env().expect("Invalid list guard configuration"),
+        draft_expiry: DraftExpiryConfig::from_env().expect("Invalid draft expiry configuration"),
+        purge: PurgeConfig::from_env().expect("Invalid purge configuration"),
+        prices: PricePolicy::from_env().expect("Invalid price policy configuration"),
+        enums: EnumPolicy::from_env().expect("Invalid enum policy configuration"),
+        game_reads: SingleFlight::new(),
+        users: Arc::new(UserServiceDirectory::from_env().expect("Invalid user service configuration")),
+    };
+
+    if let Some(every) = aggregates::recompute_interval_from_env()
+        .expect("Invalid game stats recompute configuration")
+    {
+        aggregates::spawn_recompute_job(game_service.repo.clone(), every);
+    }
+
+    if let Some(after_days) = game_service.draft_expiry.after_days {
+        drafts::spawn_expiry_job(game_service.repo.clone(), after_days, game_service.draft_expiry.check_every);
+    }
+
+    if let Some(every) = game_service.purge.every {
+        purge::spawn_purge_job(game_service.repo.clone(), game_service.purge, every);
+    }
 
-    let app = create_routes(pool.clone());
+    let app = create_routes(game_service.clone());
 
     let http_server = tokio::spawn(async move {
         let listener = tokio::net::TcpListener::bind(&http_addr).await.unwrap();
@@ -40,8 +117,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let grpc_server = tokio::spawn(async move {
         println!("gRPC service listening on {}", grpc_addr);
         Server::builder()
-            .add_service(game::game_service_server::GameServiceServer::new(
-                game_service,
+            .layer(RpcLogLayer)
+            .layer(DeadlineLayer)
+            .add_service(game::game_service_server::GameServiceServer::with_interceptor(
+                game_service.clone(),
+                game_service.callers.clone(),
             ))
             .serve(grpc_addr)
             .await
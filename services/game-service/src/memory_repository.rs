@@ -0,0 +1,680 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Days, Utc};
+use common::models::GameStatus;
+use rust_decimal::{Decimal, RoundingStrategy};
+use uuid::Uuid;
+
+use crate::models::{round_rating, AuditFilter, CategoryPriceStats, DbAuditEntry, DbGame, DbGameCategory, DbGameReview, DbGameStatus, DbPriceHistory, GameChanges, GameFilter, GameSuggestion, NewGame, ReviewSummary, SaleSchedule, StaleDraft, StatusCounts};
+use crate::repository::{GameRepository, GameRowStream};
+
+/// `GameRepository` kept in process memory, for running handlers without
+/// Postgres. Full-text and trigram search are both approximated by a
+/// case-insensitive substring match on the name.
+#[derive(Default)]
+pub struct InMemoryGameRepository {
+    games: Mutex<HashMap<Uuid, DbGame>>,
+    price_history: Mutex<Vec<DbPriceHistory>>,
+    reviews: Mutex<Vec<DbGameReview>>,
+    purchases: Mutex<Vec<Uuid>>,
+    /// Games that have been published at some point, for draft expiry.
+    ever_published: Mutex<HashSet<Uuid>>,
+    /// Oldest first, ids counting up from 1 like the table's.
+    audit_log: Mutex<Vec<DbAuditEntry>>,
+}
+
+impl InMemoryGameRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, game: DbGame) {
+        if matches!(game.status, DbGameStatus::Published | DbGameStatus::Suspended) {
+            self.ever_published.lock().unwrap().insert(game.id);
+        }
+        self.games.lock().unwrap().insert(game.id, game);
+    }
+
+    pub fn audit_log(&self) -> Vec<DbAuditEntry> {
+        self.audit_log.lock().unwrap().clone()
+    }
+
+    fn audit(
+        log: &mut Vec<DbAuditEntry>,
+        game_id: Uuid,
+        actor_id: Option<Uuid>,
+        action: &str,
+        detail: String,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) {
+        let id = log.len() as i64 + 1;
+        log.push(DbAuditEntry {
+            id,
+            game_id,
+            actor_id,
+            action: action.to_string(),
+            detail,
+            before: before.map(|v| v.to_string()),
+            after: after.map(|v| v.to_string()),
+            created_at: Utc::now(),
+        });
+    }
+
+    fn stale_drafts(&self, games: &HashMap<Uuid, DbGame>, cutoff: DateTime<Utc>) -> Vec<StaleDraft> {
+        let ever_published = self.ever_published.lock().unwrap();
+        let mut drafts: Vec<StaleDraft> = games
+            .values()
+            .filter(|g| matches!(g.status, DbGameStatus::Draft) && g.deleted_at.is_none())
+            .filter(|g| !ever_published.contains(&g.id) && g.updated_at < cutoff)
+            .map(|g| StaleDraft {
+                id: g.id,
+                name: g.name.clone(),
+                developer_id: g.developer_id,
+                updated_at: g.updated_at,
+            })
+            .collect();
+        drafts.sort_by_key(|d| d.updated_at);
+        drafts
+    }
+
+    /// Records a review without touching the game's counters, the way a
+    /// write that bypassed the incremental update would.
+    pub fn add_review(&self, game_id: Uuid, rating: i16) {
+        self.reviews.lock().unwrap().push(DbGameReview {
+            id: Uuid::new_v4(),
+            game_id,
+            user_id: Uuid::new_v4(),
+            rating,
+            created_at: Utc::now(),
+        });
+    }
+
+    pub fn add_purchase(&self, game_id: Uuid) {
+        self.purchases.lock().unwrap().push(game_id);
+    }
+
+    fn live_games(&self) -> Vec<DbGame> {
+        self.games
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|g| g.deleted_at.is_none())
+            .cloned()
+            .collect()
+    }
+
+    fn search(
+        &self,
+        filter: &GameFilter,
+        search_query: Option<&str>,
+        limit: i32,
+        offset: i32,
+    ) -> (Vec<DbGame>, i64) {
+        let query = search_query.map(str::to_lowercase);
+
+        let mut games: Vec<DbGame> = self
+            .games
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|g| filter.include_deleted || g.deleted_at.is_none())
+            .filter(|g| matches_filter(g, filter))
+            .filter(|g| query.as_ref().is_none_or(|q| g.name.to_lowercase().contains(q)))
+            .cloned()
+            .collect();
+        games.sort_by_key(|g| std::cmp::Reverse(g.created_at));
+
+        let total = games.len() as i64;
+        let page = games
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect();
+
+        (page, total)
+    }
+
+    fn published(&self) -> Vec<DbGame> {
+        self.live_games()
+            .into_iter()
+            .filter(|g| matches!(g.status, DbGameStatus::Published))
+            .collect()
+    }
+}
+
+fn matches_filter(game: &DbGame, filter: &GameFilter) -> bool {
+    let price = game.effective_price(Utc::now());
+    filter.developer_id.is_none_or(|id| game.developer_id == id)
+        && filter.exclude_id.is_none_or(|id| game.id != id)
+        && filter.publisher_id.is_none_or(|id| game.publisher_id == Some(id))
+        && filter
+            .categories
+            .as_ref()
+            .is_none_or(|cats| cats.iter().any(|c| game.categories.contains(c)))
+        && filter.min_price.is_none_or(|min| price >= min)
+        && filter.max_price.is_none_or(|max| price <= max)
+        && filter
+            .status
+            .is_none_or(|status| GameStatus::from(game.status.clone()) == status)
+}
+
+#[tonic::async_trait]
+impl GameRepository for InMemoryGameRepository {
+    async fn create_game(&self, game: NewGame) -> Result<DbGame, sqlx::Error> {
+        let now = Utc::now();
+        let created = DbGame {
+            id: Uuid::new_v4(),
+            name: game.name,
+            description: game.description,
+            developer_id: game.developer_id,
+            publisher_id: game.publisher_id,
+            cover_image: game.cover_image,
+            trailer_url: game.trailer_url,
+            release_date: game.release_date,
+            price: game.price,
+            status: DbGameStatus::Draft,
+            categories: game.categories,
+            tags: game.tags,
+            platforms: game.platforms,
+            screenshots: vec![],
+            rating_count: 0,
+            average_rating: Decimal::ZERO,
+            purchase_count: 0,
+            sale_price: None,
+            sale_starts_at: None,
+            sale_ends_at: None,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+        self.insert(created.clone());
+
+        Ok(created)
+    }
+
+    async fn get_game(&self, id: Uuid) -> Result<Option<DbGame>, sqlx::Error> {
+        Ok(self
+            .games
+            .lock()
+            .unwrap()
+            .get(&id)
+            .filter(|g| g.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn update_game(&self, id: Uuid, changes: GameChanges) -> Result<Option<DbGame>, sqlx::Error> {
+        let now = Utc::now();
+        let mut games = self.games.lock().unwrap();
+        let Some(game) = games.get_mut(&id).filter(|g| g.deleted_at.is_none()) else {
+            return Ok(None);
+        };
+
+        if let Some(price) = changes.price.filter(|p| *p != game.price) {
+            self.price_history.lock().unwrap().push(DbPriceHistory {
+                id: Uuid::new_v4(),
+                game_id: id,
+                old_price: game.price,
+                new_price: price,
+                changed_at: now,
+            });
+            game.price = price;
+        }
+
+        if let Some(name) = changes.name {
+            game.name = name;
+        }
+        if let Some(description) = changes.description {
+            game.description = description;
+        }
+        if let Some(cover_image) = changes.cover_image {
+            game.cover_image = Some(cover_image);
+        }
+        if let Some(trailer_url) = changes.trailer_url {
+            game.trailer_url = Some(trailer_url);
+        }
+        if let Some(release_date) = changes.release_date {
+            game.release_date = Some(release_date);
+        }
+        if let Some(status) = changes.status {
+            game.status = match status {
+                GameStatus::Unspecified => DbGameStatus::Unspecified,
+                GameStatus::Draft => DbGameStatus::Draft,
+                GameStatus::UnderReview => DbGameStatus::UnderReview,
+                GameStatus::Published => DbGameStatus::Published,
+                GameStatus::Suspended => DbGameStatus::Suspended,
+            };
+            if status == GameStatus::Published {
+                self.ever_published.lock().unwrap().insert(id);
+            }
+        }
+        if let Some(categories) = changes.categories {
+            game.categories = categories;
+        }
+        if let Some(tags) = changes.tags {
+            game.tags = tags;
+        }
+        if let Some(platforms) = changes.platforms {
+            game.platforms = platforms;
+        }
+        if let Some(screenshots) = changes.screenshots {
+            game.screenshots = screenshots;
+        }
+        game.updated_at = now;
+
+        Ok(Some(game.clone()))
+    }
+
+    async fn bulk_set_prices(
+        &self,
+        updates: &[(Uuid, Decimal)],
+        rollback_on_missing: bool,
+    ) -> Result<Vec<Option<(Decimal, DbGame)>>, sqlx::Error> {
+        let now = Utc::now();
+        let mut games = self.games.lock().unwrap();
+
+        let live = |id: &Uuid| games.get(id).filter(|g| g.deleted_at.is_none());
+        if rollback_on_missing && updates.iter().any(|(id, _)| live(id).is_none()) {
+            // Nothing is applied; report what each entry would have found
+            return Ok(updates
+                .iter()
+                .map(|(id, _)| live(id).map(|g| (g.price, g.clone())))
+                .collect());
+        }
+
+        let mut outcomes = Vec::with_capacity(updates.len());
+        for (id, price) in updates {
+            let Some(game) = games.get_mut(id).filter(|g| g.deleted_at.is_none()) else {
+                outcomes.push(None);
+                continue;
+            };
+
+            let old_price = game.price;
+            if *price != old_price {
+                self.price_history.lock().unwrap().push(DbPriceHistory {
+                    id: Uuid::new_v4(),
+                    game_id: *id,
+                    old_price,
+                    new_price: *price,
+                    changed_at: now,
+                });
+            }
+            game.price = *price;
+            game.updated_at = now;
+            outcomes.push(Some((old_price, game.clone())));
+        }
+
+        Ok(outcomes)
+    }
+
+    async fn set_sale(&self, id: Uuid, sale: Option<SaleSchedule>) -> Result<Option<DbGame>, sqlx::Error> {
+        let mut games = self.games.lock().unwrap();
+        let Some(game) = games.get_mut(&id).filter(|g| g.deleted_at.is_none()) else {
+            return Ok(None);
+        };
+
+        game.sale_price = sale.map(|s| s.price);
+        game.sale_starts_at = sale.map(|s| s.starts_at);
+        game.sale_ends_at = sale.map(|s| s.ends_at);
+        game.updated_at = Utc::now();
+
+        Ok(Some(game.clone()))
+    }
+
+    async fn delete_game(&self, id: Uuid, developer_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut games = self.games.lock().unwrap();
+        match games.get_mut(&id) {
+            Some(game) if game.developer_id == developer_id && game.deleted_at.is_none() => {
+                let now = Utc::now();
+                game.deleted_at = Some(now);
+                game.updated_at = now;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn list_games(
+        &self,
+        filter: &GameFilter,
+        search_query: Option<&str>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<(Vec<DbGame>, i64), sqlx::Error> {
+        Ok(self.search(filter, search_query, limit, offset))
+    }
+
+    async fn fuzzy_search_games(
+        &self,
+        filter: &GameFilter,
+        search_query: &str,
+        _similarity_threshold: f32,
+        limit: i32,
+        offset: i32,
+    ) -> Result<(Vec<DbGame>, i64), sqlx::Error> {
+        Ok(self.search(filter, Some(search_query), limit, offset))
+    }
+
+    async fn list_price_history(&self, game_id: Uuid) -> Result<Vec<DbPriceHistory>, sqlx::Error> {
+        Ok(self
+            .price_history
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.game_id == game_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_top_reviews(&self, game_id: Uuid, limit: i32) -> Result<Vec<DbGameReview>, sqlx::Error> {
+        let mut reviews: Vec<DbGameReview> = self
+            .reviews
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|review| review.game_id == game_id)
+            .cloned()
+            .collect();
+        reviews.sort_by(|a, b| b.rating.cmp(&a.rating).then(b.created_at.cmp(&a.created_at)));
+        reviews.truncate(limit.max(0) as usize);
+
+        Ok(reviews)
+    }
+
+    async fn get_review_summary(&self, game_id: Uuid) -> Result<Option<ReviewSummary>, sqlx::Error> {
+        let live = self.games.lock().unwrap().get(&game_id).is_some_and(|g| g.deleted_at.is_none());
+        if !live {
+            return Ok(None);
+        }
+        let mut summary = ReviewSummary::default();
+        for review in self.reviews.lock().unwrap().iter().filter(|r| r.game_id == game_id) {
+            summary.add(review.rating, 1);
+        }
+        Ok(Some(summary))
+    }
+
+    fn stream_developer_games(&self, developer_id: Uuid) -> GameRowStream<'_> {
+        let mut games: Vec<DbGame> = self
+            .live_games()
+            .into_iter()
+            .filter(|g| g.developer_id == developer_id)
+            .collect();
+        games.sort_by_key(|g| g.created_at);
+
+        Box::pin(tokio_stream::iter(games.into_iter().map(Ok)))
+    }
+
+    async fn get_developer_status_counts(&self, developer_id: Uuid) -> Result<StatusCounts, sqlx::Error> {
+        let mut counts = StatusCounts::default();
+        for game in self.live_games().iter().filter(|g| g.developer_id == developer_id) {
+            counts.add(&game.status, 1);
+        }
+        Ok(counts)
+    }
+
+    async fn estimate_game_rows(&self) -> Result<Option<i64>, sqlx::Error> {
+        Ok(Some(self.games.lock().unwrap().len() as i64))
+    }
+
+    async fn count_published_catalog(&self) -> Result<(i64, i64), sqlx::Error> {
+        let published = self.published();
+        let developers: HashSet<Uuid> = published.iter().map(|g| g.developer_id).collect();
+
+        Ok((published.len() as i64, developers.len() as i64))
+    }
+
+    async fn get_newest_release(&self) -> Result<Option<DbGame>, sqlx::Error> {
+        let today = Utc::now().date_naive();
+
+        Ok(self
+            .published()
+            .into_iter()
+            .filter(|g| g.release_date.is_some_and(|d| d <= today))
+            .max_by(|a, b| {
+                a.release_date
+                    .cmp(&b.release_date)
+                    .then(a.created_at.cmp(&b.created_at))
+            }))
+    }
+
+    async fn get_popular_games(&self, limit: i32) -> Result<Vec<DbGame>, sqlx::Error> {
+        let mut games = self.published();
+        games.sort_by(|a, b| {
+            b.purchase_count
+                .cmp(&a.purchase_count)
+                .then(b.average_rating.cmp(&a.average_rating))
+        });
+        games.truncate(limit.max(0) as usize);
+
+        Ok(games)
+    }
+
+    async fn list_new_releases(&self, window_days: i32, limit: i32) -> Result<Vec<DbGame>, sqlx::Error> {
+        let today = Utc::now().date_naive();
+        let first = today - Days::new(window_days.max(0) as u64);
+
+        let mut games: Vec<DbGame> = self
+            .published()
+            .into_iter()
+            .filter(|g| g.release_date.is_some_and(|d| d > first && d <= today))
+            .collect();
+        games.sort_by(|a, b| {
+            b.release_date
+                .cmp(&a.release_date)
+                .then(b.created_at.cmp(&a.created_at))
+        });
+        games.truncate(limit.max(0) as usize);
+
+        Ok(games)
+    }
+
+    async fn list_upcoming_games(&self, window_days: i32, limit: i32) -> Result<Vec<DbGame>, sqlx::Error> {
+        let today = Utc::now().date_naive();
+        let last = today + Days::new(window_days.max(0) as u64);
+
+        let mut games: Vec<DbGame> = self
+            .published()
+            .into_iter()
+            .filter(|g| g.release_date.is_some_and(|d| d > today && d <= last))
+            .collect();
+        games.sort_by(|a, b| {
+            a.release_date
+                .cmp(&b.release_date)
+                .then(a.created_at.cmp(&b.created_at))
+        });
+        games.truncate(limit.max(0) as usize);
+
+        Ok(games)
+    }
+
+    async fn get_category_price_stats(&self, category: DbGameCategory) -> Result<CategoryPriceStats, sqlx::Error> {
+        let now = Utc::now();
+        let mut prices: Vec<Decimal> = self
+            .published()
+            .into_iter()
+            .filter(|g| g.categories.contains(&category))
+            .map(|g| g.effective_price(now))
+            .collect();
+        prices.sort();
+
+        let count = prices.len();
+        let median = match count {
+            0 => None,
+            n if n % 2 == 1 => Some(prices[n / 2]),
+            n => Some((prices[n / 2 - 1] + prices[n / 2]) / Decimal::TWO),
+        };
+        let avg = (count > 0).then(|| prices.iter().sum::<Decimal>() / Decimal::from(count));
+
+        Ok(CategoryPriceStats {
+            game_count: count as i64,
+            min_price: prices.first().copied(),
+            max_price: prices.last().copied(),
+            avg_price: avg.map(|p| p.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)),
+            median_price: median.map(|p| p.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)),
+        })
+    }
+
+    async fn get_price_facets(
+        &self,
+        filter: &GameFilter,
+        search_query: Option<&str>,
+        bounds: &[Decimal],
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        let (games, _) = self.search(filter, search_query, i32::MAX, 0);
+
+        let now = Utc::now();
+        let mut counts = vec![0; bounds.len() + 1];
+        for game in games {
+            let price = game.effective_price(now);
+            counts[bounds.partition_point(|b| *b <= price)] += 1;
+        }
+
+        Ok(counts)
+    }
+
+    async fn suggest_games(&self, prefix: &str, limit: i32) -> Result<Vec<GameSuggestion>, sqlx::Error> {
+        let prefix = prefix.to_lowercase();
+        let mut games: Vec<DbGame> = self
+            .published()
+            .into_iter()
+            .filter(|g| g.name.to_lowercase().starts_with(&prefix))
+            .collect();
+        games.sort_by(|a, b| b.purchase_count.cmp(&a.purchase_count).then(a.name.cmp(&b.name)));
+
+        Ok(games
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|g| GameSuggestion { id: g.id, name: g.name })
+            .collect())
+    }
+
+    async fn recompute_game_stats(&self, game_id: Option<Uuid>) -> Result<u64, sqlx::Error> {
+        let reviews = self.reviews.lock().unwrap();
+        let purchases = self.purchases.lock().unwrap();
+        let mut games = self.games.lock().unwrap();
+
+        let mut corrected = 0;
+        for game in games
+            .values_mut()
+            .filter(|g| g.deleted_at.is_none() && game_id.is_none_or(|id| g.id == id))
+        {
+            let ratings: Vec<i16> = reviews
+                .iter()
+                .filter(|review| review.game_id == game.id)
+                .map(|review| review.rating)
+                .collect();
+            let rating_count = ratings.len() as i32;
+            let average_rating = if ratings.is_empty() {
+                Decimal::ZERO
+            } else {
+                let sum: i64 = ratings.iter().map(|&r| i64::from(r)).sum();
+                round_rating(Decimal::from(sum) / Decimal::from(rating_count))
+            };
+            let purchase_count = purchases.iter().filter(|id| **id == game.id).count() as i32;
+
+            if (game.rating_count, game.average_rating, game.purchase_count)
+                != (rating_count, average_rating, purchase_count)
+            {
+                game.rating_count = rating_count;
+                game.average_rating = average_rating;
+                game.purchase_count = purchase_count;
+                game.updated_at = Utc::now();
+                corrected += 1;
+            }
+        }
+
+        Ok(corrected)
+    }
+
+    async fn find_stale_drafts(&self, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error> {
+        Ok(self.stale_drafts(&self.games.lock().unwrap(), cutoff))
+    }
+
+    async fn expire_stale_drafts(&self, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error> {
+        let mut games = self.games.lock().unwrap();
+        let drafts = self.stale_drafts(&games, cutoff);
+        let now = Utc::now();
+        let mut audit_log = self.audit_log.lock().unwrap();
+        for draft in &drafts {
+            if let Some(game) = games.get_mut(&draft.id) {
+                game.deleted_at = Some(now);
+                game.updated_at = now;
+            }
+            Self::audit(
+                &mut audit_log,
+                draft.id,
+                None,
+                "draft_expired",
+                format!("Draft expired; last updated {}", draft.updated_at.to_rfc3339()),
+                Some(serde_json::json!({ "deleted_at": null })),
+                Some(serde_json::json!({ "deleted_at": now })),
+            );
+        }
+        Ok(drafts)
+    }
+
+    async fn purge_deleted_games(
+        &self,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+        actor_id: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error> {
+        let mut games = self.games.lock().unwrap();
+        let mut doomed: Vec<(DateTime<Utc>, Uuid)> = games
+            .values()
+            .filter_map(|g| g.deleted_at.filter(|at| *at < cutoff).map(|at| (at, g.id)))
+            .collect();
+        doomed.sort();
+        doomed.truncate(usize::try_from(limit).unwrap_or(0));
+
+        let mut audit_log = self.audit_log.lock().unwrap();
+        for (deleted_at, id) in &doomed {
+            let game = games.remove(id).expect("collected from the map above");
+            Self::audit(
+                &mut audit_log,
+                *id,
+                actor_id,
+                "purged",
+                format!("Purged '{}' by developer {}; deleted {}", game.name, game.developer_id, deleted_at.to_rfc3339()),
+                Some(serde_json::json!({
+                    "id": game.id,
+                    "name": game.name,
+                    "developer_id": game.developer_id,
+                    "deleted_at": deleted_at,
+                })),
+                None,
+            );
+        }
+
+        let purged: HashSet<Uuid> = doomed.into_iter().map(|(_, id)| id).collect();
+        self.price_history.lock().unwrap().retain(|h| !purged.contains(&h.game_id));
+        self.reviews.lock().unwrap().retain(|r| !purged.contains(&r.game_id));
+        self.purchases.lock().unwrap().retain(|game_id| !purged.contains(game_id));
+        self.ever_published.lock().unwrap().retain(|id| !purged.contains(id));
+        Ok(purged.len() as u64)
+    }
+
+    async fn list_audit(
+        &self,
+        filter: &AuditFilter,
+        limit: i64,
+        before_id: Option<i64>,
+    ) -> Result<Vec<DbAuditEntry>, sqlx::Error> {
+        Ok(self
+            .audit_log
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|e| before_id.is_none_or(|before| e.id < before))
+            .filter(|e| filter.actor_id.is_none_or(|actor| e.actor_id == Some(actor)))
+            .filter(|e| filter.game_id.is_none_or(|game| e.game_id == game))
+            .filter(|e| filter.action.as_ref().is_none_or(|action| &e.action == action))
+            .filter(|e| filter.since.is_none_or(|since| e.created_at >= since))
+            .filter(|e| filter.until.is_none_or(|until| e.created_at < until))
+            .take(usize::try_from(limit).unwrap_or(0))
+            .cloned()
+            .collect())
+    }
+}
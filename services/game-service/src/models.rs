@@ -1,9 +1,33 @@
 use chrono::{DateTime, Utc};
+use common::models::GameStatus;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::RoundingStrategy;
 use sqlx::types::Decimal;
 use uuid::Uuid;
 
-#[derive(Debug, sqlx::Type, Clone, PartialEq)]
+/// Digits after the decimal point in `average_rating` (`DECIMAL(3, 2)`).
+pub const RATING_SCALE: u32 = 2;
+/// Ratings run from 0.00 to 5.00.
+pub const MAX_RATING: Decimal = Decimal::from_parts(500, 0, 0, false, RATING_SCALE);
+
+/// Rounds an average the way Postgres' `ROUND` does, to the stored scale.
+pub fn round_rating(rating: Decimal) -> Decimal {
+     rating.round_dp_with_strategy(RATING_SCALE, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// The API's view of a stored rating: the same 0.00-5.00 value as an `f64`,
+/// converted from the decimal directly rather than through a string.
+pub fn rating_to_f64(rating: Decimal) -> f64 {
+     round_rating(rating.clamp(Decimal::ZERO, MAX_RATING))
+          .to_f64()
+          .expect("a rating between 0 and 5 fits in an f64")
+}
+
+/// `Display`/`FromStr` use the Postgres enum labels, which are also the
+/// names the HTTP APIs show.
+#[derive(Debug, sqlx::Type, Clone, PartialEq, strum::Display, strum::EnumString)]
 #[sqlx(type_name = "game_category", rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
 pub enum DbGameCategory {
      Unspecified,
      Action,
@@ -16,8 +40,9 @@ pub enum DbGameCategory {
      Puzzle,
 }
 
-#[derive(Debug, sqlx::Type, Clone)]
+#[derive(Debug, sqlx::Type, Clone, strum::Display, strum::EnumString)]
 #[sqlx(type_name = "game_status", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum DbGameStatus {
      Unspecified,
      Draft,
@@ -35,7 +60,7 @@ pub struct DbGame {
      pub publisher_id: Option<Uuid>,
      pub cover_image: Option<String>,
      pub trailer_url: Option<String>,
-     pub release_date: chrono::NaiveDate,
+     pub release_date: Option<chrono::NaiveDate>,
      pub price: Decimal,
      pub status: DbGameStatus,
      pub categories: Vec<DbGameCategory>,
@@ -45,27 +70,246 @@ pub struct DbGame {
      pub rating_count: i32,
      pub average_rating: Decimal,
      pub purchase_count: i32,
+     pub sale_price: Option<Decimal>,
+     pub sale_starts_at: Option<DateTime<Utc>>,
+     pub sale_ends_at: Option<DateTime<Utc>>,
      pub created_at: DateTime<Utc>,
      pub updated_at: DateTime<Utc>,
      #[allow(dead_code)]
      pub deleted_at: Option<DateTime<Utc>>,
 }
 
+impl DbGame {
+     /// The scheduled sale, whether or not it has started or already ended.
+     pub fn sale(&self) -> Option<SaleSchedule> {
+          Some(SaleSchedule {
+               price: self.sale_price?,
+               starts_at: self.sale_starts_at?,
+               ends_at: self.sale_ends_at?,
+          })
+     }
+
+     /// What the game costs at `now`: the sale price inside the sale window,
+     /// the base price otherwise.
+     pub fn effective_price(&self, now: DateTime<Utc>) -> Decimal {
+          match self.sale() {
+               Some(sale) if sale.is_active(now) => sale.price,
+               _ => self.price,
+          }
+     }
+}
+
+/// A discounted price that applies from `starts_at` until, but not
+/// including, `ends_at`.
+#[derive(Debug, Clone, Copy)]
+pub struct SaleSchedule {
+     pub price: Decimal,
+     pub starts_at: DateTime<Utc>,
+     pub ends_at: DateTime<Utc>,
+}
+
+impl SaleSchedule {
+     pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+          self.starts_at <= now && now < self.ends_at
+     }
+}
+
+/// A game about to be inserted; it always starts out as a draft.
+#[derive(Debug, Clone)]
+pub struct NewGame {
+     pub name: String,
+     pub description: String,
+     pub developer_id: Uuid,
+     pub publisher_id: Option<Uuid>,
+     pub cover_image: Option<String>,
+     pub trailer_url: Option<String>,
+     /// Drafts may leave this unset; it must be filled in before publishing.
+     pub release_date: Option<chrono::NaiveDate>,
+     pub categories: Vec<DbGameCategory>,
+     pub tags: Vec<String>,
+     pub platforms: Vec<String>,
+     pub price: Decimal,
+}
+
+/// Fields to change on a game; `None` leaves the column as it is.
+#[derive(Debug, Clone, Default)]
+pub struct GameChanges {
+     pub name: Option<String>,
+     pub description: Option<String>,
+     pub price: Option<Decimal>,
+     pub cover_image: Option<String>,
+     pub trailer_url: Option<String>,
+     pub release_date: Option<chrono::NaiveDate>,
+     pub status: Option<GameStatus>,
+     pub categories: Option<Vec<DbGameCategory>>,
+     pub tags: Option<Vec<String>>,
+     pub platforms: Option<Vec<String>>,
+     pub screenshots: Option<Vec<String>>,
+}
+
+/// Filters shared by the game listing queries; `None` means "any".
+#[derive(Debug, Clone, Default)]
+pub struct GameFilter {
+     pub developer_id: Option<Uuid>,
+     pub categories: Option<Vec<DbGameCategory>>,
+     pub min_price: Option<Decimal>,
+     pub max_price: Option<Decimal>,
+     pub status: Option<GameStatus>,
+     pub include_deleted: bool,
+     pub exclude_id: Option<Uuid>,
+     pub publisher_id: Option<Uuid>,
+}
+
+/// A draft the expiry job removes, or would remove.
+#[derive(Debug, Clone)]
+pub struct StaleDraft {
+     pub id: Uuid,
+     pub name: String,
+     pub developer_id: Uuid,
+     pub updated_at: DateTime<Utc>,
+}
+
+/// One row of `game_audit_log`. `before` and `after` are JSON text; either
+/// may be missing when it says nothing useful (a purge has no `after`).
+#[derive(Debug, Clone)]
+pub struct DbAuditEntry {
+     pub id: i64,
+     pub game_id: Uuid,
+     /// `None` for changes made by a background job.
+     pub actor_id: Option<Uuid>,
+     pub action: String,
+     pub detail: String,
+     pub before: Option<String>,
+     pub after: Option<String>,
+     pub created_at: DateTime<Utc>,
+}
+
+/// Filters for `list_audit`; `None` means "any". The time range is
+/// `[since, until)`.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+     pub actor_id: Option<Uuid>,
+     pub game_id: Option<Uuid>,
+     pub action: Option<String>,
+     pub since: Option<DateTime<Utc>>,
+     pub until: Option<DateTime<Utc>>,
+}
+
+/// Price spread of the published games in one category. The prices are
+/// `None` when there are no such games.
+#[derive(Debug, Clone)]
+pub struct CategoryPriceStats {
+     pub game_count: i64,
+     pub min_price: Option<Decimal>,
+     pub max_price: Option<Decimal>,
+     pub avg_price: Option<Decimal>,
+     pub median_price: Option<Decimal>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameSuggestion {
+     pub id: Uuid,
+     pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbGameReview {
+     pub id: Uuid,
+     #[allow(dead_code)]
+     pub game_id: Uuid,
+     pub user_id: Uuid,
+     pub rating: i16,
+     pub created_at: DateTime<Utc>,
+}
+
+/// How many reviews gave each star rating; `star_counts[0]` is 1 star.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewSummary {
+     pub star_counts: [i64; 5],
+}
+
+impl ReviewSummary {
+     pub fn total(&self) -> i64 {
+          self.star_counts.iter().sum()
+     }
+
+     /// Rounded like the stored `average_rating`; zero without reviews.
+     pub fn average(&self) -> Decimal {
+          let total = self.total();
+          if total == 0 {
+               return Decimal::ZERO;
+          }
+          let sum: i64 = self
+               .star_counts
+               .iter()
+               .zip(1..)
+               .map(|(count, stars)| count * stars)
+               .sum();
+          round_rating(Decimal::from(sum) / Decimal::from(total))
+     }
+
+     /// Adds `count` reviews of `rating` stars, ignoring ratings outside 1-5.
+     pub fn add(&mut self, rating: i16, count: i64) {
+          if let Some(slot) = usize::try_from(rating - 1).ok().and_then(|i| self.star_counts.get_mut(i)) {
+               *slot += count;
+          }
+     }
+}
+
+/// A developer's live games per status.
+#[derive(Debug, Clone, Default)]
+pub struct StatusCounts {
+     pub draft: i64,
+     pub under_review: i64,
+     pub published: i64,
+     pub suspended: i64,
+}
+
+impl StatusCounts {
+     /// Stored games always have a real status, so `Unspecified` is dropped.
+     pub fn add(&mut self, status: &DbGameStatus, count: i64) {
+          match status {
+               DbGameStatus::Draft => self.draft += count,
+               DbGameStatus::UnderReview => self.under_review += count,
+               DbGameStatus::Published => self.published += count,
+               DbGameStatus::Suspended => self.suspended += count,
+               DbGameStatus::Unspecified => {}
+          }
+     }
+}
+
+#[derive(Debug, Clone)]
+pub struct DbPriceHistory {
+     #[allow(dead_code)]
+     pub id: Uuid,
+     #[allow(dead_code)]
+     pub game_id: Uuid,
+     pub old_price: Decimal,
+     pub new_price: Decimal,
+     pub changed_at: DateTime<Utc>,
+}
+
 impl DbGameCategory {
-     pub fn from_proto(value: i32) -> Self {
+     /// `None` for integers the proto enum doesn't define.
+     pub fn try_from_proto(value: i32) -> Option<Self> {
           match value {
-               1 => Self::Action,
-               2 => Self::Rpg,
-               3 => Self::Strategy,
-               4 => Self::Sports,
-               5 => Self::Racing,
-               6 => Self::Adventure,
-               7 => Self::Simulation,
-               8 => Self::Puzzle,
-               _ => Self::Unspecified,
+               0 => Some(Self::Unspecified),
+               1 => Some(Self::Action),
+               2 => Some(Self::Rpg),
+               3 => Some(Self::Strategy),
+               4 => Some(Self::Sports),
+               5 => Some(Self::Racing),
+               6 => Some(Self::Adventure),
+               7 => Some(Self::Simulation),
+               8 => Some(Self::Puzzle),
+               _ => None,
           }
      }
 
+     pub fn from_proto(value: i32) -> Self {
+          Self::try_from_proto(value).unwrap_or(Self::Unspecified)
+     }
+
      pub fn to_proto(&self) -> i32 {
           match self {
                Self::Action => 1,
@@ -81,24 +325,101 @@ impl DbGameCategory {
      }
 }
 
-impl DbGameStatus {
-     pub fn from_proto(value: i32) -> Self {
-          match value {
-               1 => Self::Draft,
-               2 => Self::UnderReview,
-               3 => Self::Published,
-               4 => Self::Suspended,
-               _ => Self::Unspecified,
+impl From<DbGameStatus> for GameStatus {
+     fn from(status: DbGameStatus) -> Self {
+          match status {
+               DbGameStatus::Unspecified => GameStatus::Unspecified,
+               DbGameStatus::Draft => GameStatus::Draft,
+               DbGameStatus::UnderReview => GameStatus::UnderReview,
+               DbGameStatus::Published => GameStatus::Published,
+               DbGameStatus::Suspended => GameStatus::Suspended,
           }
      }
+}
 
-     pub fn to_proto(&self) -> i32 {
-          match self {
-               Self::Draft => 1,
-               Self::UnderReview => 2,
-               Self::Published => 3,
-               Self::Suspended => 4,
-               Self::Unspecified => 0,
+#[cfg(test)]
+mod tests {
+     use rust_decimal::prelude::FromPrimitive;
+
+     use super::*;
+
+     #[test]
+     fn every_stored_rating_round_trips_through_f64() {
+          for cents in 0..=500 {
+               let stored = Decimal::new(cents, RATING_SCALE);
+               let api = rating_to_f64(stored);
+               assert_eq!(api, stored.to_string().parse::<f64>().unwrap(), "{stored}");
+               assert_eq!(round_rating(Decimal::from_f64(api).unwrap()), stored, "{stored}");
           }
      }
-}
\ No newline at end of file
+
+     #[test]
+     fn ratings_outside_the_scale_are_clamped() {
+          assert_eq!(rating_to_f64(Decimal::new(-1, 2)), 0.0);
+          assert_eq!(rating_to_f64(Decimal::new(501, 2)), 5.0);
+     }
+
+     #[test]
+     fn averages_round_half_away_from_zero_like_postgres() {
+          assert_eq!(round_rating(Decimal::new(4125, 3)), Decimal::new(413, 2));
+          assert_eq!(round_rating(Decimal::new(4124, 3)), Decimal::new(412, 2));
+          assert_eq!(round_rating(Decimal::from(13) / Decimal::from(3)), Decimal::new(433, 2));
+     }
+
+     #[test]
+     fn review_histograms_sum_to_the_total_and_skip_impossible_ratings() {
+          let mut summary = ReviewSummary::default();
+          assert_eq!((summary.total(), summary.average()), (0, Decimal::ZERO));
+
+          for (rating, count) in [(5, 3), (4, 1), (1, 2), (0, 7), (6, 7), (-1, 7)] {
+               summary.add(rating, count);
+          }
+          assert_eq!(summary.star_counts, [2, 0, 0, 1, 3]);
+          assert_eq!(summary.total(), 6);
+          // (2 * 1 + 4 + 3 * 5) / 6 = 3.5
+          assert_eq!(summary.average(), Decimal::new(350, 2));
+     }
+
+     #[test]
+     fn statuses_round_trip_through_their_database_labels() {
+          let statuses = [
+               (DbGameStatus::Unspecified, "unspecified"),
+               (DbGameStatus::Draft, "draft"),
+               (DbGameStatus::UnderReview, "under_review"),
+               (DbGameStatus::Published, "published"),
+               (DbGameStatus::Suspended, "suspended"),
+          ];
+          for (status, label) in statuses {
+               assert_eq!(status.to_string(), label);
+               let parsed: DbGameStatus = label.parse().unwrap();
+               assert_eq!(format!("{:?}", parsed), format!("{:?}", status));
+          }
+
+          for unknown in ["", "released", "Published", "under-review", "unknown"] {
+               assert!(unknown.parse::<DbGameStatus>().is_err(), "{unknown:?}");
+          }
+     }
+
+     #[test]
+     fn categories_round_trip_through_their_database_labels() {
+          let categories = [
+               (DbGameCategory::Unspecified, "unspecified"),
+               (DbGameCategory::Action, "action"),
+               (DbGameCategory::Rpg, "rpg"),
+               (DbGameCategory::Strategy, "strategy"),
+               (DbGameCategory::Sports, "sports"),
+               (DbGameCategory::Racing, "racing"),
+               (DbGameCategory::Adventure, "adventure"),
+               (DbGameCategory::Simulation, "simulation"),
+               (DbGameCategory::Puzzle, "puzzle"),
+          ];
+          for (category, label) in categories {
+               assert_eq!(category.to_string(), label);
+               assert_eq!(label.parse::<DbGameCategory>(), Ok(category));
+          }
+
+          for unknown in ["", "shooter", "RPG", "unknown"] {
+               assert!(unknown.parse::<DbGameCategory>().is_err(), "{unknown:?}");
+          }
+     }
+}
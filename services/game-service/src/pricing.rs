@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use common::money::{Currency, Money};
+
+/// Upper bound of the `NUMERIC(10, 2)` price column's `price_max` check.
+const DB_MAX_PRICE_CENTS: i64 = 999_999;
+
+#[derive(Debug, Clone, Copy)]
+struct PriceBounds {
+    min_paid: Money,
+    max: Money,
+}
+
+impl PriceBounds {
+    fn defaults(currency: Currency) -> Self {
+        match currency {
+            Currency::Usd => Self {
+                min_paid: Money::from_minor_units(99),
+                max: Money::from_minor_units(99_999),
+            },
+        }
+    }
+}
+
+/// Storefront pricing rules: a game is either free or priced within
+/// `[min_paid, max]` for its currency. Bounds come from
+/// `PRICE_MIN_PAID_<CODE>` / `PRICE_MAX_<CODE>`, e.g. `PRICE_MIN_PAID_USD=0.99`.
+#[derive(Debug, Clone)]
+pub struct PricePolicy {
+    bounds: HashMap<Currency, PriceBounds>,
+}
+
+fn money_var(name: &str, default: Money) -> Result<Money, String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse::<Money>()
+            .map_err(|e| format!("{} has an invalid value: {}", name, e)),
+        Err(_) => Ok(default),
+    }
+}
+
+impl PricePolicy {
+    pub fn from_env() -> Result<Self, String> {
+        let mut bounds = HashMap::new();
+
+        for currency in Currency::ALL {
+            let defaults = PriceBounds::defaults(*currency);
+            let min_paid = money_var(&format!("PRICE_MIN_PAID_{}", currency.code()), defaults.min_paid)?;
+            let max = money_var(&format!("PRICE_MAX_{}", currency.code()), defaults.max)?;
+
+            if min_paid.minor_units() <= 0 || min_paid.minor_units() > max.minor_units() {
+                return Err(format!(
+                    "Minimum paid price for {} must be positive and at most the maximum",
+                    currency.code()
+                ));
+            }
+            if max.minor_units() > DB_MAX_PRICE_CENTS {
+                return Err(format!(
+                    "Maximum price for {} cannot exceed {}",
+                    currency.code(),
+                    Money::from_minor_units(DB_MAX_PRICE_CENTS)
+                ));
+            }
+
+            bounds.insert(*currency, PriceBounds { min_paid, max });
+        }
+
+        Ok(Self { bounds })
+    }
+
+    pub fn check(&self, price: Money) -> Result<(), String> {
+        let code = price.currency().code();
+        let bounds = self
+            .bounds
+            .get(&price.currency())
+            .ok_or_else(|| format!("Prices in {} are not supported", code))?;
+
+        if price.minor_units() < 0 {
+            return Err("Price must not be negative".to_string());
+        }
+        if price.minor_units() > 0 && price.minor_units() < bounds.min_paid.minor_units() {
+            return Err(format!(
+                "Paid games must cost at least {} {} (use 0 for a free game)",
+                bounds.min_paid, code
+            ));
+        }
+        if price.minor_units() > bounds.max.minor_units() {
+            return Err(format!("Price must be at most {} {}", bounds.max, code));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(min_paid_cents: i64, max_cents: i64) -> PricePolicy {
+        let bounds = PriceBounds {
+            min_paid: Money::from_minor_units(min_paid_cents),
+            max: Money::from_minor_units(max_cents),
+        };
+        PricePolicy {
+            bounds: HashMap::from([(Currency::Usd, bounds)]),
+        }
+    }
+
+    fn check(policy: &PricePolicy, cents: i64) -> Result<(), String> {
+        policy.check(Money::from_minor_units(cents))
+    }
+
+    #[test]
+    fn free_games_are_allowed() {
+        assert_eq!(check(&policy(99, 99_999), 0), Ok(()));
+    }
+
+    #[test]
+    fn the_minimum_paid_price_is_inclusive() {
+        let policy = policy(99, 99_999);
+
+        assert_eq!(check(&policy, 99), Ok(()));
+        assert!(check(&policy, 98).unwrap_err().contains("at least 0.99 USD"));
+        assert!(check(&policy, 1).is_err());
+    }
+
+    #[test]
+    fn the_maximum_price_is_inclusive() {
+        let policy = policy(99, 99_999);
+
+        assert_eq!(check(&policy, 99_999), Ok(()));
+        assert!(check(&policy, 100_000).unwrap_err().contains("at most 999.99 USD"));
+    }
+
+    #[test]
+    fn negative_prices_are_rejected() {
+        assert_eq!(check(&policy(99, 99_999), -1), Err("Price must not be negative".to_string()));
+    }
+
+    #[test]
+    fn the_bounds_follow_the_configuration() {
+        let policy = policy(499, 5_000);
+
+        assert!(check(&policy, 99).is_err());
+        assert_eq!(check(&policy, 499), Ok(()));
+        assert_eq!(check(&policy, 5_000), Ok(()));
+        assert!(check(&policy, 5_001).is_err());
+        assert_eq!(check(&policy, 0), Ok(()));
+    }
+}
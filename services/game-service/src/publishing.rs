@@ -0,0 +1,146 @@
+use common::error_details::{status_with_error_info, ErrorInfo};
+use tonic::Status;
+
+use crate::models::{DbGame, GameChanges};
+
+const ERROR_DOMAIN: &str = "game-service";
+
+/// What a game would still lack once `changes` are applied to it, in the
+/// order a storefront page shows them. Empty means it can go live.
+pub fn missing_for_publish(existing: &DbGame, changes: &GameChanges) -> Vec<&'static str> {
+    let mut missing = Vec::new();
+
+    let cover_image = changes.cover_image.as_deref().or(existing.cover_image.as_deref());
+    if cover_image.is_none_or(|c| c.trim().is_empty()) {
+        missing.push("cover_image");
+    }
+
+    let screenshots = changes.screenshots.as_ref().unwrap_or(&existing.screenshots);
+    if screenshots.iter().all(|s| s.trim().is_empty()) {
+        missing.push("screenshots");
+    }
+
+    let description = changes.description.as_deref().unwrap_or(&existing.description);
+    if description.trim().is_empty() {
+        missing.push("description");
+    }
+
+    if changes.release_date.is_none() && existing.release_date.is_none() {
+        missing.push("release_date");
+    }
+
+    missing
+}
+
+/// `failed_precondition` naming every missing asset, so the developer can fix
+/// them all in one go instead of one rejected publish at a time.
+#[allow(clippy::result_large_err)]
+pub fn check_ready(existing: &DbGame, changes: &GameChanges) -> Result<(), Status> {
+    let missing = missing_for_publish(existing, changes);
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let list = missing.join(", ");
+    Err(status_with_error_info(
+        tonic::Code::FailedPrecondition,
+        format!("Cannot publish until these are set: {}", list),
+        ErrorInfo::new("PUBLISH_NOT_READY", ERROR_DOMAIN).with_metadata("missing", missing.join(",")),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Utc};
+    use common::error_details::error_info;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::models::DbGameStatus;
+
+    /// A draft with nothing but a name and a price.
+    fn bare_draft() -> DbGame {
+        DbGame {
+            id: Uuid::new_v4(),
+            name: "Space Quest".to_string(),
+            description: String::new(),
+            developer_id: Uuid::new_v4(),
+            publisher_id: None,
+            cover_image: None,
+            trailer_url: None,
+            release_date: None,
+            price: Decimal::new(1999, 2),
+            status: DbGameStatus::Draft,
+            categories: vec![],
+            tags: vec![],
+            platforms: vec![],
+            screenshots: vec![],
+            rating_count: 0,
+            average_rating: Decimal::ZERO,
+            purchase_count: 0,
+            sale_price: None,
+            sale_starts_at: None,
+            sale_ends_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn complete_changes() -> GameChanges {
+        GameChanges {
+            cover_image: Some("https://example.com/cover.png".to_string()),
+            screenshots: Some(vec!["https://example.com/shot.png".to_string()]),
+            description: Some("Explore the stars".to_string()),
+            release_date: NaiveDate::from_ymd_opt(2025, 3, 1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn an_under_prepared_publish_lists_everything_missing() {
+        let err = check_ready(&bare_draft(), &GameChanges::default()).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(
+            err.message(),
+            "Cannot publish until these are set: cover_image, screenshots, description, release_date"
+        );
+
+        let info = error_info(&err).unwrap();
+        assert_eq!(info.reason, "PUBLISH_NOT_READY");
+        assert_eq!(info.metadata["missing"], "cover_image,screenshots,description,release_date");
+    }
+
+    #[test]
+    fn blank_values_count_as_missing() {
+        let blank = GameChanges {
+            cover_image: Some("  ".to_string()),
+            screenshots: Some(vec![" ".to_string()]),
+            description: Some("\n".to_string()),
+            ..complete_changes()
+        };
+        assert_eq!(missing_for_publish(&bare_draft(), &blank), ["cover_image", "screenshots", "description"]);
+    }
+
+    #[test]
+    fn a_complete_publish_is_accepted() {
+        assert!(check_ready(&bare_draft(), &complete_changes()).is_ok());
+    }
+
+    #[test]
+    fn what_the_game_already_has_counts() {
+        let changes = complete_changes();
+        let prepared = DbGame {
+            cover_image: changes.cover_image,
+            screenshots: changes.screenshots.unwrap(),
+            description: changes.description.unwrap(),
+            release_date: changes.release_date,
+            ..bare_draft()
+        };
+        assert!(check_ready(&prepared, &GameChanges::default()).is_ok());
+
+        let only_date = GameChanges { release_date: None, ..complete_changes() };
+        assert_eq!(missing_for_publish(&bare_draft(), &only_date), ["release_date"]);
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::repository::GameRepository;
+
+/// Hard-deletes games soft-deleted more than `PURGE_RETENTION_DAYS` ago
+/// (90 by default), `PURGE_BATCH_SIZE` rows (500) per transaction so no
+/// lock is held for long. Admins can run it at any time; it also runs every
+/// `PURGE_INTERVAL_SECS` when that is set.
+#[derive(Debug, Clone, Copy)]
+pub struct PurgeConfig {
+    pub retention_days: u32,
+    pub batch_size: i64,
+    pub every: Option<Duration>,
+}
+
+fn positive_from_env(name: &str) -> Result<Option<u64>, String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse::<u64>()
+            .ok()
+            .filter(|n| *n > 0)
+            .map(Some)
+            .ok_or_else(|| format!("{} has an invalid value: {}", name, value)),
+        Err(_) => Ok(None),
+    }
+}
+
+impl PurgeConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let retention_days = positive_from_env("PURGE_RETENTION_DAYS")?
+            .map_or(Ok(90), u32::try_from)
+            .map_err(|_| "PURGE_RETENTION_DAYS is too large".to_string())?;
+        let batch_size = positive_from_env("PURGE_BATCH_SIZE")?
+            .map_or(Ok(500), i64::try_from)
+            .map_err(|_| "PURGE_BATCH_SIZE is too large".to_string())?;
+        let every = positive_from_env("PURGE_INTERVAL_SECS")?.map(Duration::from_secs);
+
+        Ok(Self {
+            retention_days,
+            batch_size,
+            every,
+        })
+    }
+}
+
+/// Games deleted before this are purged.
+pub fn cutoff(retention_days: u32, now: DateTime<Utc>) -> DateTime<Utc> {
+    now - chrono::Duration::days(i64::from(retention_days))
+}
+
+/// Purges batch after batch until one comes back short, and returns the
+/// total. `actor_id` is the admin who asked, or `None` for the scheduled job.
+pub async fn purge_deleted(
+    repo: &dyn GameRepository,
+    cutoff: DateTime<Utc>,
+    batch_size: i64,
+    actor_id: Option<Uuid>,
+) -> Result<u64, sqlx::Error> {
+    let mut purged = 0;
+    loop {
+        let batch = repo.purge_deleted_games(cutoff, batch_size, actor_id).await?;
+        purged += batch;
+        if batch < batch_size as u64 {
+            return Ok(purged);
+        }
+    }
+}
+
+pub fn spawn_purge_job(repo: Arc<dyn GameRepository>, config: PurgeConfig, every: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            let cutoff = cutoff(config.retention_days, Utc::now());
+            match purge_deleted(repo.as_ref(), cutoff, config.batch_size, None).await {
+                Ok(0) => {}
+                Ok(purged) => println!("Purged {} deleted game(s)", purged),
+                Err(e) => eprintln!("Purging deleted games failed: {}", e),
+            }
+        }
+    });
+}
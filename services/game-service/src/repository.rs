@@ -0,0 +1,276 @@
+use std::pin::Pin;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use sqlx::types::Decimal;
+use tokio_stream::Stream;
+use uuid::Uuid;
+
+use common::db_timing::timed;
+
+use crate::db;
+use crate::models::{AuditFilter, CategoryPriceStats, DbAuditEntry, DbGame, DbGameCategory, DbGameReview, DbPriceHistory, GameChanges, GameFilter, GameSuggestion, NewGame, ReviewSummary, SaleSchedule, StaleDraft, StatusCounts};
+use crate::retry::RetryPolicy;
+
+pub type GameRowStream<'a> = Pin<Box<dyn Stream<Item = Result<DbGame, sqlx::Error>> + Send + 'a>>;
+
+/// Storage used by the gRPC handlers. `PgGameRepository` is the real one;
+/// the handler tests run against the in-process one in `memory_repository`.
+#[tonic::async_trait]
+pub trait GameRepository: Send + Sync {
+    async fn create_game(&self, game: NewGame) -> Result<DbGame, sqlx::Error>;
+
+    async fn get_game(&self, id: Uuid) -> Result<Option<DbGame>, sqlx::Error>;
+
+    async fn update_game(&self, id: Uuid, changes: GameChanges) -> Result<Option<DbGame>, sqlx::Error>;
+
+    /// Per-entry `(old price, updated game)`; see `db::bulk_set_prices`.
+    async fn bulk_set_prices(
+        &self,
+        updates: &[(Uuid, Decimal)],
+        rollback_on_missing: bool,
+    ) -> Result<Vec<Option<(Decimal, DbGame)>>, sqlx::Error>;
+
+    /// Schedules a sale on a live game, replacing any existing one, or
+    /// cancels it with `None`.
+    async fn set_sale(&self, id: Uuid, sale: Option<SaleSchedule>) -> Result<Option<DbGame>, sqlx::Error>;
+
+    /// Soft-deletes the game if it is live and owned by `developer_id`.
+    async fn delete_game(&self, id: Uuid, developer_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    async fn list_games(
+        &self,
+        filter: &GameFilter,
+        search_query: Option<&str>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<(Vec<DbGame>, i64), sqlx::Error>;
+
+    async fn fuzzy_search_games(
+        &self,
+        filter: &GameFilter,
+        search_query: &str,
+        similarity_threshold: f32,
+        limit: i32,
+        offset: i32,
+    ) -> Result<(Vec<DbGame>, i64), sqlx::Error>;
+
+    async fn list_price_history(&self, game_id: Uuid) -> Result<Vec<DbPriceHistory>, sqlx::Error>;
+
+    async fn list_top_reviews(&self, game_id: Uuid, limit: i32) -> Result<Vec<DbGameReview>, sqlx::Error>;
+
+    /// `None` if the game doesn't exist or is deleted.
+    async fn get_review_summary(&self, game_id: Uuid) -> Result<Option<ReviewSummary>, sqlx::Error>;
+
+    fn stream_developer_games(&self, developer_id: Uuid) -> GameRowStream<'_>;
+
+    async fn get_developer_status_counts(&self, developer_id: Uuid) -> Result<StatusCounts, sqlx::Error>;
+
+    /// A cheap, possibly stale, count of all game rows; `None` if unknown.
+    async fn estimate_game_rows(&self) -> Result<Option<i64>, sqlx::Error>;
+
+    async fn count_published_catalog(&self) -> Result<(i64, i64), sqlx::Error>;
+
+    async fn get_newest_release(&self) -> Result<Option<DbGame>, sqlx::Error>;
+
+    async fn get_popular_games(&self, limit: i32) -> Result<Vec<DbGame>, sqlx::Error>;
+
+    /// Published games released within the last `window_days`, newest first.
+    async fn list_new_releases(&self, window_days: i32, limit: i32) -> Result<Vec<DbGame>, sqlx::Error>;
+
+    /// Published games releasing within the next `window_days`, soonest first.
+    async fn list_upcoming_games(&self, window_days: i32, limit: i32) -> Result<Vec<DbGame>, sqlx::Error>;
+
+    async fn get_category_price_stats(&self, category: DbGameCategory) -> Result<CategoryPriceStats, sqlx::Error>;
+
+    /// Match counts per price bucket; see `db::get_price_facets`.
+    async fn get_price_facets(
+        &self,
+        filter: &GameFilter,
+        search_query: Option<&str>,
+        bounds: &[Decimal],
+    ) -> Result<Vec<i64>, sqlx::Error>;
+
+    async fn suggest_games(&self, prefix: &str, limit: i32) -> Result<Vec<GameSuggestion>, sqlx::Error>;
+
+    /// Returns how many games had drifted counters and were corrected.
+    async fn recompute_game_stats(&self, game_id: Option<Uuid>) -> Result<u64, sqlx::Error>;
+
+    /// Live drafts never published and not updated since `cutoff`.
+    async fn find_stale_drafts(&self, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error>;
+
+    /// Soft-deletes the drafts `find_stale_drafts` would return, auditing
+    /// each, and returns them.
+    async fn expire_stale_drafts(&self, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error>;
+
+    /// Hard-deletes up to `limit` games soft-deleted before `cutoff`,
+    /// auditing each under `actor_id`, and returns how many went.
+    async fn purge_deleted_games(
+        &self,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+        actor_id: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error>;
+
+    /// Newest first, only entries older than `before_id` when given.
+    async fn list_audit(
+        &self,
+        filter: &AuditFilter,
+        limit: i64,
+        before_id: Option<i64>,
+    ) -> Result<Vec<DbAuditEntry>, sqlx::Error>;
+}
+
+pub struct PgGameRepository {
+    pool: PgPool,
+    retry: RetryPolicy,
+}
+
+impl PgGameRepository {
+    pub fn new(pool: PgPool, retry: RetryPolicy) -> Self {
+        Self { pool, retry }
+    }
+}
+
+#[tonic::async_trait]
+impl GameRepository for PgGameRepository {
+    async fn create_game(&self, game: NewGame) -> Result<DbGame, sqlx::Error> {
+        timed("create_game", self.retry.write("create_game", || db::create_game(&self.pool, game.clone()))).await
+    }
+
+    async fn get_game(&self, id: Uuid) -> Result<Option<DbGame>, sqlx::Error> {
+        timed("get_game_by_id", self.retry.read("get_game_by_id", || db::get_game_by_id(&self.pool, id))).await
+    }
+
+    async fn update_game(&self, id: Uuid, changes: GameChanges) -> Result<Option<DbGame>, sqlx::Error> {
+        timed("update_game", self.retry.write("update_game", || db::update_game(&self.pool, id, changes.clone()))).await
+    }
+
+    async fn bulk_set_prices(
+        &self,
+        updates: &[(Uuid, Decimal)],
+        rollback_on_missing: bool,
+    ) -> Result<Vec<Option<(Decimal, DbGame)>>, sqlx::Error> {
+        timed("bulk_set_prices", self.retry.write("bulk_set_prices", || db::bulk_set_prices(&self.pool, updates, rollback_on_missing))).await
+    }
+
+    async fn set_sale(&self, id: Uuid, sale: Option<SaleSchedule>) -> Result<Option<DbGame>, sqlx::Error> {
+        timed("set_sale", self.retry.write("set_sale", || db::set_sale(&self.pool, id, sale))).await
+    }
+
+    async fn delete_game(&self, id: Uuid, developer_id: Uuid) -> Result<bool, sqlx::Error> {
+        timed("delete_game", self.retry.write("delete_game", || db::delete_game(&self.pool, id, developer_id))).await
+    }
+
+    async fn list_games(
+        &self,
+        filter: &GameFilter,
+        search_query: Option<&str>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<(Vec<DbGame>, i64), sqlx::Error> {
+        timed("list_games", self.retry.read("list_games", || db::list_games(&self.pool, filter, search_query, limit, offset))).await
+    }
+
+    async fn fuzzy_search_games(
+        &self,
+        filter: &GameFilter,
+        search_query: &str,
+        similarity_threshold: f32,
+        limit: i32,
+        offset: i32,
+    ) -> Result<(Vec<DbGame>, i64), sqlx::Error> {
+        timed("fuzzy_search_games", self.retry.read("fuzzy_search_games", || db::fuzzy_search_games(&self.pool, filter, search_query, similarity_threshold, limit, offset))).await
+    }
+
+    async fn list_price_history(&self, game_id: Uuid) -> Result<Vec<DbPriceHistory>, sqlx::Error> {
+        timed("list_price_history", self.retry.read("list_price_history", || db::list_price_history(&self.pool, game_id))).await
+    }
+
+    async fn list_top_reviews(&self, game_id: Uuid, limit: i32) -> Result<Vec<DbGameReview>, sqlx::Error> {
+        timed("list_top_reviews", self.retry.read("list_top_reviews", || db::list_top_reviews(&self.pool, game_id, limit))).await
+    }
+
+    async fn get_review_summary(&self, game_id: Uuid) -> Result<Option<ReviewSummary>, sqlx::Error> {
+        timed("get_review_summary", self.retry.read("get_review_summary", || db::get_review_summary(&self.pool, game_id))).await
+    }
+
+    fn stream_developer_games(&self, developer_id: Uuid) -> GameRowStream<'_> {
+        Box::pin(db::stream_developer_games(&self.pool, developer_id))
+    }
+
+    async fn get_developer_status_counts(&self, developer_id: Uuid) -> Result<StatusCounts, sqlx::Error> {
+        timed("get_developer_status_counts", self.retry.read("get_developer_status_counts", || db::get_developer_status_counts(&self.pool, developer_id))).await
+    }
+
+    async fn estimate_game_rows(&self) -> Result<Option<i64>, sqlx::Error> {
+        timed("estimate_game_rows", self.retry.read("estimate_game_rows", || db::estimate_game_rows(&self.pool))).await
+    }
+
+    async fn count_published_catalog(&self) -> Result<(i64, i64), sqlx::Error> {
+        timed("count_published_catalog", self.retry.read("count_published_catalog", || db::count_published_catalog(&self.pool))).await
+    }
+
+    async fn get_newest_release(&self) -> Result<Option<DbGame>, sqlx::Error> {
+        timed("get_newest_release", self.retry.read("get_newest_release", || db::get_newest_release(&self.pool))).await
+    }
+
+    async fn get_popular_games(&self, limit: i32) -> Result<Vec<DbGame>, sqlx::Error> {
+        timed("get_popular_games", self.retry.read("get_popular_games", || db::get_popular_games(&self.pool, limit))).await
+    }
+
+    async fn list_new_releases(&self, window_days: i32, limit: i32) -> Result<Vec<DbGame>, sqlx::Error> {
+        timed("list_new_releases", self.retry.read("list_new_releases", || db::list_new_releases(&self.pool, window_days, limit))).await
+    }
+
+    async fn list_upcoming_games(&self, window_days: i32, limit: i32) -> Result<Vec<DbGame>, sqlx::Error> {
+        timed("list_upcoming_games", self.retry.read("list_upcoming_games", || db::list_upcoming_games(&self.pool, window_days, limit))).await
+    }
+
+    async fn get_category_price_stats(&self, category: DbGameCategory) -> Result<CategoryPriceStats, sqlx::Error> {
+        timed("get_category_price_stats", self.retry.read("get_category_price_stats", || db::get_category_price_stats(&self.pool, category.clone()))).await
+    }
+
+    async fn get_price_facets(
+        &self,
+        filter: &GameFilter,
+        search_query: Option<&str>,
+        bounds: &[Decimal],
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        timed("get_price_facets", self.retry.read("get_price_facets", || db::get_price_facets(&self.pool, filter, search_query, bounds))).await
+    }
+
+    async fn suggest_games(&self, prefix: &str, limit: i32) -> Result<Vec<GameSuggestion>, sqlx::Error> {
+        timed("suggest_games", self.retry.read("suggest_games", || db::suggest_games(&self.pool, prefix, limit))).await
+    }
+
+    async fn recompute_game_stats(&self, game_id: Option<Uuid>) -> Result<u64, sqlx::Error> {
+        timed("recompute_game_stats", self.retry.write("recompute_game_stats", || db::recompute_game_stats(&self.pool, game_id))).await
+    }
+
+    async fn find_stale_drafts(&self, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error> {
+        timed("find_stale_drafts", self.retry.read("find_stale_drafts", || db::find_stale_drafts(&self.pool, cutoff))).await
+    }
+
+    async fn expire_stale_drafts(&self, cutoff: DateTime<Utc>) -> Result<Vec<StaleDraft>, sqlx::Error> {
+        timed("expire_stale_drafts", self.retry.write("expire_stale_drafts", || db::expire_stale_drafts(&self.pool, cutoff))).await
+    }
+
+    async fn purge_deleted_games(
+        &self,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+        actor_id: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error> {
+        timed("purge_deleted_games", self.retry.write("purge_deleted_games", || db::purge_deleted_games(&self.pool, cutoff, limit, actor_id))).await
+    }
+
+    async fn list_audit(
+        &self,
+        filter: &AuditFilter,
+        limit: i64,
+        before_id: Option<i64>,
+    ) -> Result<Vec<DbAuditEntry>, sqlx::Error> {
+        timed("list_audit", self.retry.read("list_audit", || db::list_audit(&self.pool, filter, limit, before_id))).await
+    }
+}
@@ -0,0 +1,220 @@
+use std::future::Future;
+use std::time::Duration;
+
+use common::config::env_or;
+
+/// How often and how patiently repository calls are retried after a
+/// transient Postgres error: up to `DB_RETRY_MAX` more attempts (2 by
+/// default, 0 turns retrying off), waiting `DB_RETRY_BACKOFF_MS` (50) before
+/// the first and doubling each time.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_env() -> Result<Self, String> {
+        let max_retries: u32 = env_or("DB_RETRY_MAX", 2)?;
+        if max_retries > 10 {
+            return Err("DB_RETRY_MAX must be at most 10".to_string());
+        }
+
+        Ok(Self {
+            max_retries,
+            backoff: Duration::from_millis(env_or("DB_RETRY_BACKOFF_MS", 50)?),
+        })
+    }
+
+    /// Runs a read, retrying on any transient error.
+    pub async fn read<T, F, Fut>(&self, query: &'static str, op: F) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        self.run(query, op, is_transient).await
+    }
+
+    /// Runs a write, retrying only when Postgres says it rolled the attempt
+    /// back. After a lost connection the write may or may not have
+    /// committed, so that error is returned rather than risking it twice.
+    pub async fn write<T, F, Fut>(&self, query: &'static str, op: F) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        self.run(query, op, is_rolled_back).await
+    }
+
+    async fn run<T, F, Fut>(
+        &self,
+        query: &'static str,
+        mut op: F,
+        retryable: fn(&sqlx::Error) -> bool,
+    ) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let mut retries = 0;
+        loop {
+            match op().await {
+                Err(e) if retries < self.max_retries && retryable(&e) => {
+                    let delay = self.backoff * 2u32.pow(retries);
+                    retries += 1;
+                    tracing::warn!(target: "db", query, retries, error = %e, "retrying after transient database error");
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+fn sqlstate(err: &sqlx::Error) -> Option<String> {
+    err.as_database_error()
+        .and_then(|e| e.code())
+        .map(|code| code.into_owned())
+}
+
+/// `serialization_failure` and `deadlock_detected`: the transaction was
+/// aborted as a whole, so running it again cannot apply anything twice.
+fn is_rolled_back(err: &sqlx::Error) -> bool {
+    sqlstate(err).is_some_and(|code| code == "40001" || code == "40P01")
+}
+
+/// Rolled back, or the connection went away: class 08, the server shutting
+/// down or starting up (57P01-57P03), or an I/O error on the socket.
+fn is_transient(err: &sqlx::Error) -> bool {
+    if matches!(err, sqlx::Error::Io(_)) || is_rolled_back(err) {
+        return true;
+    }
+    sqlstate(err).is_some_and(|code| code.starts_with("08") || matches!(code.as_str(), "57P01" | "57P02" | "57P03"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::error::Error as StdError;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use sqlx::error::{DatabaseError, ErrorKind};
+
+    use super::*;
+
+    /// A Postgres error carrying just a SQLSTATE.
+    #[derive(Debug)]
+    struct PgError(&'static str);
+
+    impl std::fmt::Display for PgError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "simulated error {}", self.0)
+        }
+    }
+
+    impl StdError for PgError {}
+
+    impl DatabaseError for PgError {
+        fn message(&self) -> &str {
+            "simulated"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(self.0))
+        }
+
+        fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        max_retries: 2,
+        backoff: Duration::from_millis(1),
+    };
+
+    fn pg_error(sqlstate: &'static str) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(PgError(sqlstate)))
+    }
+
+    fn connection_reset() -> sqlx::Error {
+        sqlx::Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset))
+    }
+
+    /// Runs an operation that fails with `error()` for its first `failures`
+    /// attempts, returning the outcome and how many attempts were made.
+    async fn run_flaky(
+        write: bool,
+        failures: u32,
+        error: fn() -> sqlx::Error,
+    ) -> (Result<u32, sqlx::Error>, u32) {
+        let attempts = AtomicU32::new(0);
+        let op = || async {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt <= failures { Err(error()) } else { Ok(attempt) }
+        };
+
+        let result = if write {
+            POLICY.write("test", op).await
+        } else {
+            POLICY.read("test", op).await
+        };
+        (result, attempts.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn read_succeeds_after_a_serialization_failure() {
+        let (result, attempts) = run_flaky(false, 1, || pg_error("40001")).await;
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn read_retries_a_reset_connection() {
+        let (result, attempts) = run_flaky(false, 2, connection_reset).await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_are_returned_immediately() {
+        let (result, attempts) = run_flaky(false, 1, || pg_error("23505")).await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let (result, attempts) = run_flaky(false, u32::MAX, || pg_error("40P01")).await;
+        assert!(result.is_err());
+        assert_eq!(attempts, POLICY.max_retries + 1);
+    }
+
+    #[tokio::test]
+    async fn write_retries_a_rolled_back_transaction() {
+        let (result, attempts) = run_flaky(true, 1, || pg_error("40P01")).await;
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn write_is_not_repeated_after_a_lost_connection() {
+        for error in [connection_reset as fn() -> sqlx::Error, || pg_error("08006")] {
+            let (result, attempts) = run_flaky(true, 1, error).await;
+            assert!(result.is_err());
+            assert_eq!(attempts, 1);
+        }
+    }
+}
@@ -2,14 +2,14 @@ use axum::{
     routing::post,
     Router,
 };
-use sqlx::PgPool;
 use tower_http::cors::CorsLayer;
 
+use crate::grpc_service::GameServiceImpl;
 use crate::handlers::create_game_http;
 
-pub fn create_routes(pool: PgPool) -> Router {
+pub fn create_routes(service: GameServiceImpl) -> Router {
     Router::new()
         .route("/api/games", post(create_game_http))
         .layer(CorsLayer::permissive())
-        .with_state(pool)
+        .with_state(service)
 }
\ No newline at end of file
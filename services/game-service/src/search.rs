@@ -0,0 +1,45 @@
+/// Controls the trigram fallback used when full-text search on game names
+/// finds too little, e.g. because the query has a typo.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchConfig {
+    /// Minimum `word_similarity` between the query and a game name, 0.0–1.0.
+    pub similarity_threshold: f32,
+    /// The fallback runs when full-text search matches fewer games than this.
+    pub fuzzy_min_results: i64,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: 0.3,
+            fuzzy_min_results: 1,
+        }
+    }
+}
+
+impl SearchConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let defaults = Self::default();
+
+        let similarity_threshold = match std::env::var("SEARCH_SIMILARITY_THRESHOLD") {
+            Ok(value) => value
+                .parse::<f32>()
+                .ok()
+                .filter(|t| (0.0..=1.0).contains(t))
+                .ok_or_else(|| "SEARCH_SIMILARITY_THRESHOLD must be between 0 and 1".to_string())?,
+            Err(_) => defaults.similarity_threshold,
+        };
+
+        let fuzzy_min_results = match std::env::var("SEARCH_FUZZY_MIN_RESULTS") {
+            Ok(value) => value
+                .parse::<i64>()
+                .map_err(|_| format!("SEARCH_FUZZY_MIN_RESULTS has an invalid value: {}", value))?,
+            Err(_) => defaults.fuzzy_min_results,
+        };
+
+        Ok(Self {
+            similarity_threshold,
+            fuzzy_min_results,
+        })
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+/// Coalesces concurrent calls for the same key: the first caller runs the
+/// work and everyone who arrives while it is in flight gets a clone of its
+/// result. Nothing is kept once the call finishes, so this is not a cache.
+pub struct SingleFlight<K, V> {
+    in_flight: Arc<Mutex<HashMap<K, Arc<OnceCell<V>>>>>,
+}
+
+impl<K, V> Clone for SingleFlight<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            in_flight: self.in_flight.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for SingleFlight<K, V> {
+    fn default() -> Self {
+        Self {
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> SingleFlight<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn run<F, Fut>(&self, key: K, work: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let cell = self
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .clone();
+
+        // If the caller running `work` is cancelled, OnceCell hands the work
+        // to one of the waiters instead of leaving them hanging.
+        let value = cell.get_or_init(work).await.clone();
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(&key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+            in_flight.remove(&key);
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Counts how often the "query" runs; each run takes long enough for
+    /// the other callers to pile up behind it.
+    async fn counted_read(calls: &AtomicUsize, value: u32) -> u32 {
+        calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        value
+    }
+
+    #[tokio::test]
+    async fn concurrent_calls_for_one_key_run_the_work_once() {
+        let flights = SingleFlight::<u32, u32>::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..32)
+            .map(|_| {
+                let flights = flights.clone();
+                let calls = calls.clone();
+                tokio::spawn(async move { flights.run(7, || counted_read(&calls, 42)).await })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), 42);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(flights.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn different_keys_do_not_share_a_call() {
+        let flights = SingleFlight::<u32, u32>::new();
+        let calls = AtomicUsize::new(0);
+
+        let (a, b) = tokio::join!(
+            flights.run(1, || counted_read(&calls, 1)),
+            flights.run(2, || counted_read(&calls, 2)),
+        );
+
+        assert_eq!((a, b), (1, 2));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn finished_calls_are_not_cached() {
+        let flights = SingleFlight::<u32, u32>::new();
+        let calls = AtomicUsize::new(0);
+
+        assert_eq!(flights.run(7, || counted_read(&calls, 1)).await, 1);
+        assert_eq!(flights.run(7, || counted_read(&calls, 2)).await, 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn waiters_take_over_when_the_running_caller_is_cancelled() {
+        let flights = SingleFlight::<u32, u32>::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let first = {
+            let flights = flights.clone();
+            let calls = calls.clone();
+            tokio::spawn(async move { flights.run(7, || counted_read(&calls, 1)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let second = {
+            let flights = flights.clone();
+            let calls = calls.clone();
+            tokio::spawn(async move { flights.run(7, || counted_read(&calls, 2)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        first.abort();
+
+        assert_eq!(second.await.unwrap(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
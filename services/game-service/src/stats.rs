@@ -0,0 +1,31 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::game;
+
+const CATALOG_STATS_TTL: Duration = Duration::from_secs(30);
+
+/// Holds the last computed catalog stats for a short while so homepage
+/// traffic doesn't run the aggregate queries on every request.
+#[derive(Clone, Default)]
+pub struct CatalogStatsCache {
+    entry: Arc<Mutex<Option<(Instant, game::CatalogStats)>>>,
+}
+
+impl CatalogStatsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> Option<game::CatalogStats> {
+        let entry = self.entry.lock().unwrap();
+        entry
+            .as_ref()
+            .filter(|(computed_at, _)| computed_at.elapsed() < CATALOG_STATS_TTL)
+            .map(|(_, stats)| stats.clone())
+    }
+
+    pub fn put(&self, stats: game::CatalogStats) {
+        *self.entry.lock().unwrap() = Some((Instant::now(), stats));
+    }
+}
@@ -1,3 +1,4 @@
+use common::money::Money;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -5,11 +6,12 @@ pub struct CreateGameRequest {
     pub name: String,
     pub description: String,
     pub developer_id: String,
-    pub release_date: String,
+    pub release_date: Option<String>,
     pub categories: Vec<i32>,
     pub tags: Vec<String>,
     pub platforms: Vec<String>,
-    pub price: f64,
+    /// `"19.99"` or a bare number, in dollars; parsed exactly, never via `f64`.
+    pub price: Money,
     pub cover_image: String,
     pub publisher_id: Option<String>,
     pub trailer_url: Option<String>,
@@ -19,16 +21,17 @@ pub struct CreateGameRequest {
 pub struct GameResponse {
     pub id: String,
     pub name: String,
-    pub description: Option<String>,
+    pub description: String,
     pub developer_id: String,
     pub publisher_id: Option<String>,
     pub cover_image: String,
     pub trailer_url: Option<String>,
-    pub release_date: String,
+    pub release_date: Option<String>,
     pub tags: Vec<String>,
     pub platforms: Vec<String>,
     pub screenshots: Vec<String>,
-    pub price: f64,
+    /// In dollars, serialized as a string like the request's `price`.
+    pub price: Money,
     pub status: String,
     pub categories: Vec<String>,
     pub rating_count: i32,
@@ -38,6 +41,7 @@ pub struct GameResponse {
     pub updated_at: String,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
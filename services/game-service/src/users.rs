@@ -0,0 +1,114 @@
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Request, Status};
+use uuid::Uuid;
+
+use common::auth::Caller;
+
+use crate::user::{GetTokenVersionRequest, GetUserRequest};
+use crate::user::user_service_client::UserServiceClient;
+
+const DEFAULT_USER_SERVICE_URL: &str = "http://[::1]:50051";
+
+/// What this service needs to know about users, which the user service owns.
+#[tonic::async_trait]
+pub trait UserDirectory: Send + Sync {
+    /// The user's current token version, or `None` when the user doesn't
+    /// exist (any more).
+    async fn token_version(&self, user_id: Uuid) -> Result<Option<i32>, Status>;
+
+    async fn user_exists(&self, user_id: Uuid) -> Result<bool, Status>;
+}
+
+/// Asks the user service at `USER_SERVICE_URL`. The channel connects lazily,
+/// so the game service starts even while the user service is down.
+pub struct UserServiceDirectory {
+    channel: Channel,
+}
+
+impl UserServiceDirectory {
+    pub fn from_env() -> Result<Self, String> {
+        let url = std::env::var("USER_SERVICE_URL").unwrap_or_else(|_| DEFAULT_USER_SERVICE_URL.to_string());
+        let endpoint = Endpoint::from_shared(url.clone())
+            .map_err(|e| format!("USER_SERVICE_URL is not a valid URL ({}): {}", url, e))?;
+
+        Ok(Self {
+            channel: endpoint.connect_lazy(),
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl UserDirectory for UserServiceDirectory {
+    async fn token_version(&self, user_id: Uuid) -> Result<Option<i32>, Status> {
+        let request = Request::new(GetTokenVersionRequest { user_id: user_id.to_string() });
+        match UserServiceClient::new(self.channel.clone()).get_token_version(request).await {
+            Ok(response) => Ok(Some(response.into_inner().token_version)),
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(None),
+            Err(status) => Err(unavailable(status)),
+        }
+    }
+
+    async fn user_exists(&self, user_id: Uuid) -> Result<bool, Status> {
+        let request = Request::new(GetUserRequest { id: user_id.to_string() });
+        match UserServiceClient::new(self.channel.clone()).get_user(request).await {
+            Ok(response) => Ok(response.into_inner().user.is_some()),
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(false),
+            Err(status) => Err(unavailable(status)),
+        }
+    }
+}
+
+fn unavailable(status: Status) -> Status {
+    Status::unavailable(format!("User service unavailable: {}", status.message()))
+}
+
+/// Rejects tokens issued before the caller's sessions were last revoked, and
+/// tokens of users that have since been deleted.
+pub async fn check_session(users: &dyn UserDirectory, caller: &Caller) -> Result<(), Status> {
+    match users.token_version(caller.user_id).await? {
+        Some(current) if caller.token_version >= current => Ok(()),
+        Some(_) => Err(Status::unauthenticated("Token has been revoked")),
+        None => Err(Status::unauthenticated("Invalid or expired token")),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Every user exists unless `deleted`; those missing from
+    /// `token_versions` are at version 0.
+    #[derive(Default)]
+    pub(crate) struct FakeUserDirectory {
+        pub(crate) token_versions: Mutex<HashMap<Uuid, i32>>,
+        pub(crate) deleted: Mutex<HashSet<Uuid>>,
+    }
+
+    impl FakeUserDirectory {
+        /// What the user service does when an admin revokes `user_id`'s sessions.
+        pub(crate) fn revoke_sessions(&self, user_id: Uuid) {
+            *self.token_versions.lock().unwrap().entry(user_id).or_insert(0) += 1;
+        }
+
+        pub(crate) fn delete(&self, user_id: Uuid) {
+            self.deleted.lock().unwrap().insert(user_id);
+        }
+    }
+
+    #[tonic::async_trait]
+    impl UserDirectory for FakeUserDirectory {
+        async fn token_version(&self, user_id: Uuid) -> Result<Option<i32>, Status> {
+            if !self.user_exists(user_id).await? {
+                return Ok(None);
+            }
+            Ok(Some(self.token_versions.lock().unwrap().get(&user_id).copied().unwrap_or(0)))
+        }
+
+        async fn user_exists(&self, user_id: Uuid) -> Result<bool, Status> {
+            Ok(!self.deleted.lock().unwrap().contains(&user_id))
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use chrono::NaiveDate;
+use common::money::Money;
+use uuid::Uuid;
+
+use crate::game::{CreateGameRequest, UpdateGameRequest};
+use crate::pricing::PricePolicy;
+
+const MAX_NAME_LENGTH: usize = 255;
+const MAX_DESCRIPTION_LENGTH: usize = 5000;
+const MAX_URL_LENGTH: usize = 500;
+/// Release dates outside this window are typos (a five-digit year, a
+/// missing century) rather than real games.
+const MIN_RELEASE_DATE: &str = "1950-01-01";
+const MAX_RELEASE_DATE: &str = "2100-12-31";
+
+/// Strips control characters and collapses all whitespace runs to a single
+/// space, so `"  Cool \t Game "` becomes `"Cool Game"`.
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Descriptions keep their line breaks but lose other control characters and
+/// surrounding whitespace.
+pub fn normalize_description(description: &str) -> String {
+    description
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!("Name must be at most {} characters", MAX_NAME_LENGTH));
+    }
+    Ok(())
+}
+
+pub fn validate_description(description: &str) -> Result<(), String> {
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(format!(
+            "Description must be at most {} characters",
+            MAX_DESCRIPTION_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+
+pub fn validate_url(field: &str, url: &str) -> Result<(), String> {
+    if url.len() > MAX_URL_LENGTH {
+        return Err(format!("{} must be at most {} characters", field, MAX_URL_LENGTH));
+    }
+    Ok(())
+}
+
+/// Parses a `YYYY-MM-DD` release date, telling a malformed string apart
+/// from a well-formed one that names no real day (e.g. `2024-02-30`).
+pub fn parse_release_date(release_date: &str) -> Result<NaiveDate, String> {
+    let value = release_date.trim();
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        let well_formed = value.len() == 10
+            && value
+                .bytes()
+                .enumerate()
+                .all(|(i, b)| if i == 4 || i == 7 { b == b'-' } else { b.is_ascii_digit() });
+        if well_formed {
+            format!("release_date '{}' is not a valid calendar date", value)
+        } else {
+            format!("release_date must be in YYYY-MM-DD format, got '{}'", value)
+        }
+    })?;
+
+    let min = NaiveDate::parse_from_str(MIN_RELEASE_DATE, "%Y-%m-%d").expect("valid constant");
+    let max = NaiveDate::parse_from_str(MAX_RELEASE_DATE, "%Y-%m-%d").expect("valid constant");
+    if date < min || date > max {
+        return Err(format!(
+            "release_date must be between {} and {}",
+            MIN_RELEASE_DATE, MAX_RELEASE_DATE
+        ));
+    }
+    Ok(date)
+}
+
+pub fn validate_create_game_request(
+    req: &CreateGameRequest,
+    prices: &PricePolicy,
+) -> Result<(), String> {
+    validate_name(&req.name)?;
+    validate_description(&req.description)?;
+
+    Uuid::parse_str(&req.developer_id).map_err(|_| "Invalid developer_id".to_string())?;
+
+    if let Some(publisher_id) = req.publisher_id.as_deref().filter(|s| !s.is_empty()) {
+        Uuid::parse_str(publisher_id).map_err(|_| "Invalid publisher_id".to_string())?;
+    }
+
+    prices.check(Money::from(req.price))?;
+    if let Some(release_date) = req.release_date.as_deref() {
+        parse_release_date(release_date)?;
+    }
+    validate_url("cover_image", &req.cover_image)?;
+
+    if let Some(trailer_url) = req.trailer_url.as_ref() {
+        validate_url("trailer_url", trailer_url)?;
+    }
+
+    Ok(())
+}
+
+pub fn validate_update_game_request(
+    req: &UpdateGameRequest,
+    prices: &PricePolicy,
+) -> Result<(), String> {
+    if let Some(name) = req.name.as_ref() {
+        validate_name(name)?;
+    }
+
+    if let Some(description) = req.description.as_ref() {
+        validate_description(description)?;
+    }
+
+    if let Some(price) = req.price {
+        prices.check(Money::from(price))?;
+    }
+
+    if let Some(cover_image) = req.cover_image.as_ref() {
+        validate_url("cover_image", cover_image)?;
+    }
+
+    if let Some(trailer_url) = req.trailer_url.as_ref() {
+        validate_url("trailer_url", trailer_url)?;
+    }
+
+    if let Some(release_date) = req.release_date.as_deref() {
+        parse_release_date(release_date)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_collapse_whitespace_and_drop_control_characters() {
+        assert_eq!(normalize_name("  Cool  Game  "), "Cool Game");
+        assert_eq!(normalize_name("Cool \t Game\n"), "Cool Game");
+        assert_eq!(normalize_name("Cool\u{0}Game"), "Cool Game");
+        assert_eq!(normalize_name("Café  Racer"), "Café Racer");
+        assert_eq!(normalize_name(" \t\n "), "");
+    }
+
+    #[test]
+    fn whitespace_only_names_are_rejected_after_normalizing() {
+        assert!(validate_name(&normalize_name("   ")).is_err());
+        assert_eq!(validate_name(&normalize_name("  Cool  Game  ")), Ok(()));
+    }
+
+    #[test]
+    fn descriptions_keep_line_breaks_but_lose_other_control_characters() {
+        assert_eq!(normalize_description("  Line one\nLine\ttwo\u{7}  "), "Line one\nLine\ttwo");
+    }
+
+    #[test]
+    fn release_dates_parse_or_say_what_is_wrong() {
+        assert_eq!(parse_release_date("2024-02-29"), Ok(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()));
+        assert_eq!(parse_release_date(" 2024-03-01 "), Ok(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()));
+
+        for malformed in ["", "01/03/2024", "2024-03-01T00:00:00", "next year"] {
+            assert_eq!(
+                parse_release_date(malformed),
+                Err(format!("release_date must be in YYYY-MM-DD format, got '{}'", malformed)),
+            );
+        }
+        for impossible in ["2023-02-29", "2024-02-30", "2024-13-01", "2024-00-10"] {
+            assert_eq!(
+                parse_release_date(impossible),
+                Err(format!("release_date '{}' is not a valid calendar date", impossible)),
+            );
+        }
+    }
+
+    #[test]
+    fn release_dates_must_fall_in_the_plausible_window() {
+        assert!(parse_release_date("1950-01-01").is_ok());
+        assert!(parse_release_date("2100-12-31").is_ok());
+        for out_of_range in ["1949-12-31", "2101-01-01"] {
+            assert_eq!(
+                parse_release_date(out_of_range),
+                Err("release_date must be between 1950-01-01 and 2100-12-31".to_string()),
+            );
+        }
+    }
+}
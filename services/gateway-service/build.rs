@@ -1,5 +1,7 @@
 fn main() {
     tonic_build::configure()
+        // Lets test doubles implement only the RPCs they need
+        .generate_default_stubs(true)
         .compile_protos(
             &["../../proto/user.proto", "../../proto/game.proto"], 
             &["../../proto"]
@@ -0,0 +1,234 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::{self, Principal};
+use crate::role::Role;
+use crate::{AppState, epoch_seconds, errors, request_id, user};
+
+#[derive(Serialize, ToSchema)]
+struct ApiKeyDto {
+    id: String,
+    name: String,
+    prefix: String,
+    scopes: Vec<String>,
+    created_at: Option<String>,
+    revoked: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+struct CreatedApiKeyResponse {
+    api_key: ApiKeyDto,
+    key: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct ApiKeyListResponse {
+    api_keys: Vec<ApiKeyDto>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateApiKeyDto {
+    name: String,
+    scopes: Vec<String>,
+}
+
+fn api_key_to_dto(key: user::ApiKey) -> ApiKeyDto {
+    ApiKeyDto {
+        id: key.id,
+        name: key.name,
+        prefix: key.prefix,
+        scopes: key.scopes,
+        created_at: epoch_seconds(key.created_at),
+        revoked: key.revoked,
+    }
+}
+
+/// Keys are managed with a logged-in developer's JWT; API keys themselves
+/// never reach these routes (see `auth::auth_middleware`). A key outlives
+/// the session that created it, so like a password change this is off
+/// limits while impersonating.
+#[allow(clippy::result_large_err)]
+fn key_owner(req: &HttpRequest) -> Result<String, HttpResponse> {
+    match auth::principal(req) {
+        Some(Principal::User(claims)) if claims.act_as.is_some() => {
+            Err(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "API keys cannot be managed while impersonating"
+            })))
+        }
+        Some(Principal::User(claims)) if claims.has_role(Role::Developer) || claims.has_role(Role::Admin) => {
+            Ok(claims.sub)
+        }
+        Some(_) => Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Only developers can manage API keys"
+        }))),
+        None => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Authentication required"
+        }))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/keys",
+    tag = "api keys",
+    request_body = CreateApiKeyDto,
+    responses(
+        (status = 201, description = "The new key; `key` is only ever shown here", body = CreatedApiKeyResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn create_api_key(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    json: web::Json<CreateApiKeyDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let developer_id = match key_owner(&req) {
+        Ok(id) => id,
+        Err(response) => return Ok(response),
+    };
+
+    let request = request_id::grpc_request(&req, user::CreateApiKeyRequest {
+        developer_id,
+        name: json.name.clone(),
+        scopes: json.scopes.clone(),
+    });
+
+    let mut client = data.user_client();
+    match client.create_api_key(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            match resp.api_key {
+                Some(api_key) => Ok(HttpResponse::Created().json(CreatedApiKeyResponse {
+                    api_key: api_key_to_dto(api_key),
+                    key: resp.key,
+                })),
+                None => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Server returned empty response"
+                }))),
+            }
+        }
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "User not found")])),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/keys",
+    tag = "api keys",
+    responses(
+        (status = 200, description = "The caller's keys", body = ApiKeyListResponse),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn list_api_keys(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let developer_id = match key_owner(&req) {
+        Ok(id) => id,
+        Err(response) => return Ok(response),
+    };
+
+    let request = request_id::grpc_request(&req, user::ListApiKeysRequest { developer_id });
+
+    let mut client = data.user_client();
+    match client.list_api_keys(request).await {
+        Ok(response) => {
+            let keys: Vec<ApiKeyDto> = response
+                .into_inner()
+                .api_keys
+                .into_iter()
+                .map(api_key_to_dto)
+                .collect();
+
+            Ok(HttpResponse::Ok().json(ApiKeyListResponse { api_keys: keys }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/keys/{id}",
+    tag = "api keys",
+    params(("id" = String, Path, description = "API key ID")),
+    responses(
+        (status = 204, description = "Revoked"),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn revoke_api_key(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let developer_id = match key_owner(&req) {
+        Ok(id) => id,
+        Err(response) => return Ok(response),
+    };
+
+    let request = request_id::grpc_request(&req, user::RevokeApiKeyRequest {
+        id: path.into_inner(),
+        developer_id,
+    });
+
+    let mut client = data.user_client();
+    match client.revoke_api_key(request).await {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "API key not found")])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::HttpMessage;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    use super::*;
+    use crate::auth::Claims;
+
+    fn as_user(role: &str, act_as: Option<&str>) -> HttpRequest {
+        let req = TestRequest::default().to_http_request();
+        req.extensions_mut().insert(Principal::User(Claims {
+            sub: "dev-1".to_string(),
+            role: role.to_string(),
+            exp: 0,
+            nbf: None,
+            iat: None,
+            act_as: act_as.map(String::from),
+            token_version: 0,
+        }));
+        req
+    }
+
+    #[test]
+    fn developers_own_their_keys() {
+        assert_eq!(key_owner(&as_user("developer", None)).unwrap(), "dev-1");
+    }
+
+    #[test]
+    fn keys_cannot_be_managed_while_impersonating() {
+        let response = key_owner(&as_user("developer", Some("admin-1"))).unwrap_err();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn players_and_anonymous_callers_have_no_keys() {
+        assert_eq!(key_owner(&as_user("player", None)).unwrap_err().status(), StatusCode::FORBIDDEN);
+
+        let anonymous = TestRequest::default().to_http_request();
+        assert_eq!(key_owner(&anonymous).unwrap_err().status(), StatusCode::UNAUTHORIZED);
+    }
+}
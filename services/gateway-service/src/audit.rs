@@ -0,0 +1,240 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use common::pagination::PageSizeConfig;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{AppState, auth, epoch_seconds, errors, game};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    /// Who made the change.
+    actor_id: Option<String>,
+    /// The game the entry is about.
+    game_id: Option<String>,
+    /// e.g. `purged` or `draft_expired`.
+    action: Option<String>,
+    /// RFC 3339; entries at or after this time.
+    since: Option<String>,
+    /// RFC 3339; entries before this time.
+    until: Option<String>,
+    limit: Option<i32>,
+    /// `next_cursor` from the previous page.
+    cursor: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct AuditEntryDto {
+    id: i64,
+    game_id: String,
+    /// Missing for changes made by a background job.
+    actor_id: Option<String>,
+    action: String,
+    detail: String,
+    #[schema(value_type = Option<Object>)]
+    before: Option<serde_json::Value>,
+    #[schema(value_type = Option<Object>)]
+    after: Option<serde_json::Value>,
+    created_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct AuditListResponse {
+    entries: Vec<AuditEntryDto>,
+    /// Absent on the last page.
+    next_cursor: Option<String>,
+}
+
+/// The stored payloads are JSON already; anything that somehow isn't is
+/// passed through as a string rather than dropped.
+fn payload(raw: Option<String>) -> Option<serde_json::Value> {
+    raw.map(|text| serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text)))
+}
+
+#[allow(clippy::result_large_err)]
+fn optional_time(field: &str, value: Option<&str>) -> Result<Option<prost_types::Timestamp>, HttpResponse> {
+    value.map(|value| crate::parse_rfc3339(field, value)).transpose()
+}
+
+fn entry_to_dto(entry: game::AuditEntry) -> AuditEntryDto {
+    AuditEntryDto {
+        id: entry.id,
+        game_id: entry.game_id,
+        actor_id: entry.actor_id,
+        action: entry.action,
+        detail: entry.detail,
+        before: payload(entry.before),
+        after: payload(entry.after),
+        created_at: epoch_seconds(entry.created_at),
+    }
+}
+
+/// `GET /api/audit` - admin-only search of the game audit log, newest first.
+/// Pages are keyed on the entry id, so new entries don't shift later pages.
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "admin",
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "One page of audit entries", body = AuditListResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn list_audit(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
+    query: web::Query<AuditQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let query = query.into_inner();
+    let limit = match page_size.resolve(query.limit) {
+        Ok(limit) => limit,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
+    let since = match optional_time("since", query.since.as_deref()) {
+        Ok(since) => since,
+        Err(response) => return Ok(response),
+    };
+    let until = match optional_time("until", query.until.as_deref()) {
+        Ok(until) => until,
+        Err(response) => return Ok(response),
+    };
+
+    let request = auth::grpc_request(&req, game::ListAuditLogRequest {
+        actor_id: query.actor_id,
+        game_id: query.game_id,
+        action: query.action,
+        since,
+        until,
+        page_size: limit,
+        page_token: query.cursor.unwrap_or_default(),
+    });
+
+    let mut client = data.game_client();
+    match client.list_audit_log(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            Ok(HttpResponse::Ok().json(AuditListResponse {
+                entries: resp.entries.into_iter().map(entry_to_dto).collect(),
+                next_cursor: Some(resp.next_page_token).filter(|token| !token.is_empty()),
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing::{FakeGameService, UNREACHABLE_URL, app_state_with, signed_in};
+
+    fn entry(id: i64, game_id: &str, actor_id: Option<&str>, action: &str) -> game::AuditEntry {
+        game::AuditEntry {
+            id,
+            game_id: game_id.to_string(),
+            actor_id: actor_id.map(str::to_string),
+            action: action.to_string(),
+            detail: format!("entry {id}"),
+            before: Some(format!(r#"{{"id":"{game_id}","status":"draft"}}"#)),
+            after: None,
+            created_at: Some(prost_types::Timestamp { seconds: 1_700_000_000 + id, nanos: 0 }),
+        }
+    }
+
+    fn audit_log() -> Arc<FakeGameService> {
+        Arc::new(
+            FakeGameService::default()
+                .with_audit_entry(entry(1, "g1", Some("alice"), "purged"))
+                .with_audit_entry(entry(2, "g2", Some("bob"), "purged"))
+                .with_audit_entry(entry(3, "g1", None, "draft_expired"))
+                .with_audit_entry(entry(4, "g2", Some("alice"), "purged")),
+        )
+    }
+
+    async fn list(games: &Arc<FakeGameService>, query: &str) -> (StatusCode, Value) {
+        let state = app_state_with(UNREACHABLE_URL.to_string(), games.clone().serve().await);
+        let response = list_audit(
+            signed_in("admin-1", "admin"),
+            web::Data::new(state),
+            web::Data::new(PageSizeConfig::default()),
+            web::Query::<AuditQuery>::from_query(query).unwrap(),
+        )
+        .await
+        .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn ids(body: &Value) -> Vec<i64> {
+        body["entries"].as_array().unwrap().iter().map(|e| e["id"].as_i64().unwrap()).collect()
+    }
+
+    #[actix_web::test]
+    async fn entries_filter_by_actor_game_and_action_newest_first() {
+        let games = audit_log();
+
+        let (status, body) = list(&games, "actor_id=alice").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ids(&body), [4, 1]);
+        let (_, body) = list(&games, "game_id=g1").await;
+        assert_eq!(ids(&body), [3, 1]);
+        let (_, body) = list(&games, "action=purged&game_id=g2").await;
+        assert_eq!(ids(&body), [4, 2]);
+
+        let sent = games.audit_requests.lock().unwrap();
+        assert_eq!(sent[0].actor_id.as_deref(), Some("alice"));
+        assert_eq!(sent[2].action.as_deref(), Some("purged"));
+        assert_eq!(sent[2].game_id.as_deref(), Some("g2"));
+    }
+
+    #[actix_web::test]
+    async fn pages_follow_the_cursor_until_it_runs_out() {
+        let games = audit_log();
+
+        let (_, first) = list(&games, "limit=2").await;
+        assert_eq!(ids(&first), [4, 3]);
+        assert_eq!(first["next_cursor"], "3");
+
+        let (_, second) = list(&games, "limit=2&cursor=3").await;
+        assert_eq!(ids(&second), [2, 1]);
+
+        let (_, last) = list(&games, "limit=2&cursor=1").await;
+        assert_eq!(ids(&last), Vec::<i64>::new());
+        assert!(last["next_cursor"].is_null());
+
+        let (_, filtered) = list(&games, "actor_id=alice&limit=1&cursor=4").await;
+        assert_eq!(ids(&filtered), [1]);
+    }
+
+    #[actix_web::test]
+    async fn entries_carry_their_payloads_as_json() {
+        let (_, body) = list(&audit_log(), "game_id=g1&limit=1").await;
+        let entry = &body["entries"][0];
+        assert!(entry["actor_id"].is_null());
+        assert_eq!(entry["before"], json!({ "id": "g1", "status": "draft" }));
+        assert!(entry["after"].is_null());
+        assert_eq!(entry["created_at"], "1700000003");
+
+        assert_eq!(payload(Some("not json".to_string())), Some(Value::String("not json".to_string())));
+        assert_eq!(payload(None), None);
+    }
+
+    #[actix_web::test]
+    async fn bad_times_are_rejected_before_asking_the_game_service() {
+        let games = audit_log();
+        let (status, _) = list(&games, "since=yesterday").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(games.audit_requests.lock().unwrap().is_empty());
+    }
+}
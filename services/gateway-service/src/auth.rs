@@ -0,0 +1,509 @@
+use actix_web::{
+    Error, HttpMessage, HttpRequest, HttpResponse,
+    dev::{ServiceRequest, ServiceResponse},
+    http::Method,
+    middleware::Next,
+    web,
+};
+use common::config::env_or;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+
+use crate::role::Role;
+use crate::{AppState, errors, request_id, user};
+
+const API_KEY_HEADER: &str = "x-api-key";
+const INTERNAL_TOKEN_TTL_SECS: usize = 60;
+/// How far the issuer's clock may drift from ours before `exp`, `nbf` and
+/// `iat` checks fail. Override with `JWT_LEEWAY_SECS`.
+const DEFAULT_JWT_LEEWAY_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub role: String,
+    pub exp: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<usize>,
+    /// Set only on impersonation tokens: the admin acting as `sub`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act_as: Option<String>,
+    /// Must match the user's current version; tokens from before the user's
+    /// sessions were revoked carry an older one. Missing means 0.
+    #[serde(default)]
+    pub token_version: i32,
+}
+
+impl Claims {
+    /// Unknown role strings match no role at all.
+    pub fn has_role(&self, role: Role) -> bool {
+        self.role.parse::<Role>().is_ok_and(|r| r == role)
+    }
+}
+
+/// Verifies user JWTs and signs the short-lived internal tokens forwarded to
+/// backend services on behalf of API-key callers.
+pub struct JwtVerifier {
+    decoding_key: DecodingKey,
+    encoding_key: EncodingKey,
+    validation: Validation,
+}
+
+impl JwtVerifier {
+    pub fn new(secret: &str, leeway_secs: u64) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = leeway_secs;
+        validation.validate_nbf = true;
+
+        Self {
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            validation,
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        let secret = std::env::var("JWT_SECRET")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| "JWT_SECRET must be set".to_string())?;
+        let leeway_secs = env_or("JWT_LEEWAY_SECS", DEFAULT_JWT_LEEWAY_SECS)?;
+        Ok(Self::new(&secret, leeway_secs))
+    }
+
+    /// Checks the signature and the `exp`/`nbf`/`iat` window, each widened by
+    /// the configured leeway. A token issued in the future is as suspect as
+    /// one that is not yet valid.
+    pub fn verify(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+        let claims = decode::<Claims>(token, &self.decoding_key, &self.validation)?.claims;
+
+        let now = jsonwebtoken::get_current_timestamp();
+        if claims.iat.is_some_and(|iat| iat as u64 > now + self.validation.leeway) {
+            return Err(ErrorKind::ImmatureSignature.into());
+        }
+
+        Ok(claims)
+    }
+
+    /// `token_version` should be `sub`'s current one, or backends that check
+    /// it reject the token as revoked.
+    pub fn issue(&self, sub: &str, role: Role, token_version: i32) -> Result<String, jsonwebtoken::errors::Error> {
+        let claims = Claims {
+            sub: sub.to_string(),
+            role: role.to_string(),
+            exp: jsonwebtoken::get_current_timestamp() as usize + INTERNAL_TOKEN_TTL_SECS,
+            nbf: None,
+            iat: None,
+            act_as: None,
+            token_version,
+        };
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+    }
+}
+
+/// Extracts the token from an `Authorization: Bearer <token>` header.
+pub fn bearer_token(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(actix_web::http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+/// Who a request is acting as. Stored in the request extensions by
+/// `auth_middleware`; absent for anonymous requests.
+#[derive(Debug, Clone)]
+pub enum Principal {
+    User(Claims),
+    ApiKey { developer_id: String, token_version: i32 },
+}
+
+pub fn principal(req: &HttpRequest) -> Option<Principal> {
+    req.extensions().get::<Principal>().cloned()
+}
+
+/// The developer an API key acts for, or `None` for JWT and anonymous
+/// requests.
+pub fn api_key_owner(req: &HttpRequest) -> Option<String> {
+    match principal(req)? {
+        Principal::ApiKey { developer_id, .. } => Some(developer_id),
+        Principal::User(_) => None,
+    }
+}
+
+/// Whether the caller may see `user_id`'s private fields: only the user
+/// themselves and admins can.
+pub fn can_view_private(req: &HttpRequest, user_id: &str) -> bool {
+    match principal(req) {
+        Some(Principal::User(claims)) => claims.sub == user_id || claims.has_role(Role::Admin),
+        _ => false,
+    }
+}
+
+/// The admin behind an impersonation token, or `None` for every other
+/// request.
+pub fn impersonator(req: &HttpRequest) -> Option<String> {
+    match principal(req)? {
+        Principal::User(claims) => claims.act_as,
+        Principal::ApiKey { .. } => None,
+    }
+}
+
+/// Builds a backend gRPC request carrying the caller's identity in the
+/// `authorization` metadata: the user's own JWT, or an internal token minted
+/// for the developer behind an API key.
+pub fn grpc_request<T>(req: &HttpRequest, message: T) -> tonic::Request<T> {
+    let mut request = request_id::grpc_request(req, message);
+
+    let token = match principal(req) {
+        Some(Principal::User(_)) => bearer_token(req).map(String::from),
+        Some(Principal::ApiKey { developer_id, token_version }) => req
+            .app_data::<web::Data<JwtVerifier>>()
+            .and_then(|jwt| jwt.issue(&developer_id, Role::Developer, token_version).ok()),
+        None => None,
+    };
+
+    if let Some(value) = token.and_then(|t| format!("Bearer {}", t).parse().ok()) {
+        request.metadata_mut().insert("authorization", value);
+    }
+
+    request
+}
+
+/// API keys only reach the game endpoints; everything else (users, keys,
+/// notifications) needs an interactive login.
+fn required_scope(method: &Method, path: &str) -> Option<&'static str> {
+    if !path.starts_with("/api/games") {
+        return None;
+    }
+
+    if matches!(*method, Method::GET | Method::HEAD) {
+        Some("games:read")
+    } else {
+        Some("games:write")
+    }
+}
+
+/// Rejects tokens issued before the user's sessions were last revoked, and
+/// tokens of users that have since been deleted, with the response to send
+/// instead. Asks the user service, so only call it for verified claims.
+#[allow(clippy::result_large_err)]
+pub async fn check_session(req: &HttpRequest, claims: &Claims) -> Result<(), HttpResponse> {
+    let data = req.app_data::<web::Data<AppState>>().unwrap();
+    let mut client = data.user_client();
+
+    let current = match client
+        .get_token_version(request_id::grpc_request(
+            req,
+            user::GetTokenVersionRequest { user_id: claims.sub.clone() },
+        ))
+        .await
+    {
+        Ok(response) => response.into_inner().token_version,
+        // A deleted user's tokens die with the account
+        Err(status) if matches!(status.code(), tonic::Code::NotFound | tonic::Code::InvalidArgument) => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({ "error": "Invalid or expired token" })));
+        }
+        Err(status) => {
+            return Err(HttpResponse::InternalServerError().json(errors::error_envelope(&status, status.message())));
+        }
+    };
+    if claims.token_version < current {
+        return Err(HttpResponse::Unauthorized().json(serde_json::json!({ "error": "Token has been revoked" })));
+    }
+    Ok(())
+}
+
+fn unauthorized(req: ServiceRequest, message: &str) -> ServiceResponse<actix_web::body::BoxBody> {
+    req.into_response(
+        HttpResponse::Unauthorized()
+            .json(serde_json::json!({ "error": message }))
+            .map_into_boxed_body(),
+    )
+}
+
+fn forbidden(req: ServiceRequest, message: String) -> ServiceResponse<actix_web::body::BoxBody> {
+    req.into_response(
+        HttpResponse::Forbidden()
+            .json(serde_json::json!({ "error": message }))
+            .map_into_boxed_body(),
+    )
+}
+
+/// Resolves `X-API-Key` or a Bearer JWT into a `Principal`. Credentials that
+/// are present but invalid are rejected; requests without any are passed
+/// through anonymously.
+pub async fn auth_middleware(
+    req: ServiceRequest,
+    next: Next<impl actix_web::body::MessageBody + 'static>,
+) -> Result<ServiceResponse<actix_web::body::BoxBody>, Error> {
+    let api_key = req
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+
+    let principal = if let Some(key) = api_key {
+        let Some(scope) = required_scope(req.method(), req.path()) else {
+            return Ok(forbidden(
+                req,
+                "API keys cannot be used for this endpoint".to_string(),
+            ));
+        };
+
+        let data = req.app_data::<web::Data<AppState>>().unwrap();
+        let mut client = data.user_client();
+
+        let verified = match client
+            .verify_api_key(tonic::Request::new(user::VerifyApiKeyRequest { key }))
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) if status.code() == tonic::Code::Unauthenticated => {
+                return Ok(unauthorized(req, "Invalid or revoked API key"));
+            }
+            Err(status) => {
+                return Ok(req.into_response(
+                    HttpResponse::InternalServerError()
+                        .json(errors::error_envelope(&status, status.message()))
+                        .map_into_boxed_body(),
+                ));
+            }
+        };
+
+        if !verified.scopes.iter().any(|s| s == scope) {
+            return Ok(forbidden(
+                req,
+                format!("API key is missing the '{}' scope", scope),
+            ));
+        }
+
+        Some(Principal::ApiKey {
+            developer_id: verified.developer_id,
+            token_version: verified.token_version,
+        })
+    } else if let Some(token) = bearer_token(req.request()) {
+        let jwt = req.app_data::<web::Data<JwtVerifier>>().unwrap();
+        match jwt.verify(token) {
+            Ok(claims) => {
+                if let Err(response) = check_session(req.request(), &claims).await {
+                    return Ok(req.into_response(response));
+                }
+
+                if let Some(admin_id) = &claims.act_as {
+                    tracing::warn!(
+                        target: "audit",
+                        %admin_id,
+                        user_id = %claims.sub,
+                        method = %req.method(),
+                        path = %req.path(),
+                        "request made while impersonating"
+                    );
+                }
+                Some(Principal::User(claims))
+            }
+            Err(_) => return Ok(unauthorized(req, "Invalid or expired token")),
+        }
+    } else {
+        None
+    };
+
+    if let Some(principal) = principal {
+        req.extensions_mut().insert(principal);
+    }
+
+    let res = next.call(req).await?;
+    Ok(res.map_into_boxed_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::test::{self as actix_test, TestRequest};
+    use actix_web::{App, HttpResponse};
+    use jsonwebtoken::get_current_timestamp;
+
+    use super::*;
+    use crate::testing::{FakeUserService, app_state, signed_in};
+
+    const SECRET: &str = "test-secret";
+
+    fn token(exp: u64, nbf: Option<u64>, iat: Option<u64>) -> String {
+        let claims = Claims {
+            sub: "alice".to_string(),
+            role: "player".to_string(),
+            exp: exp as usize,
+            nbf: nbf.map(|t| t as usize),
+            iat: iat.map(|t| t as usize),
+            act_as: None,
+            token_version: 0,
+        };
+        encode(&Header::new(Algorithm::HS256), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap()
+    }
+
+    fn kind(result: Result<Claims, jsonwebtoken::errors::Error>) -> Option<ErrorKind> {
+        result.err().map(|e| e.into_kind())
+    }
+
+    #[test]
+    fn skew_within_the_leeway_is_tolerated() {
+        let jwt = JwtVerifier::new(SECRET, 60);
+        let now = get_current_timestamp();
+
+        assert!(jwt.verify(&token(now - 30, None, None)).is_ok());
+        assert!(jwt.verify(&token(now + 300, Some(now + 30), None)).is_ok());
+        assert!(jwt.verify(&token(now + 300, None, Some(now + 30))).is_ok());
+    }
+
+    #[test]
+    fn skew_beyond_the_leeway_is_rejected() {
+        let jwt = JwtVerifier::new(SECRET, 60);
+        let now = get_current_timestamp();
+
+        assert_eq!(kind(jwt.verify(&token(now - 600, None, None))), Some(ErrorKind::ExpiredSignature));
+        assert_eq!(
+            kind(jwt.verify(&token(now + 900, Some(now + 600), None))),
+            Some(ErrorKind::ImmatureSignature)
+        );
+        assert_eq!(
+            kind(jwt.verify(&token(now + 900, None, Some(now + 600)))),
+            Some(ErrorKind::ImmatureSignature)
+        );
+    }
+
+    #[test]
+    fn zero_leeway_rejects_any_expired_token() {
+        let jwt = JwtVerifier::new(SECRET, 0);
+        let now = get_current_timestamp();
+
+        assert_eq!(kind(jwt.verify(&token(now - 30, None, None))), Some(ErrorKind::ExpiredSignature));
+    }
+
+    async fn whoami(req: HttpRequest) -> HttpResponse {
+        match principal(&req) {
+            Some(Principal::ApiKey { developer_id, token_version }) => {
+                HttpResponse::Ok().body(format!("key:{}@{}", developer_id, token_version))
+            }
+            Some(Principal::User(claims)) => HttpResponse::Ok().body(format!("user:{}", claims.sub)),
+            None => HttpResponse::Ok().body("anonymous"),
+        }
+    }
+
+    /// Status and body of `request` through `auth_middleware`, backed by
+    /// `users`.
+    async fn call(users: FakeUserService, request: TestRequest) -> (u16, String) {
+        let url = Arc::new(users).serve().await;
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_state(url)))
+                .app_data(web::Data::new(JwtVerifier::new(SECRET, 0)))
+                .wrap(actix_web::middleware::from_fn(auth_middleware))
+                .route("/api/games", web::get().to(whoami))
+                .route("/api/games", web::post().to(whoami))
+                .route("/api/users/me", web::get().to(whoami)),
+        )
+        .await;
+
+        let response = actix_test::call_service(&app, request.to_request()).await;
+        let status = response.status().as_u16();
+        let body = actix_test::read_body(response).await;
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn with_key(request: TestRequest, key: &str) -> TestRequest {
+        request.insert_header((API_KEY_HEADER, key))
+    }
+
+    #[actix_web::test]
+    async fn a_valid_api_key_acts_as_its_developer() {
+        let users = FakeUserService::default().with_api_key("k1", "dev-1", &["games:read"]);
+
+        let response = call(users, with_key(TestRequest::get().uri("/api/games"), "k1")).await;
+
+        assert_eq!(response, (200, "key:dev-1@0".to_string()));
+    }
+
+    #[actix_web::test]
+    async fn api_keys_act_at_their_developers_current_token_version() {
+        let users = FakeUserService::default()
+            .with_api_key("k1", "dev-1", &["games:read"])
+            .with_token_version("dev-1", 3);
+
+        let response = call(users, with_key(TestRequest::get().uri("/api/games"), "k1")).await;
+
+        assert_eq!(response, (200, "key:dev-1@3".to_string()));
+    }
+
+    fn with_bearer(request: TestRequest, claims: &Claims) -> TestRequest {
+        let token = encode(&Header::new(Algorithm::HS256), claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap();
+        request.insert_header((actix_web::http::header::AUTHORIZATION, format!("Bearer {}", token)))
+    }
+
+    #[actix_web::test]
+    async fn tokens_issued_before_a_revocation_are_unauthorized() {
+        let claims = JwtVerifier::new(SECRET, 0).verify(&token(get_current_timestamp() + 300, None, None)).unwrap();
+        let revoked = || FakeUserService::default().with_token_version("alice", 1);
+
+        let (status, body) = call(revoked(), with_bearer(TestRequest::get().uri("/api/games"), &claims)).await;
+        assert_eq!(status, 401);
+        assert!(body.contains("Token has been revoked"));
+
+        let reissued = Claims { token_version: 1, ..claims };
+        let response = call(revoked(), with_bearer(TestRequest::get().uri("/api/games"), &reissued)).await;
+        assert_eq!(response, (200, "user:alice".to_string()));
+    }
+
+    #[actix_web::test]
+    async fn an_unknown_or_revoked_api_key_is_unauthorized() {
+        let users = FakeUserService::default();
+
+        let (status, _) = call(users, with_key(TestRequest::get().uri("/api/games"), "revoked")).await;
+
+        assert_eq!(status, 401);
+    }
+
+    #[actix_web::test]
+    async fn api_keys_need_the_scope_of_the_request() {
+        let read_only = || FakeUserService::default().with_api_key("k1", "dev-1", &["games:read"]);
+
+        let (status, body) = call(read_only(), with_key(TestRequest::post().uri("/api/games"), "k1")).await;
+        assert_eq!(status, 403);
+        assert!(body.contains("games:write"));
+
+        let (status, _) = call(read_only(), with_key(TestRequest::get().uri("/api/users/me"), "k1")).await;
+        assert_eq!(status, 403);
+    }
+
+    #[test]
+    fn game_reads_and_writes_need_separate_scopes() {
+        assert_eq!(required_scope(&Method::GET, "/api/games/1"), Some("games:read"));
+        assert_eq!(required_scope(&Method::HEAD, "/api/games"), Some("games:read"));
+        assert_eq!(required_scope(&Method::POST, "/api/games"), Some("games:write"));
+        assert_eq!(required_scope(&Method::DELETE, "/api/games/1"), Some("games:write"));
+        assert_eq!(required_scope(&Method::GET, "/api/users/1"), None);
+    }
+
+    #[test]
+    fn private_fields_are_visible_to_the_user_and_admins_only() {
+        let user_id = "4f7c1d52-0d8e-4a8e-9f5b-1f3d2c6a7b80";
+
+        assert!(can_view_private(&signed_in(user_id, "player"), user_id));
+        assert!(can_view_private(&signed_in("someone-else", "admin"), user_id));
+
+        assert!(!can_view_private(&TestRequest::default().to_http_request(), user_id));
+        assert!(!can_view_private(&signed_in("someone-else", "player"), user_id));
+        assert!(!can_view_private(&signed_in("someone-else", "developer"), user_id));
+
+        // An API key acts for a developer but never sees private fields.
+        let api_key = TestRequest::default().to_http_request();
+        api_key.extensions_mut().insert(Principal::ApiKey {
+            developer_id: user_id.to_string(),
+            token_version: 0,
+        });
+        assert!(!can_view_private(&api_key, user_id));
+    }
+}
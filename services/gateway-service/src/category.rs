@@ -0,0 +1,72 @@
+use serde::Deserialize;
+use strum::VariantNames;
+
+use crate::errors;
+
+/// A game category as the HTTP API spells it; `to_proto`/`from_proto` map
+/// to the game service's `GameCategory` integers. Request bodies reject an
+/// unknown name while parsing.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::VariantNames,
+)]
+#[serde(try_from = "String")]
+#[strum(serialize_all = "snake_case")]
+pub enum Category {
+    Action,
+    Rpg,
+    Strategy,
+    Sports,
+    Racing,
+    Adventure,
+    Simulation,
+    Puzzle,
+}
+
+impl TryFrom<String> for Category {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .parse()
+            .map_err(|_| errors::invalid_choice("category", &value, Self::VARIANTS))
+    }
+}
+
+impl Category {
+    pub fn to_proto(self) -> i32 {
+        match self {
+            Category::Action => 1,
+            Category::Rpg => 2,
+            Category::Strategy => 3,
+            Category::Sports => 4,
+            Category::Racing => 5,
+            Category::Adventure => 6,
+            Category::Simulation => 7,
+            Category::Puzzle => 8,
+        }
+    }
+
+    /// `None` for 0 (unspecified) and for integers the proto enum doesn't
+    /// define.
+    pub fn from_proto(value: i32) -> Option<Self> {
+        match value {
+            1 => Some(Category::Action),
+            2 => Some(Category::Rpg),
+            3 => Some(Category::Strategy),
+            4 => Some(Category::Sports),
+            5 => Some(Category::Racing),
+            6 => Some(Category::Adventure),
+            7 => Some(Category::Simulation),
+            8 => Some(Category::Puzzle),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use actix_cors::Cors;
+use actix_web::http::header::HeaderName;
+use actix_web::http::{Method, Uri};
+
+use common::config::env_or;
+
+const DEFAULT_ALLOWED_ORIGINS: &[&str] = &[
+    "http://localhost:3000", // React
+    "http://localhost:5173", // Vite
+];
+const DEFAULT_ALLOWED_METHODS: &[&str] = &["GET", "POST", "PUT", "DELETE", "OPTIONS"];
+const DEFAULT_ALLOWED_HEADERS: &[&str] = &["authorization", "accept", "content-type"];
+const DEFAULT_MAX_AGE_SECS: usize = 3600;
+
+#[derive(Debug, Clone)]
+pub enum AllowedOrigins {
+    Any,
+    List(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    pub allowed_origins: AllowedOrigins,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    /// How long browsers may cache a preflight response.
+    pub max_age_secs: usize,
+    /// Lets browsers send cookies and read credentialed responses. Only
+    /// allowed with explicit origins; browsers reject credentials with `*`.
+    pub allow_credentials: bool,
+}
+
+impl CorsConfig {
+    /// Reads `CORS_ALLOWED_ORIGINS` (comma-separated, `*` allows any origin),
+    /// `CORS_ALLOWED_METHODS`, `CORS_ALLOWED_HEADERS` (both comma-separated),
+    /// `CORS_MAX_AGE_SECS` and `CORS_ALLOW_CREDENTIALS` (`true`/`false`).
+    pub fn from_env() -> Result<Self, String> {
+        let mut config = match std::env::var("CORS_ALLOWED_ORIGINS") {
+            Ok(value) => Self::parse(&value)?,
+            Err(_) => Self::default(),
+        };
+
+        if let Ok(value) = std::env::var("CORS_ALLOWED_METHODS") {
+            config.allowed_methods = parse_list(&value, "CORS_ALLOWED_METHODS", |m| {
+                Method::from_bytes(m.to_ascii_uppercase().as_bytes()).ok()
+            })?;
+        }
+        if let Ok(value) = std::env::var("CORS_ALLOWED_HEADERS") {
+            config.allowed_headers = parse_list(&value, "CORS_ALLOWED_HEADERS", |h| {
+                HeaderName::from_bytes(h.as_bytes()).ok()
+            })?;
+        }
+        config.max_age_secs = env_or("CORS_MAX_AGE_SECS", DEFAULT_MAX_AGE_SECS)?;
+        config.allow_credentials = match std::env::var("CORS_ALLOW_CREDENTIALS") {
+            Ok(value) => value
+                .trim()
+                .parse::<bool>()
+                .map_err(|_| "CORS_ALLOW_CREDENTIALS must be true or false".to_string())?,
+            Err(_) => false,
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Settings that are individually valid but can't be combined.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allow_credentials && matches!(self.allowed_origins, AllowedOrigins::Any) {
+            return Err("CORS_ALLOW_CREDENTIALS requires explicit origins, not '*'".to_string());
+        }
+        Ok(())
+    }
+
+    /// Parses the origin list; everything else keeps its default.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let origins: Vec<&str> = value
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        if origins.is_empty() {
+            return Err("CORS_ALLOWED_ORIGINS must contain at least one origin".to_string());
+        }
+
+        if origins.contains(&"*") {
+            if origins.len() > 1 {
+                return Err("Wildcard '*' cannot be combined with explicit origins".to_string());
+            }
+            return Ok(Self {
+                allowed_origins: AllowedOrigins::Any,
+                ..Self::default()
+            });
+        }
+
+        let origins = origins
+            .into_iter()
+            .map(normalize_origin)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            allowed_origins: AllowedOrigins::List(origins),
+            ..Self::default()
+        })
+    }
+
+    pub fn build(&self) -> Cors {
+        let cors = match &self.allowed_origins {
+            AllowedOrigins::Any => Cors::default().allow_any_origin(),
+            AllowedOrigins::List(origins) => origins
+                .iter()
+                .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin)),
+        };
+
+        let cors = cors
+            .allowed_methods(self.allowed_methods.clone())
+            .allowed_headers(self.allowed_headers.clone())
+            .expose_headers(vec!["x-request-id", "x-total-count", "link", "x-already-absent"])
+            .max_age(self.max_age_secs);
+
+        if self.allow_credentials {
+            cors.supports_credentials()
+        } else {
+            cors
+        }
+    }
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: AllowedOrigins::List(
+                DEFAULT_ALLOWED_ORIGINS.iter().map(|s| s.to_string()).collect(),
+            ),
+            allowed_methods: DEFAULT_ALLOWED_METHODS
+                .iter()
+                .map(|m| Method::from_bytes(m.as_bytes()).expect("valid method"))
+                .collect(),
+            allowed_headers: DEFAULT_ALLOWED_HEADERS
+                .iter()
+                .map(|h| HeaderName::from_static(h))
+                .collect(),
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+            allow_credentials: false,
+        }
+    }
+}
+
+fn parse_list<T>(value: &str, name: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Vec<T>, String> {
+    let items = value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|item| parse(item).ok_or_else(|| format!("{} has an invalid entry '{}'", name, item)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if items.is_empty() {
+        return Err(format!("{} must contain at least one entry", name));
+    }
+    Ok(items)
+}
+
+fn normalize_origin(origin: &str) -> Result<String, String> {
+    let origin = origin.trim_end_matches('/');
+    let uri: Uri = origin
+        .parse()
+        .map_err(|_| format!("Invalid CORS origin '{}'", origin))?;
+
+    let scheme = match uri.scheme_str() {
+        Some(scheme @ ("http" | "https")) => scheme,
+        _ => return Err(format!("CORS origin '{}' must use http or https", origin)),
+    };
+
+    let authority = uri
+        .authority()
+        .ok_or_else(|| format!("CORS origin '{}' has no host", origin))?;
+
+    // An origin is scheme://host[:port] only, browsers never send a path
+    let normalized = format!("{}://{}", scheme, authority);
+    if normalized != origin {
+        return Err(format!("CORS origin '{}' must not contain a path or query", origin));
+    }
+
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::header;
+    use actix_web::test::{self as actix_test, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+
+    use super::*;
+
+    async fn preflight(config: &CorsConfig, origin: &str) -> actix_web::dev::ServiceResponse {
+        preflight_with_headers(config, origin, None).await
+    }
+
+    async fn preflight_with_headers(
+        config: &CorsConfig,
+        origin: &str,
+        request_headers: Option<&str>,
+    ) -> actix_web::dev::ServiceResponse {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(config.build())
+                .route("/api/games", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let mut req = TestRequest::default()
+            .method(Method::OPTIONS)
+            .uri("/api/games")
+            .insert_header((header::ORIGIN, origin))
+            .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "GET"));
+        if let Some(headers) = request_headers {
+            req = req.insert_header((header::ACCESS_CONTROL_REQUEST_HEADERS, headers));
+        }
+        let req = req.to_request();
+        actix_test::call_service(&app, req).await.map_into_boxed_body()
+    }
+
+    fn header_value(resp: &actix_web::dev::ServiceResponse, name: header::HeaderName) -> Option<&str> {
+        resp.headers().get(name).map(|v| v.to_str().unwrap())
+    }
+
+    fn allowed_origin(resp: &actix_web::dev::ServiceResponse) -> Option<&str> {
+        header_value(resp, header::ACCESS_CONTROL_ALLOW_ORIGIN)
+    }
+
+    #[actix_web::test]
+    async fn configured_origins_pass_preflight() {
+        let config = CorsConfig::parse("https://app.example.com, https://admin.example.com/").unwrap();
+
+        let resp = preflight(&config, "https://admin.example.com").await;
+
+        assert!(resp.status().is_success());
+        assert_eq!(allowed_origin(&resp), Some("https://admin.example.com"));
+    }
+
+    #[actix_web::test]
+    async fn unlisted_origins_fail_preflight() {
+        let config = CorsConfig::parse("https://app.example.com").unwrap();
+
+        let resp = preflight(&config, "https://evil.example.com").await;
+
+        assert!(!resp.status().is_success());
+        assert_eq!(allowed_origin(&resp), None);
+    }
+
+    #[actix_web::test]
+    async fn the_defaults_allow_the_local_dev_servers() {
+        let config = CorsConfig::default();
+
+        let resp = preflight(&config, "http://localhost:5173").await;
+        assert_eq!(allowed_origin(&resp), Some("http://localhost:5173"));
+
+        let resp = preflight(&config, "http://localhost:8000").await;
+        assert_eq!(allowed_origin(&resp), None);
+    }
+
+    #[actix_web::test]
+    async fn a_wildcard_allows_any_origin() {
+        let config = CorsConfig::parse("*").unwrap();
+
+        let resp = preflight(&config, "https://anything.example.org").await;
+
+        assert!(resp.status().is_success());
+        assert!(allowed_origin(&resp).is_some());
+    }
+
+    #[test]
+    fn invalid_origin_lists_are_rejected() {
+        assert!(CorsConfig::parse("").is_err());
+        assert!(CorsConfig::parse("*, https://app.example.com").is_err());
+        assert!(CorsConfig::parse("app.example.com").is_err());
+        assert!(CorsConfig::parse("ftp://app.example.com").is_err());
+        assert!(CorsConfig::parse("https://app.example.com/login").is_err());
+    }
+
+    #[actix_web::test]
+    async fn preflights_advertise_the_configured_max_age() {
+        let resp = preflight(&CorsConfig::default(), "http://localhost:3000").await;
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_MAX_AGE), Some("3600"));
+
+        let config = CorsConfig {
+            max_age_secs: 600,
+            ..CorsConfig::default()
+        };
+        let resp = preflight(&config, "http://localhost:3000").await;
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_MAX_AGE), Some("600"));
+    }
+
+    #[actix_web::test]
+    async fn credentials_are_allowed_only_when_configured() {
+        let resp = preflight(&CorsConfig::default(), "http://localhost:3000").await;
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_ALLOW_CREDENTIALS), None);
+
+        let config = CorsConfig {
+            allow_credentials: true,
+            ..CorsConfig::parse("https://app.example.com").unwrap()
+        };
+        config.validate().unwrap();
+
+        let resp = preflight(&config, "https://app.example.com").await;
+        assert!(resp.status().is_success());
+        assert_eq!(allowed_origin(&resp), Some("https://app.example.com"));
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_ALLOW_CREDENTIALS), Some("true"));
+
+        let resp = preflight(&config, "https://evil.example.com").await;
+        assert_eq!(allowed_origin(&resp), None);
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_ALLOW_CREDENTIALS), None);
+    }
+
+    #[test]
+    fn a_wildcard_origin_with_credentials_is_refused() {
+        let config = CorsConfig {
+            allow_credentials: true,
+            ..CorsConfig::parse("*").unwrap()
+        };
+        assert!(config.validate().is_err());
+
+        let config = CorsConfig {
+            allow_credentials: false,
+            ..CorsConfig::parse("*").unwrap()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[actix_web::test]
+    async fn only_configured_request_headers_pass_preflight() {
+        let config = CorsConfig {
+            allowed_headers: vec![header::AUTHORIZATION, header::HeaderName::from_static("x-api-key")],
+            ..CorsConfig::default()
+        };
+
+        let resp = preflight_with_headers(&config, "http://localhost:3000", Some("x-api-key")).await;
+        assert!(resp.status().is_success());
+
+        let resp = preflight_with_headers(&config, "http://localhost:3000", Some("x-custom")).await;
+        assert!(!resp.status().is_success());
+    }
+}
@@ -0,0 +1,204 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::{self, Principal};
+use crate::role::Role;
+use crate::{AppState, errors, request_id, user};
+
+#[derive(Serialize, ToSchema)]
+struct DeveloperProfileDto {
+    user_id: String,
+    studio_name: String,
+    bio: String,
+    website: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateDeveloperProfileDto {
+    studio_name: String,
+    #[serde(default)]
+    bio: String,
+    website: Option<String>,
+}
+
+/// Omitted fields stay as they are; an empty `website` removes it.
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateDeveloperProfileDto {
+    studio_name: Option<String>,
+    bio: Option<String>,
+    website: Option<String>,
+}
+
+fn profile_to_dto(profile: user::DeveloperProfile) -> DeveloperProfileDto {
+    DeveloperProfileDto {
+        user_id: profile.user_id,
+        studio_name: profile.studio_name,
+        bio: profile.bio,
+        website: profile.website,
+        created_at: profile.created_at.map(|ts| ts.seconds.to_string()),
+        updated_at: profile.updated_at.map(|ts| ts.seconds.to_string()),
+    }
+}
+
+/// Only the developer themselves or an admin may write a profile. API keys
+/// are already turned away by `auth::auth_middleware`.
+#[allow(clippy::result_large_err)]
+fn check_writer(req: &HttpRequest, developer_id: &str) -> Result<(), HttpResponse> {
+    match auth::principal(req) {
+        Some(Principal::User(claims)) if claims.sub == developer_id || claims.has_role(Role::Admin) => Ok(()),
+        Some(_) => Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "You can only edit your own developer profile"
+        }))),
+        None => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Authentication required"
+        }))),
+    }
+}
+
+fn profile_response(
+    result: Result<tonic::Response<user::DeveloperProfileResponse>, tonic::Status>,
+    ok: fn() -> actix_web::HttpResponseBuilder,
+) -> HttpResponse {
+    match result {
+        Ok(response) => match response.into_inner().profile {
+            Some(profile) => ok().json(profile_to_dto(profile)),
+            None => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Server returned empty response"
+            })),
+        },
+        Err(status) => errors::tonic_status_to_http(&status),
+    }
+}
+
+/// `GET /api/developers/{id}/profile` - public studio details for a developer.
+#[utoipa::path(
+    get,
+    path = "/api/developers/{id}/profile",
+    tag = "developers",
+    params(("id" = String, Path, description = "Developer ID")),
+    responses(
+        (status = 200, description = "The developer's profile", body = DeveloperProfileDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 404, description = "No such developer, or they have no profile yet", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn get_developer_profile(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let request = request_id::grpc_request(&req, user::GetDeveloperProfileRequest {
+        user_id: path.into_inner(),
+    });
+
+    let mut client = data.user_client();
+    Ok(profile_response(client.get_developer_profile(request).await, HttpResponse::Ok))
+}
+
+/// `POST /api/developers/{id}/profile`
+#[utoipa::path(
+    post,
+    path = "/api/developers/{id}/profile",
+    tag = "developers",
+    params(("id" = String, Path, description = "Developer ID")),
+    request_body = CreateDeveloperProfileDto,
+    responses(
+        (status = 201, description = "The new profile", body = DeveloperProfileDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 409, description = "The developer already has a profile", body = errors::ErrorEnvelope),
+        (status = 422, description = "The user is not a developer", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn create_developer_profile(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    json: web::Json<CreateDeveloperProfileDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let user_id = path.into_inner();
+    if let Err(response) = check_writer(&req, &user_id) {
+        return Ok(response);
+    }
+
+    let json = json.into_inner();
+    let request = request_id::grpc_request(&req, user::CreateDeveloperProfileRequest {
+        user_id,
+        studio_name: json.studio_name,
+        bio: json.bio,
+        website: json.website,
+    });
+
+    let mut client = data.user_client();
+    Ok(profile_response(client.create_developer_profile(request).await, HttpResponse::Created))
+}
+
+/// `PUT /api/developers/{id}/profile`
+#[utoipa::path(
+    put,
+    path = "/api/developers/{id}/profile",
+    tag = "developers",
+    params(("id" = String, Path, description = "Developer ID")),
+    request_body = UpdateDeveloperProfileDto,
+    responses(
+        (status = 200, description = "The updated profile", body = DeveloperProfileDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "No such developer, or they have no profile yet", body = errors::ErrorEnvelope),
+        (status = 422, description = "The user is not a developer", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn update_developer_profile(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    json: web::Json<UpdateDeveloperProfileDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let user_id = path.into_inner();
+    if let Err(response) = check_writer(&req, &user_id) {
+        return Ok(response);
+    }
+
+    let json = json.into_inner();
+    let request = request_id::grpc_request(&req, user::UpdateDeveloperProfileRequest {
+        user_id,
+        studio_name: json.studio_name,
+        bio: json.bio,
+        website: json.website,
+    });
+
+    let mut client = data.user_client();
+    Ok(profile_response(client.update_developer_profile(request).await, HttpResponse::Ok))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    use super::*;
+    use crate::testing::signed_in;
+
+    #[test]
+    fn only_the_developer_or_an_admin_may_write_a_profile() {
+        assert!(check_writer(&signed_in("dev-1", "developer"), "dev-1").is_ok());
+        assert!(check_writer(&signed_in("admin-1", "admin"), "dev-1").is_ok());
+
+        for (user_id, role) in [("dev-2", "developer"), ("player-1", "player")] {
+            let response = check_writer(&signed_in(user_id, role), "dev-1").unwrap_err();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{user_id}");
+        }
+
+        let anonymous = TestRequest::default().to_http_request();
+        assert_eq!(check_writer(&anonymous, "dev-1").unwrap_err().status(), StatusCode::UNAUTHORIZED);
+    }
+}
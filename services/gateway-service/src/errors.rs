@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::http::{StatusCode, header};
+use actix_web::{HttpRequest, HttpResponse, ResponseError};
+use common::error_details;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+fn grpc_code_name(code: tonic::Code) -> &'static str {
+    match code {
+        tonic::Code::Ok => "OK",
+        tonic::Code::Cancelled => "CANCELLED",
+        tonic::Code::Unknown => "UNKNOWN",
+        tonic::Code::InvalidArgument => "INVALID_ARGUMENT",
+        tonic::Code::DeadlineExceeded => "DEADLINE_EXCEEDED",
+        tonic::Code::NotFound => "NOT_FOUND",
+        tonic::Code::AlreadyExists => "ALREADY_EXISTS",
+        tonic::Code::PermissionDenied => "PERMISSION_DENIED",
+        tonic::Code::ResourceExhausted => "RESOURCE_EXHAUSTED",
+        tonic::Code::FailedPrecondition => "FAILED_PRECONDITION",
+        tonic::Code::Aborted => "ABORTED",
+        tonic::Code::OutOfRange => "OUT_OF_RANGE",
+        tonic::Code::Unimplemented => "UNIMPLEMENTED",
+        tonic::Code::Internal => "INTERNAL",
+        tonic::Code::Unavailable => "UNAVAILABLE",
+        tonic::Code::DataLoss => "DATA_LOSS",
+        tonic::Code::Unauthenticated => "UNAUTHENTICATED",
+    }
+}
+
+/// JSON body of every error response. Errors raised by the gateway itself
+/// only carry `error`.
+#[derive(Serialize, ToSchema)]
+pub struct ErrorEnvelope {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<HashMap<String, String>>,
+}
+
+/// Error body for a failed gRPC call. `code` is the `ErrorInfo` reason the
+/// service attached, falling back to the gRPC status name, and any
+/// `ErrorInfo` metadata is passed through as `details`.
+pub fn error_envelope(status: &tonic::Status, message: impl Into<String>) -> ErrorEnvelope {
+    let error = message.into();
+
+    match error_details::error_info(status) {
+        Some(info) => ErrorEnvelope {
+            error,
+            code: Some(info.reason),
+            details: (!info.metadata.is_empty()).then_some(info.metadata),
+        },
+        None => ErrorEnvelope {
+            error,
+            code: Some(grpc_code_name(status.code()).to_string()),
+            details: None,
+        },
+    }
+}
+
+/// The HTTP status every route answers a failed gRPC call with.
+pub fn http_status(code: tonic::Code) -> StatusCode {
+    match code {
+        tonic::Code::InvalidArgument | tonic::Code::OutOfRange => StatusCode::BAD_REQUEST,
+        tonic::Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        tonic::Code::PermissionDenied => StatusCode::FORBIDDEN,
+        tonic::Code::NotFound => StatusCode::NOT_FOUND,
+        tonic::Code::AlreadyExists | tonic::Code::Aborted => StatusCode::CONFLICT,
+        tonic::Code::FailedPrecondition => StatusCode::UNPROCESSABLE_ENTITY,
+        tonic::Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        tonic::Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        tonic::Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        tonic::Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        tonic::Code::Ok
+        | tonic::Code::Cancelled
+        | tonic::Code::Unknown
+        | tonic::Code::Internal
+        | tonic::Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// The error response for a failed gRPC call: `http_status` plus the
+/// service's message in an `ErrorEnvelope`. Handlers that want another
+/// message or status for some code match that code first and fall back to
+/// this for the rest.
+pub fn tonic_status_to_http(status: &tonic::Status) -> HttpResponse {
+    let message = match status.code() {
+        tonic::Code::Unimplemented => format!("Not implemented: {}", status.message()),
+        _ => status.message().to_string(),
+    };
+    HttpResponse::build(http_status(status.code())).json(error_envelope(status, message))
+}
+
+/// `tonic_status_to_http` with the handler's own wording for some codes,
+/// e.g. "Game not found" instead of the service's message. Only the message
+/// changes; a handler that needs a different status matches on it itself.
+pub fn tonic_status_to_http_with(status: &tonic::Status, messages: &[(tonic::Code, &str)]) -> HttpResponse {
+    match messages.iter().find(|(code, _)| *code == status.code()) {
+        Some((_, message)) => HttpResponse::build(http_status(status.code())).json(error_envelope(status, *message)),
+        None => tonic_status_to_http(status),
+    }
+}
+
+/// Message for a string field that must be one of a fixed set of values,
+/// e.g. `Invalid role 'root'. Must be one of: player, developer, admin`.
+pub fn invalid_choice<T: std::fmt::Display>(
+    field: &str,
+    value: &str,
+    allowed: impl IntoIterator<Item = T>,
+) -> String {
+    let allowed: Vec<String> = allowed.into_iter().map(|choice| choice.to_string()).collect();
+    format!("Invalid {} '{}'. Must be one of: {}", field, value, allowed.join(", "))
+}
+
+/// `JsonConfig` error handler, so a body that fails to parse gets the same
+/// JSON envelope as every other error instead of actix's plain-text one.
+/// Deserialization messages drop serde's "at line N column M" suffix; the
+/// enum fields' messages already say which field and what it accepts.
+/// A missing or non-JSON `Content-Type` is a 415 rather than actix's 400.
+pub fn json_error(err: JsonPayloadError, req: &HttpRequest) -> actix_web::Error {
+    if matches!(err, JsonPayloadError::ContentType) {
+        let message = match req.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+            Some(content_type) => format!("Unsupported Content-Type '{}'; expected application/json", content_type),
+            None => "Missing Content-Type; expected application/json".to_string(),
+        };
+        let response = HttpResponse::UnsupportedMediaType().json(serde_json::json!({ "error": message }));
+        return InternalError::from_response(err, response).into();
+    }
+
+    let message = match &err {
+        JsonPayloadError::Deserialize(e) => {
+            let message = e.to_string();
+            let location = format!(" at line {} column {}", e.line(), e.column());
+            match message.strip_suffix(&location) {
+                Some(stripped) => stripped.to_string(),
+                None => message,
+            }
+        }
+        other => other.to_string(),
+    };
+    let response = HttpResponse::build(err.status_code()).json(serde_json::json!({ "error": message }));
+    InternalError::from_response(err, response).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::to_bytes;
+
+    use super::*;
+
+    async fn body(response: HttpResponse) -> serde_json::Value {
+        serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap()
+    }
+
+    #[test]
+    fn maps_each_grpc_code() {
+        let cases = [
+            (tonic::Code::Ok, StatusCode::INTERNAL_SERVER_ERROR),
+            (tonic::Code::Cancelled, StatusCode::INTERNAL_SERVER_ERROR),
+            (tonic::Code::Unknown, StatusCode::INTERNAL_SERVER_ERROR),
+            (tonic::Code::InvalidArgument, StatusCode::BAD_REQUEST),
+            (tonic::Code::DeadlineExceeded, StatusCode::GATEWAY_TIMEOUT),
+            (tonic::Code::NotFound, StatusCode::NOT_FOUND),
+            (tonic::Code::AlreadyExists, StatusCode::CONFLICT),
+            (tonic::Code::PermissionDenied, StatusCode::FORBIDDEN),
+            (tonic::Code::ResourceExhausted, StatusCode::TOO_MANY_REQUESTS),
+            (tonic::Code::FailedPrecondition, StatusCode::UNPROCESSABLE_ENTITY),
+            (tonic::Code::Aborted, StatusCode::CONFLICT),
+            (tonic::Code::OutOfRange, StatusCode::BAD_REQUEST),
+            (tonic::Code::Unimplemented, StatusCode::NOT_IMPLEMENTED),
+            (tonic::Code::Internal, StatusCode::INTERNAL_SERVER_ERROR),
+            (tonic::Code::Unavailable, StatusCode::SERVICE_UNAVAILABLE),
+            (tonic::Code::DataLoss, StatusCode::INTERNAL_SERVER_ERROR),
+            (tonic::Code::Unauthenticated, StatusCode::UNAUTHORIZED),
+        ];
+
+        for (code, expected) in cases {
+            let response = tonic_status_to_http(&tonic::Status::new(code, "boom"));
+            assert_eq!(response.status(), expected, "{:?}", code);
+        }
+    }
+
+    #[actix_web::test]
+    async fn envelope_carries_the_message_and_code_name() {
+        let response = tonic_status_to_http(&tonic::Status::not_found("Game 42 does not exist"));
+
+        let body = body(response).await;
+        assert_eq!(body["error"], "Game 42 does not exist");
+        assert_eq!(body["code"], "NOT_FOUND");
+    }
+
+    #[actix_web::test]
+    async fn override_replaces_only_the_message_of_its_code() {
+        let overrides = [(tonic::Code::NotFound, "Game not found")];
+
+        let response = tonic_status_to_http_with(&tonic::Status::not_found("no row"), &overrides);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body(response).await["error"], "Game not found");
+
+        let response = tonic_status_to_http_with(&tonic::Status::permission_denied("not yours"), &overrides);
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(body(response).await["error"], "not yours");
+    }
+
+    #[actix_web::test]
+    async fn non_json_content_types_are_a_415() {
+        use actix_web::{App, test, web};
+
+        async fn echo(body: web::Json<serde_json::Value>) -> HttpResponse {
+            HttpResponse::Ok().json(body.into_inner())
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::JsonConfig::default().error_handler(json_error))
+                .route("/echo", web::post().to(echo)),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/echo")
+            .insert_header((header::CONTENT_TYPE, "text/plain"))
+            .set_payload(r#"{"name":"x"}"#)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["error"], "Unsupported Content-Type 'text/plain'; expected application/json");
+
+        let request = test::TestRequest::post().uri("/echo").set_payload(r#"{"name":"x"}"#).to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["error"], "Missing Content-Type; expected application/json");
+
+        let request = test::TestRequest::post().uri("/echo").set_json(serde_json::json!({ "name": "x" })).to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,158 @@
+use actix_web::{HttpRequest, HttpResponse, http::header};
+use sha2::{Digest, Sha256};
+
+use crate::fields::Projection;
+use crate::negotiate::{self, Format};
+
+fn quoted_digest(input: &[u8]) -> String {
+    let digest = format!("{:x}", Sha256::digest(input));
+    format!("\"{}\"", &digest[..32])
+}
+
+/// A stored game only changes when its `updated_at` moves. Its current
+/// price also moves on its own when a scheduled sale starts or ends, so the
+/// tag covers that too, plus the body format and any `?fields=` projection
+/// since each of those is a separate representation.
+pub fn game_etag(
+    id: &str,
+    updated_at: Option<&prost_types::Timestamp>,
+    price: i64,
+    format: Format,
+    projection: Option<&Projection>,
+) -> String {
+    let (seconds, nanos) = updated_at.map_or((0, 0), |ts| (ts.seconds, ts.nanos));
+    let suffix = match format {
+        Format::Json => "",
+        Format::MsgPack => ":msgpack",
+    };
+    let fields = projection.map(|p| format!(":fields={}", p.key())).unwrap_or_default();
+    quoted_digest(format!("{}:{}.{}:{}{}{}", id, seconds, nanos, price, suffix, fields).as_bytes())
+}
+
+pub fn body_etag(body: &[u8]) -> String {
+    quoted_digest(body)
+}
+
+/// Weak comparison as required for `If-None-Match` (RFC 9110 §13.1.2).
+pub fn if_none_match(req: &HttpRequest, etag: &str) -> bool {
+    let Some(value) = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+
+    value.split(',').map(|tag| tag.trim()).any(|tag| {
+        tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag
+    })
+}
+
+pub fn not_modified(etag: &str) -> HttpResponse {
+    HttpResponse::NotModified()
+        .insert_header((header::ETAG, etag))
+        .finish()
+}
+
+/// Serializes `body` once, in the negotiated format, so the same bytes are
+/// both hashed and sent.
+pub fn negotiated_with_etag<T: serde::Serialize>(req: &HttpRequest, body: &T) -> HttpResponse {
+    let format = Format::from_request(req);
+    let bytes = match format.encode(body) {
+        Ok(bytes) => bytes,
+        Err(e) => return negotiate::serialization_error(e),
+    };
+
+    let etag = body_etag(&bytes);
+    if if_none_match(req, &etag) {
+        return not_modified(&etag);
+    }
+
+    HttpResponse::Ok()
+        .insert_header((header::ETAG, etag))
+        .insert_header((header::VARY, "Accept"))
+        .content_type(format.content_type())
+        .body(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn ts(seconds: i64, nanos: i32) -> prost_types::Timestamp {
+        prost_types::Timestamp { seconds, nanos }
+    }
+
+    fn etag(updated_at: prost_types::Timestamp, price: i64) -> String {
+        game_etag("game-1", Some(&updated_at), price, Format::Json, None)
+    }
+
+    #[test]
+    fn the_game_etag_is_stable_for_the_same_state() {
+        assert_eq!(etag(ts(1_700_000_000, 5), 1999), etag(ts(1_700_000_000, 5), 1999));
+    }
+
+    #[test]
+    fn a_changed_price_or_updated_at_changes_the_game_etag() {
+        let original = etag(ts(1_700_000_000, 5), 1999);
+
+        assert_ne!(etag(ts(1_700_000_000, 5), 999), original);
+        assert_ne!(etag(ts(1_700_000_001, 5), 1999), original);
+        assert_ne!(etag(ts(1_700_000_000, 6), 1999), original);
+        assert_ne!(game_etag("game-2", Some(&ts(1_700_000_000, 5)), 1999, Format::Json, None), original);
+    }
+
+    #[test]
+    fn each_representation_gets_its_own_etag() {
+        let updated_at = ts(1_700_000_000, 5);
+        let json = game_etag("game-1", Some(&updated_at), 1999, Format::Json, None);
+        let msgpack = game_etag("game-1", Some(&updated_at), 1999, Format::MsgPack, None);
+        let projection = Projection::from_query(Some("id,name"), &["id", "name"]).unwrap().unwrap();
+        let projected = game_etag("game-1", Some(&updated_at), 1999, Format::Json, Some(&projection));
+
+        assert_ne!(json, msgpack);
+        assert_ne!(json, projected);
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let etag = body_etag(b"body");
+        let matches = |value: &str| {
+            let req = TestRequest::default()
+                .insert_header((header::IF_NONE_MATCH, value))
+                .to_http_request();
+            if_none_match(&req, &etag)
+        };
+
+        assert!(matches(&etag));
+        assert!(matches(&format!("W/{}", etag)));
+        assert!(matches(&format!("\"other\", {}", etag)));
+        assert!(matches("*"));
+        assert!(!matches("\"other\""));
+        assert!(!if_none_match(&TestRequest::default().to_http_request(), &etag));
+    }
+
+    #[test]
+    fn a_matching_if_none_match_gets_304() {
+        let body = serde_json::json!({ "id": "game-1", "price": "19.99" });
+
+        let first = negotiated_with_etag(&TestRequest::default().to_http_request(), &body);
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string();
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, etag.as_str()))
+            .to_http_request();
+        let second = negotiated_with_etag(&req, &body);
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(second.headers().get(header::ETAG).unwrap(), etag.as_str());
+
+        let changed = serde_json::json!({ "id": "game-1", "price": "9.99" });
+        let third = negotiated_with_etag(&req, &changed);
+        assert_eq!(third.status(), StatusCode::OK);
+        assert_ne!(third.headers().get(header::ETAG).unwrap(), etag.as_str());
+    }
+}
@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use actix_web::{HttpResponse, web, web::Bytes};
+use serde::Serialize;
+use utoipa::ToSchema;
+use tokio::sync::broadcast;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::{BroadcastStream, IntervalStream};
+use uuid::Uuid;
+
+use common::models::GameStatus;
+use common::money::Money;
+
+use crate::grpc_pool::ChannelPool;
+use crate::{epoch_seconds, errors, game};
+
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Rebroadcasts the game service's `WatchGameEvents` stream to every
+/// connected SSE client.
+pub struct GameEventHub {
+    sender: broadcast::Sender<game::GameEvent>,
+}
+
+impl GameEventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<game::GameEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Keeps a single upstream subscription open, resubscribing on the next
+    /// pooled channel whenever the game service replica goes away.
+    pub fn spawn_forwarder(&self, channels: ChannelPool) {
+        let sender = self.sender.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let mut client = game::game_service_client::GameServiceClient::new(channels.channel());
+                let request = tonic::Request::new(game::WatchGameEventsRequest { game_id: None });
+
+                match client.watch_game_events(request).await {
+                    Ok(response) => {
+                        let mut stream = response.into_inner();
+                        while let Ok(Some(event)) = stream.message().await {
+                            let _ = sender.send(event);
+                        }
+                        println!("Game event stream closed, reconnecting");
+                    }
+                    Err(status) => {
+                        println!("Failed to subscribe to game events: {}", status.message());
+                    }
+                }
+
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+struct GameEventDto {
+    game_id: String,
+    event_type: String,
+    #[schema(value_type = Option<String>, example = "19.99")]
+    price: Option<Money>,
+    status: Option<String>,
+    occurred_at: Option<String>,
+}
+
+fn event_to_sse_frame(event: game::GameEvent) -> Bytes {
+    let event_type = match event.event_type {
+        1 => "updated",
+        2 => "published",
+        3 => "price_changed",
+        _ => "unspecified",
+    };
+
+    let dto = GameEventDto {
+        game_id: event.game_id,
+        event_type: event_type.to_string(),
+        price: event.game.as_ref().map(|game| Money::from(game.price)),
+        status: event.game.as_ref().map(|game| GameStatus::proto_name(game.status)),
+        occurred_at: epoch_seconds(event.occurred_at),
+    };
+
+    let data = serde_json::to_string(&dto).unwrap_or_default();
+    Bytes::from(format!("event: {}\ndata: {}\n\n", event_type, data))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}/events",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "Server-sent events, one JSON object per `data:` line", body = GameEventDto, content_type = "text/event-stream"),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn game_events(
+    hub: web::Data<GameEventHub>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let game_id = path.into_inner();
+
+    if Uuid::parse_str(&game_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid game ID format"
+        })));
+    }
+
+    let events = BroadcastStream::new(hub.subscribe())
+        .filter_map(|event| event.ok())
+        .filter(move |event| event.game_id == game_id)
+        .map(event_to_sse_frame);
+
+    let keep_alive = IntervalStream::new(tokio::time::interval(KEEP_ALIVE_INTERVAL))
+        .map(|_| Bytes::from_static(b": keep-alive\n\n"));
+
+    // actix drops the stream when the client disconnects, which drops the
+    // broadcast receiver along with it
+    let stream = events
+        .merge(keep_alive)
+        .map(Ok::<_, actix_web::Error>);
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_is_sent_in_dollars_like_the_rest_api() {
+        let event = game::GameEvent {
+            game_id: "g1".to_string(),
+            event_type: 3,
+            game: Some(game::Game { price: 1999, ..Default::default() }),
+            ..Default::default()
+        };
+
+        let frame = event_to_sse_frame(event);
+        let frame = std::str::from_utf8(&frame).unwrap();
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let dto: serde_json::Value = serde_json::from_str(data).unwrap();
+
+        assert!(frame.starts_with("event: price_changed\n"));
+        assert_eq!(dto["price"], "19.99");
+    }
+}
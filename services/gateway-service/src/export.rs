@@ -0,0 +1,199 @@
+use actix_web::{HttpRequest, HttpResponse, http::header, web, web::Bytes};
+use tokio_stream::StreamExt;
+
+use common::models::GameStatus;
+use common::money::Money;
+use common::timestamp::timestamp_to_datetime;
+
+use crate::auth::{self, Principal};
+use crate::role::Role;
+use crate::{AppState, errors, game};
+
+const CSV_HEADER: &str =
+    "id,name,status,price,purchase_count,average_rating,rating_count,created_at\r\n";
+
+/// Quotes a field when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn game_to_csv_row(game: game::Game) -> Bytes {
+    let created_at = game
+        .created_at
+        .as_ref()
+        .and_then(timestamp_to_datetime)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default();
+
+    Bytes::from(format!(
+        "{},{},{},{},{},{},{},{}\r\n",
+        game.id,
+        csv_field(&game.name),
+        GameStatus::proto_name(game.status),
+        Money::from(game.price),
+        game.purchase_count,
+        game.average_rating,
+        game.rating_count,
+        created_at,
+    ))
+}
+
+/// Streams a CSV of a developer's games. Only the developer themselves or an
+/// admin may export; rows are forwarded as the game service produces them.
+#[utoipa::path(
+    get,
+    path = "/api/developers/{id}/export",
+    tag = "developers",
+    params(("id" = String, Path, description = "Developer ID")),
+    responses(
+        (status = 200, description = "CSV of the developer's games", body = String, content_type = "text/csv"),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn developer_export(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let developer_id = path.into_inner();
+
+    if uuid::Uuid::parse_str(&developer_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid developer ID format"
+        })));
+    }
+
+    match auth::principal(&req) {
+        Some(Principal::User(claims)) if claims.sub == developer_id || claims.has_role(Role::Admin) => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "You can only export your own games"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
+    }
+
+    let request = auth::grpc_request(
+        &req,
+        game::ExportDeveloperGamesRequest {
+            developer_id: developer_id.clone(),
+        },
+    );
+
+    let mut client = data.game_client();
+    let rows = match client.export_developer_games(request).await {
+        Ok(response) => response.into_inner(),
+        Err(status) => {
+            return Ok(errors::tonic_status_to_http(&status));
+        }
+    };
+
+    // Headers are already sent once rows start flowing, so a mid-stream
+    // failure can only abort the response.
+    let body = tokio_stream::once(Ok(Bytes::from_static(CSV_HEADER.as_bytes()))).chain(
+        rows.map(|row| {
+            row.map(game_to_csv_row)
+                .map_err(|status| actix_web::error::ErrorInternalServerError(status.message().to_string()))
+        }),
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"games-{}.csv\"", developer_id),
+        ))
+        .streaming(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    use super::*;
+    use crate::testing::{UNREACHABLE_URL, app_state, signed_in};
+
+    fn game(name: &str) -> game::Game {
+        game::Game {
+            id: "7f1c6a43-2a3e-4c7b-9a57-1c0f4b0e2d11".to_string(),
+            name: name.to_string(),
+            status: GameStatus::Published.to_proto(),
+            price: 1999,
+            purchase_count: 42,
+            average_rating: 4.5,
+            rating_count: 8,
+            created_at: Some(prost_types::Timestamp { seconds: 1_700_000_000, nanos: 0 }),
+            ..Default::default()
+        }
+    }
+
+    fn row(game: game::Game) -> String {
+        String::from_utf8(game_to_csv_row(game).to_vec()).unwrap()
+    }
+
+    #[test]
+    fn header_lists_the_columns_in_row_order() {
+        assert_eq!(
+            CSV_HEADER,
+            "id,name,status,price,purchase_count,average_rating,rating_count,created_at\r\n"
+        );
+        let columns = CSV_HEADER.trim_end().split(',').count();
+        assert_eq!(row(game("Plain")).trim_end().split(',').count(), columns);
+    }
+
+    #[test]
+    fn a_known_game_becomes_one_row() {
+        assert_eq!(
+            row(game("Space Quest")),
+            "7f1c6a43-2a3e-4c7b-9a57-1c0f4b0e2d11,Space Quest,published,19.99,42,4.5,8,2023-11-14T22:13:20+00:00\r\n"
+        );
+    }
+
+    #[test]
+    fn names_with_delimiters_quotes_or_line_breaks_are_quoted() {
+        assert_eq!(csv_field("Plain"), "Plain");
+        assert_eq!(csv_field("Swords, Sorcery"), "\"Swords, Sorcery\"");
+        assert_eq!(csv_field("The \"Best\" Game"), "\"The \"\"Best\"\" Game\"");
+        assert_eq!(csv_field("Two\nLines"), "\"Two\nLines\"");
+        assert_eq!(csv_field("Two\r\nLines"), "\"Two\r\nLines\"");
+
+        assert!(row(game("Swords, Sorcery")).contains(",\"Swords, Sorcery\",published,"));
+    }
+
+    #[test]
+    fn games_without_a_creation_time_leave_the_column_empty() {
+        let mut game = game("Undated");
+        game.created_at = None;
+        assert!(row(game).ends_with(",8,\r\n"));
+    }
+
+    #[actix_web::test]
+    async fn only_the_developer_or_an_admin_may_export() {
+        let developer_id = uuid::Uuid::new_v4().to_string();
+        let export = |req: HttpRequest| {
+            developer_export(
+                req,
+                web::Data::new(app_state(UNREACHABLE_URL.to_string())),
+                web::Path::from(developer_id.clone()),
+            )
+        };
+
+        let anonymous = TestRequest::default().to_http_request();
+        assert_eq!(export(anonymous).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+
+        let other = signed_in(&uuid::Uuid::new_v4().to_string(), "developer");
+        assert_eq!(export(other).await.unwrap().status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Every key `GameDto` can serialize. Kept by hand because `deleted_at` and
+/// `sale` are skipped when empty, so it cannot be read off an arbitrary instance.
+pub const GAME_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "description",
+    "developer_id",
+    "publisher_id",
+    "cover_image",
+    "trailer_url",
+    "release_date",
+    "tags",
+    "platforms",
+    "screenshots",
+    "price",
+    "base_price",
+    "sale",
+    "status",
+    "categories",
+    "rating_count",
+    "average_rating",
+    "purchase_count",
+    "created_at",
+    "updated_at",
+    "deleted_at",
+];
+
+/// The top-level keys a client asked for with `?fields=a,b,c`.
+#[derive(Debug, Clone)]
+pub struct Projection(Vec<String>);
+
+impl Projection {
+    /// `None` when the parameter is absent or blank, meaning the full object.
+    /// Unknown names are rejected rather than silently dropped so a typo does
+    /// not look like a missing value.
+    pub fn from_query(raw: Option<&str>, allowed: &[&str]) -> Result<Option<Self>, String> {
+        let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+            return Ok(None);
+        };
+
+        let mut fields: Vec<String> = Vec::new();
+        for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            if !allowed.contains(&name) {
+                return Err(format!("Unknown field '{}'", name));
+            }
+            fields.push(name.to_string());
+        }
+        fields.sort();
+        fields.dedup();
+
+        Ok(Some(Projection(fields)))
+    }
+
+    /// Serializes `value` and keeps only the selected keys of the resulting
+    /// object. Non-object values pass through untouched.
+    pub fn apply<T: Serialize>(&self, value: &T) -> Result<Value, String> {
+        let mut value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+        if let Value::Object(map) = &mut value {
+            map.retain(|key, _| self.0.iter().any(|f| f == key));
+        }
+        Ok(value)
+    }
+
+    /// Stable across field order and duplicates, for cache validators.
+    pub fn key(&self) -> String {
+        self.0.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn projection(raw: &str) -> Projection {
+        Projection::from_query(Some(raw), GAME_FIELDS).unwrap().unwrap()
+    }
+
+    #[test]
+    fn absent_or_blank_fields_mean_the_whole_object() {
+        for raw in [None, Some(""), Some("  ")] {
+            assert!(Projection::from_query(raw, GAME_FIELDS).unwrap().is_none(), "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected_by_name() {
+        assert_eq!(
+            Projection::from_query(Some("id,nmae"), GAME_FIELDS).unwrap_err(),
+            "Unknown field 'nmae'"
+        );
+        assert_eq!(
+            Projection::from_query(Some("ID"), GAME_FIELDS).unwrap_err(),
+            "Unknown field 'ID'"
+        );
+    }
+
+    #[test]
+    fn every_game_field_can_be_selected() {
+        for field in GAME_FIELDS {
+            assert_eq!(projection(field).key(), *field);
+        }
+    }
+
+    #[test]
+    fn only_the_requested_fields_are_kept() {
+        let game = json!({ "id": "g1", "name": "Space Quest", "price": { "cents": 1999 }, "tags": ["retro"] });
+
+        let projected = projection(" name , id ").apply(&game).unwrap();
+        assert_eq!(projected, json!({ "id": "g1", "name": "Space Quest" }));
+
+        // A selected key the object doesn't have is simply absent.
+        let projected = projection("id,deleted_at").apply(&game).unwrap();
+        assert_eq!(projected, json!({ "id": "g1" }));
+    }
+
+    #[test]
+    fn non_objects_pass_through() {
+        let list = json!([{ "id": "g1", "name": "Space Quest" }]);
+        assert_eq!(projection("id").apply(&list).unwrap(), list);
+    }
+
+    #[test]
+    fn the_key_ignores_order_and_duplicates() {
+        assert_eq!(projection("name,id,name").key(), "id,name");
+        assert_eq!(projection("id,name").key(), projection("name, id").key());
+    }
+}
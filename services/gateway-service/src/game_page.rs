@@ -0,0 +1,263 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use common::models::GameStatus;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{AppState, DeveloperSummary, GameDto, epoch_seconds, errors, game, game_to_dto, negotiate, request_id, user};
+
+const SIMILAR_GAMES: i32 = 6;
+const TOP_REVIEWS: i32 = 5;
+
+#[derive(Serialize, ToSchema)]
+struct ReviewDto {
+    id: String,
+    user_id: String,
+    rating: i32,
+    created_at: Option<String>,
+}
+
+/// Everything a game's store page shows. Only `game` is required; the other
+/// sections come back empty (or null) with a warning when their source fails.
+#[derive(Serialize, ToSchema)]
+struct GamePageResponse {
+    game: GameDto,
+    developer: Option<DeveloperSummary>,
+    /// Published games sharing a category with this one.
+    similar: Vec<GameDto>,
+    top_reviews: Vec<ReviewDto>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+/// `GET /api/games/{id}/page` - the game plus its developer, similar games
+/// and top reviews in one response. The game and its reviews are fetched
+/// together, then the developer and similar games, which depend on the game.
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}/page",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "The assembled page; `warnings` names any section that could not be loaded", body = GamePageResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn game_page(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let game_id = path.into_inner();
+
+    if Uuid::parse_str(&game_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid game ID format"
+        })));
+    }
+
+    let game_request = request_id::grpc_request(&req, game::GetGameRequest { id: game_id.clone() });
+    let mut game_client = data.game_client();
+    let game = async move { game_client.get_game(game_request).await };
+
+    let reviews_request = request_id::grpc_request(&req, game::ListTopReviewsRequest {
+        game_id: game_id.clone(),
+        limit: TOP_REVIEWS,
+    });
+    let mut reviews_client = data.game_client();
+    let reviews = async move { reviews_client.list_top_reviews(reviews_request).await };
+
+    let (game, reviews) = tokio::join!(game, reviews);
+
+    let game = match game {
+        Ok(response) => match response.into_inner().game {
+            Some(game) => game,
+            None => {
+                return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Game not found"
+                })));
+            }
+        },
+        Err(status) => {
+            return Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")]));
+        }
+    };
+
+    let mut warnings = Vec::new();
+
+    let top_reviews = match reviews {
+        Ok(response) => response
+            .into_inner()
+            .reviews
+            .into_iter()
+            .map(|review| ReviewDto {
+                id: review.id,
+                user_id: review.user_id,
+                rating: review.rating,
+                created_at: epoch_seconds(review.created_at),
+            })
+            .collect(),
+        Err(status) => {
+            log::warn!("Game page {}: reviews failed: {}", game_id, status.message());
+            warnings.push(format!("Reviews unavailable: {}", status.message()));
+            Vec::new()
+        }
+    };
+
+    let developer_request = request_id::grpc_request(&req, user::GetUserRequest { id: game.developer_id.clone() });
+    let mut user_client = data.user_client();
+    let developer = async move { user_client.get_user(developer_request).await };
+
+    // Without categories there is nothing to call the game similar by.
+    let similar_request = (!game.categories.is_empty()).then(|| {
+        request_id::grpc_request(&req, game::ListGamesRequest {
+            categories: game.categories.clone(),
+            status: Some(GameStatus::Published.to_proto()),
+            page_size: SIMILAR_GAMES,
+            exclude_id: Some(game_id.clone()),
+            ..Default::default()
+        })
+    });
+    let mut similar_client = data.game_client();
+    let similar = async move {
+        match similar_request {
+            Some(request) => Some(similar_client.list_games(request).await),
+            None => None,
+        }
+    };
+
+    let (developer, similar) = tokio::join!(developer, similar);
+
+    let developer = match developer {
+        Ok(response) => response.into_inner().user.map(|user| DeveloperSummary {
+            id: user.id,
+            username: user.username,
+            verified: user.verified,
+        }),
+        Err(status) => {
+            log::warn!("Game page {}: developer failed: {}", game_id, status.message());
+            warnings.push(format!("Developer unavailable: {}", status.message()));
+            None
+        }
+    };
+
+    let similar = match similar {
+        Some(Ok(response)) => response.into_inner().games.into_iter().map(game_to_dto).collect(),
+        Some(Err(status)) => {
+            log::warn!("Game page {}: similar games failed: {}", game_id, status.message());
+            warnings.push(format!("Similar games unavailable: {}", status.message()));
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+
+    Ok(negotiate::ok(&req, &GamePageResponse {
+        game: game_to_dto(game),
+        developer,
+        similar,
+        top_reviews,
+        warnings,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::testing::{FakeGameService, FakeUserService, UNREACHABLE_URL, app_state_with};
+
+    const DEVELOPER_ID: &str = "dev-1";
+
+    fn game(name: &str, category: game::GameCategory, status: GameStatus) -> game::Game {
+        game::Game {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            developer_id: DEVELOPER_ID.to_string(),
+            categories: vec![category as i32],
+            status: status.to_proto(),
+            ..Default::default()
+        }
+    }
+
+    fn developer() -> user::UserMessage {
+        user::UserMessage {
+            id: DEVELOPER_ID.to_string(),
+            username: "stellar".to_string(),
+            email: "dev@example.com".to_string(),
+            verified: true,
+            ..Default::default()
+        }
+    }
+
+    async fn fetch(state: AppState, id: &str) -> (StatusCode, Value) {
+        let response = game_page(TestRequest::default().to_http_request(), web::Data::new(state), web::Path::from(id.to_string()))
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[actix_web::test]
+    async fn the_page_has_the_game_its_developer_similar_games_and_reviews() {
+        let page = game("Space Quest", game::GameCategory::Adventure, GameStatus::Published);
+        let page_id = page.id.clone();
+        let games = Arc::new(
+            FakeGameService::default()
+                .with_game(page)
+                .with_game(game("Space Quest II", game::GameCategory::Adventure, GameStatus::Published))
+                .with_game(game("Space Quest III", game::GameCategory::Adventure, GameStatus::Draft))
+                .with_game(game("Kart Racer", game::GameCategory::Racing, GameStatus::Published))
+                .with_review(&page_id, game::GameReview { id: "r1".to_string(), user_id: "u1".to_string(), rating: 5, created_at: None }),
+        );
+        let users = Arc::new(FakeUserService::default().with_user(developer()));
+        let state = app_state_with(users.serve().await, games.serve().await);
+
+        let (status, body) = fetch(state, &page_id).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["game"]["name"], "Space Quest");
+        assert_eq!(body["developer"], serde_json::json!({ "id": DEVELOPER_ID, "username": "stellar", "verified": true }));
+        let similar: Vec<&str> = body["similar"].as_array().unwrap().iter().map(|g| g["name"].as_str().unwrap()).collect();
+        assert_eq!(similar, ["Space Quest II"]);
+        assert_eq!(body["top_reviews"][0]["rating"], 5);
+        assert!(body.get("warnings").is_none(), "{body}");
+    }
+
+    #[actix_web::test]
+    async fn failing_sections_are_left_empty_with_a_warning() {
+        let page = game("Space Quest", game::GameCategory::Adventure, GameStatus::Published);
+        let page_id = page.id.clone();
+        let games = Arc::new(FakeGameService::default().with_game(page));
+        let state = app_state_with(UNREACHABLE_URL.to_string(), games.serve().await);
+
+        let (status, body) = fetch(state, &page_id).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["developer"].is_null());
+        assert_eq!(body["similar"], serde_json::json!([]));
+        let warnings = body["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 1, "{body}");
+        assert!(warnings[0].as_str().unwrap().starts_with("Developer unavailable"));
+    }
+
+    #[actix_web::test]
+    async fn unknown_or_malformed_games_have_no_page() {
+        let games = Arc::new(FakeGameService::default());
+        let game_url = games.serve().await;
+
+        let (status, body) = fetch(app_state_with(UNREACHABLE_URL.to_string(), game_url.clone()), &Uuid::new_v4().to_string()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "Game not found");
+
+        let (status, body) = fetch(app_state_with(UNREACHABLE_URL.to_string(), game_url), "not-a-uuid").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Invalid game ID format");
+    }
+}
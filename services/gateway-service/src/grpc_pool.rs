@@ -0,0 +1,219 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tonic::transport::{Channel, Endpoint};
+
+use common::config::env_or;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How many connections to open per backend replica and how often to probe
+/// the replicas (`GRPC_CHANNELS_PER_ENDPOINT`, `GRPC_HEALTH_CHECK_SECS`).
+pub struct PoolConfig {
+    pub channels_per_endpoint: usize,
+    pub health_check_interval: Duration,
+}
+
+impl PoolConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let channels_per_endpoint = env_or("GRPC_CHANNELS_PER_ENDPOINT", 1)?;
+        if channels_per_endpoint == 0 {
+            return Err("GRPC_CHANNELS_PER_ENDPOINT must be at least 1".to_string());
+        }
+        let interval = env_or("GRPC_HEALTH_CHECK_SECS", 5)?;
+        if interval == 0 {
+            return Err("GRPC_HEALTH_CHECK_SECS must be at least 1".to_string());
+        }
+
+        Ok(Self {
+            channels_per_endpoint,
+            health_check_interval: Duration::from_secs(interval),
+        })
+    }
+}
+
+/// Backend URLs from a comma-separated variable such as `USER_SERVICE_URLS`,
+/// or just `default` when it is unset.
+pub fn urls_from_env(name: &str, default: &str) -> Vec<String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => vec![default.to_string()],
+    }
+}
+
+struct Member {
+    url: String,
+    endpoint: Endpoint,
+    channels: Vec<Channel>,
+    healthy: AtomicBool,
+}
+
+struct Inner {
+    name: &'static str,
+    members: Vec<Member>,
+    /// (member, channel) for every channel, in round-robin order.
+    slots: Vec<(usize, usize)>,
+    next: AtomicUsize,
+}
+
+/// Channels to every replica of one backend. Each call takes the next
+/// channel round-robin, skipping replicas the last health probe could not
+/// reach. Channels connect lazily and reconnect on their own, so a replica
+/// that comes back is used again after its next successful probe.
+#[derive(Clone)]
+pub struct ChannelPool {
+    inner: Arc<Inner>,
+}
+
+impl ChannelPool {
+    pub fn new(name: &'static str, urls: &[String], config: &PoolConfig) -> Result<Self, String> {
+        if urls.is_empty() {
+            return Err(format!("no {} URLs configured", name));
+        }
+
+        let mut members = Vec::with_capacity(urls.len());
+        let mut slots = Vec::new();
+        for (index, url) in urls.iter().enumerate() {
+            let endpoint = Endpoint::from_shared(url.clone())
+                .map_err(|e| format!("invalid {} URL {}: {}", name, url, e))?;
+            let channels: Vec<Channel> = (0..config.channels_per_endpoint)
+                .map(|_| endpoint.connect_lazy())
+                .collect();
+            slots.extend((0..channels.len()).map(|channel| (index, channel)));
+            members.push(Member {
+                url: url.clone(),
+                endpoint,
+                channels,
+                healthy: AtomicBool::new(true),
+            });
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                name,
+                members,
+                slots,
+                next: AtomicUsize::new(0),
+            }),
+        })
+    }
+
+    /// The channel for the next call. When every replica is marked down the
+    /// call goes out anyway, so it fails with the real transport error.
+    pub fn channel(&self) -> Channel {
+        let inner = &self.inner;
+        let start = inner.next.fetch_add(1, Ordering::Relaxed);
+
+        let pick = (0..inner.slots.len())
+            .map(|offset| inner.slots[(start + offset) % inner.slots.len()])
+            .find(|&(member, _)| inner.members[member].healthy.load(Ordering::Relaxed))
+            .unwrap_or(inner.slots[start % inner.slots.len()]);
+
+        inner.members[pick.0].channels[pick.1].clone()
+    }
+
+    /// Probes every replica right away and then every `interval`, with a
+    /// fresh connection each time. With a single replica there is nothing
+    /// to fail over to, so nothing runs.
+    pub fn spawn_health_checks(&self, interval: Duration) {
+        if self.inner.members.len() < 2 {
+            return;
+        }
+
+        let pool = self.clone();
+        tokio::spawn(async move {
+            loop {
+                pool.check_health().await;
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// Probes every replica once, marking each up or down.
+    async fn check_health(&self) {
+        let inner = &self.inner;
+        for member in &inner.members {
+            let up = probe(&member.endpoint).await;
+            if member.healthy.swap(up, Ordering::Relaxed) != up {
+                if up {
+                    log::info!("{} at {} is reachable again", inner.name, member.url);
+                } else {
+                    log::warn!("{} at {} is unreachable; routing around it", inner.name, member.url);
+                }
+            }
+        }
+    }
+}
+
+async fn probe(endpoint: &Endpoint) -> bool {
+    endpoint
+        .clone()
+        .connect_timeout(PROBE_TIMEOUT)
+        .connect()
+        .await
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{FakeUserService, UNREACHABLE_URL, pool};
+    use crate::user::GetTokenVersionRequest;
+    use crate::user::user_service_client::UserServiceClient;
+
+    /// Makes `count` calls through `pool`, returning how many succeeded.
+    async fn call(pool: &ChannelPool, count: usize) -> usize {
+        let mut succeeded = 0;
+        for _ in 0..count {
+            let request = GetTokenVersionRequest { user_id: "alice".to_string() };
+            if UserServiceClient::new(pool.channel()).get_token_version(request).await.is_ok() {
+                succeeded += 1;
+            }
+        }
+        succeeded
+    }
+
+    #[tokio::test]
+    async fn calls_are_spread_across_replicas() {
+        let first = Arc::new(FakeUserService::default());
+        let second = Arc::new(FakeUserService::default());
+        let urls = [first.clone().serve().await, second.clone().serve().await];
+        let pool = pool("user service", &urls);
+
+        assert_eq!(call(&pool, 10).await, 10);
+
+        assert_eq!(first.calls.load(Ordering::Relaxed), 5);
+        assert_eq!(second.calls.load(Ordering::Relaxed), 5);
+    }
+
+    #[tokio::test]
+    async fn calls_route_around_an_unreachable_replica() {
+        let up = Arc::new(FakeUserService::default());
+        let urls = [up.clone().serve().await, UNREACHABLE_URL.to_string()];
+        let pool = pool("user service", &urls);
+
+        // Until a probe notices, the dead replica still gets its turn
+        assert_eq!(call(&pool, 4).await, 2);
+
+        pool.check_health().await;
+        assert_eq!(call(&pool, 6).await, 6);
+        assert_eq!(up.calls.load(Ordering::Relaxed), 8);
+    }
+
+    #[tokio::test]
+    async fn calls_still_go_out_when_every_replica_is_down() {
+        let pool = pool("user service", &[UNREACHABLE_URL.to_string(), UNREACHABLE_URL.to_string()]);
+        pool.check_health().await;
+
+        let request = GetTokenVersionRequest { user_id: "alice".to_string() };
+        let err = UserServiceClient::new(pool.channel()).get_token_version(request).await.unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::Unavailable);
+    }
+}
@@ -0,0 +1,55 @@
+/// Related data a game listing can embed with `?include=developer,ratings`.
+/// Everything is off by default so the plain listing stays one backend call.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Includes {
+    pub developer: bool,
+    pub ratings: bool,
+}
+
+impl Includes {
+    /// Unknown names are rejected, like unknown `?fields=` entries.
+    pub fn from_query(raw: Option<&str>) -> Result<Self, String> {
+        let mut includes = Includes::default();
+        let Some(raw) = raw else {
+            return Ok(includes);
+        };
+
+        for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match name {
+                "developer" => includes.developer = true,
+                "ratings" => includes.ratings = true,
+                _ => return Err(format!("Unknown include '{}'", name)),
+            }
+        }
+        Ok(includes)
+    }
+
+    pub fn any(&self) -> bool {
+        self.developer || self.ratings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn includes_are_off_unless_named() {
+        for raw in [None, Some(""), Some(" , ")] {
+            let includes = Includes::from_query(raw).unwrap();
+            assert!(!includes.any(), "{raw:?}");
+        }
+
+        let includes = Includes::from_query(Some("ratings")).unwrap();
+        assert!(includes.ratings && !includes.developer);
+
+        let includes = Includes::from_query(Some(" developer , ratings,developer")).unwrap();
+        assert!(includes.ratings && includes.developer);
+    }
+
+    #[test]
+    fn unknown_includes_are_rejected_by_name() {
+        assert_eq!(Includes::from_query(Some("developer,reviews")).unwrap_err(), "Unknown include 'reviews'");
+        assert_eq!(Includes::from_query(Some("Developer")).unwrap_err(), "Unknown include 'Developer'");
+    }
+}
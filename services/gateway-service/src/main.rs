@@ -1,94 +1,187 @@
-use actix_web::{
-    App, Error, HttpMessage, HttpResponse, HttpServer,
-    dev::{ServiceRequest, ServiceResponse},
-    middleware::{self, Next},
-    web,
-};
-use serde_json;
-
-use actix_cors::Cors;
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Mutex;
-use std::time::{Duration, Instant};
-use tonic::transport::Channel;
-use uuid::Uuid;
-
-struct RateLimiter {
-    requests: Mutex<HashMap<String, Vec<Instant>>>,
-}
-
-impl RateLimiter {
-    fn new() -> Self {
-        Self {
-            requests: Mutex::new(HashMap::new()),
-        }
-    }
 
-    fn check_rate_limit(&self, ip: &str, limit: usize, window: Duration) -> bool {
-        let mut requests = self.requests.lock().unwrap();
-        let now = Instant::now();
+use actix_web::http::Method;
+use actix_web::{App, FromRequest, Handler, HttpRequest, HttpResponse, HttpServer, Responder, middleware, web};
 
-        let timestamps = requests.entry(ip.to_string()).or_insert_with(Vec::new);
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use common::models::GameStatus;
+use common::money::Money;
+use common::pagination::PageSizeConfig;
+use tonic::transport::Channel;
+use tracing_subscriber::EnvFilter;
 
-        timestamps.retain(|&t| now.duration_since(t) < window);
+use category::Category;
+use role::Role;
+use uuid::Uuid;
 
-        if timestamps.len() >= limit {
-            false
-        } else {
-            timestamps.push(now);
-            true
-        }
-    }
-}
+mod api_keys;
+mod audit;
+mod auth;
+mod category;
+mod cors;
+mod developer_profile;
+mod errors;
+mod etag;
+mod export;
+mod fields;
+mod game_page;
+mod include;
+mod events;
+mod grpc_pool;
+mod negotiate;
+mod notifications;
+mod openapi;
+mod pagination;
+mod permissions;
+mod rate_limit;
+mod releases;
+mod request_id;
+mod role;
+mod search;
+mod selfcheck;
+mod signup;
+#[cfg(test)]
+mod testing;
+mod timeout;
 
 pub mod game {
     tonic::include_proto!("game");
 }
 
+const USER_SERVICE_URL: &str = "http://[::1]:50051";
+const GAME_SERVICE_URL: &str = "http://[::1]:50052";
+/// Debug timing the game service attaches to listings, passed through as-is.
+const QUERY_TIME_KEY: &str = "x-query-time-ms";
+
 pub mod user {
     tonic::include_proto!("user");
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateUserDto {
     email: String,
     username: String,
     password: String,
-    role: String,
+    /// Defaults to the signup policy's default role.
+    #[schema(value_type = Option<String>, example = "player")]
+    role: Option<Role>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CreateUserQuery {
+    validate_only: Option<bool>,
+    /// Return the existing user instead of a 409 when the email or username
+    /// is taken.
+    upsert: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct EnsuredUserDto {
+    #[serde(flatten)]
+    user: UserDto,
+    created: bool,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ValidateOnlyQuery {
+    validate_only: Option<bool>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct FieldsQuery {
+    fields: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct UserDto {
     id: String,
-    email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
     username: String,
     role: String,
-    created_at: String,
+    /// Unix seconds; null if the user service didn't send one.
+    created_at: Option<String>,
+    /// Storefront badge; only developers can have it.
+    verified: bool,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
+struct SetVerifiedDto {
+    verified: bool,
+}
+
+#[derive(Deserialize, Default, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+enum BatchModeDto {
+    #[default]
+    AllOrNothing,
+    SkipFailed,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct BatchCreateUsersDto {
+    users: Vec<CreateUserDto>,
+    #[serde(default)]
+    mode: BatchModeDto,
+}
+
+#[derive(Serialize, ToSchema)]
+struct BatchCreateUserResultDto {
+    index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<UserDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct BatchCreateUsersResponseDto {
+    created: u32,
+    results: Vec<BatchCreateUserResultDto>,
+}
+
+#[derive(Deserialize, ToSchema)]
 struct UpdateUserDto {
     email: Option<String>,
     username: Option<String>,
     password: Option<String>,
-    role: Option<String>,
+    #[schema(value_type = Option<String>, example = "developer")]
+    role: Option<Role>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
+struct ImpersonateDto {
+    reason: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct ImpersonationDto {
+    token: String,
+    expires_at: Option<String>,
+    user: UserDto,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct ListUsersQuery {
     limit: Option<i32>,
     offset: Option<i32>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct ListUsersHttpResponse {
     users: Vec<UserDto>,
     total: i32,
 }
 
 // Game DTOs and handlers would go here similarly
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateGameDto {
     name: String,
     description: Option<String>,
@@ -101,324 +194,952 @@ struct CreateGameDto {
     platforms: Vec<String>,
     #[allow(dead_code)]
     screenshots: Vec<String>,
-    price: f64,
+    #[schema(value_type = String, example = "19.99")]
+    price: Money,
     #[allow(dead_code)]
-    status: String,
-    categories: Vec<String>,
+    #[schema(value_type = String, example = "draft")]
+    status: SelectableStatus,
+    #[schema(value_type = Vec<String>, example = json!(["rpg"]))]
+    categories: Vec<Category>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct GameDto {
     id: String,
     name: String,
-    description: Option<String>,
+    /// Empty rather than null when there is none.
+    description: String,
     developer_id: String,
+    /// The optional strings below are null, never empty, when unset.
     publisher_id: Option<String>,
-    cover_image: String,
+    cover_image: Option<String>,
     trailer_url: Option<String>,
-    release_date: String,
+    release_date: Option<String>,
     tags: Vec<String>,
     platforms: Vec<String>,
     screenshots: Vec<String>,
-    price: f64,
+    /// What the game costs now, with any running sale applied.
+    #[schema(value_type = String, example = "19.99")]
+    price: Money,
+    #[schema(value_type = String, example = "19.99")]
+    base_price: Money,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sale: Option<SaleDto>,
     status: String,
     categories: Vec<String>,
     rating_count: i32,
     average_rating: f64,
     purchase_count: i32,
-    created_at: String,
-    updated_at: String,
+    /// Unix seconds; null if the game service didn't send one.
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<String>,
 }
 
-#[derive(Deserialize)]
+/// A scheduled discount. `active` is true between `starts_at` (inclusive)
+/// and `ends_at` (exclusive), given in Unix seconds.
+#[derive(Serialize, ToSchema)]
+struct SaleDto {
+    #[schema(value_type = String, example = "9.99")]
+    price: Money,
+    starts_at: Option<String>,
+    ends_at: Option<String>,
+    active: bool,
+}
+
+/// Times are RFC 3339, e.g. `2025-11-28T00:00:00Z`.
+#[derive(Deserialize, ToSchema)]
+struct ScheduleSaleDto {
+    #[schema(value_type = String, example = "9.99")]
+    sale_price: Money,
+    starts_at: String,
+    ends_at: String,
+}
+
+#[derive(Deserialize, ToSchema)]
 struct UpdateGameDto {
     name: Option<String>,
     description: Option<String>,
-    price: Option<f64>,
+    #[schema(value_type = Option<String>, example = "19.99")]
+    price: Option<Money>,
     cover_image: Option<String>,
     tags: Option<Vec<String>>,
     platforms: Option<Vec<String>>,
     screenshots: Option<Vec<String>>,
     trailer_url: Option<String>,
-    status: Option<String>,
-    categories: Option<Vec<String>>,
+    #[schema(value_type = Option<String>, example = "published")]
+    status: Option<SelectableStatus>,
+    #[schema(value_type = Option<Vec<String>>, example = json!(["rpg"]))]
+    categories: Option<Vec<Category>>,
+    release_date: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+enum BulkPriceModeDto {
+    #[default]
+    Strict,
+    SkipFailed,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct PriceUpdateDto {
+    game_id: String,
+    #[schema(value_type = String, example = "14.99")]
+    price: Money,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct BulkSetPricesDto {
+    updates: Vec<PriceUpdateDto>,
+    #[serde(default)]
+    mode: BulkPriceModeDto,
+}
+
+#[derive(Serialize, ToSchema)]
+struct PriceUpdateResultDto {
+    index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    game: Option<GameDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct BulkSetPricesResponseDto {
+    updated: u32,
+    results: Vec<PriceUpdateResultDto>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct ListGamesQuery {
     developer_id: Option<String>,
     categories: Option<Vec<String>>,
-    min_price: Option<f64>,
-    max_price: Option<f64>,
+    #[param(value_type = Option<String>)]
+    min_price: Option<Money>,
+    #[param(value_type = Option<String>)]
+    max_price: Option<Money>,
     status: Option<String>,
     search_query: Option<String>,
     limit: Option<i32>,
     offset: Option<i32>,
     sort_by: Option<String>,
     sort_desc: Option<bool>,
+    include_deleted: Option<bool>,
+    fields: Option<String>,
+    include: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GamesByDeveloperQuery {
+    limit: Option<i32>,
+    offset: Option<i32>,
 }
 
-#[derive(Serialize)]
-struct ListGamesResponse {
-    games: Vec<GameDto>,
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct PublisherGamesQuery {
+    status: Option<String>,
+    limit: Option<i32>,
+    offset: Option<i32>,
+}
+
+/// Generic so a `?fields=` request can carry projected objects in place of
+/// full DTOs without changing the envelope.
+#[derive(Serialize, ToSchema)]
+struct ListGamesResponse<G> {
+    games: Vec<G>,
     total: i32,
 }
 
-#[derive(Deserialize)]
+/// The developer as embedded by `?include=developer` and on the game page.
+#[derive(Serialize, ToSchema)]
+struct DeveloperSummary {
+    id: String,
+    username: String,
+    verified: bool,
+}
+
+/// Embedded by `?include=ratings`, from the aggregate stored on the game.
+#[derive(Serialize, ToSchema)]
+struct RatingSummary {
+    average: f64,
+    count: i32,
+}
+
+/// `stars` has five counts, 1 star first, summing to `count`.
+#[derive(Serialize, ToSchema)]
+struct ReviewSummaryDto {
+    game_id: String,
+    average: f64,
+    count: i32,
+    stars: Vec<i32>,
+}
+
+/// A developer's non-deleted games per status; `total` is their sum.
+#[derive(Serialize, ToSchema)]
+struct StatusCountsDto {
+    developer_id: String,
+    draft: u64,
+    under_review: u64,
+    published: u64,
+    suspended: u64,
+    total: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+struct PriceHistoryEntryDto {
+    #[schema(value_type = String, example = "19.99")]
+    old_price: Money,
+    #[schema(value_type = String, example = "19.99")]
+    new_price: Money,
+    changed_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct PriceHistoryResponse {
+    game_id: String,
+    entries: Vec<PriceHistoryEntryDto>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct CatalogStatsDto {
+    published_games: u64,
+    developers: u64,
+    newest_release: Option<GameDto>,
+    most_purchased: Option<GameDto>,
+}
+
+/// Prices are null when the category has no published games.
+#[derive(Serialize, ToSchema)]
+struct CategoryPriceStatsDto {
+    category: String,
+    game_count: u64,
+    #[schema(value_type = Option<String>, example = "19.99")]
+    min_price: Option<Money>,
+    #[schema(value_type = Option<String>, example = "19.99")]
+    max_price: Option<Money>,
+    #[schema(value_type = Option<String>, example = "19.99")]
+    avg_price: Option<Money>,
+    #[schema(value_type = Option<String>, example = "19.99")]
+    median_price: Option<Money>,
+}
+
+/// The `list_games` filters plus the bucket edges; paging, sorting and
+/// projection don't apply to facet counts.
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct PriceFacetsQuery {
+    developer_id: Option<String>,
+    categories: Option<Vec<String>>,
+    #[param(value_type = Option<String>)]
+    min_price: Option<Money>,
+    #[param(value_type = Option<String>)]
+    max_price: Option<Money>,
+    status: Option<String>,
+    search_query: Option<String>,
+    include_deleted: Option<bool>,
+    /// Comma-separated ascending bucket edges, e.g. `10,20,40`.
+    buckets: Option<String>,
+}
+
+/// A bucket includes `min_price` and excludes `max_price`; either is null
+/// where the bucket is open-ended.
+#[derive(Serialize, ToSchema)]
+struct PriceFacetDto {
+    #[schema(value_type = Option<String>, example = "10.00")]
+    min_price: Option<Money>,
+    #[schema(value_type = Option<String>, example = "20.00")]
+    max_price: Option<Money>,
+    count: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+struct PriceFacetsResponse {
+    facets: Vec<PriceFacetDto>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct SuggestQuery {
+    q: String,
+    limit: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct GameSuggestionDto {
+    id: String,
+    name: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct SuggestResponse {
+    suggestions: Vec<GameSuggestionDto>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct RecomputeStatsQuery {
+    game_id: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct RecomputeStatsResponse {
+    games_corrected: u64,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct DraftExpiryQuery {
+    older_than_days: Option<u32>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct StaleDraftDto {
+    id: String,
+    name: String,
+    developer_id: String,
+    updated_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct DraftExpiryPreviewResponse {
+    cutoff: Option<String>,
+    drafts: Vec<StaleDraftDto>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct PurgeQuery {
+    /// Defaults to the game service's `PURGE_RETENTION_DAYS`.
+    older_than_days: Option<u32>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct PurgeResponse {
+    purged: u64,
+    cutoff: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
 struct DeleteGameDto {
     developer_id: String,
 }
 
 struct AppState {
-    user_client: user::user_service_client::UserServiceClient<Channel>,
-    game_client: game::game_service_client::GameServiceClient<Channel>,
+    user_channels: grpc_pool::ChannelPool,
+    game_channels: grpc_pool::ChannelPool,
+}
+
+impl AppState {
+    /// A client on the next pooled channel; take one per request.
+    fn user_client(&self) -> user::user_service_client::UserServiceClient<Channel> {
+        user::user_service_client::UserServiceClient::new(self.user_channels.channel())
+    }
+
+    fn game_client(&self) -> game::game_service_client::GameServiceClient<Channel> {
+        game::game_service_client::GameServiceClient::new(self.game_channels.channel())
+    }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "users",
+    params(CreateUserQuery),
+    request_body = CreateUserDto,
+    responses(
+        (status = 200, description = "The created user. With `upsert` it may be an existing one and carries `created`; with `validate_only` the body is `{\"valid\": true}`", body = UserDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 409, description = "Email or username already taken; `code` is `EMAIL_TAKEN` or `USERNAME_TAKEN` and `details.field` names the field", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
 async fn create_user(
+    req: HttpRequest,
     data: web::Data<AppState>,
+    signup: web::Data<signup::SignupPolicy>,
+    query: web::Query<CreateUserQuery>,
     json: web::Json<CreateUserDto>,
 ) -> Result<HttpResponse, actix_web::Error> {
-    let role = match json.role.as_str() {
-        "player" => 0,
-        "developer" => 1,
-        "admin" => 2,
-        _ => {
+    let dry_run = query.validate_only.unwrap_or(false);
+    let upsert = query.upsert.unwrap_or(false);
+
+    let role = json.role.unwrap_or(signup.default_role);
+    if let Err(response) = signup.check(&req, role) {
+        return Ok(response);
+    }
+    let role = role.to_proto();
+
+    if upsert {
+        if dry_run {
             return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                "error": "Invalid role"
+                "error": "upsert cannot be combined with validate_only"
             })));
         }
-    };
+        return ensure_user(req, data, json.into_inner(), role).await;
+    }
 
-    let request = tonic::Request::new(user::CreateUserRequest {
+    let request = request_id::grpc_request(&req, user::CreateUserRequest {
         email: json.email.clone(),
         username: json.username.clone(),
         password: json.password.clone(),
         role,
+        dry_run,
     });
 
-    let mut client = data.user_client.clone();
+    let mut client = data.user_client();
     match client.create_user(request).await {
+        Ok(_) if dry_run => Ok(HttpResponse::Ok().json(serde_json::json!({ "valid": true }))),
         Ok(response) => {
             let user = response.into_inner();
 
-            let user_dto = UserDto {
-                id: user.id,
-                email: user.email,
-                username: user.username,
-                role: proto_role_to_string(user.role),
-                created_at: user
-                    .created_at
-                    .map(|ts| format!("{}", ts.seconds))
-                    .unwrap_or_default(),
-            };
+            let user_dto = user_to_dto(user, true);
 
             Ok(HttpResponse::Ok().json(user_dto))
         }
-        Err(status) => match status.code() {
-            tonic::Code::InvalidArgument => {
-                Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                    "error": status.message()
-                })))
-            }
-            tonic::Code::AlreadyExists => Ok(HttpResponse::Conflict().json(serde_json::json!({
-                "error": "User with this email or username already exists"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": status.message()
-            }))),
-        },
+        Err(status) if dry_run && is_validation_failure(&status) => {
+            Ok(HttpResponse::BadRequest().json(errors::error_envelope(&status, status.message())))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
     }
 }
 
-async fn get_user(
+/// Create-or-get for provisioning. Admin-only: answering with an existing
+/// account would otherwise tell anyone which emails are registered.
+async fn ensure_user(
+    req: HttpRequest,
     data: web::Data<AppState>,
-    path: web::Path<String>,
+    json: CreateUserDto,
+    role: i32,
 ) -> Result<HttpResponse, actix_web::Error> {
-    let user_id = path.into_inner();
+    match auth::principal(&req) {
+        Some(auth::Principal::User(claims)) if claims.has_role(Role::Admin) => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Only admins can upsert users"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
+    }
 
-    let request = tonic::Request::new(user::GetUserRequest { id: user_id });
+    let request = request_id::grpc_request(&req, user::CreateUserRequest {
+        email: json.email,
+        username: json.username,
+        password: json.password,
+        role,
+        dry_run: false,
+    });
 
-    let mut client = data.user_client.clone();
-    match client.get_user(request).await {
+    let mut client = data.user_client();
+    match client.ensure_user(request).await {
         Ok(response) => {
             let resp = response.into_inner();
-            if let Some(user) = resp.user {
-                let user_dto = UserDto {
-                    id: user.id,
-                    email: user.email,
-                    username: user.username,
-                    role: proto_role_to_string(user.role),
-                    created_at: user
-                        .created_at
-                        .map(|ts| format!("{}", ts.seconds))
-                        .unwrap_or_default(),
-                };
-                Ok(HttpResponse::Ok().json(user_dto))
-            } else {
-                Ok(HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "User not found"
-                })))
-            }
+            let Some(user) = resp.user else {
+                return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Server returned empty response"
+                })));
+            };
+
+            Ok(HttpResponse::Ok().json(EnsuredUserDto {
+                user: user_to_dto(user, true),
+                created: resp.created,
+            }))
         }
-        Err(status) => match status.code() {
-            tonic::Code::NotFound => Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "error": "User not found"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": status.message()
-            }))),
-        },
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
     }
 }
 
-async fn update_user(
+/// Admin-only bulk import. Row-level failures come back in `results`; with
+/// the default `all_or_nothing` mode a single failure means nothing is created.
+#[utoipa::path(
+    post,
+    path = "/api/users/batch",
+    tag = "users",
+    request_body = BatchCreateUsersDto,
+    responses(
+        (status = 200, description = "Per-user outcome, in request order", body = BatchCreateUsersResponseDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn batch_create_users(
+    req: HttpRequest,
     data: web::Data<AppState>,
-    path: web::Path<String>,
-    json: web::Json<UpdateUserDto>,
+    signup: web::Data<signup::SignupPolicy>,
+    json: web::Json<BatchCreateUsersDto>,
 ) -> Result<HttpResponse, actix_web::Error> {
-    let user_id = path.into_inner();
-
-    if uuid::Uuid::parse_str(&user_id).is_err() {
-        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Invalid user ID format"
-        })));
+    match auth::principal(&req) {
+        Some(auth::Principal::User(claims)) if claims.has_role(Role::Admin) => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Only admins can import users"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
     }
 
-    let role = if let Some(role_str) = &json.role {
-        match role_str.as_str() {
-            "player" => Some(0),
-            "developer" => Some(1),
-            "admin" => Some(2),
-            _ => {
-                return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                    "error": "Invalid role. Must be: player, developer, or admin"
-                })));
-            }
-        }
-    } else {
-        None
+    let batch = json.into_inner();
+    let users = batch.users.into_iter().map(|user| user::CreateUserRequest {
+        email: user.email,
+        username: user.username,
+        password: user.password,
+        role: user.role.unwrap_or(signup.default_role).to_proto(),
+        dry_run: false,
+    }).collect();
+
+    let mode = match batch.mode {
+        BatchModeDto::AllOrNothing => user::BatchConflictMode::AllOrNothing,
+        BatchModeDto::SkipFailed => user::BatchConflictMode::SkipFailed,
     };
 
-    let request = tonic::Request::new(user::UpdateUserRequest {
-        id: user_id,
-        email: json.email.clone(),
-        username: json.username.clone(),
-        password: json.password.clone(),
-        role,
+    let request = request_id::grpc_request(&req, user::BatchCreateUsersRequest {
+        users,
+        mode: mode as i32,
     });
 
-    let mut client = data.user_client.clone();
-    match client.update_user(request).await {
+    let mut client = data.user_client();
+    match client.batch_create_users(request).await {
         Ok(response) => {
             let resp = response.into_inner();
+            let results = resp
+                .results
+                .into_iter()
+                .map(|result| BatchCreateUserResultDto {
+                    index: result.index,
+                    user: result.user.map(|user| user_to_dto(user, true)),
+                    error: result.error,
+                    code: result.error_code,
+                })
+                .collect();
 
-            match resp.user {
-                Some(user) => {
-                    let user_dto = UserDto {
-                        id: user.id,
-                        email: user.email,
-                        username: user.username,
-                        role: proto_role_to_string(user.role),
-                        created_at: user
-                            .created_at
-                            .map(|ts| format!("{}", ts.seconds))
-                            .unwrap_or_default(),
-                    };
-                    Ok(HttpResponse::Ok().json(user_dto))
-                }
-                None => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                    "error": "Server returned empty response"
-                }))),
-            }
+            Ok(HttpResponse::Ok().json(BatchCreateUsersResponseDto {
+                created: resp.created_count,
+                results,
+            }))
         }
-        Err(status) => match status.code() {
-            tonic::Code::NotFound => Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "error": "User not found"
-            }))),
-            tonic::Code::InvalidArgument => {
-                Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                    "error": status.message()
-                })))
-            }
-            tonic::Code::AlreadyExists => Ok(HttpResponse::Conflict().json(serde_json::json!({
-                "error": "Email or username already taken"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Internal error: {}", status.message())
-            }))),
-        },
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
     }
 }
 
-async fn delete_user(
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/impersonate",
+    tag = "users",
+    params(("id" = String, Path, description = "User ID")),
+    request_body = ImpersonateDto,
+    responses(
+        (status = 200, description = "A short-lived token acting as the user", body = ImpersonationDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 429, description = "Too many attempts", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn impersonate_user(
+    req: HttpRequest,
     data: web::Data<AppState>,
     path: web::Path<String>,
+    json: web::Json<ImpersonateDto>,
 ) -> Result<HttpResponse, actix_web::Error> {
-    let user_id = path.into_inner();
-
-    let request = tonic::Request::new(user::DeleteUserRequest { id: user_id });
-
-    let mut client = data.user_client.clone();
-    match client.delete_user(request).await {
-        Ok(_) => Ok(HttpResponse::Ok().json(serde_json::json!({
-            "message": "User deleted successfully"
-        }))),
-        Err(status) => match status.code() {
-            tonic::Code::NotFound => Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "error": "User not found"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": status.message()
-            }))),
-        },
+    match auth::principal(&req) {
+        Some(auth::Principal::User(claims)) if claims.act_as.is_some() => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Cannot start an impersonation while impersonating"
+            })));
+        }
+        Some(auth::Principal::User(claims)) if claims.has_role(Role::Admin) => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Only admins can impersonate users"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
     }
-}
 
-async fn users_list(
-    data: web::Data<AppState>,
-    query: web::Query<ListUsersQuery>,
-) -> Result<HttpResponse, actix_web::Error> {
-    let request = tonic::Request::new(user::ListUsersRequest {
-        limit: query.limit.unwrap_or(50),
-        offset: query.offset.unwrap_or(0),
-        role: None,
+    let request = auth::grpc_request(&req, user::ImpersonateRequest {
+        user_id: path.into_inner(),
+        reason: json.into_inner().reason,
     });
 
-    let mut client = data.user_client.clone();
-    match client.list_users(request).await {
+    let mut client = data.user_client();
+    match client.impersonate(request).await {
         Ok(response) => {
             let resp = response.into_inner();
+            let Some(user) = resp.user else {
+                return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Server returned empty response"
+                })));
+            };
 
-            let user_dtos: Vec<UserDto> = resp
-                .users
-                .into_iter()
-                .map(|user| UserDto {
-                    id: user.id,
-                    email: user.email,
-                    username: user.username,
-                    role: proto_role_to_string(user.role),
-                    created_at: user
-                        .created_at
-                        .map(|ts| format!("{}", ts.seconds))
-                        .unwrap_or_default(),
-                })
-                .collect();
-
-            Ok(HttpResponse::Ok().json(ListUsersHttpResponse {
-                users: user_dtos,
-                total: resp.total,
+            Ok(HttpResponse::Ok().json(ImpersonationDto {
+                token: resp.token,
+                expires_at: epoch_seconds(resp.expires_at),
+                user: user_to_dto(user, true),
             }))
         }
-        Err(status) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": status.message()
-        }))),
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "User not found")])),
     }
 }
 
-async fn create_game(
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/revoke",
+    tag = "users",
+    params(("id" = String, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "Every token issued so far is rejected from now on"),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn revoke_user_sessions(
+    req: HttpRequest,
     data: web::Data<AppState>,
-    json: web::Json<CreateGameDto>,
+    path: web::Path<String>,
 ) -> Result<HttpResponse, actix_web::Error> {
+    match auth::principal(&req) {
+        Some(auth::Principal::User(claims)) if claims.has_role(Role::Admin) && claims.act_as.is_none() => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Only admins can revoke sessions"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
+    }
+
+    let request = auth::grpc_request(&req, user::RevokeSessionsRequest {
+        user_id: path.into_inner(),
+    });
+
+    let mut client = data.user_client();
+    match client.revoke_sessions(request).await {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "User not found")])),
+    }
+}
+
+/// `PUT /api/users/{id}/verified` - admins grant or withdraw a developer's
+/// verified badge.
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/verified",
+    tag = "users",
+    params(("id" = String, Path, description = "User ID")),
+    request_body = SetVerifiedDto,
+    responses(
+        (status = 200, description = "The user with the new badge state", body = UserDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 422, description = "Only developers can be verified", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn set_developer_verified(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    json: web::Json<SetVerifiedDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    match auth::principal(&req) {
+        Some(auth::Principal::User(claims)) if claims.has_role(Role::Admin) && claims.act_as.is_none() => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Only admins can verify developers"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
+    }
+
+    let request = auth::grpc_request(&req, user::SetDeveloperVerifiedRequest {
+        user_id: path.into_inner(),
+        verified: json.verified,
+    });
+
+    let mut client = data.user_client();
+    match client.set_developer_verified(request).await {
+        Ok(response) => match response.into_inner().user {
+            Some(user) => Ok(HttpResponse::Ok().json(user_to_dto(user, true))),
+            None => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Server returned empty response"
+            }))),
+        },
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "User not found")])),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = String, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user; `email` only for the user themselves or an admin", body = UserDto),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn get_user(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let user_id = path.into_inner();
+
+    let request = request_id::grpc_request(&req, user::GetUserRequest { id: user_id });
+
+    let mut client = data.user_client();
+    match client.get_user(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            if let Some(user) = resp.user {
+                let include_email = auth::can_view_private(&req, &user.id);
+                Ok(negotiate::ok(&req, &user_to_dto(user, include_email)))
+            } else {
+                Ok(HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "User not found"
+                })))
+            }
+        }
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "User not found")])),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = String, Path, description = "User ID")),
+    request_body = UpdateUserDto,
+    responses(
+        (status = 200, description = "The updated user", body = UserDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 409, description = "Email or username already taken; `code` is `EMAIL_TAKEN` or `USERNAME_TAKEN` and `details.field` names the field", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn update_user(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    json: web::Json<UpdateUserDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let user_id = path.into_inner();
+
+    let is_admin = match auth::principal(&req) {
+        Some(auth::Principal::User(claims)) => {
+            let is_admin = claims.has_role(Role::Admin);
+            if !is_admin && claims.sub != user_id {
+                return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                    "error": "You can only update your own account"
+                })));
+            }
+            is_admin
+        }
+        _ => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
+    };
+
+    let role = json.role.map(Role::to_proto);
+
+    // Roles are granted, never self-assigned.
+    if role.is_some() && !is_admin {
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Only admins can change a user's role"
+        })));
+    }
+
+    // Credentials stay with the real account holder, whoever support is
+    // acting as.
+    if auth::impersonator(&req).is_some() && (json.email.is_some() || json.password.is_some()) {
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Email and password cannot be changed while impersonating"
+        })));
+    }
+
+    if uuid::Uuid::parse_str(&user_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid user ID format"
+        })));
+    }
+
+    let request = request_id::grpc_request(&req, user::UpdateUserRequest {
+        id: user_id,
+        email: json.email.clone(),
+        username: json.username.clone(),
+        password: json.password.clone(),
+        role,
+    });
+
+    let mut client = data.user_client();
+    match client.update_user(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+
+            match resp.user {
+                Some(user) => {
+                    let include_email = auth::can_view_private(&req, &user.id);
+                    Ok(HttpResponse::Ok().json(user_to_dto(user, include_email)))
+                }
+                None => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Server returned empty response"
+                }))),
+            }
+        }
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "User not found")])),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = String, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "Deleted; `x-already-absent: true` when there was nothing to delete"),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn delete_user(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let user_id = path.into_inner();
+
+    let request = request_id::grpc_request(&req, user::DeleteUserRequest { id: user_id });
+
+    let mut client = data.user_client();
+    match client.delete_user(request).await {
+        Ok(response) => Ok(deleted_response(response.into_inner().already_absent)),
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    params(ListUsersQuery),
+    responses(
+        (status = 200, description = "One page of users", body = ListUsersHttpResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn users_list(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
+    query: web::Query<ListUsersQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let limit = match page_size.resolve(query.limit) {
+        Ok(limit) => limit,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let request = request_id::grpc_request(&req, user::ListUsersRequest {
+        limit,
+        offset,
+        role: None,
+    });
+
+    let mut client = data.user_client();
+    match client.list_users(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+
+            let user_dtos: Vec<UserDto> = resp
+                .users
+                .into_iter()
+                .map(|user| {
+                    let include_email = auth::can_view_private(&req, &user.id);
+                    user_to_dto(user, include_email)
+                })
+                .collect();
+
+            let mut http_response = etag::negotiated_with_etag(
+                &req,
+                &ListUsersHttpResponse {
+                    users: user_dtos,
+                    total: resp.total,
+                },
+            );
+            pagination::insert_headers(&mut http_response, &req, limit, offset, resp.total.into());
+
+            Ok(http_response)
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/games",
+    tag = "games",
+    params(ValidateOnlyQuery),
+    request_body = CreateGameDto,
+    responses(
+        (status = 200, description = "The created game; with `validate_only` the body is `{\"valid\": true}`", body = GameDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 409, description = "A game with this name already exists", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn create_game(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    query: web::Query<ValidateOnlyQuery>,
+    json: web::Json<CreateGameDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let dry_run = query.validate_only.unwrap_or(false);
+
     let developer_id = match Uuid::parse_str(&json.developer_id) {
         Ok(uuid) => uuid.to_string(),
         Err(_) => {
@@ -428,161 +1149,394 @@ async fn create_game(
         }
     };
 
-    let request = tonic::Request::new(game::CreateGameRequest {
+    if auth::api_key_owner(&req).is_some_and(|owner| owner != developer_id) {
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "API keys can only create games for their own developer"
+        })));
+    }
+
+    let request = auth::grpc_request(&req, game::CreateGameRequest {
         name: json.name.clone(),
         description: json.description.clone().unwrap_or_default(),
         developer_id,
+        // The game service checks that it names an existing user
         publisher_id: json.publisher_id.clone(),
         cover_image: json.cover_image.clone().unwrap_or_default(),
         trailer_url: json.trailer_url.clone(),
-        release_date: json.release_date.clone().unwrap_or_default(),
+        release_date: json.release_date.clone(),
         tags: json.tags.clone(),
         platforms: json.platforms.clone(),
-        price: json.price as i64,
-        categories: json.categories.iter().map(|cat| match cat.as_str() {
-            "action" => 1,
-            "rpg" => 2,
-            "strategy" => 3,
-            "sports" => 4,
-            "racing" => 5,
-            "adventure" => 6,
-            "simulation" => 7,
-            "puzzle" => 8,
-            _ => 0, // unspecified
-        }).collect(),
+        price: json.price.into(),
+        categories: json.categories.iter().map(|&cat| cat.to_proto()).collect(),
+        dry_run,
     });
 
-    let mut client = data.game_client.clone();
+    let mut client = data.game_client();
     match client.create_game(request).await {
+        Ok(_) if dry_run => Ok(HttpResponse::Ok().json(serde_json::json!({ "valid": true }))),
         Ok(response) => {
             let game = response.into_inner();
-            let game_dto = GameDto {
-                id: game.id,
-                name: game.name,
-                description: game.description,
-                developer_id: game.developer_id,
-                publisher_id: game.publisher_id,
-                cover_image: game.cover_image.unwrap_or_default(),
-                trailer_url: game.trailer_url,
-                release_date: game.release_date.unwrap_or_default(),
-                tags: game.tags,
-                platforms: game.platforms,
-                screenshots: game.screenshots,
-                price: game.price as f64,
-                status: match game.status {
-                    0 => "unspecified".to_string(),
-                    1 => "draft".to_string(),
-                    2 => "under_review".to_string(),
-                    3 => "published".to_string(),
-                    4 => "suspended".to_string(),
-                    _ => "unknown".to_string(),
-                },
-                categories: game.categories.iter().map(|&cat| match cat {
-                    1 => "action".to_string(),
-                    2 => "rpg".to_string(),
-                    3 => "strategy".to_string(),
-                    4 => "sports".to_string(),
-                    5 => "racing".to_string(),
-                    6 => "adventure".to_string(),
-                    7 => "simulation".to_string(),
-                    8 => "puzzle".to_string(),
-                    _ => "unspecified".to_string(),
-                }).collect(),
-                rating_count: game.rating_count as i32,
-                average_rating: game.average_rating,
-                purchase_count: game.purchase_count as i32,
-                created_at: game.created_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
-                updated_at: game.updated_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
-            };
+            let game_dto = game_to_dto(game);
             Ok(HttpResponse::Ok().json(game_dto))
         }
-        Err(status) => match status.code() {
-            tonic::Code::InvalidArgument => {
-                Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                    "error": status.message()
-                })))
-            }
-            tonic::Code::AlreadyExists => Ok(HttpResponse::Conflict().json(serde_json::json!({
-                "error": "Game with this name already exists"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": status.message()
-            }))),
-        },
+        Err(status) if dry_run && is_validation_failure(&status) => {
+            Ok(HttpResponse::BadRequest().json(errors::error_envelope(&status, status.message())))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http_with(
+            &status,
+            &[(tonic::Code::AlreadyExists, "Game with this name already exists")],
+        )),
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID"), FieldsQuery),
+    responses(
+        (status = 200, description = "The game, projected to `fields` when given", body = GameDto),
+        (status = 304, description = "Matches `If-None-Match`"),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
 async fn get_game(
+    req: HttpRequest,
     data: web::Data<AppState>,
     path: web::Path<String>,
+    query: web::Query<FieldsQuery>,
 ) -> Result<HttpResponse, actix_web::Error> {
     let game_id = path.into_inner();
+    let projection = match fields::Projection::from_query(query.fields.as_deref(), fields::GAME_FIELDS) {
+        Ok(projection) => projection,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
 
-    let request = tonic::Request::new(game::GetGameRequest { id: game_id });
+    let request = request_id::grpc_request(&req, game::GetGameRequest { id: game_id });
 
-    let mut client = data.game_client.clone();
+    let mut client = data.game_client();
     match client.get_game(request).await {
         Ok(response) => {
             let resp = response.into_inner();
             if let Some(game) = resp.game {
-                let game_dto = GameDto {
-                    id: game.id,
-                    name: game.name,
-                    description: game.description,
-                    developer_id: game.developer_id,
-                    publisher_id: game.publisher_id,
-                    cover_image: game.cover_image.unwrap_or_default(),
-                    trailer_url: game.trailer_url,
-                    release_date: game.release_date.unwrap_or_default(),
-                    tags: game.tags,
-                    platforms: game.platforms,
-                    screenshots: game.screenshots,
-                    price: game.price as f64,
-                    status: match game.status {
-                        0 => "unspecified".to_string(),
-                        1 => "draft".to_string(),
-                        2 => "under_review".to_string(),
-                        3 => "published".to_string(),
-                        4 => "suspended".to_string(),
-                        _ => "unknown".to_string(),
+                let format = negotiate::Format::from_request(&req);
+                let etag = etag::game_etag(
+                    &game.id,
+                    game.updated_at.as_ref(),
+                    game.price,
+                    format,
+                    projection.as_ref(),
+                );
+                if etag::if_none_match(&req, &etag) {
+                    return Ok(etag::not_modified(&etag));
+                }
+
+                let game_dto = game_to_dto(game);
+                let mut http_response = match &projection {
+                    Some(projection) => match projection.apply(&game_dto) {
+                        Ok(projected) => negotiate::ok(&req, &projected),
+                        Err(e) => return Ok(negotiate::serialization_error(e)),
                     },
-                    categories: game.categories.iter().map(|&cat| match cat {
-                        1 => "action".to_string(),
-                        2 => "rpg".to_string(),
-                        3 => "strategy".to_string(),
-                        4 => "sports".to_string(),
-                        5 => "racing".to_string(),
-                        6 => "adventure".to_string(),
-                        7 => "simulation".to_string(),
-                        8 => "puzzle".to_string(),
-                        _ => "unspecified".to_string(),
-                    }).collect(),
-                    rating_count: game.rating_count as i32,
-                    average_rating: game.average_rating,
-                    purchase_count: game.purchase_count as i32,
-                    created_at: game.created_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
-                    updated_at: game.updated_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
+                    None => negotiate::ok(&req, &game_dto),
                 };
-                Ok(HttpResponse::Ok().json(game_dto))
+                if http_response.status().is_success() {
+                    http_response.headers_mut().insert(
+                        actix_web::http::header::ETAG,
+                        actix_web::http::header::HeaderValue::from_str(&etag).unwrap(),
+                    );
+                }
+                Ok(http_response)
             } else {
                 Ok(HttpResponse::NotFound().json(serde_json::json!({
                     "error": "Game not found"
                 })))
             }
         }
-        Err(status) => match status.code() {
-            tonic::Code::NotFound => Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "error": "Game not found"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": status.message()
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")])),
+    }
+}
+
+/// The public profile of a game's developer, with email only for the
+/// developer themselves and admins.
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}/developer",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "The game's developer", body = UserDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn game_developer(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let request = request_id::grpc_request(&req, game::GetGameRequest { id: path.into_inner() });
+
+    let mut game_client = data.game_client();
+    let developer_id = match game_client.get_game(request).await {
+        Ok(response) => match response.into_inner().game {
+            Some(game) => game.developer_id,
+            None => {
+                return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Game not found"
+                })));
+            }
+        },
+        Err(status) => {
+            return Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")]));
+        }
+    };
+
+    let include_email = auth::can_view_private(&req, &developer_id);
+    let request = request_id::grpc_request(&req, user::GetUserRequest { id: developer_id });
+
+    let mut user_client = data.user_client();
+    match user_client.get_user(request).await {
+        Ok(response) => match response.into_inner().user {
+            Some(user) => Ok(negotiate::ok(&req, &user_to_dto(user, include_email))),
+            None => Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Developer not found"
             }))),
         },
-        
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Developer not found")])),
+    }
+}
+
+/// "More from this developer": the developer's other published games,
+/// newest first, without the game being viewed.
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}/by-developer",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID"), GamesByDeveloperQuery),
+    responses(
+        (status = 200, description = "Other published games by the same developer", body = ListGamesResponse<GameDto>),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn games_by_developer(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
+    path: web::Path<String>,
+    query: web::Query<GamesByDeveloperQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let game_id = path.into_inner();
+    let limit = match page_size.resolve(query.limit) {
+        Ok(limit) => limit,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let request = request_id::grpc_request(&req, game::GetGameRequest { id: game_id.clone() });
+
+    let mut client = data.game_client();
+    let developer_id = match client.get_game(request).await {
+        Ok(response) => match response.into_inner().game {
+            Some(game) => game.developer_id,
+            None => {
+                return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Game not found"
+                })));
+            }
+        },
+        Err(status) => {
+            return Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")]));
+        }
+    };
+
+    let request = request_id::grpc_request(&req, game::ListGamesRequest {
+        developer_id: Some(developer_id),
+        status: Some(GameStatus::Published.to_proto()),
+        page_size: limit,
+        page_token: offset.to_string(),
+        exclude_id: Some(game_id),
+        ..Default::default()
+    });
+
+    match client.list_games(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            let games: Vec<GameDto> = resp.games.into_iter().map(game_to_dto).collect();
+
+            let mut http_response = negotiate::ok(
+                &req,
+                &ListGamesResponse { games, total: resp.total_count as i32 },
+            );
+            pagination::insert_headers(&mut http_response, &req, limit, offset, resp.total_count as i64);
+
+            Ok(http_response)
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+/// A publisher's portfolio. Everyone sees the published games; the
+/// publisher and admins see every status and may filter by `status`.
+#[utoipa::path(
+    get,
+    path = "/api/publishers/{id}/games",
+    tag = "publishers",
+    params(("id" = String, Path, description = "Publisher ID"), PublisherGamesQuery),
+    responses(
+        (status = 200, description = "One page of the publisher's games", body = ListGamesResponse<GameDto>),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn publisher_games(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
+    path: web::Path<String>,
+    query: web::Query<PublisherGamesQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let publisher_id = match Uuid::parse_str(&path.into_inner()) {
+        Ok(uuid) => uuid.to_string(),
+        Err(_) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid publisher ID format"
+            })));
+        }
+    };
+    let limit = match page_size.resolve(query.limit) {
+        Ok(limit) => limit,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
+    let offset = query.offset.unwrap_or(0).max(0);
+    let status = match parse_status_param(query.status.as_deref()) {
+        Ok(status) => status,
+        Err(response) => return Ok(response),
+    };
+
+    let published = GameStatus::Published.to_proto();
+    let status = if auth::can_view_private(&req, &publisher_id) {
+        status
+    } else if status.is_none_or(|s| s == published) {
+        Some(published)
+    } else {
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Only the publisher or an admin can list unpublished games"
+        })));
+    };
+
+    let request = auth::grpc_request(&req, game::ListGamesRequest {
+        publisher_id: Some(publisher_id),
+        status,
+        page_size: limit,
+        page_token: offset.to_string(),
+        ..Default::default()
+    });
+
+    let mut client = data.game_client();
+    match client.list_games(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            let games: Vec<GameDto> = resp.games.into_iter().map(game_to_dto).collect();
+
+            let mut http_response = negotiate::ok(
+                &req,
+                &ListGamesResponse { games, total: resp.total_count as i32 },
+            );
+            pagination::insert_headers(&mut http_response, &req, limit, offset, resp.total_count as i64);
+
+            Ok(http_response)
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
     }
 }
 
+/// Sets many prices at once, e.g. to start or end a sale. Only the caller's
+/// own games can be changed unless they are an admin. In the default
+/// `strict` mode one bad entry means no price changes; `skip_failed` applies
+/// the rest. Per-entry outcomes come back in `results`.
+#[utoipa::path(
+    post,
+    path = "/api/games/prices/bulk",
+    tag = "games",
+    request_body = BulkSetPricesDto,
+    responses(
+        (status = 200, description = "Per-game outcome, in request order", body = BulkSetPricesResponseDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn bulk_set_prices(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    json: web::Json<BulkSetPricesDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let batch = json.into_inner();
+    let mode = match batch.mode {
+        BulkPriceModeDto::Strict => game::BulkPriceMode::Strict,
+        BulkPriceModeDto::SkipFailed => game::BulkPriceMode::SkipFailed,
+    };
+
+    let request = auth::grpc_request(&req, game::BulkSetPricesRequest {
+        updates: batch
+            .updates
+            .into_iter()
+            .map(|update| game::PriceUpdate {
+                game_id: update.game_id,
+                price: i64::from(update.price),
+            })
+            .collect(),
+        mode: mode as i32,
+    });
+
+    let mut client = data.game_client();
+    match client.bulk_set_prices(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            let results = resp
+                .results
+                .into_iter()
+                .map(|result| PriceUpdateResultDto {
+                    index: result.index,
+                    game: result.game.map(game_to_dto),
+                    error: result.error,
+                    code: result.error_code,
+                })
+                .collect();
+
+            Ok(HttpResponse::Ok().json(BulkSetPricesResponseDto {
+                updated: resp.updated_count,
+                results,
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/games/{id}",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    request_body = UpdateGameDto,
+    responses(
+        (status = 200, description = "The updated game", body = GameDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 422, description = "Publishing while required assets are missing; `details.missing` lists them", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
 async fn update_game(
+    req: HttpRequest,
     data: web::Data<AppState>,
     path: web::Path<String>,
     json: web::Json<UpdateGameDto>,
@@ -595,39 +1549,16 @@ async fn update_game(
         })));
     }
 
-    let status = match json.status.as_deref() {
-        Some("draft") => Some(1),
-        Some("under_review") => Some(2),
-        Some("published") => Some(3),
-        Some("suspended") => Some(4),
-        Some("unspecified") => Some(0),
-        None => None,
-        Some(_) => {
-            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                "error": "Invalid status. Must be: draft, under_review, published, suspended, or unspecified"
-            })));
-        }
-    };
-
-    let categories = json.categories.as_ref().map(|cats| 
-        cats.iter().map(|cat| match cat.as_str() {
-            "action" => 1,
-            "rpg" => 2,
-            "strategy" => 3,
-            "sports" => 4,
-            "racing" => 5,
-            "adventure" => 6,
-            "simulation" => 7,
-            "puzzle" => 8,
-            _ => 0, // unspecified
-        }).collect()
+    let status = json.status.as_ref().map(|status| status.0.to_proto());
+    let categories = json.categories.as_ref().map(|cats|
+        cats.iter().map(|&cat| cat.to_proto()).collect()
     ).unwrap_or_default();
 
-    let request = tonic::Request::new(game::UpdateGameRequest {
+    let request = auth::grpc_request(&req, game::UpdateGameRequest {
         id: game_id,
         name: json.name.clone(),
         description: json.description.clone(),
-        price: json.price.map(|p| p as i64),
+        price: json.price.map(i64::from),
         cover_image: json.cover_image.clone(),
         tags: json.tags.clone().unwrap_or_default(),
         platforms: json.platforms.clone().unwrap_or_default(),
@@ -635,73 +1566,43 @@ async fn update_game(
         trailer_url: json.trailer_url.clone(),
         status,
         categories,
+        release_date: json.release_date.clone(),
     });
 
-    let mut client = data.game_client.clone();
+    let mut client = data.game_client();
     match client.update_game(request).await {
         Ok(response) => {
             let game = response.into_inner();
-            let game_dto = GameDto {
-                id: game.id,
-                name: game.name,
-                description: game.description,
-                developer_id: game.developer_id,
-                publisher_id: game.publisher_id,
-                cover_image: game.cover_image.unwrap_or_default(),
-                trailer_url: game.trailer_url,
-                release_date: game.release_date.unwrap_or_default(),
-                tags: game.tags,
-                platforms: game.platforms,
-                screenshots: game.screenshots,
-                price: game.price as f64,
-                status: match game.status {
-                    0 => "unspecified".to_string(), 
-                    1 => "draft".to_string(),
-                    2 => "under_review".to_string(),
-                    3 => "published".to_string(),
-                    4 => "suspended".to_string(),
-                    _ => "unknown".to_string(),
-                },
-                categories: game.categories.iter().map(|&cat| match cat {
-                    1 => "action".to_string(),
-                    2 => "rpg".to_string(),
-                    3 => "strategy".to_string(),
-                    4 => "sports".to_string(),
-                    5 => "racing".to_string(),
-                    6 => "adventure".to_string(),
-                    7 => "simulation".to_string(),
-                    8 => "puzzle".to_string(),
-                    _ => "unspecified".to_string(),
-                }).collect(),
-                rating_count: game.rating_count as i32,
-                average_rating: game.average_rating,
-                purchase_count: game.purchase_count as i32,
-                created_at: game.created_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
-                updated_at: game.updated_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
-            };
+            let game_dto = game_to_dto(game);
             Ok(HttpResponse::Ok().json(game_dto))
         }
-        Err(status) => match status.code() {
-            tonic::Code::NotFound => Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "error": "Game not found"
-            }))),
-            tonic::Code::InvalidArgument => {
-                Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                    "error": status.message()
-                })))
-            }
-            tonic::Code::PermissionDenied => Ok(HttpResponse::Forbidden().json(serde_json::json!({
-                "error": "Permission denied: You can only update your own games"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": status.message()
-            }))),
-        },
+        Err(status) => Ok(errors::tonic_status_to_http_with(
+            &status,
+            &[
+                (tonic::Code::NotFound, "Game not found"),
+                (tonic::Code::PermissionDenied, "Permission denied: You can only update your own games"),
+            ],
+        )),
     }
 }
 
 
+#[utoipa::path(
+    delete,
+    path = "/api/games/{id}",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    request_body = DeleteGameDto,
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
 async fn delete_game(
+    req: HttpRequest,
     data: web::Data<AppState>,
     path: web::Path<String>,
     json: web::Json<DeleteGameDto>,
@@ -720,240 +1621,1685 @@ async fn delete_game(
         })));
     }
 
-    let request = tonic::Request::new(game::DeleteGameRequest {
-        id: game_id,
+    if auth::api_key_owner(&req).is_some_and(|owner| owner != json.developer_id) {
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Permission denied: You can only delete your own games"
+        })));
+    }
+
+    let request = auth::grpc_request(&req, game::DeleteGameRequest {
+        id: game_id,
         developer_id: json.developer_id.clone(),
     });
 
-    let mut client = data.game_client.clone();
+    let mut client = data.game_client();
     match client.delete_game(request).await {
-        Ok(_) => Ok(HttpResponse::Ok().json(serde_json::json!({
-            "message": "Game deleted successfully"
-        }))),
-        Err(status) => match status.code() {
-            tonic::Code::NotFound => Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "error": "Game not found"
-            }))),
-            tonic::Code::PermissionDenied => Ok(HttpResponse::Forbidden().json(serde_json::json!({
-                "error": "Permission denied: You can only delete your own games"
-            }))),
-            _ => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": status.message()
-            }))),
-        },
+        Ok(response) => Ok(deleted_response(response.into_inner().already_absent)),
+        Err(status) => Ok(errors::tonic_status_to_http_with(
+            &status,
+            &[(tonic::Code::PermissionDenied, "Permission denied: You can only delete your own games")],
+        )),
+    }
+}
+
+/// Parses an RFC 3339 time from a request body into a protobuf timestamp.
+#[allow(clippy::result_large_err)]
+fn parse_rfc3339(field: &str, value: &str) -> Result<prost_types::Timestamp, HttpResponse> {
+    chrono::DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| prost_types::Timestamp {
+            seconds: dt.timestamp(),
+            nanos: dt.timestamp_subsec_nanos() as i32,
+        })
+        .map_err(|_| {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("{} must be an RFC 3339 time, e.g. 2025-11-28T00:00:00Z", field)
+            }))
+        })
+}
+
+/// Schedules a discounted price for a time window, replacing any sale
+/// already scheduled. The game's `price` switches to the sale price when the
+/// window opens and back to `base_price` when it closes.
+#[utoipa::path(
+    put,
+    path = "/api/games/{id}/sale",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    request_body = ScheduleSaleDto,
+    responses(
+        (status = 200, description = "The game with its sale", body = GameDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn schedule_sale(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    json: web::Json<ScheduleSaleDto>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let game_id = path.into_inner();
+
+    if uuid::Uuid::parse_str(&game_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid game ID format"
+        })));
+    }
+
+    let starts_at = match parse_rfc3339("starts_at", &json.starts_at) {
+        Ok(ts) => ts,
+        Err(response) => return Ok(response),
+    };
+    let ends_at = match parse_rfc3339("ends_at", &json.ends_at) {
+        Ok(ts) => ts,
+        Err(response) => return Ok(response),
+    };
+
+    let request = auth::grpc_request(&req, game::ScheduleSaleRequest {
+        game_id,
+        sale_price: i64::from(json.sale_price),
+        starts_at: Some(starts_at),
+        ends_at: Some(ends_at),
+    });
+
+    let mut client = data.game_client();
+    match client.schedule_sale(request).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(game_to_dto(response.into_inner()))),
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")])),
+    }
+}
+
+/// Removes the game's sale, whether it has started or not. Cancelling when
+/// there is no sale is a no-op.
+#[utoipa::path(
+    delete,
+    path = "/api/games/{id}/sale",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "The game without a sale", body = GameDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn cancel_sale(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let game_id = path.into_inner();
+
+    if uuid::Uuid::parse_str(&game_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid game ID format"
+        })));
+    }
+
+    let request = auth::grpc_request(&req, game::CancelSaleRequest { game_id });
+
+    let mut client = data.game_client();
+    match client.cancel_sale(request).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(game_to_dto(response.into_inner()))),
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")])),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}/price-history",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "Price changes, oldest first", body = PriceHistoryResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn game_price_history(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let game_id = path.into_inner();
+
+    if uuid::Uuid::parse_str(&game_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid game ID format"
+        })));
+    }
+
+    let request = request_id::grpc_request(&req, game::ListPriceHistoryRequest {
+        game_id: game_id.clone(),
+    });
+
+    let mut client = data.game_client();
+    match client.list_price_history(request).await {
+        Ok(response) => {
+            let entries = response
+                .into_inner()
+                .entries
+                .into_iter()
+                .map(|entry| PriceHistoryEntryDto {
+                    old_price: Money::from(entry.old_price),
+                    new_price: Money::from(entry.new_price),
+                    changed_at: epoch_seconds(entry.changed_at),
+                })
+                .collect();
+
+            Ok(HttpResponse::Ok().json(PriceHistoryResponse { game_id, entries }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")])),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}/reviews/summary",
+    tag = "games",
+    params(("id" = String, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "Average rating and a 1-5 star histogram", body = ReviewSummaryDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn game_review_summary(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let game_id = path.into_inner();
+
+    if uuid::Uuid::parse_str(&game_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid game ID format"
+        })));
+    }
+
+    let request = request_id::grpc_request(&req, game::GetReviewSummaryRequest {
+        game_id: game_id.clone(),
+    });
+
+    let mut client = data.game_client();
+    match client.get_review_summary(request).await {
+        Ok(response) => {
+            let summary = response.into_inner();
+            Ok(negotiate::ok(&req, &ReviewSummaryDto {
+                game_id,
+                average: summary.average_rating,
+                count: summary.total,
+                stars: summary.star_counts,
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")])),
+    }
+}
+
+/// `GET /api/developers/{id}/status-counts` - dashboard counts of the
+/// developer's games by status, for the developer themselves or an admin.
+#[utoipa::path(
+    get,
+    path = "/api/developers/{id}/status-counts",
+    tag = "developers",
+    params(("id" = String, Path, description = "Developer ID")),
+    responses(
+        (status = 200, description = "Game counts per status", body = StatusCountsDto),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn developer_status_counts(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let developer_id = path.into_inner();
+
+    if uuid::Uuid::parse_str(&developer_id).is_err() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid developer ID format"
+        })));
+    }
+
+    match auth::principal(&req) {
+        Some(auth::Principal::User(claims)) if claims.sub == developer_id || claims.has_role(Role::Admin) => {}
+        Some(_) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "You can only view your own status counts"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
+    }
+
+    let request = auth::grpc_request(&req, game::GetDeveloperStatusCountsRequest {
+        developer_id: developer_id.clone(),
+    });
+
+    let mut client = data.game_client();
+    match client.get_developer_status_counts(request).await {
+        Ok(response) => {
+            let counts = response.into_inner();
+            Ok(negotiate::ok(&req, &StatusCountsDto {
+                developer_id,
+                draft: counts.draft,
+                under_review: counts.under_review,
+                published: counts.published,
+                suspended: counts.suspended,
+                total: counts.draft + counts.under_review + counts.published + counts.suspended,
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/games/stats",
+    tag = "games",
+    responses(
+        (status = 200, description = "Catalog-wide counters", body = CatalogStatsDto),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn catalog_stats(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let request = request_id::grpc_request(&req, game::GetCatalogStatsRequest {});
+
+    let mut client = data.game_client();
+    match client.get_catalog_stats(request).await {
+        Ok(response) => {
+            let stats = response.into_inner();
+            Ok(HttpResponse::Ok().json(CatalogStatsDto {
+                published_games: stats.published_games,
+                developers: stats.developers,
+                newest_release: stats.newest_release.map(game_to_dto),
+                most_purchased: stats.most_purchased.map(game_to_dto),
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+/// Min, max, average and median price of the published games in a category,
+/// for developers pricing a new game.
+#[utoipa::path(
+    get,
+    path = "/api/games/category/{category}/price-stats",
+    tag = "games",
+    params(("category" = String, Path, description = "Category name, e.g. `rpg`")),
+    responses(
+        (status = 200, description = "Price statistics for the category", body = CategoryPriceStatsDto),
+        (status = 400, description = "Unknown category", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn category_price_stats(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let name = path.into_inner();
+    let category = match name.parse::<Category>() {
+        Ok(category) => category.to_proto(),
+        Err(_) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("Unknown category '{}'", name)
+            })));
+        }
+    };
+
+    let request = request_id::grpc_request(&req, game::GetCategoryPriceStatsRequest { category });
+
+    let mut client = data.game_client();
+    match client.get_category_price_stats(request).await {
+        Ok(response) => {
+            let stats = response.into_inner();
+            Ok(HttpResponse::Ok().json(CategoryPriceStatsDto {
+                category: name,
+                game_count: stats.game_count,
+                min_price: stats.min_price.map(Money::from),
+                max_price: stats.max_price.map(Money::from),
+                avg_price: stats.avg_price.map(Money::from),
+                median_price: stats.median_price.map(Money::from),
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+/// Name autocomplete for the search box: published games starting with `q`.
+#[utoipa::path(
+    get,
+    path = "/api/games/suggest",
+    tag = "games",
+    params(SuggestQuery),
+    responses(
+        (status = 200, description = "Name completions", body = SuggestResponse),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn suggest_games(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    query: web::Query<SuggestQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let request = request_id::grpc_request(&req, game::SuggestGamesRequest {
+        prefix: query.q.clone(),
+        limit: query.limit.unwrap_or(0),
+    });
+
+    let mut client = data.game_client();
+    match client.suggest_games(request).await {
+        Ok(response) => {
+            let suggestions = response
+                .into_inner()
+                .suggestions
+                .into_iter()
+                .map(|s| GameSuggestionDto { id: s.id, name: s.name })
+                .collect();
+
+            Ok(negotiate::ok(&req, &SuggestResponse { suggestions }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/games/stats/recompute",
+    tag = "admin",
+    params(RecomputeStatsQuery),
+    responses(
+        (status = 200, description = "How many games had drifted aggregates", body = RecomputeStatsResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 404, description = "Not found", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn recompute_game_stats(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    query: web::Query<RecomputeStatsQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    if let Some(game_id) = &query.game_id
+        && uuid::Uuid::parse_str(game_id).is_err()
+    {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid game ID format"
+        })));
+    }
+
+    let request = auth::grpc_request(&req, game::RecomputeGameStatsRequest {
+        game_id: query.game_id.clone(),
+    });
+
+    let mut client = data.game_client();
+    match client.recompute_game_stats(request).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(RecomputeStatsResponse {
+            games_corrected: response.into_inner().games_corrected,
+        })),
+        Err(status) => Ok(errors::tonic_status_to_http_with(&status, &[(tonic::Code::NotFound, "Game not found")])),
+    }
+}
+
+/// What the draft expiry job would remove right now; nothing is changed.
+#[utoipa::path(
+    get,
+    path = "/api/games/drafts/expiry-preview",
+    tag = "admin",
+    params(DraftExpiryQuery),
+    responses(
+        (status = 200, description = "Drafts the expiry job would remove", body = DraftExpiryPreviewResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn preview_draft_expiry(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    query: web::Query<DraftExpiryQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let request = auth::grpc_request(&req, game::PreviewDraftExpiryRequest {
+        older_than_days: query.older_than_days,
+    });
+
+    let mut client = data.game_client();
+    match client.preview_draft_expiry(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            Ok(HttpResponse::Ok().json(DraftExpiryPreviewResponse {
+                cutoff: epoch_seconds(resp.cutoff),
+                drafts: resp
+                    .drafts
+                    .into_iter()
+                    .map(|draft| StaleDraftDto {
+                        id: draft.id,
+                        name: draft.name,
+                        developer_id: draft.developer_id,
+                        updated_at: epoch_seconds(draft.updated_at),
+                    })
+                    .collect(),
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+/// Hard-deletes games soft-deleted before the retention window. Their audit
+/// entries stay.
+#[utoipa::path(
+    post,
+    path = "/api/games/deleted/purge",
+    tag = "admin",
+    params(PurgeQuery),
+    responses(
+        (status = 200, description = "How many games were purged", body = PurgeResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn purge_deleted_games(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    query: web::Query<PurgeQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let request = auth::grpc_request(&req, game::PurgeDeletedGamesRequest {
+        older_than_days: query.older_than_days,
+    });
+
+    let mut client = data.game_client();
+    match client.purge_deleted_games(request).await {
+        Ok(response) => {
+            let resp = response.into_inner();
+            Ok(HttpResponse::Ok().json(PurgeResponse {
+                purged: resp.purged,
+                cutoff: epoch_seconds(resp.cutoff),
+            }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/games",
+    tag = "games",
+    params(ListGamesQuery),
+    responses(
+        (status = 200, description = "One page of games", body = ListGamesResponse<GameDto>),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
 async fn list_games(
+    req: HttpRequest,
     data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
     query: web::Query<ListGamesQuery>,
 ) -> Result<HttpResponse, actix_web::Error> {
-    let categories = query.categories.as_ref().map(|cats| 
-        cats.iter().map(|cat| match cat.as_str() {
-            "action" => 1,
-            "rpg" => 2,
-            "strategy" => 3,
-            "sports" => 4,
-            "racing" => 5,
-            "adventure" => 6,
-            "simulation" => 7,
-            "puzzle" => 8,
-            _ => 0, // unspecified
-        }).collect()
-    ).unwrap_or_default();
+    let limit = match page_size.resolve(query.limit) {
+        Ok(limit) => limit,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
+    let offset = query.offset.unwrap_or(0).max(0);
+    let projection = match fields::Projection::from_query(query.fields.as_deref(), fields::GAME_FIELDS) {
+        Ok(projection) => projection,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
+    let includes = match include::Includes::from_query(query.include.as_deref()) {
+        Ok(includes) => includes,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
 
-    let status = query.status.as_ref().and_then(|status_str| match status_str.as_str() {
-        "draft" => Some(1),
-        "under_review" => Some(2),
-        "published" => Some(3),
-        "suspended" => Some(4),
-        "unspecified" => Some(0),
-        _ => None,
-    });
+    let categories = category_codes(query.categories.as_deref());
 
-    let request = tonic::Request::new(game::ListGamesRequest {
+    let status = match parse_status_param(query.status.as_deref()) {
+        Ok(status) => status,
+        Err(response) => return Ok(response),
+    };
+
+    let request = auth::grpc_request(&req, game::ListGamesRequest {
         developer_id: query.developer_id.clone(),
         categories,
-        min_price: query.min_price.map(|p| p as i64),
-        max_price: query.max_price.map(|p| p as i64),
+        min_price: query.min_price.map(i64::from),
+        max_price: query.max_price.map(i64::from),
         status,
-        search_query: query.search_query.clone(),
-        page_size: query.limit.unwrap_or(50),
-        page_token: query.offset.unwrap_or(0).to_string(),
+        search_query: search_param(query.search_query.as_deref()),
+        page_size: limit,
+        page_token: offset.to_string(),
         sort_by: query.sort_by.clone(),
         sort_desc: query.sort_desc,
+        include_deleted: query.include_deleted,
+        exclude_id: None,
+        publisher_id: None,
     });
 
-    let mut client = data.game_client.clone();
+    let mut client = data.game_client();
     match client.list_games(request).await {
         Ok(response) => {
+            // Only present when the game service runs with LIST_DEBUG_TIMING.
+            let query_time = response
+                .metadata()
+                .get(QUERY_TIME_KEY)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| actix_web::http::header::HeaderValue::from_str(v).ok());
             let resp = response.into_inner();
 
             let game_dtos: Vec<GameDto> = resp
                 .games
                 .into_iter()
-                .map(|game| GameDto {
-                    id: game.id,
-                    name: game.name,
-                    description: game.description,
-                    developer_id: game.developer_id,
-                    publisher_id: game.publisher_id,
-                    cover_image: game.cover_image.unwrap_or_default(),
-                    trailer_url: game.trailer_url,
-                    release_date: game.release_date.unwrap_or_default(),
-                    tags: game.tags,
-                    platforms: game.platforms,
-                    screenshots: game.screenshots,
-                    price: game.price as f64,
-                    status: match game.status {
-                        0 => "unspecified".to_string(),
-                        1 => "draft".to_string(),
-                        2 => "under_review".to_string(),
-                        3 => "published".to_string(),
-                        4 => "suspended".to_string(),
-                        _ => "unknown".to_string(),
-                    },
-                    categories: game.categories.iter().map(|&cat| match cat {
-                        1 => "action".to_string(),
-                        2 => "rpg".to_string(),
-                        3 => "strategy".to_string(),
-                        4 => "sports".to_string(),
-                        5 => "racing".to_string(),
-                        6 => "adventure".to_string(),
-                        7 => "simulation".to_string(),
-                        8 => "puzzle".to_string(),
-                        _ => "unspecified".to_string(),
-                    }).collect(),
-                    rating_count: game.rating_count as i32,
-                    average_rating: game.average_rating,
-                    purchase_count: game.purchase_count as i32,
-                    created_at: game.created_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
-                    updated_at: game.updated_at.map(|ts| format!("{}", ts.seconds)).unwrap_or_default(),
+                .map(game_to_dto)
+                .collect();
+            let total = resp.total_count as i32;
+
+            let mut http_response = if includes.any() {
+                match hydrate_games(&req, &data, &game_dtos, projection.as_ref(), includes).await {
+                    Ok(games) => etag::negotiated_with_etag(&req, &ListGamesResponse { games, total }),
+                    Err(response) => return Ok(response),
+                }
+            } else {
+                match &projection {
+                    Some(projection) => {
+                        let projected: Result<Vec<_>, _> =
+                            game_dtos.iter().map(|dto| projection.apply(dto)).collect();
+                        match projected {
+                            Ok(games) => etag::negotiated_with_etag(&req, &ListGamesResponse { games, total }),
+                            Err(e) => return Ok(negotiate::serialization_error(e)),
+                        }
+                    }
+                    None => etag::negotiated_with_etag(
+                        &req,
+                        &ListGamesResponse {
+                            games: game_dtos,
+                            total,
+                        },
+                    ),
+                }
+            };
+            pagination::insert_headers(
+                &mut http_response,
+                &req,
+                limit,
+                offset,
+                resp.total_count as i64,
+            );
+            if let Some(query_time) = query_time {
+                http_response.headers_mut().insert(actix_web::http::header::HeaderName::from_static(QUERY_TIME_KEY), query_time);
+            }
+
+            Ok(http_response)
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+/// Match counts per price bucket for the storefront filters, using the same
+/// filters as `GET /api/games`. Without `buckets` the game service's default
+/// edges apply.
+#[utoipa::path(
+    get,
+    path = "/api/games/price-facets",
+    tag = "games",
+    params(PriceFacetsQuery),
+    responses(
+        (status = 200, description = "Game counts per price bucket", body = PriceFacetsResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Caller is not allowed to do this", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+async fn price_facets(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    query: web::Query<PriceFacetsQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let bounds = match query.buckets.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(raw) => match raw.split(',').map(|b| b.trim().parse::<Money>().map(i64::from)).collect() {
+            Ok(bounds) => bounds,
+            Err(e) => {
+                return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("Invalid buckets: {}", e)
+                })));
+            }
+        },
+        None => Vec::new(),
+    };
+
+    let status = match parse_status_param(query.status.as_deref()) {
+        Ok(status) => status,
+        Err(response) => return Ok(response),
+    };
+
+    let request = auth::grpc_request(&req, game::GetPriceFacetsRequest {
+        filter: Some(game::ListGamesRequest {
+            developer_id: query.developer_id.clone(),
+            categories: category_codes(query.categories.as_deref()),
+            min_price: query.min_price.map(i64::from),
+            max_price: query.max_price.map(i64::from),
+            status,
+            search_query: search_param(query.search_query.as_deref()),
+            include_deleted: query.include_deleted,
+            ..Default::default()
+        }),
+        bounds,
+    });
+
+    let mut client = data.game_client();
+    match client.get_price_facets(request).await {
+        Ok(response) => {
+            let facets = response
+                .into_inner()
+                .facets
+                .into_iter()
+                .map(|facet| PriceFacetDto {
+                    min_price: facet.min_price.map(Money::from),
+                    max_price: facet.max_price.map(Money::from),
+                    count: facet.count,
                 })
                 .collect();
+            Ok(negotiate::ok(&req, &PriceFacetsResponse { facets }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
 
-            Ok(HttpResponse::Ok().json(ListGamesResponse {
-                games: game_dtos,
-                total: resp.total_count as i32,
-            }))
+/// `?search_query=` trimmed, or `None` when blank: a blank query would
+/// still make the service evaluate the full-text predicate against every
+/// row, so it is treated as no query at all.
+fn search_param(raw: Option<&str>) -> Option<String> {
+    raw.map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+/// Maps `?categories=` names to proto enum values.
+fn category_codes(names: Option<&[String]>) -> Vec<i32> {
+    names
+        .unwrap_or_default()
+        .iter()
+        .map(|cat| cat.parse::<Category>().map_or(0, Category::to_proto))
+        .collect()
+}
+
+/// Adds the `?include=` extras to each (possibly projected) game. All the
+/// developers on the page come from one `BatchGetUsers` call; one that no
+/// longer exists is embedded as `null`.
+async fn hydrate_games(
+    req: &HttpRequest,
+    data: &AppState,
+    games: &[GameDto],
+    projection: Option<&fields::Projection>,
+    includes: include::Includes,
+) -> Result<Vec<serde_json::Value>, HttpResponse> {
+    let mut developers: HashMap<String, DeveloperSummary> = HashMap::new();
+    if includes.developer && !games.is_empty() {
+        let mut ids: Vec<String> = games.iter().map(|game| game.developer_id.clone()).collect();
+        ids.sort();
+        ids.dedup();
+
+        let request = request_id::grpc_request(req, user::BatchGetUsersRequest { ids });
+        let mut user_client = data.user_client();
+        match user_client.batch_get_users(request).await {
+            Ok(response) => {
+                for user in response.into_inner().users {
+                    developers.insert(
+                        user.id.clone(),
+                        DeveloperSummary { id: user.id, username: user.username, verified: user.verified },
+                    );
+                }
+            }
+            Err(status) => {
+                return Err(errors::tonic_status_to_http(&status));
+            }
         }
-        Err(status) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": status.message()
-        }))),
     }
+
+    let mut hydrated = Vec::with_capacity(games.len());
+    for game in games {
+        let mut value = match projection {
+            Some(projection) => projection.apply(game),
+            None => serde_json::to_value(game).map_err(|e| e.to_string()),
+        }
+        .map_err(negotiate::serialization_error)?;
+
+        if let serde_json::Value::Object(map) = &mut value {
+            if includes.developer {
+                let developer = serde_json::to_value(developers.get(&game.developer_id))
+                    .map_err(|e| negotiate::serialization_error(e.to_string()))?;
+                map.insert("developer".to_string(), developer);
+            }
+            if includes.ratings {
+                let ratings = RatingSummary { average: game.average_rating, count: game.rating_count };
+                let ratings = serde_json::to_value(ratings)
+                    .map_err(|e| negotiate::serialization_error(e.to_string()))?;
+                map.insert("ratings".to_string(), ratings);
+            }
+        }
+        hydrated.push(value);
+    }
+    Ok(hydrated)
 }
 
+/// Deletes answer 204 whether or not the entity was still there, so a retry
+/// after a lost response doesn't turn into a 404.
+fn deleted_response(already_absent: bool) -> HttpResponse {
+    HttpResponse::NoContent()
+        .insert_header(("x-already-absent", already_absent.to_string()))
+        .finish()
+}
 
-fn proto_role_to_string(role: i32) -> String {
-    match role {
-        0 => "player".to_string(),
-        1 => "developer".to_string(),
-        2 => "admin".to_string(),
-        _ => "unknown".to_string(),
+/// A status from a body or query string, as its proto value. Absent means
+/// "leave as is" or "any"; `unspecified` and unknown names are a 400.
+#[allow(clippy::result_large_err)]
+fn parse_status_param(raw: Option<&str>) -> Result<Option<i32>, HttpResponse> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    match GameStatus::parse_selectable(raw) {
+        Some(status) => Ok(Some(status.to_proto())),
+        None => Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": errors::invalid_choice("status", raw, GameStatus::SELECTABLE)
+        }))),
     }
 }
 
-async fn rate_limit_middleware(
-    req: ServiceRequest,
-    next: Next<impl actix_web::body::MessageBody + 'static>,
-) -> Result<ServiceResponse<actix_web::body::BoxBody>, Error> {
-    let rate_limiter = req.app_data::<web::Data<RateLimiter>>().unwrap();
-    let ip = req
-        .peer_addr()
-        .map(|addr| addr.ip().to_string())
-        .unwrap_or_else(|| "unknown".to_string());
+/// A game status in a request body. `unspecified` and unknown names fail
+/// the JSON parse, like `parse_status_param` refuses them in a query.
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct SelectableStatus(GameStatus);
+
+impl TryFrom<String> for SelectableStatus {
+    type Error = String;
 
-    if !rate_limiter.check_rate_limit(&ip, 100, Duration::from_secs(60)) {
-        return Ok(req.into_response(
-            HttpResponse::TooManyRequests()
-                .json(serde_json::json!({
-                    "error": "Rate limit exceeded. Please try again later."
-                }))
-                .map_into_boxed_body(),
-        ));
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        GameStatus::parse_selectable(&value)
+            .map(Self)
+            .ok_or_else(|| errors::invalid_choice("status", &value, GameStatus::SELECTABLE))
     }
+}
+
+/// In validate-only mode a uniqueness conflict is reported as a plain
+/// validation error rather than a 409.
+fn is_validation_failure(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        tonic::Code::InvalidArgument | tonic::Code::AlreadyExists
+    )
+}
 
-    let res = next.call(req).await?;
-    Ok(res.map_into_boxed_body())
+/// Optional game strings come back from the game service either unset or
+/// empty depending on how they were cleared; both mean "none" and are sent
+/// to clients as `null`.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
 }
 
-async fn request_id_middleware(
-    req: ServiceRequest,
-    next: Next<impl actix_web::body::MessageBody + 'static>,
-) -> Result<ServiceResponse<actix_web::body::BoxBody>, Error> {
-    let request_id = Uuid::new_v4().to_string();
+/// Unix seconds for a DTO. An unset upstream timestamp becomes `None` (sent
+/// as `null`), never an empty string clients would fail to parse as a date.
+fn epoch_seconds(ts: Option<prost_types::Timestamp>) -> Option<String> {
+    ts.map(|ts| ts.seconds.to_string())
+}
 
-    req.extensions_mut().insert(request_id.clone());
+fn game_to_dto(game: game::Game) -> GameDto {
+    GameDto {
+        id: game.id,
+        name: game.name,
+        description: game.description,
+        developer_id: game.developer_id,
+        publisher_id: non_empty(game.publisher_id),
+        cover_image: non_empty(game.cover_image),
+        trailer_url: non_empty(game.trailer_url),
+        release_date: non_empty(game.release_date),
+        tags: game.tags,
+        platforms: game.platforms,
+        screenshots: game.screenshots,
+        price: Money::from(game.price),
+        base_price: Money::from(game.base_price),
+        sale: game.sale.map(|sale| SaleDto {
+            price: Money::from(sale.price),
+            starts_at: epoch_seconds(sale.starts_at),
+            ends_at: epoch_seconds(sale.ends_at),
+            active: sale.active,
+        }),
+        status: GameStatus::proto_name(game.status),
+        categories: game.categories.iter().map(|&cat| {
+            Category::from_proto(cat).map_or_else(|| "unspecified".to_string(), |c| c.to_string())
+        }).collect(),
+        rating_count: game.rating_count,
+        average_rating: game.average_rating,
+        purchase_count: game.purchase_count,
+        created_at: epoch_seconds(game.created_at),
+        updated_at: epoch_seconds(game.updated_at),
+        deleted_at: epoch_seconds(game.deleted_at),
+    }
+}
 
-    println!(
-        "Request ID: {} - {} {}",
-        request_id,
-        req.method(),
-        req.path()
-    );
+/// `include_email` is false when the viewer may only see the public profile.
+fn user_to_dto(user: user::UserMessage, include_email: bool) -> UserDto {
+    UserDto {
+        id: user.id,
+        email: include_email.then_some(user.email),
+        username: user.username,
+        role: Role::from_proto(user.role).map_or_else(|| "unknown".to_string(), |r| r.to_string()),
+        created_at: epoch_seconds(user.created_at),
+        verified: user.verified,
+    }
+}
 
-    let mut res = next.call(req).await?;
+/// Somewhere the gateway's routes can go: the app, or the OpenAPI test,
+/// which records them to compare with the document.
+trait Routes {
+    fn handle<F, Args>(&mut self, method: Method, path: &'static str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static;
+}
 
-    res.headers_mut().insert(
-        actix_web::http::header::HeaderName::from_static("x-request-id"),
-        actix_web::http::header::HeaderValue::from_str(&request_id).unwrap(),
-    );
+impl Routes for web::ServiceConfig {
+    fn handle<F, Args>(&mut self, method: Method, path: &'static str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(path, web::method(method).to(handler))
+    }
+}
 
-    Ok(res.map_into_boxed_body())
+/// Every route the gateway serves. Each one needs an entry in
+/// `openapi::ApiDoc` as well.
+fn routes(routes: &mut impl Routes) {
+    routes
+        .handle(Method::POST, "/api/users", create_user)
+        .handle(Method::POST, "/api/users/batch", batch_create_users)
+        .handle(Method::GET, "/api/users/me/permissions", permissions::my_permissions)
+        .handle(Method::GET, "/api/users/{id}", get_user)
+        .handle(Method::PUT, "/api/users/{id}", update_user)
+        .handle(Method::DELETE, "/api/users/{id}", delete_user)
+        .handle(Method::POST, "/api/users/{id}/impersonate", impersonate_user)
+        .handle(Method::POST, "/api/users/{id}/revoke", revoke_user_sessions)
+        .handle(Method::PUT, "/api/users/{id}/verified", set_developer_verified)
+        .handle(Method::GET, "/api/users", users_list)
+        .handle(Method::GET, "/api/search", search::search)
+        .handle(Method::POST, "/api/games", create_game)
+        .handle(Method::GET, "/api/games/stats", catalog_stats)
+        .handle(Method::GET, "/api/games/category/{category}/price-stats", category_price_stats)
+        .handle(Method::POST, "/api/games/stats/recompute", recompute_game_stats)
+        .handle(Method::GET, "/api/games/drafts/expiry-preview", preview_draft_expiry)
+        .handle(Method::POST, "/api/games/deleted/purge", purge_deleted_games)
+        .handle(Method::GET, "/api/games/suggest", suggest_games)
+        .handle(Method::GET, "/api/games/new", releases::new_releases)
+        .handle(Method::GET, "/api/games/upcoming", releases::upcoming_games)
+        .handle(Method::GET, "/api/games/price-facets", price_facets)
+        .handle(Method::POST, "/api/games/prices/bulk", bulk_set_prices)
+        .handle(Method::GET, "/api/games/{id}", get_game)
+        .handle(Method::PUT, "/api/games/{id}", update_game)
+        .handle(Method::DELETE, "/api/games/{id}", delete_game)
+        .handle(Method::GET, "/api/games", list_games)
+        .handle(Method::GET, "/api/games/{id}/events", events::game_events)
+        .handle(Method::GET, "/api/games/{id}/developer", game_developer)
+        .handle(Method::GET, "/api/games/{id}/page", game_page::game_page)
+        .handle(Method::GET, "/api/games/{id}/by-developer", games_by_developer)
+        .handle(Method::GET, "/api/games/{id}/price-history", game_price_history)
+        .handle(Method::GET, "/api/games/{id}/reviews/summary", game_review_summary)
+        .handle(Method::PUT, "/api/games/{id}/sale", schedule_sale)
+        .handle(Method::DELETE, "/api/games/{id}/sale", cancel_sale)
+        .handle(Method::GET, "/api/developers/{id}/export", export::developer_export)
+        .handle(Method::GET, "/api/developers/{id}/status-counts", developer_status_counts)
+        .handle(Method::GET, "/api/developers/{id}/profile", developer_profile::get_developer_profile)
+        .handle(Method::POST, "/api/developers/{id}/profile", developer_profile::create_developer_profile)
+        .handle(Method::PUT, "/api/developers/{id}/profile", developer_profile::update_developer_profile)
+        .handle(Method::GET, "/api/publishers/{id}/games", publisher_games)
+        .handle(Method::GET, "/api/audit", audit::list_audit)
+        .handle(Method::POST, "/api/keys", api_keys::create_api_key)
+        .handle(Method::GET, "/api/keys", api_keys::list_api_keys)
+        .handle(Method::DELETE, "/api/keys/{id}", api_keys::revoke_api_key)
+        .handle(Method::GET, "/ws", notifications::notifications_ws)
+        .handle(Method::GET, "/api-docs/openapi.json", openapi::openapi_json);
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
+    dotenv::dotenv().ok();
+    // Records from the `log` crate (actix's Logger, our `log::` calls) are
+    // bridged into the same subscriber.
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let user_urls = grpc_pool::urls_from_env("USER_SERVICE_URLS", USER_SERVICE_URL);
+    let game_urls = grpc_pool::urls_from_env("GAME_SERVICE_URLS", GAME_SERVICE_URL);
+
+    if std::env::args().any(|arg| arg == selfcheck::SKIP_FLAG) {
+        println!("Startup self-check skipped ({})", selfcheck::SKIP_FLAG);
+    } else {
+        let report = selfcheck::run(&[
+            ("user service", &user_urls),
+            ("game service", &game_urls),
+        ])
+        .await;
+        report.print();
+        if !report.passed() {
+            eprintln!("Startup self-check failed; fix the problems above or pass {} to start anyway", selfcheck::SKIP_FLAG);
+            std::process::exit(1);
+        }
+    }
 
-    let user_client = user::user_service_client::UserServiceClient::connect("http://[::1]:50051")
-        .await
-        .expect("Failed to connect to user service");
+    let pool_config = grpc_pool::PoolConfig::from_env().expect("Invalid gRPC pool configuration");
+    let user_channels = grpc_pool::ChannelPool::new("user service", &user_urls, &pool_config)
+        .expect("Invalid user service configuration");
+    let game_channels = grpc_pool::ChannelPool::new("game service", &game_urls, &pool_config)
+        .expect("Invalid game service configuration");
+    user_channels.spawn_health_checks(pool_config.health_check_interval);
+    game_channels.spawn_health_checks(pool_config.health_check_interval);
 
-    let game_client = game::game_service_client::GameServiceClient::connect("http://[::1]:50052")
-        .await
-        .expect("Failed to connect to game service");
+    let game_events = web::Data::new(events::GameEventHub::new());
+    game_events.spawn_forwarder(game_channels.clone());
 
-    let app_state = web::Data::new(AppState { user_client, game_client });
+    let notification_hub = web::Data::new(notifications::NotificationHub::new());
+    notifications::NotificationHub::spawn_game_event_feed(notification_hub.clone(), &game_events);
 
-    let rate_limiter = web::Data::new(RateLimiter::new());
+    let jwt = web::Data::new(auth::JwtVerifier::from_env().expect("Invalid JWT configuration"));
+
+    let app_state = web::Data::new(AppState { user_channels, game_channels });
+
+    let rate_limiter = web::Data::new(rate_limit::RateLimiter::new());
+    let rate_limit_config =
+        web::Data::new(rate_limit::RateLimitConfig::from_env().expect("Invalid rate limit configuration"));
+
+    let timeout_config =
+        web::Data::new(timeout::TimeoutConfig::from_env().expect("Invalid timeout configuration"));
+
+    let page_size = web::Data::new(
+        PageSizeConfig::from_env().expect("Invalid page size configuration"),
+    );
+
+    let cors_config = cors::CorsConfig::from_env().expect("Invalid CORS configuration");
+
+    let request_log = web::Data::new(
+        request_id::RequestLogConfig::from_env().expect("Invalid request log configuration"),
+    );
+
+    let signup_policy = web::Data::new(
+        signup::SignupPolicy::from_env().expect("Invalid signup configuration"),
+    );
 
     println!("Gateway service listening on http://localhost:8080");
 
     HttpServer::new(move || {
-        let cors = Cors::default()
-            .allowed_origin("http://localhost:3000") // React
-            .allowed_origin("http://localhost:5173") // Vite
-            .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
-            .allowed_headers(vec![
-                actix_web::http::header::AUTHORIZATION,
-                actix_web::http::header::ACCEPT,
-                actix_web::http::header::CONTENT_TYPE,
-            ])
-            .expose_headers(vec!["x-request-id"])
-            .max_age(3600);
+        let cors = cors_config.build();
 
         App::new()
             .app_data(app_state.clone())
             .app_data(rate_limiter.clone())
-            .wrap(middleware::from_fn(request_id_middleware))
-            .wrap(middleware::from_fn(rate_limit_middleware))
+            .app_data(rate_limit_config.clone())
+            .app_data(game_events.clone())
+            .app_data(notification_hub.clone())
+            .app_data(jwt.clone())
+            .app_data(timeout_config.clone())
+            .app_data(page_size.clone())
+            .app_data(request_log.clone())
+            .app_data(signup_policy.clone())
+            .app_data(web::JsonConfig::default().error_handler(errors::json_error))
+            .wrap(middleware::from_fn(auth::auth_middleware))
+            .wrap(middleware::from_fn(timeout::timeout_middleware))
+            .wrap(middleware::from_fn(request_id::request_id_middleware))
+            .wrap(middleware::from_fn(rate_limit::rate_limit_middleware))
             .wrap(cors)
             .wrap(middleware::Logger::new(
                 "%a \"%r\" %s %b \"%{Referer}i\" \"%{User-Agent}i\" %T",
             ))
-            .route("/api/users", web::post().to(create_user))
-            .route("/api/users/{id}", web::get().to(get_user))
-            .route("/api/users/{id}", web::put().to(update_user))
-            .route("/api/users/{id}", web::delete().to(delete_user))
-            .route("/api/users", web::get().to(users_list))
-            .route("/api/games", web::post().to(create_game))
-            .route("/api/games/{id}", web::get().to(get_game))
-            .route("/api/games/{id}", web::put().to(update_game))
-            .route("/api/games/{id}", web::delete().to(delete_game))
-            .route("/api/games", web::get().to(list_games))
+            .configure(routes)
     })
     .bind("127.0.0.1:8080")?
     .run()
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::HttpMessage;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    use super::*;
+    use crate::testing::{
+        FakeGameService, FakeUserService, UNREACHABLE_URL, app_state, app_state_with, claims, signed_in,
+    };
+
+    /// A request from `admin_id` acting as `user_id`.
+    fn impersonating(admin_id: &str, user_id: &str) -> HttpRequest {
+        let req = TestRequest::default().to_http_request();
+        req.extensions_mut().insert(auth::Principal::User(auth::Claims {
+            act_as: Some(admin_id.to_string()),
+            ..claims(user_id, "player")
+        }));
+        req
+    }
+
+    fn developer(id: &str) -> user::UserMessage {
+        user::UserMessage {
+            id: id.to_string(),
+            email: "dev@example.com".to_string(),
+            username: "dev".to_string(),
+            role: Role::Developer.to_proto(),
+            ..Default::default()
+        }
+    }
+
+    async fn json_body(response: HttpResponse) -> serde_json::Value {
+        let body = actix_web::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn only_admins_can_change_roles() {
+        let player_id = uuid::Uuid::new_v4().to_string();
+        let player = user::UserMessage { role: Role::Player.to_proto(), ..developer(&player_id) };
+        let users = Arc::new(FakeUserService::default().with_user(player));
+        let state = web::Data::new(app_state(users.clone().serve().await));
+        let update = |req: HttpRequest, user_id: &str, role: Option<Role>| {
+            let dto = UpdateUserDto { email: None, username: None, password: None, role };
+            update_user(req, state.clone(), web::Path::from(user_id.to_string()), web::Json(dto))
+        };
+
+        let response = update(signed_in(&player_id, "player"), &player_id, Some(Role::Admin)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(json_body(response).await["error"], "Only admins can change a user's role");
+
+        let response = update(signed_in(&player_id, "developer"), &player_id, Some(Role::Developer)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let someone = uuid::Uuid::new_v4().to_string();
+        let response = update(signed_in(&someone, "player"), &player_id, None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        assert_eq!(users.calls.load(std::sync::atomic::Ordering::Relaxed), 0);
+        assert_eq!(users.users.lock().unwrap()[&player_id].role, Role::Player.to_proto());
+
+        let admin = signed_in(&uuid::Uuid::new_v4().to_string(), "admin");
+        let response = update(admin, &player_id, Some(Role::Developer)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["role"], "developer");
+        assert_eq!(users.users.lock().unwrap()[&player_id].role, Role::Developer.to_proto());
+    }
+
+    #[test]
+    fn user_dtos_leave_the_email_out_unless_asked() {
+        let public = serde_json::to_value(user_to_dto(developer("d1"), false)).unwrap();
+        assert!(public.get("email").is_none(), "{public}");
+        assert_eq!(public["username"], "dev");
+
+        let private = serde_json::to_value(user_to_dto(developer("d1"), true)).unwrap();
+        assert_eq!(private["email"], "dev@example.com");
+    }
+
+    #[actix_web::test]
+    async fn users_show_their_email_only_to_themselves_and_admins() {
+        let user_id = uuid::Uuid::new_v4().to_string();
+        let users = Arc::new(FakeUserService::default().with_user(developer(&user_id)));
+        let state = web::Data::new(app_state(users.serve().await));
+        let fetch = |req: HttpRequest| get_user(req, state.clone(), web::Path::from(user_id.clone()));
+
+        let anonymous = TestRequest::default().to_http_request();
+        let other = signed_in(&uuid::Uuid::new_v4().to_string(), "developer");
+        for req in [anonymous, other] {
+            let body = json_body(fetch(req).await.unwrap()).await;
+            assert_eq!(body["id"], user_id.as_str());
+            assert!(body.get("email").is_none(), "{body}");
+        }
+
+        let own = signed_in(&user_id, "developer");
+        let admin = signed_in(&uuid::Uuid::new_v4().to_string(), "admin");
+        for req in [own, admin] {
+            let body = json_body(fetch(req).await.unwrap()).await;
+            assert_eq!(body["email"], "dev@example.com");
+        }
+    }
+
+    #[actix_web::test]
+    async fn game_developers_show_their_email_only_to_themselves_and_admins() {
+        let developer_id = uuid::Uuid::new_v4().to_string();
+        let game_id = uuid::Uuid::new_v4().to_string();
+        let orphan_id = uuid::Uuid::new_v4().to_string();
+        let users = Arc::new(FakeUserService::default().with_user(developer(&developer_id)));
+        let games = Arc::new(
+            FakeGameService::default()
+                .with_game(game::Game { id: game_id.clone(), developer_id: developer_id.clone(), ..Default::default() })
+                .with_game(game::Game { id: orphan_id.clone(), developer_id: uuid::Uuid::new_v4().to_string(), ..Default::default() }),
+        );
+        let state = web::Data::new(app_state_with(users.serve().await, games.serve().await));
+        let fetch = |req: HttpRequest, id: &str| game_developer(req, state.clone(), web::Path::from(id.to_string()));
+
+        let anonymous = TestRequest::default().to_http_request();
+        let other = signed_in(&uuid::Uuid::new_v4().to_string(), "player");
+        for req in [anonymous, other] {
+            let response = fetch(req, &game_id).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = json_body(response).await;
+            assert_eq!(body["id"], developer_id.as_str());
+            assert!(body.get("email").is_none(), "{body}");
+        }
+
+        let owner = signed_in(&developer_id, "developer");
+        let admin = signed_in(&uuid::Uuid::new_v4().to_string(), "admin");
+        for req in [owner, admin] {
+            let body = json_body(fetch(req, &game_id).await.unwrap()).await;
+            assert_eq!(body["email"], "dev@example.com");
+        }
+
+        let response = fetch(TestRequest::default().to_http_request(), &uuid::Uuid::new_v4().to_string()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(json_body(response).await["error"], "Game not found");
+
+        let response = fetch(TestRequest::default().to_http_request(), &orphan_id).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(json_body(response).await["error"], "Developer not found");
+    }
+
+    #[actix_web::test]
+    async fn games_can_be_fetched_with_only_the_requested_fields() {
+        let game_id = uuid::Uuid::new_v4().to_string();
+        let games = Arc::new(FakeGameService::default().with_game(game::Game {
+            id: game_id.clone(),
+            name: "Space Quest".to_string(),
+            price: 1999,
+            ..Default::default()
+        }));
+        let state = web::Data::new(app_state_with(UNREACHABLE_URL.to_string(), games.serve().await));
+        let fetch = |fields: &str| {
+            get_game(
+                TestRequest::default().to_http_request(),
+                state.clone(),
+                web::Path::from(game_id.clone()),
+                web::Query(FieldsQuery { fields: Some(fields.to_string()) }),
+            )
+        };
+
+        let response = fetch("name,id").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        let keys: Vec<&String> = body.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["id", "name"]);
+        assert_eq!(body["name"], "Space Quest");
+
+        let response = fetch("id,nmae").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(json_body(response).await["error"], "Unknown field 'nmae'");
+    }
+
+    #[actix_web::test]
+    async fn by_developer_lists_the_other_published_games() {
+        let (alice, bob) = (uuid::Uuid::new_v4().to_string(), uuid::Uuid::new_v4().to_string());
+        let game = |name: &str, developer_id: &str, status: GameStatus| game::Game {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            developer_id: developer_id.to_string(),
+            status: status.to_proto(),
+            ..Default::default()
+        };
+        let current = game("Current", &alice, GameStatus::Published);
+        let current_id = current.id.clone();
+        let games = Arc::new(
+            FakeGameService::default()
+                .with_game(current)
+                .with_game(game("Sequel", &alice, GameStatus::Published))
+                .with_game(game("Unfinished", &alice, GameStatus::Draft))
+                .with_game(game("Someone Else's", &bob, GameStatus::Published)),
+        );
+        let state = web::Data::new(app_state_with(UNREACHABLE_URL.to_string(), games.clone().serve().await));
+
+        let response = games_by_developer(
+            TestRequest::default().to_http_request(),
+            state,
+            web::Data::new(PageSizeConfig::default()),
+            web::Path::from(current_id.clone()),
+            web::Query(GamesByDeveloperQuery { limit: None, offset: None }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        let names: Vec<&str> = body["games"].as_array().unwrap().iter().map(|g| g["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["Sequel"]);
+        assert_eq!(body["total"], 1);
+
+        let requests = games.list_requests.lock().unwrap();
+        assert_eq!(requests[0].developer_id.as_deref(), Some(alice.as_str()));
+        assert_eq!(requests[0].exclude_id.as_deref(), Some(current_id.as_str()));
+        assert_eq!(requests[0].status, Some(GameStatus::Published.to_proto()));
+    }
+
+    #[actix_web::test]
+    async fn included_developers_come_from_one_batched_lookup() {
+        let (alice, bob, gone) = ("alice-id", "bob-id", "gone-id");
+        let game = |name: &str, developer_id: &str| game::Game {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            developer_id: developer_id.to_string(),
+            rating_count: 4,
+            average_rating: 4.5,
+            ..Default::default()
+        };
+        let games = Arc::new(
+            FakeGameService::default()
+                .with_game(game("First", alice))
+                .with_game(game("Second", bob))
+                .with_game(game("Third", alice))
+                .with_game(game("Orphan", gone)),
+        );
+        let users = Arc::new(
+            FakeUserService::default()
+                .with_user(user::UserMessage { id: alice.to_string(), username: "alice".to_string(), ..Default::default() })
+                .with_user(user::UserMessage { id: bob.to_string(), username: "bob".to_string(), verified: true, ..Default::default() }),
+        );
+        let state = web::Data::new(app_state_with(users.clone().serve().await, games.serve().await));
+        let list = |query: &str| {
+            list_games(
+                TestRequest::default().to_http_request(),
+                state.clone(),
+                web::Data::new(PageSizeConfig::default()),
+                web::Query::<ListGamesQuery>::from_query(query).unwrap(),
+            )
+        };
+
+        let response = list("include=developer,ratings").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(users.calls.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+        let body = json_body(response).await;
+        let games = body["games"].as_array().unwrap();
+        let developers: Vec<&serde_json::Value> = games.iter().map(|g| &g["developer"]).collect();
+        assert_eq!(developers[0]["username"], "alice");
+        assert_eq!(developers[1]["username"], "bob");
+        assert_eq!(developers[1]["verified"], true);
+        assert_eq!(developers[2]["username"], "alice");
+        assert!(developers[3].is_null());
+        for game in games {
+            assert_eq!(game["ratings"], serde_json::json!({ "average": 4.5, "count": 4 }));
+        }
+
+        // Ratings are on the games already, so they need no lookup at all.
+        let response = list("include=ratings").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(users.calls.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+        let response = list("include=reviews").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn publisher_listings_hide_unpublished_games_from_outsiders() {
+        let (publisher, rival) = (uuid::Uuid::new_v4().to_string(), uuid::Uuid::new_v4().to_string());
+        let game = |name: &str, publisher_id: &str, status: GameStatus| game::Game {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            publisher_id: Some(publisher_id.to_string()),
+            status: status.to_proto(),
+            ..Default::default()
+        };
+        let games = Arc::new(
+            FakeGameService::default()
+                .with_game(game("Released", &publisher, GameStatus::Published))
+                .with_game(game("In Progress", &publisher, GameStatus::Draft))
+                .with_game(game("Rival Hit", &rival, GameStatus::Published)),
+        );
+        let state = web::Data::new(app_state_with(UNREACHABLE_URL.to_string(), games.clone().serve().await));
+        let list = |req: HttpRequest, id: &str, status: Option<&str>| {
+            publisher_games(
+                req,
+                state.clone(),
+                web::Data::new(PageSizeConfig::default()),
+                web::Path::from(id.to_string()),
+                web::Query(PublisherGamesQuery { status: status.map(String::from), limit: None, offset: None }),
+            )
+        };
+        let names = |body: serde_json::Value| -> Vec<String> {
+            body["games"].as_array().unwrap().iter().map(|g| g["name"].as_str().unwrap().to_string()).collect()
+        };
+        let anonymous = || TestRequest::default().to_http_request();
+
+        for status in [None, Some("published")] {
+            let response = list(anonymous(), &publisher, status).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(names(json_body(response).await), ["Released"]);
+        }
+
+        let requests_before = games.list_requests.lock().unwrap().len();
+        for req in [anonymous(), signed_in(&rival, "developer")] {
+            let response = list(req, &publisher, Some("draft")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+        assert_eq!(games.list_requests.lock().unwrap().len(), requests_before);
+
+        let response = list(signed_in(&publisher, "developer"), &publisher, None).await.unwrap();
+        assert_eq!(names(json_body(response).await), ["Released", "In Progress"]);
+
+        let admin = signed_in(&uuid::Uuid::new_v4().to_string(), "admin");
+        let response = list(admin, &publisher, Some("draft")).await.unwrap();
+        assert_eq!(names(json_body(response).await), ["In Progress"]);
+
+        let response = list(anonymous(), "not-a-uuid", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn conflicts_say_whether_the_email_or_the_username_is_taken() {
+        let (alice, bob) = (uuid::Uuid::new_v4().to_string(), uuid::Uuid::new_v4().to_string());
+        let users = Arc::new(
+            FakeUserService::default()
+                .with_user(user::UserMessage { id: alice.clone(), email: "alice@example.com".to_string(), username: "alice".to_string(), ..Default::default() })
+                .with_user(user::UserMessage { id: bob.clone(), email: "bob@example.com".to_string(), username: "bob".to_string(), ..Default::default() }),
+        );
+        let state = web::Data::new(app_state(users.serve().await));
+        let change = |email: Option<&str>, username: Option<&str>| {
+            update_user(
+                signed_in(&bob, "player"),
+                state.clone(),
+                web::Path::from(bob.clone()),
+                web::Json(UpdateUserDto { email: email.map(String::from), username: username.map(String::from), password: None, role: None }),
+            )
+        };
+
+        let response = change(Some("alice@example.com"), None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            json_body(response).await,
+            serde_json::json!({
+                "error": "User with this email already exists",
+                "code": "EMAIL_TAKEN",
+                "details": { "field": "email" },
+            })
+        );
+
+        let response = change(None, Some("alice")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            json_body(response).await,
+            serde_json::json!({
+                "error": "User with this username already exists",
+                "code": "USERNAME_TAKEN",
+                "details": { "field": "username" },
+            })
+        );
+
+        let response = change(Some("bob@example.com"), Some("bob")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn credentials_cannot_be_changed_while_impersonating() {
+        let user_id = uuid::Uuid::new_v4().to_string();
+        let change = |email: Option<&str>, password: Option<&str>| UpdateUserDto {
+            email: email.map(String::from),
+            username: None,
+            password: password.map(String::from),
+            role: None,
+        };
+
+        for dto in [change(Some("new@example.com"), None), change(None, Some("hunter22hunter22"))] {
+            let response = update_user(
+                impersonating("admin-1", &user_id),
+                web::Data::new(app_state(UNREACHABLE_URL.to_string())),
+                web::Path::from(user_id.clone()),
+                web::Json(dto),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[actix_web::test]
+    async fn status_params_are_a_real_status_or_absent() {
+        assert_eq!(parse_status_param(None).unwrap(), None);
+        for status in GameStatus::SELECTABLE {
+            assert_eq!(parse_status_param(Some(&status.to_string())).unwrap(), Some(status.to_proto()));
+        }
+
+        for raw in ["unspecified", "0", "", "live"] {
+            let response = parse_status_param(Some(raw)).unwrap_err();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{raw:?}");
+            assert_eq!(
+                json_body(response).await["error"],
+                format!("Invalid status '{}'. Must be one of: draft, under_review, published, suspended", raw)
+            );
+        }
+    }
+
+    #[test]
+    fn blank_search_queries_are_no_search_at_all() {
+        for raw in [None, Some(""), Some("   "), Some("\t\n")] {
+            assert_eq!(search_param(raw), None, "{raw:?}");
+        }
+        assert_eq!(search_param(Some("  space quest ")), Some("space quest".to_string()));
+    }
+
+    #[test]
+    fn descriptions_are_always_strings_and_never_null() {
+        let without = serde_json::to_value(game_to_dto(game::Game::default())).unwrap();
+        assert_eq!(without["description"], "");
+
+        let game = game::Game { description: "Explore the stars".to_string(), ..Default::default() };
+        let with = serde_json::to_value(game_to_dto(game)).unwrap();
+        assert_eq!(with["description"], "Explore the stars");
+    }
+
+    #[actix_web::test]
+    async fn empty_and_unset_optional_game_strings_are_both_null() {
+        const OPTIONAL: [&str; 4] = ["publisher_id", "cover_image", "trailer_url", "release_date"];
+        let empty_id = uuid::Uuid::new_v4().to_string();
+        let unset_id = uuid::Uuid::new_v4().to_string();
+        let games = Arc::new(
+            FakeGameService::default()
+                .with_game(game::Game {
+                    id: empty_id.clone(),
+                    publisher_id: Some(String::new()),
+                    cover_image: Some(String::new()),
+                    trailer_url: Some("  ".to_string()),
+                    release_date: Some(String::new()),
+                    ..Default::default()
+                })
+                .with_game(game::Game { id: unset_id.clone(), ..Default::default() }),
+        );
+        let state = web::Data::new(app_state_with(UNREACHABLE_URL.to_string(), games.serve().await));
+
+        for id in [&empty_id, &unset_id] {
+            let response = get_game(
+                TestRequest::default().to_http_request(),
+                state.clone(),
+                web::Path::from(id.clone()),
+                web::Query(FieldsQuery { fields: None }),
+            )
+            .await
+            .unwrap();
+            let body = json_body(response).await;
+            for field in OPTIONAL {
+                assert!(body[field].is_null(), "{field} of {id}: {body}");
+            }
+        }
+
+        let response = list_games(
+            TestRequest::default().to_http_request(),
+            state.clone(),
+            web::Data::new(PageSizeConfig::default()),
+            web::Query::<ListGamesQuery>::from_query("").unwrap(),
+        )
+        .await
+        .unwrap();
+        let body = json_body(response).await;
+        let listed = body["games"].as_array().unwrap();
+        assert_eq!(listed.len(), 2);
+        for game in listed {
+            for field in OPTIONAL {
+                assert!(game[field].is_null(), "{field}: {game}");
+            }
+        }
+
+        let set = serde_json::to_value(game_to_dto(game::Game {
+            publisher_id: Some("pub-1".to_string()),
+            cover_image: Some("cover.png".to_string()),
+            trailer_url: Some("https://example.com/t".to_string()),
+            release_date: Some("2024-01-01".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(set["publisher_id"], "pub-1");
+        assert_eq!(set["cover_image"], "cover.png");
+        assert_eq!(set["trailer_url"], "https://example.com/t");
+        assert_eq!(set["release_date"], "2024-01-01");
+    }
+
+    #[test]
+    fn missing_upstream_timestamps_are_sent_as_null() {
+        let ts = prost_types::Timestamp { seconds: 1_700_000_000, nanos: 5 };
+        assert_eq!(epoch_seconds(Some(ts)), Some("1700000000".to_string()));
+        assert_eq!(epoch_seconds(None), None);
+
+        let game = serde_json::to_value(game_to_dto(game::Game::default())).unwrap();
+        assert!(game["created_at"].is_null());
+        assert!(game["updated_at"].is_null());
+
+        let preview = serde_json::to_value(DraftExpiryPreviewResponse {
+            cutoff: epoch_seconds(None),
+            drafts: vec![StaleDraftDto {
+                id: "g1".to_string(),
+                name: "Draft".to_string(),
+                developer_id: "d1".to_string(),
+                updated_at: epoch_seconds(None),
+            }],
+        })
+        .unwrap();
+        assert!(preview["cutoff"].is_null());
+        assert!(preview["drafts"][0]["updated_at"].is_null());
+
+        let impersonation = serde_json::to_value(ImpersonationDto {
+            token: "t".to_string(),
+            expires_at: epoch_seconds(None),
+            user: user_to_dto(user::UserMessage::default(), true),
+        })
+        .unwrap();
+        assert!(impersonation["expires_at"].is_null());
+    }
+
+    #[actix_web::test]
+    async fn unimplemented_rpcs_are_501_and_unknown_routes_404() {
+        // The fake answers ListUsers with the generated `Unimplemented` stub.
+        let url = Arc::new(FakeUserService::default()).serve().await;
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_state(url)))
+                .app_data(web::Data::new(PageSizeConfig::default()))
+                .configure(routes),
+        )
+        .await;
+
+        let request = TestRequest::get().uri("/api/users").to_request();
+        let response = actix_web::test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+        let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+        assert_eq!(body["code"], "UNIMPLEMENTED");
+        assert!(body["error"].as_str().unwrap().starts_with("Not implemented"));
+
+        let request = TestRequest::get().uri("/api/no-such-route").to_request();
+        let response = actix_web::test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn unknown_roles_categories_and_statuses_are_a_uniform_400() {
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_state(UNREACHABLE_URL.to_string())))
+                .app_data(web::Data::new(signup::SignupPolicy { default_role: Role::Player, allow_developer: true }))
+                .app_data(web::JsonConfig::default().error_handler(errors::json_error))
+                .configure(routes),
+        )
+        .await;
+        let game_id = uuid::Uuid::new_v4();
+        let new_game = |categories: serde_json::Value, status: &str| {
+            serde_json::json!({
+                "name": "Space Quest",
+                "developer_id": uuid::Uuid::new_v4(),
+                "tags": [],
+                "platforms": [],
+                "screenshots": [],
+                "price": "19.99",
+                "status": status,
+                "categories": categories,
+            })
+        };
+        let cases = [
+            (
+                TestRequest::post().uri("/api/users").set_json(serde_json::json!({
+                    "email": "a@example.com",
+                    "username": "alice",
+                    "password": "correct horse battery",
+                    "role": "root",
+                })),
+                "Invalid role 'root'. Must be one of: player, developer, admin",
+            ),
+            (
+                TestRequest::put().uri("/api/users/u1").set_json(serde_json::json!({ "role": "superuser" })),
+                "Invalid role 'superuser'. Must be one of: player, developer, admin",
+            ),
+            (
+                TestRequest::post().uri("/api/games").set_json(new_game(serde_json::json!(["rpg", "shooter"]), "draft")),
+                "Invalid category 'shooter'. Must be one of: action, rpg, strategy, sports, racing, adventure, simulation, puzzle",
+            ),
+            (
+                TestRequest::put()
+                    .uri(&format!("/api/games/{game_id}"))
+                    .set_json(serde_json::json!({ "categories": ["Action"] })),
+                "Invalid category 'Action'. Must be one of: action, rpg, strategy, sports, racing, adventure, simulation, puzzle",
+            ),
+        ];
+
+        for (request, message) in cases {
+            let response = actix_web::test::call_service(&app, request.to_request()).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{message}");
+            let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+            assert_eq!(body, serde_json::json!({ "error": message }));
+        }
+
+        let request = TestRequest::post().uri("/api/games").set_json(new_game(serde_json::json!(["rpg"]), "released"));
+        let response = actix_web::test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+        assert!(body["error"].as_str().unwrap().starts_with("Invalid status 'released'"), "{body}");
+
+        // A well-formed body gets as far as the (unreachable) game service
+        let request = TestRequest::post().uri("/api/games").set_json(new_game(serde_json::json!(["rpg"]), "draft"));
+        let response = actix_web::test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,161 @@
+use actix_web::{HttpRequest, HttpResponse, http::header};
+use serde::Serialize;
+
+const MSGPACK_TYPES: [&str; 2] = ["application/msgpack", "application/x-msgpack"];
+const JSON_TYPES: [&str; 3] = ["application/json", "application/*", "*/*"];
+
+/// Response body encoding chosen from the `Accept` header. The DTOs are the
+/// same either way; MessagePack just drops the text overhead for clients on
+/// slow links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    MsgPack,
+}
+
+impl Format {
+    /// JSON unless the client names MessagePack explicitly with at least
+    /// the quality it gives JSON. Wildcards never select MessagePack.
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let Some(accept) = req.headers().get(header::ACCEPT).and_then(|v| v.to_str().ok()) else {
+            return Format::Json;
+        };
+
+        let mut json_q = 0.0_f32;
+        let mut msgpack_q = 0.0_f32;
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let q = parts
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if MSGPACK_TYPES.contains(&media.as_str()) {
+                msgpack_q = msgpack_q.max(q);
+            } else if JSON_TYPES.contains(&media.as_str()) {
+                json_q = json_q.max(q);
+            }
+        }
+
+        if msgpack_q > 0.0 && msgpack_q >= json_q {
+            Format::MsgPack
+        } else {
+            Format::Json
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::MsgPack => MSGPACK_TYPES[0],
+        }
+    }
+
+    /// MessagePack maps keep the field names so both bodies have the same
+    /// shape.
+    pub fn encode<T: Serialize>(self, body: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Format::Json => serde_json::to_vec(body).map_err(|e| e.to_string()),
+            Format::MsgPack => rmp_serde::to_vec_named(body).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+pub fn serialization_error(e: String) -> HttpResponse {
+    HttpResponse::InternalServerError().json(serde_json::json!({
+        "error": format!("Failed to serialize response: {}", e)
+    }))
+}
+
+/// A 200 with `body` in the format the client asked for.
+pub fn ok<T: Serialize>(req: &HttpRequest, body: &T) -> HttpResponse {
+    let format = Format::from_request(req);
+    match format.encode(body) {
+        Ok(bytes) => HttpResponse::Ok()
+            .insert_header((header::VARY, "Accept"))
+            .content_type(format.content_type())
+            .body(bytes),
+        Err(e) => serialization_error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Body {
+        id: String,
+        price: i64,
+        tags: Vec<String>,
+        email: Option<String>,
+    }
+
+    fn body() -> Body {
+        Body {
+            id: "g1".to_string(),
+            price: 1999,
+            tags: vec!["indie".to_string()],
+            email: None,
+        }
+    }
+
+    fn accepting(accept: Option<&str>) -> HttpRequest {
+        let mut req = TestRequest::default();
+        if let Some(accept) = accept {
+            req = req.insert_header((header::ACCEPT, accept));
+        }
+        req.to_http_request()
+    }
+
+    async fn respond(accept: Option<&str>) -> (String, Vec<u8>) {
+        let response = ok(&accepting(accept), &body());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(header::VARY).unwrap(), "Accept");
+        let content_type = response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap().to_string();
+        let bytes = actix_web::body::to_bytes(response.into_body()).await.unwrap();
+        (content_type, bytes.to_vec())
+    }
+
+    #[actix_web::test]
+    async fn msgpack_bodies_decode_to_the_same_dto() {
+        for accept in ["application/msgpack", "application/x-msgpack"] {
+            let (content_type, bytes) = respond(Some(accept)).await;
+            assert_eq!(content_type, "application/msgpack");
+            assert_eq!(rmp_serde::from_slice::<Body>(&bytes).unwrap(), body());
+        }
+    }
+
+    #[actix_web::test]
+    async fn json_is_the_default_and_the_fallback() {
+        for accept in [None, Some("application/json"), Some("*/*"), Some("text/html"), Some("application/xml")] {
+            let (content_type, bytes) = respond(accept).await;
+            assert_eq!(content_type, "application/json", "{accept:?}");
+            assert_eq!(serde_json::from_slice::<Body>(&bytes).unwrap(), body());
+        }
+    }
+
+    #[test]
+    fn quality_values_decide_between_the_two() {
+        let format = |accept| Format::from_request(&accepting(Some(accept)));
+
+        assert_eq!(format("application/json;q=0.5, application/msgpack"), Format::MsgPack);
+        assert_eq!(format("application/json, application/msgpack;q=0.5"), Format::Json);
+        assert_eq!(format("application/msgpack, application/json"), Format::MsgPack);
+        assert_eq!(format("application/msgpack;q=0"), Format::Json);
+        assert_eq!(format("*/*, application/msgpack;q=0.9"), Format::Json);
+        assert_eq!(format("APPLICATION/MSGPACK"), Format::MsgPack);
+    }
+
+    #[test]
+    fn msgpack_keeps_field_names() {
+        let bytes = Format::MsgPack.encode(&body()).unwrap();
+        let value: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(value, serde_json::to_value(body()).unwrap());
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use actix_web::{HttpRequest, HttpResponse, web};
+use actix_ws::Message;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use utoipa::IntoParams;
+
+use crate::auth::{self, JwtVerifier};
+use crate::events::GameEventHub;
+use crate::{epoch_seconds, errors, game};
+
+const TOPIC_CAPACITY: usize = 64;
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(45);
+const TOKEN_SUBPROTOCOL: &str = "access_token";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub kind: String,
+    pub game_id: Option<String>,
+    pub message: String,
+    pub created_at: Option<String>,
+}
+
+/// Per-user notification topics. A topic exists only while at least one
+/// connection for that user is open.
+pub struct NotificationHub {
+    topics: Mutex<HashMap<String, broadcast::Sender<Notification>>>,
+}
+
+impl NotificationHub {
+    pub fn new() -> Self {
+        Self {
+            topics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn subscribe(&self, user_id: &str) -> broadcast::Receiver<Notification> {
+        let mut topics = self.topics.lock().unwrap();
+        topics
+            .entry(user_id.to_string())
+            .or_insert_with(|| broadcast::channel(TOPIC_CAPACITY).0)
+            .subscribe()
+    }
+
+    pub fn publish(&self, user_id: &str, notification: Notification) {
+        let topics = self.topics.lock().unwrap();
+        if let Some(sender) = topics.get(user_id) {
+            let _ = sender.send(notification);
+        }
+    }
+
+    fn prune(&self, user_id: &str) {
+        let mut topics = self.topics.lock().unwrap();
+        if topics.get(user_id).is_some_and(|sender| sender.receiver_count() == 0) {
+            topics.remove(user_id);
+        }
+    }
+
+    /// Routes game events to the owning developer's topic. Developers are the
+    /// only recipients for now: there are no wishlists or review replies yet
+    /// to tell players about.
+    pub fn spawn_game_event_feed(hub: web::Data<NotificationHub>, events: &GameEventHub) {
+        let mut receiver = events.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                if let Some((user_id, notification)) = game_event_to_notification(event) {
+                    hub.publish(&user_id, notification);
+                }
+            }
+        });
+    }
+}
+
+fn game_event_to_notification(event: game::GameEvent) -> Option<(String, Notification)> {
+    let game = event.game?;
+
+    let (kind, message) = match event.event_type {
+        2 => ("game_published", format!("{} is now live", game.name)),
+        3 => ("game_price_changed", format!("{} has a new price", game.name)),
+        _ => return None,
+    };
+
+    let notification = Notification {
+        kind: kind.to_string(),
+        game_id: Some(event.game_id),
+        message,
+        created_at: epoch_seconds(event.occurred_at),
+    };
+
+    Some((game.developer_id, notification))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WsQuery {
+    token: Option<String>,
+}
+
+/// Browsers can't set headers on a WebSocket handshake, so the token may also
+/// travel as `Sec-WebSocket-Protocol: access_token, <jwt>`.
+fn subprotocol_token(req: &HttpRequest) -> Option<String> {
+    let protocols = req
+        .headers()
+        .get(actix_web::http::header::SEC_WEBSOCKET_PROTOCOL)?
+        .to_str()
+        .ok()?;
+
+    let mut parts = protocols.split(',').map(|p| p.trim());
+    parts.find(|p| *p == TOKEN_SUBPROTOCOL)?;
+    parts.next().map(String::from)
+}
+
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "notifications",
+    params(WsQuery),
+    responses(
+        (status = 101, description = "WebSocket upgrade; notifications arrive as JSON text frames"),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn notifications_ws(
+    req: HttpRequest,
+    body: web::Payload,
+    query: web::Query<WsQuery>,
+    jwt: web::Data<JwtVerifier>,
+    hub: web::Data<NotificationHub>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let from_subprotocol = subprotocol_token(&req);
+    let token = query
+        .token
+        .clone()
+        .or_else(|| from_subprotocol.clone())
+        .or_else(|| auth::bearer_token(&req).map(String::from));
+
+    let claims = match token.as_deref().map(|t| jwt.verify(t)) {
+        Some(Ok(claims)) => claims,
+        Some(Err(_)) => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Invalid or expired token"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Missing token"
+            })));
+        }
+    };
+
+    // A token in the query string or subprotocol bypasses auth_middleware
+    if let Err(response) = auth::check_session(&req, &claims).await {
+        return Ok(response);
+    }
+
+    let (mut response, session, messages) = actix_ws::handle(&req, body)?;
+
+    if from_subprotocol.is_some() {
+        response.headers_mut().insert(
+            actix_web::http::header::SEC_WEBSOCKET_PROTOCOL,
+            actix_web::http::header::HeaderValue::from_static(TOKEN_SUBPROTOCOL),
+        );
+    }
+
+    let notifications = hub.subscribe(&claims.sub);
+    actix_web::rt::spawn(run_session(session, messages, notifications, hub, claims.sub));
+
+    Ok(response)
+}
+
+async fn run_session(
+    mut session: actix_ws::Session,
+    mut messages: actix_ws::MessageStream,
+    mut notifications: broadcast::Receiver<Notification>,
+    hub: web::Data<NotificationHub>,
+    user_id: String,
+) {
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    loop {
+        tokio::select! {
+            message = messages.recv() => match message {
+                Some(Ok(Message::Ping(bytes))) => {
+                    last_seen = Instant::now();
+                    if session.pong(&bytes).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => last_seen = Instant::now(),
+            },
+            notification = notifications.recv() => match notification {
+                Ok(notification) => {
+                    let frame = serde_json::to_string(&notification).unwrap_or_default();
+                    if session.text(frame).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > CLIENT_TIMEOUT {
+                    break;
+                }
+                if session.ping(b"").await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    let _ = session.close(None).await;
+    drop(notifications);
+    hub.prune(&user_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::test::{self as actix_test, TestRequest};
+    use actix_web::{App, HttpServer};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    use super::*;
+    use crate::role::Role;
+    use crate::testing::{FakeUserService, app_state};
+
+    const SECRET: &str = "test-secret";
+
+    fn event(event_type: i32) -> game::GameEvent {
+        game::GameEvent {
+            game_id: "game-1".to_string(),
+            event_type,
+            game: Some(game::Game {
+                name: "Hollow".to_string(),
+                developer_id: "dev-1".to_string(),
+                ..Default::default()
+            }),
+            occurred_at: None,
+        }
+    }
+
+    #[test]
+    fn published_and_repriced_games_notify_their_developer() {
+        let (user_id, notification) = game_event_to_notification(event(2)).unwrap();
+        assert_eq!(user_id, "dev-1");
+        assert_eq!(notification.kind, "game_published");
+
+        let (user_id, notification) = game_event_to_notification(event(3)).unwrap();
+        assert_eq!(user_id, "dev-1");
+        assert_eq!(notification.kind, "game_price_changed");
+
+        assert!(game_event_to_notification(event(1)).is_none());
+    }
+
+    #[actix_web::test]
+    async fn revoked_tokens_cannot_connect() {
+        let users = Arc::new(FakeUserService::default().with_token_version("alice", 1));
+        let jwt = JwtVerifier::new(SECRET, 0);
+        let token = jwt.issue("alice", Role::Player, 0).unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_state(users.serve().await)))
+                .app_data(web::Data::new(jwt))
+                .app_data(web::Data::new(NotificationHub::new()))
+                .route("/ws", web::get().to(notifications_ws)),
+        )
+        .await;
+
+        let request = TestRequest::get().uri(&format!("/ws?token={}", token)).to_request();
+        let response = actix_test::call_service(&app, request).await;
+
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    /// The opcode and payload of the next frame from the server, which
+    /// never masks and sends only short frames here.
+    async fn read_frame(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+        let mut header = [0u8; 2];
+        stream.read_exact(&mut header).await.unwrap();
+        let mut payload = vec![0u8; usize::from(header[1] & 0x7f)];
+        stream.read_exact(&mut payload).await.unwrap();
+        (header[0] & 0x0f, payload)
+    }
+
+    #[actix_web::test]
+    async fn published_notifications_reach_the_connected_user() {
+        let hub = web::Data::new(NotificationHub::new());
+        let state = web::Data::new(app_state(Arc::new(FakeUserService::default()).serve().await));
+        let jwt = web::Data::new(JwtVerifier::new(SECRET, 0));
+        let token = jwt.issue("alice", Role::Player, 0).unwrap();
+        let server = {
+            let hub = hub.clone();
+            HttpServer::new(move || {
+                App::new()
+                    .app_data(state.clone())
+                    .app_data(jwt.clone())
+                    .app_data(hub.clone())
+                    .route("/ws", web::get().to(notifications_ws))
+            })
+            .workers(1)
+            .bind("127.0.0.1:0")
+            .unwrap()
+        };
+        let addr = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let handshake = format!(
+            "GET /ws?token={} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+            token, addr
+        );
+        stream.write_all(handshake.as_bytes()).await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        assert!(head.starts_with(b"HTTP/1.1 101"));
+
+        // Subscribed before the upgrade was answered
+        hub.publish(
+            "alice",
+            Notification {
+                kind: "game_published".to_string(),
+                game_id: Some("game-1".to_string()),
+                message: "Hollow is now live".to_string(),
+                created_at: Some("0".to_string()),
+            },
+        );
+
+        let text = loop {
+            match read_frame(&mut stream).await {
+                (0x1, payload) => break String::from_utf8(payload).unwrap(),
+                (0x9, _) => continue,
+                (opcode, _) => panic!("unexpected opcode {:#x}", opcode),
+            }
+        };
+        let frame: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(frame["kind"], "game_published");
+        assert_eq!(frame["message"], "Hollow is now live");
+    }
+}
@@ -0,0 +1,184 @@
+use actix_web::HttpResponse;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::{DeveloperSummary, RatingSummary, api_keys, audit, developer_profile, errors, events, export, game_page, notifications, permissions, releases, search};
+
+/// Everything the gateway serves, built from the handler annotations and the
+/// DTOs they reference, so adding a route means adding it here as well;
+/// the tests check this list against `routes`.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "GameHub gateway",
+        description = "HTTP front for the user and game services. Every error response carries an \
+                       `ErrorEnvelope`; any route can also answer 429 when the caller is rate limited."
+    ),
+    paths(
+        crate::create_user,
+        crate::batch_create_users,
+        crate::get_user,
+        crate::update_user,
+        crate::delete_user,
+        crate::impersonate_user,
+        crate::revoke_user_sessions,
+        crate::set_developer_verified,
+        crate::users_list,
+        search::search,
+        crate::create_game,
+        crate::catalog_stats,
+        crate::category_price_stats,
+        crate::recompute_game_stats,
+        crate::preview_draft_expiry,
+        crate::purge_deleted_games,
+        crate::suggest_games,
+        releases::new_releases,
+        releases::upcoming_games,
+        crate::price_facets,
+        crate::get_game,
+        crate::bulk_set_prices,
+        crate::update_game,
+        crate::delete_game,
+        crate::list_games,
+        events::game_events,
+        crate::game_developer,
+        game_page::game_page,
+        permissions::my_permissions,
+        crate::games_by_developer,
+        crate::game_price_history,
+        crate::game_review_summary,
+        crate::schedule_sale,
+        crate::cancel_sale,
+        export::developer_export,
+        crate::developer_status_counts,
+        developer_profile::get_developer_profile,
+        developer_profile::create_developer_profile,
+        developer_profile::update_developer_profile,
+        crate::publisher_games,
+        audit::list_audit,
+        api_keys::create_api_key,
+        api_keys::list_api_keys,
+        api_keys::revoke_api_key,
+        notifications::notifications_ws,
+        openapi_json,
+    ),
+    components(schemas(errors::ErrorEnvelope, DeveloperSummary, RatingSummary)),
+    modifiers(&SecuritySchemes),
+    security((), ("bearer" = []), ("api_key" = []))
+)]
+pub struct ApiDoc;
+
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+    }
+}
+
+/// `GET /api-docs/openapi.json`
+#[utoipa::path(
+    get,
+    path = "/api-docs/openapi.json",
+    tag = "meta",
+    responses(
+        (status = 200, description = "This document", content_type = "application/json"),
+    )
+)]
+pub async fn openapi_json() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use actix_web::http::Method;
+    use actix_web::test::{self as actix_test, TestRequest};
+    use actix_web::{App, FromRequest, Handler, Responder};
+    use serde_json::Value;
+
+    use crate::Routes;
+
+    /// Method and path of every route registered, lowercase method first.
+    #[derive(Default)]
+    struct Registered(BTreeSet<(String, String)>);
+
+    impl Routes for Registered {
+        fn handle<F, Args>(&mut self, method: Method, path: &'static str, _handler: F) -> &mut Self
+        where
+            F: Handler<Args>,
+            Args: FromRequest + 'static,
+            F::Output: Responder + 'static,
+        {
+            self.0.insert((method.as_str().to_lowercase(), path.to_string()));
+            self
+        }
+    }
+
+    async fn served_document() -> Value {
+        let app = actix_test::init_service(App::new().configure(crate::routes)).await;
+        let request = TestRequest::get().uri("/api-docs/openapi.json").to_request();
+        actix_test::call_and_read_body_json(&app, request).await
+    }
+
+    /// Every `$ref` string anywhere under `value`.
+    fn refs<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(target)) = map.get("$ref") {
+                    found.push(target);
+                }
+                map.values().for_each(|v| refs(v, found));
+            }
+            Value::Array(items) => items.iter().for_each(|v| refs(v, found)),
+            _ => {}
+        }
+    }
+
+    #[actix_web::test]
+    async fn served_document_is_valid_openapi() {
+        let document = served_document().await;
+
+        assert!(document["openapi"].as_str().is_some_and(|v| v.starts_with("3.")));
+        serde_json::from_value::<utoipa::openapi::OpenApi>(document.clone()).unwrap();
+
+        let mut found = Vec::new();
+        refs(&document, &mut found);
+        for target in found {
+            let pointer = target.strip_prefix('#').unwrap_or_else(|| panic!("external $ref {}", target));
+            assert!(document.pointer(pointer).is_some(), "dangling $ref {}", target);
+        }
+
+        for (path, item) in document["paths"].as_object().unwrap() {
+            for (method, operation) in item.as_object().unwrap() {
+                let responses = operation["responses"].as_object();
+                assert!(responses.is_some_and(|r| !r.is_empty()), "{} {} has no responses", method, path);
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn served_document_lists_every_registered_route() {
+        let mut registered = Registered::default();
+        crate::routes(&mut registered);
+
+        let document = served_document().await;
+        let documented: BTreeSet<(String, String)> = document["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| item.as_object().unwrap().keys().map(move |method| (method.clone(), path.clone())))
+            .collect();
+
+        assert_eq!(documented, registered.0);
+    }
+}
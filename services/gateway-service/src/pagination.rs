@@ -0,0 +1,149 @@
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::header::{self, HeaderName, HeaderValue},
+};
+
+const X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
+
+/// Rebuilds the request URL with `limit`/`offset` replaced, keeping every
+/// other query parameter as sent.
+fn page_url(req: &HttpRequest, limit: i32, offset: i32) -> String {
+    let info = req.connection_info();
+
+    let mut params: Vec<&str> = req
+        .query_string()
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && key != "limit" && key != "offset"
+        })
+        .collect();
+
+    let paging = format!("limit={}&offset={}", limit, offset);
+    params.push(&paging);
+
+    format!(
+        "{}://{}{}?{}",
+        info.scheme(),
+        info.host(),
+        req.path(),
+        params.join("&")
+    )
+}
+
+/// Adds `X-Total-Count` and an RFC 8288 `Link` header with `next`/`prev`
+/// relations. The JSON body keeps its own `total` field.
+pub fn insert_headers(
+    res: &mut HttpResponse,
+    req: &HttpRequest,
+    limit: i32,
+    offset: i32,
+    total: i64,
+) {
+    res.headers_mut().insert(X_TOTAL_COUNT, HeaderValue::from(total));
+
+    if limit <= 0 {
+        return;
+    }
+
+    let mut links = Vec::new();
+
+    if i64::from(offset) + i64::from(limit) < total {
+        links.push(format!(
+            "<{}>; rel=\"next\"",
+            page_url(req, limit, offset + limit)
+        ));
+    }
+
+    if offset > 0 {
+        links.push(format!(
+            "<{}>; rel=\"prev\"",
+            page_url(req, limit, (offset - limit).max(0))
+        ));
+    }
+
+    if links.is_empty() {
+        return;
+    }
+
+    if let Ok(value) = HeaderValue::from_str(&links.join(", ")) {
+        res.headers_mut().insert(header::LINK, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    /// The `X-Total-Count` and `Link` headers for a page of `/api/games`.
+    fn headers(query: &str, limit: i32, offset: i32, total: i64) -> (String, Option<String>) {
+        let req = TestRequest::get()
+            .uri(&format!("/api/games?{}", query))
+            .insert_header((header::HOST, "gateway.test"))
+            .to_http_request();
+        let mut res = HttpResponse::Ok().finish();
+
+        insert_headers(&mut res, &req, limit, offset, total);
+
+        let total = res.headers().get(X_TOTAL_COUNT).unwrap().to_str().unwrap().to_string();
+        let link = res.headers().get(header::LINK).map(|v| v.to_str().unwrap().to_string());
+        (total, link)
+    }
+
+    #[test]
+    fn the_first_page_links_only_to_the_next() {
+        let (total, link) = headers("limit=10&offset=0", 10, 0, 25);
+
+        assert_eq!(total, "25");
+        assert_eq!(link.as_deref(), Some("<http://gateway.test/api/games?limit=10&offset=10>; rel=\"next\""));
+    }
+
+    #[test]
+    fn a_middle_page_links_both_ways() {
+        let (total, link) = headers("limit=10&offset=10", 10, 10, 25);
+
+        assert_eq!(total, "25");
+        assert_eq!(
+            link.as_deref(),
+            Some(
+                "<http://gateway.test/api/games?limit=10&offset=20>; rel=\"next\", \
+                 <http://gateway.test/api/games?limit=10&offset=0>; rel=\"prev\""
+            )
+        );
+    }
+
+    #[test]
+    fn the_last_page_links_only_to_the_previous() {
+        let (total, link) = headers("limit=10&offset=20", 10, 20, 25);
+
+        assert_eq!(total, "25");
+        assert_eq!(link.as_deref(), Some("<http://gateway.test/api/games?limit=10&offset=10>; rel=\"prev\""));
+    }
+
+    #[test]
+    fn a_single_page_has_no_links() {
+        let (total, link) = headers("", 10, 0, 3);
+
+        assert_eq!(total, "3");
+        assert_eq!(link, None);
+    }
+
+    #[test]
+    fn other_query_parameters_are_kept() {
+        let (_, link) = headers("category=rpg&limit=10&search=dragon", 10, 0, 25);
+
+        assert_eq!(
+            link.as_deref(),
+            Some("<http://gateway.test/api/games?category=rpg&search=dragon&limit=10&offset=10>; rel=\"next\"")
+        );
+    }
+
+    #[test]
+    fn prev_never_goes_below_zero() {
+        let (_, link) = headers("limit=10&offset=5", 10, 5, 8);
+
+        assert_eq!(link.as_deref(), Some("<http://gateway.test/api/games?limit=10&offset=0>; rel=\"prev\""));
+    }
+}
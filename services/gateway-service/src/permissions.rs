@@ -0,0 +1,194 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::auth::{self, Principal};
+use crate::role::Role;
+use crate::{AppState, errors, request_id, user};
+
+/// What the caller may do, for the UI to decide what to show. The gateway
+/// authorizes by the token's role and the user service re-checks the stored
+/// one, so a capability needs both; `stale_token` says they disagree and a
+/// fresh login would change the answer.
+#[derive(Serialize, ToSchema)]
+struct PermissionsResponse {
+    user_id: String,
+    /// The role as currently stored, which may differ from the token's.
+    role: String,
+    is_admin: bool,
+    is_developer: bool,
+    /// Storefront badge; only developers can have it.
+    verified: bool,
+    stale_token: bool,
+    /// Set when an admin is acting as this user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    impersonated_by: Option<String>,
+    can_manage_api_keys: bool,
+    /// Import, verify, impersonate and revoke sessions for other users.
+    /// Never granted while impersonating.
+    can_manage_users: bool,
+}
+
+/// `GET /api/users/me/permissions` - the signed-in user's capabilities,
+/// from their token plus one lookup of their current account.
+#[utoipa::path(
+    get,
+    path = "/api/users/me/permissions",
+    tag = "users",
+    responses(
+        (status = 200, description = "The caller's capabilities", body = PermissionsResponse),
+        (status = 401, description = "Missing or invalid credentials", body = errors::ErrorEnvelope),
+        (status = 403, description = "Called with an API key", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn my_permissions(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let claims = match auth::principal(&req) {
+        Some(Principal::User(claims)) => claims,
+        Some(Principal::ApiKey { .. }) => {
+            return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "API keys have no user permissions"
+            })));
+        }
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Authentication required"
+            })));
+        }
+    };
+
+    let request = request_id::grpc_request(&req, user::GetUserRequest { id: claims.sub.clone() });
+
+    let mut client = data.user_client();
+    let stored = match client.get_user(request).await {
+        Ok(response) => response.into_inner().user,
+        Err(status) if status.code() == tonic::Code::NotFound => None,
+        Err(status) => {
+            return Ok(errors::tonic_status_to_http(&status));
+        }
+    };
+    // The auth middleware has just checked the account, so this only
+    // happens if it was deleted in between
+    let Some(stored) = stored else {
+        return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired token"
+        })));
+    };
+
+    let role = Role::from_proto(stored.role);
+    let holds = |wanted: Role| role == Some(wanted) && claims.has_role(wanted);
+    let is_admin = holds(Role::Admin);
+    let is_developer = holds(Role::Developer);
+    let impersonating = claims.act_as.is_some();
+
+    Ok(HttpResponse::Ok().json(PermissionsResponse {
+        user_id: stored.id,
+        role: role.map_or_else(|| "unknown".to_string(), |r| r.to_string()),
+        is_admin,
+        is_developer,
+        verified: stored.verified,
+        stale_token: role.is_none_or(|r| !claims.has_role(r)),
+        impersonated_by: claims.act_as,
+        can_manage_api_keys: is_developer || is_admin,
+        can_manage_users: is_admin && !impersonating,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::HttpMessage;
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::auth::Claims;
+    use crate::testing::{FakeUserService, UNREACHABLE_URL, app_state_with, claims, signed_in};
+
+    fn stored(id: &str, role: user::UserRole, verified: bool) -> user::UserMessage {
+        user::UserMessage {
+            id: id.to_string(),
+            username: id.to_string(),
+            role: role as i32,
+            verified,
+            ..Default::default()
+        }
+    }
+
+    async fn permissions(req: HttpRequest) -> (StatusCode, Value) {
+        let users = Arc::new(
+            FakeUserService::default()
+                .with_user(stored("player-1", user::UserRole::Player, false))
+                .with_user(stored("dev-1", user::UserRole::Developer, true))
+                .with_user(stored("admin-1", user::UserRole::Admin, false)),
+        );
+        let state = app_state_with(users.serve().await, UNREACHABLE_URL.to_string());
+        let response = my_permissions(req, web::Data::new(state)).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn capabilities(body: &Value) -> [bool; 6] {
+        ["is_admin", "is_developer", "verified", "stale_token", "can_manage_api_keys", "can_manage_users"]
+            .map(|key| body[key].as_bool().unwrap())
+    }
+
+    #[actix_web::test]
+    async fn each_role_gets_its_own_capabilities() {
+        let (status, player) = permissions(signed_in("player-1", "player")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(player["role"], "player");
+        assert_eq!(capabilities(&player), [false, false, false, false, false, false]);
+        assert!(player.get("impersonated_by").is_none());
+
+        let (_, developer) = permissions(signed_in("dev-1", "developer")).await;
+        assert_eq!(developer["role"], "developer");
+        assert_eq!(capabilities(&developer), [false, true, true, false, true, false]);
+
+        let (_, admin) = permissions(signed_in("admin-1", "admin")).await;
+        assert_eq!(admin["role"], "admin");
+        assert_eq!(capabilities(&admin), [true, false, false, false, true, true]);
+    }
+
+    #[actix_web::test]
+    async fn a_token_role_the_account_no_longer_has_grants_nothing() {
+        let (status, body) = permissions(signed_in("player-1", "admin")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["role"], "player");
+        assert_eq!(capabilities(&body), [false, false, false, true, false, false]);
+    }
+
+    #[actix_web::test]
+    async fn impersonating_admins_never_manage_users() {
+        let req = TestRequest::default().to_http_request();
+        let impersonated = Claims { act_as: Some("admin-1".to_string()), ..claims("dev-1", "developer") };
+        req.extensions_mut().insert(Principal::User(impersonated));
+
+        let (status, body) = permissions(req).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["impersonated_by"], "admin-1");
+        assert_eq!(capabilities(&body), [false, true, true, false, true, false]);
+    }
+
+    #[actix_web::test]
+    async fn api_keys_and_anonymous_callers_have_no_permissions() {
+        let api_key = TestRequest::default().to_http_request();
+        api_key.extensions_mut().insert(Principal::ApiKey { developer_id: "dev-1".to_string(), token_version: 0 });
+        let (status, body) = permissions(api_key).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body, json!({ "error": "API keys have no user permissions" }));
+
+        let (status, _) = permissions(TestRequest::default().to_http_request()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = permissions(signed_in("gone-1", "player")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,586 @@
+use actix_web::{
+    Error, HttpResponse,
+    dev::{ServiceRequest, ServiceResponse},
+    http::{Method, header},
+    middleware::Next,
+    web,
+};
+use common::config::env_or;
+use ipnet::IpNet;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::auth::{self, Claims, JwtVerifier};
+use crate::role::Role;
+
+#[derive(Default)]
+struct KeyState {
+    requests: Vec<Instant>,
+    /// Rejections since the key's last allowed request, for escalation.
+    violations: Vec<Instant>,
+    blocked_until: Option<Instant>,
+}
+
+impl KeyState {
+    /// Nothing left that could affect the next decision: not blocked, and
+    /// every request and counted violation has aged out.
+    fn is_idle(&self, now: Instant, window: Duration, escalation: Option<&EscalationPolicy>) -> bool {
+        let aged_out = |t: &Instant, within: Duration| now.duration_since(*t) >= within;
+        self.blocked_until.is_none_or(|until| until <= now)
+            && self.requests.last().is_none_or(|t| aged_out(t, window))
+            && escalation.is_none_or(|policy| {
+                self.violations.last().is_none_or(|t| aged_out(t, policy.violation_window))
+            })
+    }
+}
+
+struct Buckets {
+    keys: HashMap<String, KeyState>,
+    last_sweep: Instant,
+}
+
+pub enum Decision {
+    Allowed,
+    /// Over the limit; a slot frees up after `retry_after`.
+    Limited { retry_after: Duration },
+    /// Escalated to a temporary block, which nothing shortens.
+    Blocked { retry_after: Duration },
+}
+
+pub struct RateLimiter {
+    buckets: Mutex<Buckets>,
+    /// Requests monitor mode let through that enforce mode would have
+    /// rejected, since startup.
+    would_throttle: AtomicU64,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self {
+            buckets: Mutex::new(Buckets {
+                keys: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+            would_throttle: AtomicU64::new(0),
+        }
+    }
+
+    /// Counts a request that was over the limit but let through, returning
+    /// the running total.
+    fn record_would_throttle(&self) -> u64 {
+        self.would_throttle.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn check_rate_limit(
+        &self,
+        key: &str,
+        limit: usize,
+        window: Duration,
+        escalation: Option<&EscalationPolicy>,
+    ) -> Decision {
+        self.check_rate_limit_at(key, limit, window, escalation, Instant::now())
+    }
+
+    fn check_rate_limit_at(
+        &self,
+        key: &str,
+        limit: usize,
+        window: Duration,
+        escalation: Option<&EscalationPolicy>,
+        now: Instant,
+    ) -> Decision {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        // Every key ever seen would otherwise stay in the map for good, so
+        // drop idle ones, at most once per window.
+        if now.duration_since(buckets.last_sweep) >= window {
+            buckets.keys.retain(|_, state| !state.is_idle(now, window, escalation));
+            buckets.last_sweep = now;
+        }
+
+        let state = buckets.keys.entry(key.to_string()).or_default();
+
+        if let Some(until) = state.blocked_until {
+            if until > now {
+                return Decision::Blocked { retry_after: until - now };
+            }
+            state.blocked_until = None;
+        }
+
+        state.requests.retain(|&t| now.duration_since(t) < window);
+
+        if state.requests.len() < limit {
+            state.requests.push(now);
+            state.violations.clear();
+            return Decision::Allowed;
+        }
+
+        if let Some(policy) = escalation {
+            state.violations.retain(|&t| now.duration_since(t) < policy.violation_window);
+            state.violations.push(now);
+            if state.violations.len() >= policy.violations {
+                state.violations.clear();
+                state.blocked_until = Some(now + policy.block);
+                return Decision::Blocked { retry_after: policy.block };
+            }
+        }
+
+        let retry_after = state
+            .requests
+            .first()
+            .map_or(window, |&oldest| window.saturating_sub(now.duration_since(oldest)));
+        Decision::Limited { retry_after }
+    }
+}
+
+/// Turns a client that keeps hitting the limit into a temporary block:
+/// `violations` rejections within `violation_window`, with no allowed
+/// request in between, block the key for `block`.
+pub struct EscalationPolicy {
+    pub violations: usize,
+    pub violation_window: Duration,
+    pub block: Duration,
+}
+
+/// What happens to a request over the limit. `Monitor` only logs it, so
+/// limits can be tuned against real traffic before anything is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+    Enforce,
+    Monitor,
+}
+
+impl std::str::FromStr for RateLimitMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "enforce" => Ok(Self::Enforce),
+            "monitor" => Ok(Self::Monitor),
+            _ => Err("RATE_LIMIT_MODE must be enforce or monitor".to_string()),
+        }
+    }
+}
+
+pub struct RateLimitConfig {
+    /// `RATE_LIMIT_MODE`, `enforce` unless set.
+    pub mode: RateLimitMode,
+    pub anonymous_limit: usize,
+    pub authenticated_limit: usize,
+    pub window: Duration,
+    /// Peers in these ranges (health checks, internal dashboards) are never
+    /// counted. From `RATE_LIMIT_EXEMPT_CIDRS`, comma-separated; a bare
+    /// address means just that host.
+    pub exempt_networks: Vec<IpNet>,
+    /// Skip the limit for requests carrying a valid admin token
+    /// (`RATE_LIMIT_EXEMPT_ADMINS`). Impersonation tokens don't count.
+    pub exempt_admins: bool,
+    /// Off unless `RATE_LIMIT_BLOCK_AFTER_VIOLATIONS` is set above zero;
+    /// `RATE_LIMIT_VIOLATION_WINDOW_SECS` and `RATE_LIMIT_BLOCK_SECS` tune it.
+    pub escalation: Option<EscalationPolicy>,
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let exempt_networks = match std::env::var("RATE_LIMIT_EXEMPT_CIDRS") {
+            Ok(value) => parse_networks(&value)?,
+            Err(_) => Vec::new(),
+        };
+        let exempt_admins = match std::env::var("RATE_LIMIT_EXEMPT_ADMINS") {
+            Ok(value) => value
+                .trim()
+                .parse::<bool>()
+                .map_err(|_| "RATE_LIMIT_EXEMPT_ADMINS must be true or false".to_string())?,
+            Err(_) => false,
+        };
+
+        let violations: usize = env_or("RATE_LIMIT_BLOCK_AFTER_VIOLATIONS", 0)?;
+        let escalation = if violations == 0 {
+            None
+        } else {
+            Some(EscalationPolicy {
+                violations,
+                violation_window: Duration::from_secs(env_or("RATE_LIMIT_VIOLATION_WINDOW_SECS", 60)?),
+                block: Duration::from_secs(env_or("RATE_LIMIT_BLOCK_SECS", 600)?),
+            })
+        };
+
+        let mode = match std::env::var("RATE_LIMIT_MODE") {
+            Ok(value) => value.parse()?,
+            Err(_) => RateLimitMode::Enforce,
+        };
+
+        Ok(Self {
+            mode,
+            anonymous_limit: env_or("RATE_LIMIT_ANONYMOUS", 100)?,
+            authenticated_limit: env_or("RATE_LIMIT_AUTHENTICATED", 300)?,
+            window: Duration::from_secs(env_or("RATE_LIMIT_WINDOW_SECS", 60)?),
+            exempt_networks,
+            exempt_admins,
+            escalation,
+        })
+    }
+
+    fn is_exempt_ip(&self, ip: IpAddr) -> bool {
+        // IPv4 clients on a dual-stack socket show up as ::ffff:a.b.c.d.
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+            IpAddr::V4(_) => ip,
+        };
+        self.exempt_networks.iter().any(|net| net.contains(&ip))
+    }
+}
+
+fn parse_networks(raw: &str) -> Result<Vec<IpNet>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|entry| {
+            entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| format!("RATE_LIMIT_EXEMPT_CIDRS has an invalid range: {}", entry))
+        })
+        .collect()
+}
+
+fn verified_claims(req: &ServiceRequest) -> Option<Claims> {
+    let jwt = req.app_data::<web::Data<JwtVerifier>>()?;
+    auth::bearer_token(req.request()).and_then(|token| jwt.verify(token).ok())
+}
+
+/// Allowlisted IPs, and with `exempt_admins` admins acting as themselves on
+/// a token that hasn't been revoked since.
+async fn is_exempt(req: &ServiceRequest, config: &RateLimitConfig) -> bool {
+    if req.peer_addr().is_some_and(|addr| config.is_exempt_ip(addr.ip())) {
+        return true;
+    }
+    if !config.exempt_admins {
+        return false;
+    }
+    match verified_claims(req) {
+        Some(claims) if claims.has_role(Role::Admin) && claims.act_as.is_none() => {
+            auth::check_session(req.request(), &claims).await.is_ok()
+        }
+        _ => false,
+    }
+}
+
+fn is_write(method: &Method) -> bool {
+    matches!(*method, Method::POST | Method::PUT | Method::PATCH | Method::DELETE)
+}
+
+/// Authenticated writes are budgeted per user so callers sharing an IP
+/// (e.g. behind a NAT) don't throttle each other; everything else is per IP.
+fn rate_limit_key(req: &ServiceRequest, config: &RateLimitConfig) -> (String, usize) {
+    if is_write(req.method())
+        && let Some(claims) = verified_claims(req)
+    {
+        return (format!("user:{}", claims.sub), config.authenticated_limit);
+    }
+
+    let ip = req
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    (format!("ip:{}", ip), config.anonymous_limit)
+}
+
+pub async fn rate_limit_middleware(
+    req: ServiceRequest,
+    next: Next<impl actix_web::body::MessageBody + 'static>,
+) -> Result<ServiceResponse<actix_web::body::BoxBody>, Error> {
+    let rate_limiter = req.app_data::<web::Data<RateLimiter>>().unwrap();
+    let config = req.app_data::<web::Data<RateLimitConfig>>().unwrap();
+
+    if is_exempt(&req, config).await {
+        let res = next.call(req).await?;
+        return Ok(res.map_into_boxed_body());
+    }
+
+    let (key, limit) = rate_limit_key(&req, config);
+
+    let (retry_after, message) =
+        match rate_limiter.check_rate_limit(&key, limit, config.window, config.escalation.as_ref()) {
+            Decision::Allowed => {
+                let res = next.call(req).await?;
+                return Ok(res.map_into_boxed_body());
+            }
+            Decision::Limited { retry_after } => (retry_after, "Rate limit exceeded. Please try again later."),
+            Decision::Blocked { retry_after } => (
+                retry_after,
+                "Temporarily blocked after repeatedly exceeding the rate limit.",
+            ),
+        };
+
+    if config.mode == RateLimitMode::Monitor {
+        let total = rate_limiter.record_would_throttle();
+        tracing::warn!(
+            target: "rate_limit",
+            %key,
+            method = %req.method(),
+            path = %req.path(),
+            retry_after_secs = retry_after_secs(retry_after),
+            total,
+            "would throttle: {}",
+            message
+        );
+        let res = next.call(req).await?;
+        return Ok(res.map_into_boxed_body());
+    }
+
+    Ok(req.into_response(
+        HttpResponse::TooManyRequests()
+            .insert_header((header::RETRY_AFTER, retry_after_secs(retry_after).to_string()))
+            .json(serde_json::json!({ "error": message }))
+            .map_into_boxed_body(),
+    ))
+}
+
+/// `Retry-After` is whole seconds; round up so a client that waits exactly
+/// that long isn't rejected again.
+fn retry_after_secs(wait: Duration) -> u64 {
+    (wait.as_secs() + u64::from(wait.subsec_nanos() > 0)).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    use actix_web::test::TestRequest;
+
+    use super::*;
+    use crate::testing::{FakeUserService, app_state};
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn config() -> RateLimitConfig {
+        RateLimitConfig {
+            mode: RateLimitMode::Enforce,
+            anonymous_limit: 2,
+            authenticated_limit: 2,
+            window: WINDOW,
+            exempt_networks: Vec::new(),
+            exempt_admins: false,
+            escalation: None,
+        }
+    }
+
+    fn allowed(decision: Decision) -> bool {
+        matches!(decision, Decision::Allowed)
+    }
+
+    fn write_from(ip: &str, jwt: &web::Data<JwtVerifier>, user: &str) -> ServiceRequest {
+        let token = jwt.issue(user, Role::Developer, 0).unwrap();
+        TestRequest::post()
+            .uri("/api/games")
+            .peer_addr(SocketAddr::new(ip.parse().unwrap(), 40000))
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .app_data(jwt.clone())
+            .to_srv_request()
+    }
+
+    /// Status of each of `count` GETs from `ip` through the middleware.
+    async fn statuses(config: RateLimitConfig, ip: &str, count: usize) -> Vec<u16> {
+        statuses_with(web::Data::new(RateLimiter::new()), config, ip, count).await
+    }
+
+    async fn statuses_with(
+        limiter: web::Data<RateLimiter>,
+        config: RateLimitConfig,
+        ip: &str,
+        count: usize,
+    ) -> Vec<u16> {
+        let app = actix_web::test::init_service(
+            actix_web::App::new()
+                .app_data(limiter)
+                .app_data(web::Data::new(config))
+                .wrap(actix_web::middleware::from_fn(rate_limit_middleware))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let mut statuses = Vec::new();
+        for _ in 0..count {
+            let request = TestRequest::get()
+                .peer_addr(SocketAddr::new(ip.parse().unwrap(), 40000))
+                .to_request();
+            statuses.push(actix_web::test::call_service(&app, request).await.status().as_u16());
+        }
+        statuses
+    }
+
+    #[actix_web::test]
+    async fn allowlisted_ips_are_never_throttled() {
+        let exempt = || RateLimitConfig {
+            exempt_networks: parse_networks("10.0.0.0/8, 192.0.2.1").unwrap(),
+            ..config()
+        };
+
+        assert_eq!(statuses(exempt(), "10.1.2.3", 5).await, [200; 5]);
+        assert_eq!(statuses(exempt(), "::ffff:192.0.2.1", 5).await, [200; 5]);
+        assert_eq!(statuses(exempt(), "192.0.2.2", 3).await, [200, 200, 429]);
+    }
+
+    #[actix_web::test]
+    async fn monitor_mode_lets_everything_through_but_counts_it() {
+        let limiter = web::Data::new(RateLimiter::new());
+        let monitor = RateLimitConfig {
+            mode: RateLimitMode::Monitor,
+            ..config()
+        };
+
+        assert_eq!(statuses_with(limiter.clone(), monitor, "192.0.2.2", 5).await, [200; 5]);
+        assert_eq!(limiter.would_throttle.load(Ordering::Relaxed), 3);
+    }
+
+    #[actix_web::test]
+    async fn enforce_mode_rejects_over_the_limit() {
+        let limiter = web::Data::new(RateLimiter::new());
+
+        assert_eq!(statuses_with(limiter.clone(), config(), "192.0.2.2", 3).await, [200, 200, 429]);
+        assert_eq!(limiter.would_throttle.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn mode_parses_case_insensitively() {
+        assert_eq!(" Monitor ".parse::<RateLimitMode>(), Ok(RateLimitMode::Monitor));
+        assert_eq!("ENFORCE".parse::<RateLimitMode>(), Ok(RateLimitMode::Enforce));
+        assert!("off".parse::<RateLimitMode>().is_err());
+    }
+
+    /// A request carrying `root`'s version-0 admin token, checked against
+    /// `users`.
+    async fn admin_request(users: FakeUserService) -> ServiceRequest {
+        let jwt = web::Data::new(JwtVerifier::new("test-secret", 0));
+        let token = jwt.issue("root", Role::Admin, 0).unwrap();
+        let url = Arc::new(users).serve().await;
+        TestRequest::get()
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .app_data(jwt)
+            .app_data(web::Data::new(app_state(url)))
+            .to_srv_request()
+    }
+
+    #[actix_web::test]
+    async fn admin_tokens_are_exempt_only_when_enabled() {
+        let exempt_admins = RateLimitConfig { exempt_admins: true, ..config() };
+
+        assert!(!is_exempt(&admin_request(FakeUserService::default()).await, &config()).await);
+        assert!(is_exempt(&admin_request(FakeUserService::default()).await, &exempt_admins).await);
+    }
+
+    #[actix_web::test]
+    async fn revoked_admin_tokens_are_not_exempt() {
+        let exempt_admins = RateLimitConfig { exempt_admins: true, ..config() };
+        let revoked = FakeUserService::default().with_token_version("root", 1);
+
+        assert!(!is_exempt(&admin_request(revoked).await, &exempt_admins).await);
+    }
+
+    #[test]
+    fn users_behind_one_ip_get_independent_budgets() {
+        let config = config();
+        let jwt = web::Data::new(JwtVerifier::new("test-secret", 0));
+        let limiter = RateLimiter::new();
+
+        let (alice, limit) = rate_limit_key(&write_from("203.0.113.7", &jwt, "alice"), &config);
+        let (bob, _) = rate_limit_key(&write_from("203.0.113.7", &jwt, "bob"), &config);
+        assert_ne!(alice, bob);
+
+        for _ in 0..limit {
+            assert!(allowed(limiter.check_rate_limit(&alice, limit, WINDOW, None)));
+        }
+        assert!(!allowed(limiter.check_rate_limit(&alice, limit, WINDOW, None)));
+        assert!(allowed(limiter.check_rate_limit(&bob, limit, WINDOW, None)));
+    }
+
+    #[test]
+    fn anonymous_requests_share_their_ip_budget() {
+        let config = config();
+        let request = || {
+            TestRequest::post()
+                .peer_addr(SocketAddr::new("203.0.113.7".parse().unwrap(), 40000))
+                .to_srv_request()
+        };
+
+        let (first, limit) = rate_limit_key(&request(), &config);
+        let (second, _) = rate_limit_key(&request(), &config);
+        assert_eq!(first, "ip:203.0.113.7");
+        assert_eq!((first, limit), (second, config.anonymous_limit));
+    }
+
+    #[test]
+    fn idle_keys_are_swept() {
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+        limiter.check_rate_limit_at("ip:a", 2, WINDOW, None, start);
+        limiter.check_rate_limit_at("ip:b", 2, WINDOW, None, start + WINDOW / 2);
+
+        limiter.check_rate_limit_at("ip:c", 2, WINDOW, None, start + WINDOW);
+
+        let buckets = limiter.buckets.lock().unwrap();
+        let mut keys: Vec<&str> = buckets.keys.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["ip:b", "ip:c"]);
+    }
+
+    fn retry_after(decision: Decision) -> (&'static str, Duration) {
+        match decision {
+            Decision::Allowed => ("allowed", Duration::ZERO),
+            Decision::Limited { retry_after } => ("limited", retry_after),
+            Decision::Blocked { retry_after } => ("blocked", retry_after),
+        }
+    }
+
+    #[test]
+    fn repeated_violations_escalate_to_a_longer_block() {
+        let policy = EscalationPolicy {
+            violations: 2,
+            violation_window: WINDOW,
+            block: Duration::from_secs(600),
+        };
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+        let check = |offset: u64| {
+            let now = start + Duration::from_secs(offset);
+            retry_after(limiter.check_rate_limit_at("ip:a", 1, WINDOW, Some(&policy), now))
+        };
+
+        assert_eq!(check(0).0, "allowed");
+        assert_eq!(check(10), ("limited", Duration::from_secs(50)));
+        assert_eq!(check(20), ("blocked", Duration::from_secs(600)));
+        // Staying within the limit doesn't shorten the block.
+        assert_eq!(check(100), ("blocked", Duration::from_secs(520)));
+
+        // After a quiet period the key starts over, with no violations left.
+        assert_eq!(check(620).0, "allowed");
+        assert_eq!(check(630).0, "limited");
+    }
+
+    #[test]
+    fn an_allowed_request_resets_the_violation_count() {
+        let policy = EscalationPolicy {
+            violations: 2,
+            violation_window: Duration::from_secs(600),
+            block: Duration::from_secs(600),
+        };
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+        let check = |offset: u64| {
+            let now = start + Duration::from_secs(offset);
+            retry_after(limiter.check_rate_limit_at("ip:a", 1, WINDOW, Some(&policy), now)).0
+        };
+
+        assert_eq!(check(0), "allowed");
+        assert_eq!(check(10), "limited");
+        assert_eq!(check(60), "allowed");
+        assert_eq!(check(70), "limited");
+        assert_eq!(check(80), "blocked");
+    }
+}
@@ -0,0 +1,192 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use common::pagination::PageSizeConfig;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{AppState, GameDto, errors, game, game_to_dto, negotiate, request_id};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReleasesQuery {
+    /// How many days to look back (new) or ahead (upcoming); 30 and 90 by
+    /// default, at most 365.
+    days: Option<i32>,
+    limit: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct ReleasesResponse {
+    games: Vec<GameDto>,
+}
+
+enum Releases {
+    New,
+    Upcoming,
+}
+
+async fn list_releases(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
+    query: web::Query<ReleasesQuery>,
+    which: Releases,
+) -> Result<HttpResponse, actix_web::Error> {
+    let limit = match page_size.resolve(query.limit) {
+        Ok(limit) => limit,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e }))),
+    };
+
+    let request = request_id::grpc_request(&req, game::ListReleasesRequest {
+        window_days: query.days.unwrap_or(0),
+        limit,
+    });
+
+    let mut client = data.game_client();
+    let result = match which {
+        Releases::New => client.list_new_releases(request).await,
+        Releases::Upcoming => client.list_upcoming_games(request).await,
+    };
+    match result {
+        Ok(response) => {
+            let games = response.into_inner().games.into_iter().map(game_to_dto).collect();
+            Ok(negotiate::ok(&req, &ReleasesResponse { games }))
+        }
+        Err(status) => Ok(errors::tonic_status_to_http(&status)),
+    }
+}
+
+/// `GET /api/games/new` - published games released in the last `days`
+/// days, most recent first.
+#[utoipa::path(
+    get,
+    path = "/api/games/new",
+    tag = "games",
+    params(ReleasesQuery),
+    responses(
+        (status = 200, description = "Recent releases", body = ReleasesResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn new_releases(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
+    query: web::Query<ReleasesQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    list_releases(req, data, page_size, query, Releases::New).await
+}
+
+/// `GET /api/games/upcoming` - published games coming out in the next
+/// `days` days, soonest first. Today's releases count as new, not upcoming.
+#[utoipa::path(
+    get,
+    path = "/api/games/upcoming",
+    tag = "games",
+    params(ReleasesQuery),
+    responses(
+        (status = 200, description = "Games releasing soon", body = ReleasesResponse),
+        (status = 400, description = "Invalid input", body = errors::ErrorEnvelope),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn upcoming_games(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    page_size: web::Data<PageSizeConfig>,
+    query: web::Query<ReleasesQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    list_releases(req, data, page_size, query, Releases::Upcoming).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use chrono::{Days, NaiveDate, Utc};
+    use serde_json::Value;
+
+    use super::*;
+    use crate::testing::{FakeGameService, UNREACHABLE_URL, app_state_with};
+
+    fn game(name: &str, release_date: NaiveDate) -> game::Game {
+        game::Game {
+            id: name.to_lowercase().replace(' ', "-"),
+            name: name.to_string(),
+            release_date: Some(release_date.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Dated a quarter ago, last week, yesterday, today, tomorrow, next
+    /// month and next year, added out of order.
+    fn catalog() -> Arc<FakeGameService> {
+        let today = Utc::now().date_naive();
+        Arc::new(
+            FakeGameService::default()
+                .with_game(game("Tomorrow", today + Days::new(1)))
+                .with_game(game("Last Week", today - Days::new(10)))
+                .with_game(game("Next Year", today + Days::new(200)))
+                .with_game(game("Launch Day", today))
+                .with_game(game("Last Quarter", today - Days::new(60)))
+                .with_game(game("Next Month", today + Days::new(20)))
+                .with_game(game("Yesterday", today - Days::new(1))),
+        )
+    }
+
+    async fn list(games: &Arc<FakeGameService>, which: Releases, query: &str) -> (StatusCode, Value) {
+        let state = app_state_with(UNREACHABLE_URL.to_string(), games.clone().serve().await);
+        let response = list_releases(
+            TestRequest::default().to_http_request(),
+            web::Data::new(state),
+            web::Data::new(PageSizeConfig::default()),
+            web::Query::<ReleasesQuery>::from_query(query).unwrap(),
+            which,
+        )
+        .await
+        .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn names(body: &Value) -> Vec<&str> {
+        body["games"].as_array().unwrap().iter().map(|g| g["name"].as_str().unwrap()).collect()
+    }
+
+    #[actix_web::test]
+    async fn new_releases_are_newest_first_and_include_today() {
+        let games = catalog();
+
+        let (status, body) = list(&games, Releases::New, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(names(&body), ["Launch Day", "Yesterday", "Last Week"]);
+
+        let (_, body) = list(&games, Releases::New, "days=90").await;
+        assert_eq!(names(&body), ["Launch Day", "Yesterday", "Last Week", "Last Quarter"]);
+        let (_, body) = list(&games, Releases::New, "days=90&limit=2").await;
+        assert_eq!(names(&body), ["Launch Day", "Yesterday"]);
+
+        let sent = games.release_requests.lock().unwrap();
+        assert_eq!(sent[0].window_days, 0);
+        assert_eq!(sent[2].window_days, 90);
+        assert_eq!(sent[2].limit, 2);
+    }
+
+    #[actix_web::test]
+    async fn upcoming_games_are_soonest_first_and_exclude_today() {
+        let games = catalog();
+
+        let (status, body) = list(&games, Releases::Upcoming, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(names(&body), ["Tomorrow", "Next Month"]);
+
+        let (_, body) = list(&games, Releases::Upcoming, "days=365").await;
+        assert_eq!(names(&body), ["Tomorrow", "Next Month", "Next Year"]);
+        let (_, body) = list(&games, Releases::Upcoming, "days=365&limit=1").await;
+        assert_eq!(names(&body), ["Tomorrow"]);
+    }
+}
@@ -0,0 +1,185 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use actix_web::{
+    Error, HttpMessage, HttpRequest,
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::Next,
+    web,
+};
+use common::config::env_or;
+use common::rpc_log::REQUEST_ID_KEY;
+use uuid::Uuid;
+
+use crate::timeout;
+
+/// Controls the per-request access line. Only every `sample_every`-th
+/// successful request is logged; failed ones always are.
+pub struct RequestLogConfig {
+    sample_every: u64,
+    seen: AtomicU64,
+}
+
+impl RequestLogConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let sample_every = env_or("REQUEST_LOG_SAMPLE_EVERY", 1u64)?;
+        if sample_every == 0 {
+            return Err("REQUEST_LOG_SAMPLE_EVERY must be a positive integer".to_string());
+        }
+
+        Ok(Self {
+            sample_every,
+            seen: AtomicU64::new(0),
+        })
+    }
+
+    /// Counts the request and says whether it falls on the sampling stride.
+    fn sampled(&self) -> bool {
+        self.seen.fetch_add(1, Ordering::Relaxed).is_multiple_of(self.sample_every)
+    }
+}
+
+/// Assigns each request an id, echoes it in `X-Request-Id` and logs the
+/// outcome as a `tracing` event, filtered by `RUST_LOG` like everything else.
+pub async fn request_id_middleware(
+    req: ServiceRequest,
+    next: Next<impl actix_web::body::MessageBody + 'static>,
+) -> Result<ServiceResponse<actix_web::body::BoxBody>, Error> {
+    let config = req.app_data::<web::Data<RequestLogConfig>>().unwrap().clone();
+    let request_id = Uuid::new_v4().to_string();
+
+    req.extensions_mut().insert(request_id.clone());
+
+    let method = req.method().clone();
+    let path = req.path().to_string();
+    let started = Instant::now();
+
+    let result = next.call(req).await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    let mut res = match result {
+        Ok(res) => res,
+        Err(e) => {
+            tracing::warn!(%request_id, %method, %path, elapsed_ms, error = %e, "request failed");
+            return Err(e);
+        }
+    };
+
+    let status = res.status();
+    if status.is_server_error() {
+        tracing::warn!(%request_id, %method, %path, status = status.as_u16(), elapsed_ms, "request");
+    } else if status.is_client_error() || config.sampled() {
+        tracing::info!(%request_id, %method, %path, status = status.as_u16(), elapsed_ms, "request");
+    }
+
+    res.headers_mut().insert(
+        actix_web::http::header::HeaderName::from_static("x-request-id"),
+        actix_web::http::header::HeaderValue::from_str(&request_id).unwrap(),
+    );
+
+    Ok(res.map_into_boxed_body())
+}
+
+
+/// The id `request_id_middleware` assigned to this request.
+fn current(req: &HttpRequest) -> Option<String> {
+    req.extensions().get::<String>().cloned()
+}
+
+/// Wraps a backend gRPC message, forwarding the gateway's request id so the
+/// service's access log lines up with ours, and passing on the remaining
+/// request timeout as the call's deadline.
+pub fn grpc_request<T>(req: &HttpRequest, message: T) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+
+    if let Some(remaining) = timeout::remaining(req) {
+        request.set_timeout(remaining);
+    }
+
+    if let Some(value) = current(req).and_then(|id| id.parse().ok()) {
+        request.metadata_mut().insert(REQUEST_ID_KEY, value);
+    }
+
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use actix_web::http::StatusCode;
+    use actix_web::test::{self as actix_test, TestRequest};
+    use actix_web::{App, HttpResponse, middleware};
+
+    use super::*;
+
+    fn config(sample_every: u64) -> RequestLogConfig {
+        RequestLogConfig {
+            sample_every,
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn one_in_n_requests_is_sampled() {
+        let config = config(3);
+        let sampled: Vec<bool> = (0..7).map(|_| config.sampled()).collect();
+        assert_eq!(sampled, [true, false, false, true, false, false, true]);
+    }
+
+    #[test]
+    fn a_stride_of_one_samples_everything() {
+        let config = config(1);
+        assert!((0..5).all(|_| config.sampled()));
+    }
+
+    #[actix_web::test]
+    async fn failed_requests_are_logged_whatever_the_sampling() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let sink = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || Sink(sink.clone()))
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(config(4)))
+                .wrap(middleware::from_fn(request_id_middleware))
+                .route("/ok", web::get().to(HttpResponse::Ok))
+                .route("/missing", web::get().to(HttpResponse::NotFound))
+                .route("/broken", web::get().to(HttpResponse::InternalServerError)),
+        )
+        .await;
+
+        for _ in 0..8 {
+            let response = actix_test::call_service(&app, TestRequest::get().uri("/ok").to_request()).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().contains_key("x-request-id"));
+        }
+        for uri in ["/missing", "/broken"] {
+            actix_test::call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+        }
+
+        let logs = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        let logged = |path: &str| logs.lines().filter(|l| l.contains(&format!("path={}", path))).count();
+        assert_eq!(logged("/ok"), 2, "{logs}");
+        assert_eq!(logged("/missing"), 1, "{logs}");
+        assert_eq!(logged("/broken"), 1, "{logs}");
+    }
+}
@@ -0,0 +1,99 @@
+use serde::Deserialize;
+use strum::VariantNames;
+
+use crate::errors;
+
+/// A user's role. The lowercase spelling is what the HTTP API and the JWT
+/// `role` claim use; `to_proto`/`from_proto` map to the user service's
+/// `UserRole` integers. In request bodies an unknown role fails the JSON
+/// parse itself.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::VariantNames,
+)]
+#[serde(try_from = "String")]
+#[strum(serialize_all = "snake_case")]
+pub enum Role {
+    Player,
+    Developer,
+    Admin,
+}
+
+impl TryFrom<String> for Role {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .parse()
+            .map_err(|_| errors::invalid_choice("role", &value, Self::VARIANTS))
+    }
+}
+
+impl Role {
+    pub fn to_proto(self) -> i32 {
+        match self {
+            Role::Player => 0,
+            Role::Developer => 1,
+            Role::Admin => 2,
+        }
+    }
+
+    /// `None` for integers the proto enum doesn't define.
+    pub fn from_proto(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Role::Player),
+            1 => Some(Role::Developer),
+            2 => Some(Role::Admin),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::UserRole;
+
+    const ALL: [Role; 3] = [Role::Player, Role::Developer, Role::Admin];
+
+    #[test]
+    fn roles_round_trip_through_their_spelling() {
+        for (role, spelling) in ALL.into_iter().zip(["player", "developer", "admin"]) {
+            assert_eq!(role.to_string(), spelling);
+            assert_eq!(spelling.parse::<Role>(), Ok(role));
+            assert_eq!(serde_json::from_value::<Role>(serde_json::json!(spelling)).unwrap(), role);
+        }
+        assert_eq!(Role::VARIANTS, ["player", "developer", "admin"]);
+    }
+
+    #[test]
+    fn roles_round_trip_through_the_proto_enum() {
+        for (role, proto) in ALL.into_iter().zip([UserRole::Player, UserRole::Developer, UserRole::Admin]) {
+            assert_eq!(role.to_proto(), proto as i32);
+            assert_eq!(Role::from_proto(proto as i32), Some(role));
+        }
+        assert_eq!(Role::from_proto(3), None);
+        assert_eq!(Role::from_proto(-1), None);
+    }
+
+    #[test]
+    fn unknown_roles_are_refused_with_the_choices() {
+        for spelling in ["root", "Admin", "ADMIN", " admin", ""] {
+            assert!(spelling.parse::<Role>().is_err(), "{spelling:?}");
+            assert_eq!(
+                Role::try_from(spelling.to_string()).unwrap_err(),
+                format!("Invalid role '{}'. Must be one of: player, developer, admin", spelling)
+            );
+        }
+
+        let err = serde_json::from_value::<Role>(serde_json::json!("root")).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid role 'root'. Must be one of: player, developer, admin");
+    }
+}
@@ -0,0 +1,263 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use common::models::GameStatus;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::auth::{self, Principal};
+use crate::role::Role;
+use crate::{AppState, GameDto, UserDto, errors, game, game_to_dto, negotiate, request_id, user, user_to_dto};
+
+const DEFAULT_LIMIT: i32 = 10;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    q: String,
+    limit: Option<i32>,
+}
+
+/// One hit, tagged with where it came from so clients can render each kind
+/// without guessing from the fields present.
+#[derive(Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SearchHit {
+    Game(Box<GameDto>),
+    User(UserDto),
+}
+
+#[derive(Serialize, ToSchema)]
+struct SearchResponse {
+    results: Vec<SearchHit>,
+    /// Sources that failed. The results from the others are still returned.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+/// `GET /api/search?q=` - games for everyone, plus users for admins. Both
+/// services are queried at once; if only one of them fails its results are
+/// left out and a warning says so.
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matching games, plus users for admins", body = SearchResponse),
+        (status = 500, description = "Backend service failure", body = errors::ErrorEnvelope),
+    )
+)]
+pub async fn search(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let q = query.q.trim().to_string();
+    if q.is_empty() {
+        return Ok(negotiate::ok(&req, &SearchResponse { results: Vec::new(), warnings: Vec::new() }));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+
+    let is_admin = matches!(auth::principal(&req), Some(Principal::User(claims)) if claims.has_role(Role::Admin));
+
+    // Drafts and suspended games only show up for admins, same as they are
+    // the only ones searching users.
+    let games_request = auth::grpc_request(&req, game::ListGamesRequest {
+        search_query: Some(q.clone()),
+        status: (!is_admin).then(|| GameStatus::Published.to_proto()),
+        page_size: limit,
+        ..Default::default()
+    });
+    let mut game_client = data.game_client();
+    let games = async move { game_client.list_games(games_request).await };
+
+    let users_request = request_id::grpc_request(&req, user::SearchUsersRequest { query: q, limit });
+    let mut user_client = data.user_client();
+    let users = async move {
+        if is_admin {
+            Some(user_client.search_users(users_request).await)
+        } else {
+            None
+        }
+    };
+
+    let (games, users) = tokio::join!(games, users);
+
+    let mut results = Vec::new();
+    let mut warnings = Vec::new();
+    let mut failures = Vec::new();
+
+    match games {
+        Ok(response) => results.extend(
+            response
+                .into_inner()
+                .games
+                .into_iter()
+                .map(|g| SearchHit::Game(Box::new(game_to_dto(g)))),
+        ),
+        Err(status) => {
+            log::warn!("Search: game service failed: {}", status.message());
+            warnings.push(format!("Game results unavailable: {}", status.message()));
+            failures.push(status);
+        }
+    }
+
+    match users {
+        Some(Ok(response)) => results.extend(
+            response
+                .into_inner()
+                .users
+                .into_iter()
+                .map(|u| SearchHit::User(user_to_dto(u, true))),
+        ),
+        Some(Err(status)) => {
+            log::warn!("Search: user service failed: {}", status.message());
+            warnings.push(format!("User results unavailable: {}", status.message()));
+            failures.push(status);
+        }
+        None => {}
+    }
+
+    let sources = if is_admin { 2 } else { 1 };
+    if failures.len() == sources {
+        let status = &failures[0];
+        return Ok(errors::tonic_status_to_http(status));
+    }
+
+    Ok(negotiate::ok(&req, &SearchResponse { results, warnings }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::Ordering;
+
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::testing::{FakeGameService, FakeUserService, UNREACHABLE_URL, app_state_with, signed_in};
+
+    fn game(name: &str, status: GameStatus) -> game::Game {
+        game::Game {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            status: status.to_proto(),
+            ..Default::default()
+        }
+    }
+
+    /// One published and one draft game, and one user, all matching "Space".
+    fn services() -> (Arc<FakeGameService>, Arc<FakeUserService>) {
+        let games = FakeGameService::default()
+            .with_game(game("Space Quest", GameStatus::Published))
+            .with_game(game("Space Quest II", GameStatus::Draft));
+        let users = FakeUserService::default().with_user(user::UserMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            username: "SpaceFan".to_string(),
+            email: "fan@example.com".to_string(),
+            ..Default::default()
+        });
+        (Arc::new(games), Arc::new(users))
+    }
+
+    async fn run(req: HttpRequest, game_url: String, user_url: String, q: &str) -> (StatusCode, Value) {
+        let state = web::Data::new(app_state_with(user_url, game_url));
+        let query = web::Query(SearchQuery { q: q.to_string(), limit: None });
+        let response = search(req, state, query).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    /// `(type, name or username)` of every hit, in order.
+    fn hits(body: &Value) -> Vec<(String, String)> {
+        body["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|hit| {
+                let label = hit.get("name").or(hit.get("username")).unwrap();
+                (hit["type"].as_str().unwrap().to_string(), label.as_str().unwrap().to_string())
+            })
+            .collect()
+    }
+
+    fn hit(kind: &str, label: &str) -> (String, String) {
+        (kind.to_string(), label.to_string())
+    }
+
+    #[actix_web::test]
+    async fn non_admins_get_published_games_and_no_users() {
+        for req in [TestRequest::default().to_http_request(), signed_in("player-1", "player")] {
+            let (games, users) = services();
+            let (game_url, user_url) = (games.clone().serve().await, users.clone().serve().await);
+
+            let (status, body) = run(req, game_url, user_url, "Space").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(hits(&body), [hit("game", "Space Quest")]);
+            assert!(body.get("warnings").is_none(), "{body}");
+
+            let requests = games.list_requests.lock().unwrap();
+            assert_eq!(requests[0].status, Some(GameStatus::Published.to_proto()));
+            assert_eq!(requests[0].search_query.as_deref(), Some("Space"));
+            assert_eq!(users.calls.load(Ordering::Relaxed), 0);
+        }
+    }
+
+    #[actix_web::test]
+    async fn admins_get_every_game_and_matching_users() {
+        let (games, users) = services();
+        let (game_url, user_url) = (games.clone().serve().await, users.clone().serve().await);
+
+        let (status, body) = run(signed_in("admin-1", "admin"), game_url, user_url, "Space").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            hits(&body),
+            [hit("game", "Space Quest"), hit("game", "Space Quest II"), hit("user", "SpaceFan")]
+        );
+        assert_eq!(body["results"][2]["email"], "fan@example.com");
+        assert_eq!(games.list_requests.lock().unwrap()[0].status, None);
+    }
+
+    #[actix_web::test]
+    async fn one_failing_source_leaves_a_warning() {
+        let (games, users) = services();
+
+        let game_url = games.clone().serve().await;
+        let (status, body) = run(signed_in("admin-1", "admin"), game_url, UNREACHABLE_URL.to_string(), "Space").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(hits(&body), [hit("game", "Space Quest"), hit("game", "Space Quest II")]);
+        assert!(body["warnings"][0].as_str().unwrap().starts_with("User results unavailable"), "{body}");
+
+        let user_url = users.clone().serve().await;
+        let (status, body) = run(signed_in("admin-1", "admin"), UNREACHABLE_URL.to_string(), user_url, "Space").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(hits(&body), [hit("user", "SpaceFan")]);
+        assert!(body["warnings"][0].as_str().unwrap().starts_with("Game results unavailable"), "{body}");
+    }
+
+    #[actix_web::test]
+    async fn every_source_failing_is_an_error() {
+        let unreachable = || UNREACHABLE_URL.to_string();
+
+        let (status, _) = run(TestRequest::default().to_http_request(), unreachable(), unreachable(), "Space").await;
+        assert!(status.is_server_error(), "{status}");
+
+        let (status, _) = run(signed_in("admin-1", "admin"), unreachable(), unreachable(), "Space").await;
+        assert!(status.is_server_error(), "{status}");
+    }
+
+    #[actix_web::test]
+    async fn blank_queries_search_nothing() {
+        let (games, users) = services();
+        let (game_url, user_url) = (games.clone().serve().await, users.clone().serve().await);
+
+        let (status, body) = run(signed_in("admin-1", "admin"), game_url, user_url, "   ").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(hits(&body), []);
+        assert!(games.list_requests.lock().unwrap().is_empty());
+        assert_eq!(users.calls.load(Ordering::Relaxed), 0);
+    }
+}
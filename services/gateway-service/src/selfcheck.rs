@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use common::pagination::PageSizeConfig;
+use tonic::transport::Endpoint;
+
+use crate::{cors, grpc_pool, rate_limit, request_id, signup, timeout};
+
+/// HS256 keys shorter than the 256-bit hash output are easy to brute-force.
+const MIN_JWT_SECRET_LEN: usize = 32;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Passing this on the command line starts the gateway without the checks.
+pub const SKIP_FLAG: &str = "--skip-checks";
+
+struct CheckResult {
+    name: String,
+    outcome: Result<(), String>,
+}
+
+/// Startup diagnostics: every check runs, so one report lists all problems
+/// instead of the first `expect` to trip.
+#[derive(Default)]
+pub struct Report {
+    results: Vec<CheckResult>,
+    /// Problems worth reading that don't fail the check.
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn record(&mut self, name: impl Into<String>, outcome: Result<(), String>) {
+        self.results.push(CheckResult { name: name.into(), outcome });
+    }
+
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.outcome.is_ok())
+    }
+
+    pub fn print(&self) {
+        println!("Startup self-check:");
+        for result in &self.results {
+            match &result.outcome {
+                Ok(()) => println!("  [PASS] {}", result.name),
+                Err(e) => println!("  [FAIL] {}: {}", result.name, e),
+            }
+        }
+        for warning in &self.warnings {
+            println!("  [WARN] {}", warning);
+        }
+    }
+}
+
+/// Checks the environment-driven configuration and that each backend in
+/// `services` (name, replica URLs) accepts a connection on at least one
+/// replica; the pool routes around the others.
+pub async fn run(services: &[(&str, &[String])]) -> Report {
+    let mut report = Report::default();
+
+    report.record("JWT_SECRET", check_jwt_secret(std::env::var("JWT_SECRET").ok().as_deref()));
+    report.record("rate limit configuration", rate_limit::RateLimitConfig::from_env().map(drop));
+    report.record("timeout configuration", timeout::TimeoutConfig::from_env().map(drop));
+    report.record("page size configuration", PageSizeConfig::from_env().map(drop));
+    report.record("CORS configuration", cors::CorsConfig::from_env().map(drop));
+    report.record("request log configuration", request_id::RequestLogConfig::from_env().map(drop));
+    report.record("signup configuration", signup::SignupPolicy::from_env().map(drop));
+    report.record("gRPC pool configuration", grpc_pool::PoolConfig::from_env().map(drop));
+
+    for (name, urls) in services {
+        let outcome = check_replicas(urls).await.map(|unreachable| {
+            report.warnings.extend(unreachable.into_iter().map(|e| format!("{} replica {}", name, e)));
+        });
+        report.record(format!("{} at {}", name, urls.join(", ")), outcome);
+    }
+
+    report
+}
+
+fn check_jwt_secret(secret: Option<&str>) -> Result<(), String> {
+    match secret {
+        None => Err("not set".to_string()),
+        Some(secret) if secret.len() < MIN_JWT_SECRET_LEN => Err(format!(
+            "must be at least {} bytes, got {}",
+            MIN_JWT_SECRET_LEN,
+            secret.len()
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Passes with the unreachable replicas' errors as long as one answers.
+async fn check_replicas(urls: &[String]) -> Result<Vec<String>, String> {
+    if urls.is_empty() {
+        return Err("no URLs configured".to_string());
+    }
+    let mut failures = Vec::new();
+    for url in urls {
+        match check_connect(url).await {
+            Ok(()) => {}
+            Err(e) if urls.len() == 1 => return Err(e),
+            Err(e) => failures.push(format!("{}: {}", url, e)),
+        }
+    }
+    if failures.len() == urls.len() {
+        return Err(failures.join("; "));
+    }
+    Ok(failures)
+}
+
+async fn check_connect(url: &str) -> Result<(), String> {
+    Endpoint::from_shared(url.to_string())
+        .map_err(|e| format!("invalid URL: {}", e))?
+        .connect_timeout(CONNECT_TIMEOUT)
+        .connect()
+        .await
+        .map(drop)
+        .map_err(|e| match std::error::Error::source(&e) {
+            Some(cause) => format!("cannot connect: {}", cause),
+            None => format!("cannot connect: {}", e),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::{FakeUserService, UNREACHABLE_URL};
+
+    #[test]
+    fn jwt_secrets_must_be_set_and_long_enough() {
+        assert_eq!(check_jwt_secret(None), Err("not set".to_string()));
+        assert_eq!(check_jwt_secret(Some("short")), Err("must be at least 32 bytes, got 5".to_string()));
+        assert_eq!(check_jwt_secret(Some(&"x".repeat(MIN_JWT_SECRET_LEN))), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn one_reachable_replica_passes_with_warnings_for_the_rest() {
+        let live = Arc::new(FakeUserService::default()).serve().await;
+
+        assert_eq!(check_replicas(std::slice::from_ref(&live)).await, Ok(vec![]));
+
+        let warnings = check_replicas(&[UNREACHABLE_URL.to_string(), live]).await.unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with(&format!("{}: cannot connect", UNREACHABLE_URL)), "{warnings:?}");
+    }
+
+    #[tokio::test]
+    async fn no_reachable_replica_fails() {
+        let err = check_replicas(&[UNREACHABLE_URL.to_string()]).await.unwrap_err();
+        assert!(err.starts_with("cannot connect"), "{err}");
+
+        let both = [UNREACHABLE_URL.to_string(), "http://127.0.0.1:2".to_string()];
+        let err = check_replicas(&both).await.unwrap_err();
+        assert_eq!(err.matches("cannot connect").count(), 2, "{err}");
+
+        assert_eq!(check_replicas(&[]).await, Err("no URLs configured".to_string()));
+
+        let err = check_replicas(&["not a url".to_string()]).await.unwrap_err();
+        assert!(err.starts_with("invalid URL"), "{err}");
+    }
+
+    #[test]
+    fn a_report_passes_only_when_every_check_does() {
+        let mut report = Report::default();
+        report.record("first", Ok(()));
+        assert!(report.passed());
+
+        report.warnings.push("only a warning".to_string());
+        assert!(report.passed());
+
+        report.record("second", Err("broken".to_string()));
+        assert!(!report.passed());
+    }
+}
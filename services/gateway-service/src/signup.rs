@@ -0,0 +1,121 @@
+use actix_web::{HttpRequest, HttpResponse};
+
+use crate::auth::{self, Principal};
+use crate::role::Role;
+
+/// Which roles `POST /api/users` hands out. Admins may create any role;
+/// everyone else gets players, plus developers when
+/// `SIGNUP_ALLOW_DEVELOPER=true`. `SIGNUP_DEFAULT_ROLE` is used when the
+/// body leaves `role` out and must itself be open to public signup.
+#[derive(Debug, Clone)]
+pub struct SignupPolicy {
+    pub default_role: Role,
+    pub allow_developer: bool,
+}
+
+impl SignupPolicy {
+    pub fn from_env() -> Result<Self, String> {
+        let allow_developer = match std::env::var("SIGNUP_ALLOW_DEVELOPER") {
+            Ok(value) => value
+                .trim()
+                .parse::<bool>()
+                .map_err(|_| "SIGNUP_ALLOW_DEVELOPER must be true or false".to_string())?,
+            Err(_) => false,
+        };
+        let default_role = match std::env::var("SIGNUP_DEFAULT_ROLE") {
+            Ok(value) => value
+                .trim()
+                .parse::<Role>()
+                .map_err(|_| format!("SIGNUP_DEFAULT_ROLE has an invalid role: {}", value))?,
+            Err(_) => Role::Player,
+        };
+
+        let policy = Self { default_role, allow_developer };
+        if !policy.is_public(default_role) {
+            return Err(format!(
+                "SIGNUP_DEFAULT_ROLE cannot be {} when public signup does not allow it",
+                default_role
+            ));
+        }
+        Ok(policy)
+    }
+
+    fn is_public(&self, role: Role) -> bool {
+        match role {
+            Role::Player => true,
+            Role::Developer => self.allow_developer,
+            Role::Admin => false,
+        }
+    }
+
+    /// Lets the request through when the caller may create an account with
+    /// `role`, otherwise returns the 401/403 to send back.
+    #[allow(clippy::result_large_err)]
+    pub fn check(&self, req: &HttpRequest, role: Role) -> Result<(), HttpResponse> {
+        if self.is_public(role) {
+            return Ok(());
+        }
+
+        match auth::principal(req) {
+            Some(Principal::User(claims)) if claims.has_role(Role::Admin) => Ok(()),
+            Some(_) => Err(HttpResponse::Forbidden().json(serde_json::json!({
+                "error": format!("Only admins can create {} accounts", role)
+            }))),
+            None => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": format!("Authentication required to create {} accounts", role)
+            }))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::HttpMessage;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    use super::*;
+    use crate::testing::signed_in;
+
+    fn policy(allow_developer: bool) -> SignupPolicy {
+        SignupPolicy { default_role: Role::Player, allow_developer }
+    }
+
+    fn rejection(result: Result<(), HttpResponse>) -> StatusCode {
+        result.unwrap_err().status()
+    }
+
+    #[test]
+    fn anonymous_admin_signup_is_rejected() {
+        let anonymous = TestRequest::default().to_http_request();
+        assert_eq!(rejection(policy(true).check(&anonymous, Role::Admin)), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn only_admin_tokens_may_create_admins() {
+        assert!(policy(false).check(&signed_in("admin-1", "admin"), Role::Admin).is_ok());
+
+        for role in ["player", "developer"] {
+            let req = signed_in("user-1", role);
+            assert_eq!(rejection(policy(true).check(&req, Role::Admin)), StatusCode::FORBIDDEN, "{role}");
+        }
+
+        let api_key = TestRequest::default().to_http_request();
+        api_key.extensions_mut().insert(Principal::ApiKey { developer_id: "dev-1".to_string(), token_version: 0 });
+        assert_eq!(rejection(policy(true).check(&api_key, Role::Admin)), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn developer_signup_is_public_only_when_allowed() {
+        let anonymous = TestRequest::default().to_http_request();
+        assert!(policy(true).check(&anonymous, Role::Developer).is_ok());
+        assert_eq!(rejection(policy(false).check(&anonymous, Role::Developer)), StatusCode::UNAUTHORIZED);
+        assert!(policy(false).check(&signed_in("admin-1", "admin"), Role::Developer).is_ok());
+    }
+
+    #[test]
+    fn anyone_may_sign_up_as_a_player() {
+        let anonymous = TestRequest::default().to_http_request();
+        assert!(policy(false).check(&anonymous, Role::Player).is_ok());
+    }
+}
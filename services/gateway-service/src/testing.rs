@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use actix_web::test::TestRequest;
+use actix_web::{HttpMessage, HttpRequest};
+use common::error_details;
+use tonic::transport::Server;
+use tonic::transport::server::{Router, TcpIncoming};
+use tonic::{Request, Response, Status};
+
+use crate::game::game_service_server::{GameService, GameServiceServer};
+use crate::grpc_pool::{ChannelPool, PoolConfig};
+use crate::user::user_service_server::{UserService, UserServiceServer};
+use crate::auth::{Claims, Principal};
+use crate::{AppState, game, user};
+
+/// Nothing listens here, so calls to it fail with a transport error.
+pub const UNREACHABLE_URL: &str = "http://127.0.0.1:1";
+
+/// A user service that only knows users, API keys and token versions; every
+/// other RPC answers `Unimplemented`.
+#[derive(Default)]
+pub struct FakeUserService {
+    /// Users by id, for `GetUser`, `BatchGetUsers`, `UpdateUser` and `SearchUsers`.
+    pub users: Mutex<HashMap<String, user::UserMessage>>,
+    /// Active keys by plaintext: the owning developer and the key's scopes.
+    pub api_keys: Mutex<HashMap<String, (String, Vec<String>)>>,
+    /// Users missing here are at version 0.
+    pub token_versions: Mutex<HashMap<String, i32>>,
+    /// RPCs answered so far.
+    pub calls: AtomicUsize,
+}
+
+impl FakeUserService {
+    pub fn with_user(self, user: user::UserMessage) -> Self {
+        self.users.lock().unwrap().insert(user.id.clone(), user);
+        self
+    }
+
+    pub fn with_api_key(self, key: &str, developer_id: &str, scopes: &[&str]) -> Self {
+        let scopes = scopes.iter().map(|s| s.to_string()).collect();
+        self.api_keys.lock().unwrap().insert(key.to_string(), (developer_id.to_string(), scopes));
+        self
+    }
+
+    /// As if `user_id`'s sessions had been revoked `version` times.
+    pub fn with_token_version(self, user_id: &str, version: i32) -> Self {
+        self.token_versions.lock().unwrap().insert(user_id.to_string(), version);
+        self
+    }
+
+    /// Serves the fake on a free local port, returning its URL.
+    pub async fn serve(self: Arc<Self>) -> String {
+        serve(Server::builder().add_service(UserServiceServer::from_arc(self))).await
+    }
+}
+
+#[tonic::async_trait]
+impl UserService for FakeUserService {
+    async fn get_user(
+        &self,
+        request: Request<user::GetUserRequest>,
+    ) -> Result<Response<user::GetUserResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let user = self.users.lock().unwrap().get(&request.into_inner().id).cloned();
+
+        match user {
+            Some(user) => Ok(Response::new(user::GetUserResponse { user: Some(user) })),
+            None => Err(Status::not_found("User not found")),
+        }
+    }
+
+    async fn update_user(
+        &self,
+        request: Request<user::UpdateUserRequest>,
+    ) -> Result<Response<user::UpdateUserResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let req = request.into_inner();
+        let mut users = self.users.lock().unwrap();
+        if !users.contains_key(&req.id) {
+            return Err(Status::not_found("User not found"));
+        }
+        // Reported like the real service does, naming the field
+        let taken = |field: &str, reason: &str| {
+            error_details::status_with_error_info(
+                tonic::Code::AlreadyExists,
+                format!("User with this {} already exists", field),
+                error_details::ErrorInfo::new(reason, "user-service").with_metadata("field", field),
+            )
+        };
+        let others = || users.values().filter(|u| u.id != req.id);
+        if req.email.as_ref().is_some_and(|email| others().any(|u| &u.email == email)) {
+            return Err(taken("email", "EMAIL_TAKEN"));
+        }
+        if req.username.as_ref().is_some_and(|username| others().any(|u| &u.username == username)) {
+            return Err(taken("username", "USERNAME_TAKEN"));
+        }
+        let user = users.get_mut(&req.id).unwrap();
+
+        if let Some(email) = req.email {
+            user.email = email;
+        }
+        if let Some(username) = req.username {
+            user.username = username;
+        }
+        if let Some(role) = req.role {
+            user.role = role;
+        }
+
+        Ok(Response::new(user::UpdateUserResponse { user: Some(user.clone()) }))
+    }
+
+    async fn batch_get_users(
+        &self,
+        request: Request<user::BatchGetUsersRequest>,
+    ) -> Result<Response<user::BatchGetUsersResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let users = self.users.lock().unwrap();
+        let found = request.into_inner().ids.iter().filter_map(|id| users.get(id).cloned()).collect();
+
+        Ok(Response::new(user::BatchGetUsersResponse { users: found }))
+    }
+
+    async fn search_users(
+        &self,
+        request: Request<user::SearchUsersRequest>,
+    ) -> Result<Response<user::SearchUsersResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let req = request.into_inner();
+        let users = self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|u| u.username.contains(&req.query))
+            .take(req.limit.max(0) as usize)
+            .cloned()
+            .collect();
+
+        Ok(Response::new(user::SearchUsersResponse { users }))
+    }
+
+    async fn verify_api_key(
+        &self,
+        request: Request<user::VerifyApiKeyRequest>,
+    ) -> Result<Response<user::VerifyApiKeyResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let keys = self.api_keys.lock().unwrap();
+        let (developer_id, scopes) = keys
+            .get(&request.into_inner().key)
+            .ok_or_else(|| Status::unauthenticated("Invalid or revoked API key"))?;
+
+        let token_version = self.token_versions.lock().unwrap().get(developer_id).copied().unwrap_or(0);
+
+        Ok(Response::new(user::VerifyApiKeyResponse {
+            developer_id: developer_id.clone(),
+            scopes: scopes.clone(),
+            token_version,
+        }))
+    }
+
+    async fn get_token_version(
+        &self,
+        request: Request<user::GetTokenVersionRequest>,
+    ) -> Result<Response<user::GetTokenVersionResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let user_id = request.into_inner().user_id;
+        let token_version = self.token_versions.lock().unwrap().get(&user_id).copied().unwrap_or(0);
+
+        Ok(Response::new(user::GetTokenVersionResponse { token_version }))
+    }
+}
+
+/// A game service holding a fixed set of games, their reviews and an audit
+/// log. `ListGames` applies the developer, exclusion, status, publisher and
+/// category filters, matches the search query as a substring of the name and
+/// pages by offset. `ListAuditLog` filters by actor, game and action and
+/// pages by id like the real service. Every other RPC answers
+/// `Unimplemented`.
+#[derive(Default)]
+pub struct FakeGameService {
+    /// In the order `ListGames` returns them.
+    pub games: Mutex<Vec<game::Game>>,
+    /// Every `ListGames` request received, for checking what the gateway asked.
+    pub list_requests: Mutex<Vec<game::ListGamesRequest>>,
+    /// Reviews by game id, in the order `ListTopReviews` returns them.
+    pub reviews: Mutex<HashMap<String, Vec<game::GameReview>>>,
+    /// Audit entries in the order they were added; ids should ascend.
+    pub audit: Mutex<Vec<game::AuditEntry>>,
+    /// Every `ListAuditLog` request received.
+    pub audit_requests: Mutex<Vec<game::ListAuditLogRequest>>,
+    /// Every `ListNewReleases` and `ListUpcomingGames` request received.
+    pub release_requests: Mutex<Vec<game::ListReleasesRequest>>,
+}
+
+impl FakeGameService {
+    pub fn with_game(self, game: game::Game) -> Self {
+        self.games.lock().unwrap().push(game);
+        self
+    }
+
+    pub fn with_review(self, game_id: &str, review: game::GameReview) -> Self {
+        self.reviews.lock().unwrap().entry(game_id.to_string()).or_default().push(review);
+        self
+    }
+
+    pub fn with_audit_entry(self, entry: game::AuditEntry) -> Self {
+        self.audit.lock().unwrap().push(entry);
+        self
+    }
+
+    /// Games whose release date matches `keep`, paired with that date and
+    /// in insertion order, so a stable sort keeps ties as they were added.
+    fn dated_games(&self, keep: impl Fn(chrono::NaiveDate) -> bool) -> Vec<(chrono::NaiveDate, game::Game)> {
+        self.games
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|g| {
+                let date = g.release_date.as_deref()?.parse::<chrono::NaiveDate>().ok()?;
+                keep(date).then(|| (date, g.clone()))
+            })
+            .collect()
+    }
+
+    /// Serves the fake on a free local port, returning its URL.
+    pub async fn serve(self: Arc<Self>) -> String {
+        serve(Server::builder().add_service(GameServiceServer::from_arc(self))).await
+    }
+}
+
+/// At most `limit` of the sorted games, or all of them for `0`.
+fn take(games: Vec<(chrono::NaiveDate, game::Game)>, limit: i32) -> Vec<game::Game> {
+    let limit = if limit > 0 { limit as usize } else { games.len() };
+    games.into_iter().take(limit).map(|(_, game)| game).collect()
+}
+
+#[tonic::async_trait]
+impl GameService for FakeGameService {
+    async fn get_game(
+        &self,
+        request: Request<game::GetGameRequest>,
+    ) -> Result<Response<game::GetGameResponse>, Status> {
+        let id = request.into_inner().id;
+        let game = self.games.lock().unwrap().iter().find(|g| g.id == id).cloned();
+
+        match game {
+            Some(game) => Ok(Response::new(game::GetGameResponse { game: Some(game) })),
+            None => Err(Status::not_found("Game not found")),
+        }
+    }
+
+    async fn list_games(
+        &self,
+        request: Request<game::ListGamesRequest>,
+    ) -> Result<Response<game::ListGamesResponse>, Status> {
+        let req = request.into_inner();
+        self.list_requests.lock().unwrap().push(req.clone());
+
+        let matching: Vec<game::Game> = self
+            .games
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|g| req.developer_id.as_ref().is_none_or(|id| &g.developer_id == id))
+            .filter(|g| req.exclude_id.as_ref().is_none_or(|id| &g.id != id))
+            .filter(|g| req.status.is_none_or(|status| g.status == status))
+            .filter(|g| req.publisher_id.is_none() || g.publisher_id == req.publisher_id)
+            .filter(|g| req.search_query.as_ref().is_none_or(|q| g.name.contains(q.as_str())))
+            .filter(|g| req.categories.is_empty() || g.categories.iter().any(|c| req.categories.contains(c)))
+            .cloned()
+            .collect();
+
+        let offset = req.page_token.parse::<usize>().unwrap_or(0);
+        let page_size = if req.page_size > 0 { req.page_size as usize } else { matching.len() };
+        let games: Vec<game::Game> = matching.iter().skip(offset).take(page_size).cloned().collect();
+        let next = offset + games.len();
+
+        Ok(Response::new(game::ListGamesResponse {
+            total_count: matching.len() as u64,
+            next_page_token: if next < matching.len() { next.to_string() } else { String::new() },
+            games,
+        }))
+    }
+
+    async fn list_top_reviews(
+        &self,
+        request: Request<game::ListTopReviewsRequest>,
+    ) -> Result<Response<game::ListTopReviewsResponse>, Status> {
+        let req = request.into_inner();
+        let reviews = self.reviews.lock().unwrap().get(&req.game_id).cloned().unwrap_or_default();
+        let limit = if req.limit > 0 { req.limit as usize } else { reviews.len() };
+
+        Ok(Response::new(game::ListTopReviewsResponse {
+            reviews: reviews.into_iter().take(limit).collect(),
+        }))
+    }
+
+    async fn list_new_releases(
+        &self,
+        request: Request<game::ListReleasesRequest>,
+    ) -> Result<Response<game::ListReleasesResponse>, Status> {
+        let req = request.into_inner();
+        self.release_requests.lock().unwrap().push(req);
+
+        let today = chrono::Utc::now().date_naive();
+        let first = today - chrono::Days::new(if req.window_days > 0 { req.window_days as u64 } else { 30 });
+        let mut games = self.dated_games(|date| date > first && date <= today);
+        games.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
+
+        Ok(Response::new(game::ListReleasesResponse { games: take(games, req.limit) }))
+    }
+
+    async fn list_upcoming_games(
+        &self,
+        request: Request<game::ListReleasesRequest>,
+    ) -> Result<Response<game::ListReleasesResponse>, Status> {
+        let req = request.into_inner();
+        self.release_requests.lock().unwrap().push(req);
+
+        let today = chrono::Utc::now().date_naive();
+        let last = today + chrono::Days::new(if req.window_days > 0 { req.window_days as u64 } else { 90 });
+        let mut games = self.dated_games(|date| date > today && date <= last);
+        games.sort_by_key(|(date, _)| *date);
+
+        Ok(Response::new(game::ListReleasesResponse { games: take(games, req.limit) }))
+    }
+
+    async fn list_audit_log(
+        &self,
+        request: Request<game::ListAuditLogRequest>,
+    ) -> Result<Response<game::ListAuditLogResponse>, Status> {
+        let req = request.into_inner();
+        self.audit_requests.lock().unwrap().push(req.clone());
+
+        let before_id = req.page_token.parse::<i64>().ok();
+        let page_size = if req.page_size > 0 { req.page_size as usize } else { usize::MAX };
+        let entries: Vec<game::AuditEntry> = self
+            .audit
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|e| before_id.is_none_or(|before| e.id < before))
+            .filter(|e| req.actor_id.is_none() || e.actor_id == req.actor_id)
+            .filter(|e| req.game_id.as_ref().is_none_or(|id| &e.game_id == id))
+            .filter(|e| req.action.as_ref().is_none_or(|action| &e.action == action))
+            .take(page_size)
+            .cloned()
+            .collect();
+
+        Ok(Response::new(game::ListAuditLogResponse {
+            next_page_token: match entries.last() {
+                Some(last) if entries.len() == page_size => last.id.to_string(),
+                _ => String::new(),
+            },
+            entries,
+        }))
+    }
+}
+
+/// Serves `router` on a free local port, returning its URL.
+async fn serve(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+    tokio::spawn(router.serve_with_incoming(incoming));
+    url
+}
+
+pub fn pool(name: &'static str, urls: &[String]) -> ChannelPool {
+    let config = PoolConfig {
+        channels_per_endpoint: 1,
+        health_check_interval: Duration::from_secs(1),
+    };
+    ChannelPool::new(name, urls, &config).unwrap()
+}
+
+/// Gateway state talking to the given user service, with no game service.
+pub fn app_state(user_url: String) -> AppState {
+    app_state_with(user_url, UNREACHABLE_URL.to_string())
+}
+
+/// Gateway state talking to the given user and game services.
+pub fn app_state_with(user_url: String, game_url: String) -> AppState {
+    AppState {
+        user_channels: pool("user service", &[user_url]),
+        game_channels: pool("game service", &[game_url]),
+    }
+}
+
+/// Claims of an ordinary (not impersonated) session of `user_id`.
+pub fn claims(user_id: &str, role: &str) -> Claims {
+    Claims {
+        sub: user_id.to_string(),
+        role: role.to_string(),
+        exp: 0,
+        nbf: None,
+        iat: None,
+        act_as: None,
+        token_version: 0,
+    }
+}
+
+/// A request as the auth middleware hands it over for `user_id`.
+pub fn signed_in(user_id: &str, role: &str) -> HttpRequest {
+    let req = TestRequest::default().to_http_request();
+    req.extensions_mut().insert(Principal::User(claims(user_id, role)));
+    req
+}
@@ -0,0 +1,177 @@
+use actix_web::{
+    Error, HttpMessage, HttpRequest, HttpResponse,
+    dev::{ServiceRequest, ServiceResponse},
+    error::InternalError,
+    middleware::Next,
+    web,
+};
+use std::time::{Duration, Instant};
+
+use common::config::env_or;
+
+pub struct TimeoutConfig {
+    pub request_timeout: Duration,
+    pub slow_threshold: Duration,
+}
+
+impl TimeoutConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Ok(Self {
+            request_timeout: Duration::from_secs(env_or("REQUEST_TIMEOUT_SECS", 30)?),
+            slow_threshold: Duration::from_millis(env_or("SLOW_REQUEST_THRESHOLD_MS", 1000)?),
+        })
+    }
+
+    /// Whether a request that took `elapsed` gets a slow-request warning.
+    fn is_slow(&self, elapsed: Duration) -> bool {
+        elapsed >= self.slow_threshold
+    }
+}
+
+/// The instant the current request runs out of `request_timeout`.
+#[derive(Debug, Clone, Copy)]
+struct RequestDeadline(Instant);
+
+/// What is left of the request's time budget, for use as the deadline of a
+/// backend call. `None` outside the timeout middleware.
+pub fn remaining(req: &HttpRequest) -> Option<Duration> {
+    req.extensions()
+        .get::<RequestDeadline>()
+        .map(|deadline| deadline.0.saturating_duration_since(Instant::now()))
+}
+
+/// Fails requests that run past `request_timeout` with a 504 and warns about
+/// any request slower than `slow_threshold`. Streaming bodies (SSE, WebSocket)
+/// are not affected because only the time to produce the response head counts.
+pub async fn timeout_middleware(
+    req: ServiceRequest,
+    next: Next<impl actix_web::body::MessageBody + 'static>,
+) -> Result<ServiceResponse<actix_web::body::BoxBody>, Error> {
+    let config = req.app_data::<web::Data<TimeoutConfig>>().unwrap().clone();
+
+    let request_id = req.extensions().get::<String>().cloned().unwrap_or_default();
+    let route = format!(
+        "{} {}",
+        req.method(),
+        req.match_pattern().unwrap_or_else(|| req.path().to_string())
+    );
+
+    let started = Instant::now();
+    req.extensions_mut().insert(RequestDeadline(started + config.request_timeout));
+    let result = tokio::time::timeout(config.request_timeout, next.call(req)).await;
+    let elapsed = started.elapsed();
+
+    if config.is_slow(elapsed) {
+        log::warn!(
+            "Slow request: request_id={} route=\"{}\" elapsed_ms={}",
+            request_id,
+            route,
+            elapsed.as_millis()
+        );
+    }
+
+    match result {
+        Ok(res) => Ok(res?.map_into_boxed_body()),
+        Err(_) => {
+            let response = HttpResponse::GatewayTimeout()
+                .insert_header(("x-request-id", request_id))
+                .json(serde_json::json!({
+                    "error": format!(
+                        "Request timed out after {} seconds",
+                        config.request_timeout.as_secs()
+                    )
+                }));
+
+            Err(InternalError::from_response("request timed out", response).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::{self as actix_test, TestRequest};
+    use actix_web::{App, http::StatusCode, middleware};
+
+    use super::*;
+
+    fn config(request_timeout_ms: u64, slow_threshold_ms: u64) -> TimeoutConfig {
+        TimeoutConfig {
+            request_timeout: Duration::from_millis(request_timeout_ms),
+            slow_threshold: Duration::from_millis(slow_threshold_ms),
+        }
+    }
+
+    async fn sleep_then_ok(millis: u64) -> HttpResponse {
+        tokio::time::sleep(Duration::from_millis(millis)).await;
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_web::test]
+    async fn handlers_running_past_the_timeout_get_a_504() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(config(50, 10)))
+                .wrap(middleware::from_fn(timeout_middleware))
+                .route("/slow", web::get().to(|| sleep_then_ok(500)))
+                .route("/fast", web::get().to(|| sleep_then_ok(0))),
+        )
+        .await;
+
+        let err = actix_test::try_call_service(&app, TestRequest::get().uri("/slow").to_request())
+            .await
+            .unwrap_err();
+        let response = err.error_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        let body = actix_web::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["error"].as_str().unwrap().starts_with("Request timed out"));
+
+        let response = actix_test::call_service(&app, TestRequest::get().uri("/fast").to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn slow_requests_under_the_timeout_still_succeed() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(config(1000, 10)))
+                .wrap(middleware::from_fn(timeout_middleware))
+                .route("/slow", web::get().to(|| sleep_then_ok(50))),
+        )
+        .await;
+
+        let response = actix_test::call_service(&app, TestRequest::get().uri("/slow").to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn requests_at_or_over_the_threshold_are_slow() {
+        let config = config(30_000, 1000);
+
+        assert!(!config.is_slow(Duration::from_millis(999)));
+        assert!(config.is_slow(Duration::from_millis(1000)));
+        assert!(config.is_slow(Duration::from_millis(1001)));
+    }
+
+    #[actix_web::test]
+    async fn handlers_see_what_is_left_of_the_budget() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(config(10_000, 1000)))
+                .wrap(middleware::from_fn(timeout_middleware))
+                .route(
+                    "/budget",
+                    web::get().to(|req: HttpRequest| async move {
+                        let left = remaining(&req).unwrap();
+                        assert!(left <= Duration::from_secs(10) && left > Duration::from_secs(9));
+                        HttpResponse::Ok().finish()
+                    }),
+                ),
+        )
+        .await;
+
+        let response = actix_test::call_service(&app, TestRequest::get().uri("/budget").to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(remaining(&TestRequest::default().to_http_request()), None);
+    }
+}
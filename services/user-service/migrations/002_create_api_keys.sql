@@ -0,0 +1,12 @@
+CREATE TABLE api_keys (
+     id UUID PRIMARY KEY,
+     developer_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+     name VARCHAR(100) NOT NULL,
+     key_prefix VARCHAR(16) NOT NULL,
+     key_hash CHAR(64) UNIQUE NOT NULL,
+     scopes TEXT[] NOT NULL DEFAULT '{}',
+     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+     revoked BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX idx_api_keys_developer_id ON api_keys(developer_id);
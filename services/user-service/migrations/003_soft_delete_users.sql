@@ -0,0 +1,11 @@
+ALTER TABLE users ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Deleted users keep their row, so uniqueness only applies to live accounts.
+-- The indexes keep the old constraint names that conflict errors are mapped from.
+ALTER TABLE users DROP CONSTRAINT users_email_key;
+ALTER TABLE users DROP CONSTRAINT users_username_key;
+DROP INDEX idx_users_email;
+DROP INDEX idx_users_username;
+
+CREATE UNIQUE INDEX users_email_key ON users(email) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX users_username_key ON users(username) WHERE deleted_at IS NULL;
@@ -0,0 +1,10 @@
+CREATE TABLE impersonations (
+     id UUID PRIMARY KEY,
+     admin_id UUID NOT NULL REFERENCES users(id),
+     target_user_id UUID NOT NULL REFERENCES users(id),
+     reason TEXT NOT NULL,
+     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+     expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_impersonations_admin_created ON impersonations(admin_id, created_at);
@@ -0,0 +1,2 @@
+-- Bumped to invalidate every token issued for the user before the bump.
+ALTER TABLE users ADD COLUMN token_version INTEGER NOT NULL DEFAULT 0;
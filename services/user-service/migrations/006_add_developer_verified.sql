@@ -0,0 +1,6 @@
+-- Storefront badge for developers an admin has vetted. Changing a verified
+-- developer to another role clears it (see db::update_user).
+ALTER TABLE users ADD COLUMN verified BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE users
+    ADD CONSTRAINT users_verified_developer CHECK (NOT verified OR role = 'developer');
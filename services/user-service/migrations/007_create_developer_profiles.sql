@@ -0,0 +1,11 @@
+-- Public studio details for a developer account, one per developer. Reads
+-- and writes only go through while the owner is a live developer (see
+-- db::get_developer_profile), so a demoted account's profile stays hidden.
+CREATE TABLE developer_profiles (
+     user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+     studio_name VARCHAR(100) NOT NULL,
+     bio TEXT NOT NULL DEFAULT '',
+     website VARCHAR(255),
+     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
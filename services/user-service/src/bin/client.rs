@@ -23,6 +23,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         username: "newuser".to_string(),
         password: "secret123".to_string(),
         role: user::UserRole::Developer as i32,
+        dry_run: false,
     });
 
     let create_response = client.create_user(create_request).await?;
@@ -1,9 +1,9 @@
 use crate::UserServiceError;
-use argon2::password_hash::{rand_core::OsRng, SaltString};
-use argon2::{Argon2, PasswordHasher};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sha2::{Digest, Sha256};
+use sqlx::{Acquire, PgConnection, PgExecutor, Postgres};
 use uuid::Uuid;
 
 #[derive(Debug, sqlx::Type, Clone, Copy, Serialize, Deserialize)]
@@ -14,6 +14,18 @@ pub enum DbUserRole {
     Admin,
 }
 
+impl DbUserRole {
+    /// `None` for integers the proto enum doesn't define.
+    pub fn from_proto(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Player),
+            1 => Some(Self::Developer),
+            2 => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DbUser {
     pub id: Uuid,
@@ -21,61 +33,155 @@ pub struct DbUser {
     pub username: String,
     pub created_at: DateTime<Utc>,
     pub role: DbUserRole,
+    /// Only ever true for developers.
+    pub verified: bool,
 }
 
-pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    Ok(argon2
-        .hash_password(password.as_bytes(), &salt)?
-        .to_string())
+#[derive(Debug, Clone)]
+pub struct DbApiKey {
+    pub id: Uuid,
+    pub developer_id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub revoked: bool,
 }
 
-pub async fn get_user_by_id(pool: &PgPool, id: &str) -> Result<DbUser, UserServiceError> {
-    let uuid = Uuid::parse_str(id).map_err(|_| UserServiceError::UserNotFound)?;
+#[derive(Debug, Clone)]
+pub struct DbDeveloperProfile {
+    pub user_id: Uuid,
+    pub studio_name: String,
+    pub bio: String,
+    pub website: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
 
+/// Changes for `update_developer_profile`; `None` leaves a field alone and
+/// `website: Some(None)` removes the website.
+pub struct DeveloperProfileChanges<'a> {
+    pub studio_name: Option<&'a str>,
+    pub bio: Option<&'a str>,
+    pub website: Option<Option<&'a str>>,
+}
+
+const API_KEY_PREFIX_LENGTH: usize = 12;
+
+/// Returns a new random key and its prefix. Only the SHA-256 of the key is
+/// stored; keys carry 256 bits of entropy so a slow hash isn't needed.
+pub fn generate_api_key() -> (String, String) {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    let key = format!(
+        "ghk_{}",
+        bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+    );
+    let prefix = key[..API_KEY_PREFIX_LENGTH].to_string();
+
+    (key, prefix)
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+pub async fn get_user_by_id(executor: impl PgExecutor<'_>, id: &Uuid) -> Result<Option<DbUser>, UserServiceError> {
     let record = sqlx::query_as!(
         DbUser,
         r#"
-            SELECT id, email, username, created_at, role as "role: DbUserRole"
+            SELECT id, email, username, created_at, role as "role: DbUserRole", verified
             FROM users
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
-        uuid
+        id
     )
-    .fetch_one(pool)
+    .fetch_optional(executor)
     .await?;
 
-    Ok(DbUser {
-        id: record.id,
-        email: record.email,
-        username: record.username,
-        created_at: record.created_at,
-        role: record.role,
-    })
+    Ok(record)
+}
+
+pub async fn get_users_by_ids(executor: impl PgExecutor<'_>, ids: &[Uuid]) -> Result<Vec<DbUser>, UserServiceError> {
+    let records = sqlx::query_as!(
+        DbUser,
+        r#"
+            SELECT id, email, username, created_at, role as "role: DbUserRole", verified
+            FROM users
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            "#,
+        ids
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records)
+}
+
+/// Returns the name of the unique field (`email` or `username`) an existing
+/// user already holds, if any.
+pub async fn find_user_conflict(
+    executor: impl PgExecutor<'_>,
+    email: &str,
+    username: &str,
+) -> Result<Option<&'static str>, UserServiceError> {
+    let record = sqlx::query!(
+        r#"
+            SELECT email, username
+            FROM users
+            WHERE (email = $1 OR username = $2) AND deleted_at IS NULL
+            LIMIT 1
+            "#,
+        email,
+        username
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record.map(|r| if r.email == email { "email" } else { "username" }))
+}
+
+/// The live user holding `email`, or failing that `username`.
+pub async fn find_live_user(
+    executor: impl PgExecutor<'_>,
+    email: &str,
+    username: &str,
+) -> Result<Option<DbUser>, UserServiceError> {
+    let record = sqlx::query_as!(
+        DbUser,
+        r#"
+            SELECT id, email, username, created_at, role as "role: DbUserRole", verified
+            FROM users
+            WHERE (email = $1 OR username = $2) AND deleted_at IS NULL
+            ORDER BY (email = $1) DESC
+            LIMIT 1
+            "#,
+        email,
+        username
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record)
 }
 
 pub async fn create_user(
-    pool: &PgPool,
+    executor: impl PgExecutor<'_>,
     req: &crate::user::CreateUserRequest,
     password_hash: &str,
 ) -> Result<DbUser, UserServiceError> {
     let id = Uuid::new_v4();
     let now = Utc::now();
 
-    let db_role = match req.role {
-        0 => DbUserRole::Player,
-        1 => DbUserRole::Developer,
-        2 => DbUserRole::Admin,
-        _ => DbUserRole::Player,
-    };
+    let db_role = DbUserRole::from_proto(req.role).unwrap_or(DbUserRole::Player);
 
     let record = sqlx::query_as!(
         DbUser,
         r#"
             INSERT INTO users (id, email, username, password_hash, role, created_at, updated_at)
             VALUES ($1, $2, $3, $4, $5, $6, $6)
-            RETURNING id, email, username, created_at, role as "role: DbUserRole"
+            RETURNING id, email, username, created_at, role as "role: DbUserRole", verified
             "#,
         id,
         req.email,
@@ -84,7 +190,7 @@ pub async fn create_user(
         db_role as DbUserRole,
         now
     )
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await?;
 
     Ok(DbUser {
@@ -93,20 +199,81 @@ pub async fn create_user(
         username: record.username,
         created_at: record.created_at,
         role: record.role,
+        verified: record.verified,
     })
 }
 
+/// Inserts a user unless a live account already holds the email or username,
+/// in which case the conflicting field is returned instead. Meant for use
+/// inside a transaction, where earlier rows of the same batch count too.
+async fn insert_user_if_absent(
+    conn: &mut PgConnection,
+    req: &crate::user::CreateUserRequest,
+    password_hash: &str,
+) -> Result<Result<DbUser, &'static str>, UserServiceError> {
+    let db_role = DbUserRole::from_proto(req.role).unwrap_or(DbUserRole::Player);
+
+    let record = sqlx::query_as!(
+        DbUser,
+        r#"
+            INSERT INTO users (id, email, username, password_hash, role, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, NOW(), NOW())
+            ON CONFLICT DO NOTHING
+            RETURNING id, email, username, created_at, role as "role: DbUserRole", verified
+            "#,
+        Uuid::new_v4(),
+        req.email,
+        req.username,
+        password_hash,
+        db_role as DbUserRole
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    match record {
+        Some(user) => Ok(Ok(user)),
+        None => {
+            let field = find_user_conflict(&mut *conn, &req.email, &req.username)
+                .await?
+                .unwrap_or("email");
+            Ok(Err(field))
+        }
+    }
+}
+
+/// Inserts the users in one transaction, returning each row's outcome in
+/// order. With `rollback_on_conflict` a single conflicting row rolls back the
+/// whole batch, though every row is still attempted so all conflicts are
+/// reported.
+pub async fn insert_users(
+    conn: impl Acquire<'_, Database = Postgres>,
+    users: &[(&crate::user::CreateUserRequest, String)],
+    rollback_on_conflict: bool,
+) -> Result<Vec<Result<DbUser, &'static str>>, UserServiceError> {
+    let mut tx = conn.begin().await?;
+
+    let mut outcomes = Vec::with_capacity(users.len());
+    for (req, password_hash) in users {
+        outcomes.push(insert_user_if_absent(&mut tx, req, password_hash).await?);
+    }
+
+    if rollback_on_conflict && outcomes.iter().any(Result::is_err) {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+
+    Ok(outcomes)
+}
+
 pub async fn update_user(
-    pool: &PgPool,
+    executor: impl PgExecutor<'_>,
     req: &crate::user::UpdateUserRequest,
-) -> Result<DbUser, UserServiceError> {
+    password_hash: Option<&str>,
+) -> Result<Option<DbUser>, UserServiceError> {
     let id = Uuid::parse_str(&req.id)?;
 
-    let password_hash = if let Some(password) = &req.password {
-        Some(hash_password(password)?)
-    } else {
-        None
-    };
+    let db_role = req.role.map(|role| DbUserRole::from_proto(role).unwrap_or(DbUserRole::Player));
 
     let record = sqlx::query_as!(
         DbUser,
@@ -116,35 +283,37 @@ pub async fn update_user(
                 email = COALESCE($2, email),
                 username = COALESCE($3, username),
                 password_hash = COALESCE($4, password_hash),
+                role = COALESCE($5, role),
+                verified = verified AND COALESCE($5, role) = 'developer',
                 updated_at = NOW()
-            WHERE id = $1
-            RETURNING id, email, username, created_at, role as "role: DbUserRole"
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id, email, username, created_at, role as "role: DbUserRole", verified
             "#,
         id,
         req.email,
         req.username,
-        password_hash
+        password_hash,
+        db_role as Option<DbUserRole>
     )
-    .fetch_one(pool)
+    .fetch_optional(executor)
     .await?;
 
     Ok(record)
 }
 
-pub async fn delete_user(pool: &PgPool, id: &Uuid) -> Result<bool, UserServiceError> {
-    let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
-        .execute(pool)
-        .await?;
+pub async fn delete_user(executor: impl PgExecutor<'_>, id: &Uuid) -> Result<bool, UserServiceError> {
+    let result = sqlx::query!(
+        "UPDATE users SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+        id
+    )
+    .execute(executor)
+    .await?;
 
-    if result.rows_affected() > 0 {
-        Ok(true)
-    } else {
-        Err(UserServiceError::UserNotFound)
-    }
+    Ok(result.rows_affected() > 0)
 }
 
 pub async fn list_users(
-    pool: &PgPool,
+    executor: impl PgExecutor<'_>,
     limit: Option<i32>,
     offset: Option<i32>,
 ) -> Result<Vec<DbUser>, UserServiceError> {
@@ -154,16 +323,379 @@ pub async fn list_users(
     let records = sqlx::query_as!(
         DbUser,
         r#"
-            SELECT id, email, username, created_at, role as "role: DbUserRole"
+            SELECT id, email, username, created_at, role as "role: DbUserRole", verified
             FROM users
+            WHERE deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
             "#,
         limit as i64,
         offset as i64,
     )
-    .fetch_all(pool)
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records)
+}
+
+pub async fn search_users(executor: impl PgExecutor<'_>, query: &str, limit: i32) -> Result<Vec<DbUser>, UserServiceError> {
+    let pattern = format!(
+        "%{}%",
+        query.to_lowercase().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    );
+
+    let records = sqlx::query_as!(
+        DbUser,
+        r#"
+            SELECT id, email, username, created_at, role as "role: DbUserRole", verified
+            FROM users
+            WHERE deleted_at IS NULL
+                AND (lower(username) LIKE $1 OR lower(email) LIKE $1)
+            ORDER BY username
+            LIMIT $2
+            "#,
+        pattern,
+        limit as i64,
+    )
+    .fetch_all(executor)
     .await?;
 
     Ok(records)
 }
+
+pub async fn count_users(executor: impl PgExecutor<'_>) -> Result<i64, UserServiceError> {
+    let total = sqlx::query_scalar!("SELECT COUNT(*) FROM users WHERE deleted_at IS NULL")
+        .fetch_one(executor)
+        .await?;
+
+    Ok(total.unwrap_or(0))
+}
+
+pub async fn create_api_key(
+    executor: impl PgExecutor<'_>,
+    developer_id: &Uuid,
+    name: &str,
+    scopes: &[String],
+    key_prefix: &str,
+    key_hash: &str,
+) -> Result<DbApiKey, UserServiceError> {
+    let record = sqlx::query_as!(
+        DbApiKey,
+        r#"
+            INSERT INTO api_keys (id, developer_id, name, key_prefix, key_hash, scopes)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, developer_id, name, key_prefix, scopes, created_at, revoked
+            "#,
+        Uuid::new_v4(),
+        developer_id,
+        name,
+        key_prefix,
+        key_hash,
+        scopes
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(record)
+}
+
+pub async fn list_api_keys(
+    executor: impl PgExecutor<'_>,
+    developer_id: &Uuid,
+) -> Result<Vec<DbApiKey>, UserServiceError> {
+    let records = sqlx::query_as!(
+        DbApiKey,
+        r#"
+            SELECT id, developer_id, name, key_prefix, scopes, created_at, revoked
+            FROM api_keys
+            WHERE developer_id = $1
+            ORDER BY created_at DESC
+            "#,
+        developer_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records)
+}
+
+pub async fn revoke_api_key(
+    executor: impl PgExecutor<'_>,
+    id: &Uuid,
+    developer_id: &Uuid,
+) -> Result<(), UserServiceError> {
+    let result = sqlx::query!(
+        "UPDATE api_keys SET revoked = TRUE WHERE id = $1 AND developer_id = $2",
+        id,
+        developer_id
+    )
+    .execute(executor)
+    .await?;
+
+    if result.rows_affected() > 0 {
+        Ok(())
+    } else {
+        Err(UserServiceError::ApiKeyNotFound)
+    }
+}
+
+pub async fn find_active_api_key(
+    executor: impl PgExecutor<'_>,
+    key_hash: &str,
+) -> Result<Option<DbApiKey>, UserServiceError> {
+    let record = sqlx::query_as!(
+        DbApiKey,
+        r#"
+            SELECT id, developer_id, name, key_prefix, scopes, created_at, revoked
+            FROM api_keys
+            WHERE key_hash = $1
+              AND NOT revoked
+              AND EXISTS (SELECT 1 FROM users WHERE users.id = developer_id AND deleted_at IS NULL)
+            "#,
+        key_hash
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record)
+}
+
+/// The version a token for `id` must carry to be accepted, or `None` when
+/// the user does not exist or has been deleted.
+pub async fn get_token_version(executor: impl PgExecutor<'_>, id: &Uuid) -> Result<Option<i32>, UserServiceError> {
+    let version = sqlx::query_scalar!(
+        "SELECT token_version FROM users WHERE id = $1 AND deleted_at IS NULL",
+        id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(version)
+}
+
+/// Increments the user's token version and returns the new value, or `None`
+/// when there is no live user with that id.
+pub async fn revoke_sessions(executor: impl PgExecutor<'_>, id: &Uuid) -> Result<Option<i32>, UserServiceError> {
+    let version = sqlx::query_scalar!(
+        r#"
+            UPDATE users
+            SET token_version = token_version + 1, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING token_version
+            "#,
+        id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(version)
+}
+
+/// A live user together with their stored password hash.
+pub async fn get_credentials_by_email(
+    executor: impl PgExecutor<'_>,
+    email: &str,
+) -> Result<Option<(DbUser, String)>, UserServiceError> {
+    let record = sqlx::query!(
+        r#"
+            SELECT id, email, username, created_at, role as "role: DbUserRole", verified, password_hash
+            FROM users
+            WHERE email = $1 AND deleted_at IS NULL
+            "#,
+        email
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record.map(|r| {
+        (
+            DbUser {
+                id: r.id,
+                email: r.email,
+                username: r.username,
+                created_at: r.created_at,
+                role: r.role,
+                verified: r.verified,
+            },
+            r.password_hash,
+        )
+    }))
+}
+
+/// Swaps in a rehashed password, but only if the stored hash is still
+/// `old_hash`, so a password changed in the meantime is never overwritten.
+/// `updated_at` is left alone: nothing about the user changed.
+pub async fn replace_password_hash(
+    executor: impl PgExecutor<'_>,
+    id: &Uuid,
+    old_hash: &str,
+    new_hash: &str,
+) -> Result<bool, UserServiceError> {
+    let result = sqlx::query!(
+        "UPDATE users SET password_hash = $3 WHERE id = $1 AND password_hash = $2 AND deleted_at IS NULL",
+        id,
+        old_hash,
+        new_hash
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Sets the badge on a live developer. `None` when there is no live
+/// developer with that id.
+pub async fn set_developer_verified(
+    executor: impl PgExecutor<'_>,
+    id: &Uuid,
+    verified: bool,
+) -> Result<Option<DbUser>, UserServiceError> {
+    let record = sqlx::query_as!(
+        DbUser,
+        r#"
+            UPDATE users
+            SET verified = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL AND role = 'developer'
+            RETURNING id, email, username, created_at, role as "role: DbUserRole", verified
+            "#,
+        id,
+        verified
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record)
+}
+
+/// Inserts the profile, or returns `None` if the user already has one or
+/// is not a live developer.
+pub async fn create_developer_profile(
+    executor: impl PgExecutor<'_>,
+    user_id: &Uuid,
+    studio_name: &str,
+    bio: &str,
+    website: Option<&str>,
+) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+    let record = sqlx::query_as!(
+        DbDeveloperProfile,
+        r#"
+            INSERT INTO developer_profiles (user_id, studio_name, bio, website)
+            SELECT id, $2, $3, $4
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL AND role = 'developer'
+            ON CONFLICT (user_id) DO NOTHING
+            RETURNING user_id, studio_name, bio, website, created_at, updated_at
+            "#,
+        user_id,
+        studio_name,
+        bio,
+        website
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record)
+}
+
+/// The profile of a live developer; `None` for anyone else.
+pub async fn get_developer_profile(
+    executor: impl PgExecutor<'_>,
+    user_id: &Uuid,
+) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+    let record = sqlx::query_as!(
+        DbDeveloperProfile,
+        r#"
+            SELECT p.user_id, p.studio_name, p.bio, p.website, p.created_at, p.updated_at
+            FROM developer_profiles p
+            JOIN users u ON u.id = p.user_id
+            WHERE p.user_id = $1 AND u.deleted_at IS NULL AND u.role = 'developer'
+            "#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record)
+}
+
+pub async fn update_developer_profile(
+    executor: impl PgExecutor<'_>,
+    user_id: &Uuid,
+    changes: &DeveloperProfileChanges<'_>,
+) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+    let record = sqlx::query_as!(
+        DbDeveloperProfile,
+        r#"
+            UPDATE developer_profiles p
+            SET studio_name = COALESCE($2, p.studio_name),
+                bio = COALESCE($3, p.bio),
+                website = CASE WHEN $4 THEN $5 ELSE p.website END,
+                updated_at = NOW()
+            FROM users u
+            WHERE p.user_id = $1 AND u.id = p.user_id AND u.deleted_at IS NULL AND u.role = 'developer'
+            RETURNING p.user_id, p.studio_name, p.bio, p.website, p.created_at, p.updated_at
+            "#,
+        user_id,
+        changes.studio_name,
+        changes.bio,
+        changes.website.is_some(),
+        changes.website.flatten()
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record)
+}
+
+/// Writes the audit entry for an impersonation unless `admin_id` has already
+/// started `max_per_hour` of them in the last hour, in which case nothing is
+/// written and `false` is returned. The admin's row is locked so concurrent
+/// requests can't both slip under the limit.
+pub async fn record_impersonation(
+    conn: impl Acquire<'_, Database = Postgres>,
+    admin_id: &Uuid,
+    target_user_id: &Uuid,
+    reason: &str,
+    expires_at: DateTime<Utc>,
+    max_per_hour: i64,
+) -> Result<bool, UserServiceError> {
+    let mut tx = conn.begin().await?;
+
+    sqlx::query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", admin_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let recent = sqlx::query_scalar!(
+        r#"
+            SELECT COUNT(*)
+            FROM impersonations
+            WHERE admin_id = $1 AND created_at > NOW() - INTERVAL '1 hour'
+            "#,
+        admin_id
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .unwrap_or(0);
+
+    if recent >= max_per_hour {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+            INSERT INTO impersonations (id, admin_id, target_user_id, reason, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        Uuid::new_v4(),
+        admin_id,
+        target_user_id,
+        reason,
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
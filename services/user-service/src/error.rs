@@ -3,8 +3,10 @@ pub enum UserServiceError {
     Database(sqlx::Error),
     InvalidUuid(uuid::Error),
     PasswordHash(argon2::password_hash::Error),
-    UserNotFound,
+    ApiKeyNotFound,
     ValidationError(String),
+    /// A live user already holds the named field (`email` or `username`)
+    Conflict(&'static str),
 }
 
 impl std::fmt::Display for UserServiceError {
@@ -13,8 +15,9 @@ impl std::fmt::Display for UserServiceError {
             UserServiceError::Database(e) => write!(f, "Database error: {}", e),
             UserServiceError::InvalidUuid(e) => write!(f, "Invalid UUID: {}", e),
             UserServiceError::PasswordHash(e) => write!(f, "Password hashing error: {}", e),
-            UserServiceError::UserNotFound => write!(f, "User not found"),
+            UserServiceError::ApiKeyNotFound => write!(f, "API key not found"),
             UserServiceError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
+            UserServiceError::Conflict(field) => write!(f, "User with this {} already exists", field),
         }
     }
 }
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::{DbUser, DbUserRole};
+
+/// Same shape as the gateway's user tokens, plus `act_as`, which carries the
+/// id of the admin acting as `sub` and marks the token as an impersonation.
+#[derive(Serialize)]
+struct Claims<'a> {
+    sub: String,
+    role: &'a str,
+    exp: usize,
+    act_as: String,
+    token_version: i32,
+}
+
+/// Signs impersonation tokens with the same secret the gateway verifies
+/// user tokens with.
+pub struct ImpersonationConfig {
+    encoding_key: EncodingKey,
+    pub ttl: Duration,
+    pub max_per_hour: i64,
+}
+
+impl ImpersonationConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let secret = std::env::var("JWT_SECRET")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| "JWT_SECRET must be set".to_string())?;
+
+        let ttl_secs = match std::env::var("IMPERSONATION_TOKEN_TTL_SECS") {
+            Ok(value) => value
+                .parse::<i64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .ok_or_else(|| format!("IMPERSONATION_TOKEN_TTL_SECS has an invalid value: {}", value))?,
+            Err(_) => 15 * 60,
+        };
+
+        let max_per_hour = match std::env::var("IMPERSONATION_MAX_PER_HOUR") {
+            Ok(value) => value
+                .parse::<i64>()
+                .map_err(|_| format!("IMPERSONATION_MAX_PER_HOUR has an invalid value: {}", value))?,
+            Err(_) => 10,
+        };
+
+        Ok(Self::new(&secret, Duration::seconds(ttl_secs), max_per_hour))
+    }
+
+    pub fn new(secret: &str, ttl: Duration, max_per_hour: i64) -> Self {
+        Self {
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            ttl,
+            max_per_hour,
+        }
+    }
+
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        Utc::now() + self.ttl
+    }
+
+    pub fn mint(
+        &self,
+        user: &DbUser,
+        token_version: i32,
+        admin_id: &Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let role = match user.role {
+            DbUserRole::Player => "player",
+            DbUserRole::Developer => "developer",
+            DbUserRole::Admin => "admin",
+        };
+
+        let claims = Claims {
+            sub: user.id.to_string(),
+            role,
+            exp: expires_at.timestamp() as usize,
+            act_as: admin_id.to_string(),
+            token_version,
+        };
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+    }
+}
@@ -2,17 +2,26 @@ use tonic::transport::Server;
 use tonic::{Request, Response, Status};
 
 use sqlx::postgres::PgPoolOptions;
-use sqlx::PgPool;
 
 use dotenv::dotenv;
 use std::env;
-
-use chrono::{DateTime, Utc};
-use prost_types::Timestamp;
+use std::sync::Arc;
+use tracing_subscriber::EnvFilter;
 
 use uuid::Uuid;
 
+use common::auth::{self, Caller, CallerVerifier};
+use common::deadline::{Deadline, DeadlineLayer};
+use common::enum_policy::EnumPolicy;
+use common::error_details::{status_with_error_info, ErrorInfo};
+use common::pagination::PageSizeConfig;
+use common::rpc_log::RpcLogLayer;
+use common::timestamp::datetime_to_timestamp;
 use error::UserServiceError;
+use impersonation::ImpersonationConfig;
+use password::PasswordConfig;
+use repository::{PgUserRepository, UserRepository};
+use validation::FieldLimits;
 
 pub mod user {
     tonic::include_proto!("user");
@@ -20,15 +29,48 @@ pub mod user {
 
 mod db;
 mod error;
+mod impersonation;
+#[cfg(test)]
+mod memory_repository;
+mod password;
+mod repository;
 mod validation;
 
 pub struct UserServiceImpl {
-    pool: PgPool,
+    repo: Arc<dyn UserRepository>,
+    page_size: PageSizeConfig,
+    impersonation: ImpersonationConfig,
+    passwords: PasswordConfig,
+    enums: EnumPolicy,
+    limits: FieldLimits,
 }
 
 impl UserServiceImpl {
-    fn new(pool: PgPool) -> Self {
-        Self { pool }
+    fn new(
+        repo: Arc<dyn UserRepository>,
+        page_size: PageSizeConfig,
+        impersonation: ImpersonationConfig,
+        passwords: PasswordConfig,
+        enums: EnumPolicy,
+        limits: FieldLimits,
+    ) -> Self {
+        Self {
+            repo,
+            page_size,
+            impersonation,
+            passwords,
+            enums,
+            limits,
+        }
+    }
+
+    /// The proto value of the role a request asked for, after the unknown
+    /// enum policy: an undefined value becomes a player, or is refused in
+    /// strict mode.
+    fn resolve_role(&self, value: i32) -> Result<i32, String> {
+        self.enums
+            .resolve("role", value, db::DbUserRole::from_proto(value), db::DbUserRole::Player)
+            .map(db_role_to_proto)
     }
 }
 
@@ -38,74 +80,164 @@ impl user::user_service_server::UserService for UserServiceImpl {
         &self,
         request: Request<user::GetUserRequest>,
     ) -> Result<Response<user::GetUserResponse>, Status> {
-        let user_id = request.into_inner().id;
+        let user_id = parse_id(&request.into_inner().id)?;
 
-        let user_record = db::get_user_by_id(&self.pool, &user_id)
+        let user_record = self.repo.get_user_by_id(&user_id)
             .await
-            .map_err(user_service_error_to_status)?;
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
 
-        let user_msg = user::UserMessage {
-            id: user_record.id.to_string(),
-            email: user_record.email,
-            username: user_record.username,
-            role: db_role_to_proto(user_record.role),
-            created_at: Some(datetime_to_timestamp(user_record.created_at)),
-        };
+        let user_msg = db_user_to_proto(user_record);
 
         Ok(Response::new(user::GetUserResponse {
             user: Some(user_msg),
         }))
     }
 
+    async fn batch_get_users(
+        &self,
+        request: Request<user::BatchGetUsersRequest>,
+    ) -> Result<Response<user::BatchGetUsersResponse>, Status> {
+        let req = request.into_inner();
+
+        if let Err(e) = validation::validate_batch_get_users_request(&req) {
+            return Err(validation_failed(e));
+        }
+
+        let mut ids = Vec::with_capacity(req.ids.len());
+        for id in &req.ids {
+            ids.push(parse_id(id)?);
+        }
+        ids.sort();
+        ids.dedup();
+
+        let users = self.repo.get_users_by_ids(&ids)
+            .await
+            .map_err(user_service_error_to_status)?;
+
+        Ok(Response::new(user::BatchGetUsersResponse {
+            users: users.into_iter().map(db_user_to_proto).collect(),
+        }))
+    }
+
     async fn create_user(
         &self,
         request: Request<user::CreateUserRequest>,
     ) -> Result<Response<user::UserMessage>, Status> {
-        let req = request.into_inner();
+        let mut req = request.into_inner();
+
+        if let Err(e) = validation::validate_create_user_request(&req, &self.limits) {
+            return Err(validation_failed(e));
+        }
+        req.role = self.resolve_role(req.role).map_err(unknown_enum_value)?;
+
+        if req.dry_run {
+            if let Some(field) = self.repo.find_user_conflict(&req.email, &req.username)
+                .await
+                .map_err(user_service_error_to_status)?
+            {
+                return Err(user_conflict(field));
+            }
 
-        if let Err(e) = validation::validate_create_user_request(&req) {
-            return Err(Status::invalid_argument(e));
+            return Ok(Response::new(user::UserMessage {
+                id: String::new(),
+                email: req.email,
+                username: req.username,
+                role: req.role,
+                created_at: None,
+                verified: false,
+            }));
         }
 
-        let password_hash = db::hash_password(&req.password)
+        let password_hash = self.passwords.hash(&req.password)
             .map_err(|e| Status::internal(format!("Password hash failed: {}", e)))?;
 
-        let user_record = db::create_user(&self.pool, &req, &password_hash)
+        let user_record = self.repo.create_user(&req, &password_hash)
             .await
             .map_err(user_service_error_to_status)?;
 
-        let user_msg = user::UserMessage {
-            id: user_record.id.to_string(),
-            email: user_record.email,
-            username: user_record.username,
-            role: db_role_to_proto(user_record.role),
-            created_at: Some(datetime_to_timestamp(user_record.created_at)),
-        };
+        let user_msg = db_user_to_proto(user_record);
 
         Ok(Response::new(user_msg))
     }
 
+    async fn ensure_user(
+        &self,
+        request: Request<user::CreateUserRequest>,
+    ) -> Result<Response<user::EnsureUserResponse>, Status> {
+        let mut req = request.into_inner();
+
+        if req.dry_run {
+            return Err(error_status(
+                tonic::Code::InvalidArgument,
+                "dry_run is not supported by EnsureUser",
+                "DRY_RUN_UNSUPPORTED",
+            ));
+        }
+        if let Err(e) = validation::validate_create_user_request(&req, &self.limits) {
+            return Err(validation_failed(e));
+        }
+        req.role = self.resolve_role(req.role).map_err(unknown_enum_value)?;
+
+        let password_hash = self.passwords.hash(&req.password)
+            .map_err(|e| Status::internal(format!("Password hash failed: {}", e)))?;
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+
+        // Insert first and fall back to a lookup, so two concurrent calls
+        // for the same user both end up with the row the unique index kept.
+        let (user_record, created) = match repo.create_user(&req, &password_hash).await {
+            Ok(user_record) => (user_record, true),
+            Err(e) => {
+                let Some(field) = conflict_field(&e) else {
+                    return Err(user_service_error_to_status(e));
+                };
+                let existing = repo.find_live_user(&req.email, &req.username)
+                    .await
+                    .map_err(user_service_error_to_status)?
+                    // Deleted between the insert and the lookup
+                    .ok_or_else(|| user_conflict(field))?;
+                (existing, false)
+            }
+        };
+
+        Ok(Response::new(user::EnsureUserResponse {
+            user: Some(db_user_to_proto(user_record)),
+            created,
+        }))
+    }
+
     async fn update_user(
         &self,
         request: Request<user::UpdateUserRequest>,
     ) -> Result<Response<user::UpdateUserResponse>, Status> {
-        let req = request.into_inner();
+        let mut req = request.into_inner();
+        parse_id(&req.id)?;
 
-        if let Err(e) = validation::validate_update_user_request(&req) {
-            return Err(Status::invalid_argument(e));
+        if let Err(e) = validation::validate_update_user_request(&req, &self.limits) {
+            return Err(validation_failed(e));
         }
+        req.role = req
+            .role
+            .map(|role| self.resolve_role(role))
+            .transpose()
+            .map_err(unknown_enum_value)?;
+
+        let password_hash = match &req.password {
+            Some(password) => Some(
+                self.passwords
+                    .hash(password)
+                    .map_err(|e| Status::internal(format!("Password hash failed: {}", e)))?,
+            ),
+            None => None,
+        };
 
-        let user_record = db::update_user(&self.pool, &req)
+        let user_record = self.repo.update_user(&req, password_hash.as_deref())
             .await
-            .map_err(user_service_error_to_status)?;
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
 
-        let user_msg = user::UserMessage {
-            id: user_record.id.to_string(),
-            email: user_record.email,
-            username: user_record.username,
-            role: db_role_to_proto(user_record.role),
-            created_at: Some(datetime_to_timestamp(user_record.created_at)),
-        };
+        let user_msg = db_user_to_proto(user_record);
 
         Ok(Response::new(user::UpdateUserResponse {
             user: Some(user_msg),
@@ -116,18 +248,24 @@ impl user::user_service_server::UserService for UserServiceImpl {
         &self,
         request: Request<user::DeleteUserRequest>,
     ) -> Result<Response<user::DeleteUserResponse>, Status> {
-        let req = request.into_inner();
-
-        let id = Uuid::parse_str(&req.id)
-            .map_err(|e| Status::invalid_argument(format!("Invalid UUID: {}", e)))?;
+        let id = parse_id(&request.into_inner().id)?;
 
-        let success = db::delete_user(&self.pool, &id)
+        // Deleting a user that is already gone succeeds, so clients can
+        // retry a delete whose response they never saw.
+        let deleted = self.repo.delete_user(&id)
             .await
             .map_err(user_service_error_to_status)?;
 
+        let message = if deleted {
+            "User deleted successfully"
+        } else {
+            "User already deleted"
+        };
+
         Ok(Response::new(user::DeleteUserResponse {
-            success,
-            message: "User deleted successfully".to_string(),
+            success: true,
+            message: message.to_string(),
+            already_absent: !deleted,
         }))
     }
 
@@ -135,49 +273,713 @@ impl user::user_service_server::UserService for UserServiceImpl {
         &self,
         request: Request<user::ListUsersRequest>,
     ) -> Result<Response<user::ListUsersResponse>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
         let req = request.into_inner();
 
-        let users = db::list_users(&self.pool, Some(req.limit), Some(req.offset))
+        let limit = self.page_size.clamp(req.limit);
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+
+        let users = repo.list_users(limit, req.offset.max(0))
+            .await
+            .map_err(user_service_error_to_status)?;
+
+        let total = repo.count_users()
             .await
-            .map_err(|e| Status::internal(format!("Failed to list users: {}", e)))?;
+            .map_err(user_service_error_to_status)?;
 
         let user_messages: Vec<user::UserMessage> = users
             .into_iter()
-            .map(|user| user::UserMessage {
-                id: user.id.to_string(),
-                email: user.email,
-                username: user.username,
-                role: db_role_to_proto(user.role),
-                created_at: Some(datetime_to_timestamp(user.created_at)),
-            })
+            .map(db_user_to_proto)
             .collect();
 
-        let total = user_messages.len() as i32;
-
         Ok(Response::new(user::ListUsersResponse {
             users: user_messages,
-            total,
+            total: total as i32,
+        }))
+    }
+
+    async fn search_users(
+        &self,
+        request: Request<user::SearchUsersRequest>,
+    ) -> Result<Response<user::SearchUsersResponse>, Status> {
+        Deadline::from_metadata(request.metadata()).check()?;
+        let req = request.into_inner();
+
+        let query = req.query.trim();
+        if query.is_empty() {
+            return Ok(Response::new(user::SearchUsersResponse { users: Vec::new() }));
+        }
+
+        let limit = self.page_size.clamp(req.limit);
+        let users = self.repo.search_users(query, limit)
+            .await
+            .map_err(user_service_error_to_status)?;
+
+        Ok(Response::new(user::SearchUsersResponse {
+            users: users.into_iter().map(db_user_to_proto).collect(),
+        }))
+    }
+
+    async fn create_api_key(
+        &self,
+        request: Request<user::CreateApiKeyRequest>,
+    ) -> Result<Response<user::CreateApiKeyResponse>, Status> {
+        let req = request.into_inner();
+        let developer_id = parse_id(&req.developer_id)?;
+
+        if let Err(e) = validation::validate_create_api_key_request(&req) {
+            return Err(validation_failed(e));
+        }
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+
+        let owner = repo.get_user_by_id(&developer_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
+
+        if !matches!(owner.role, db::DbUserRole::Developer | db::DbUserRole::Admin) {
+            return Err(error_status(
+                tonic::Code::PermissionDenied,
+                "Only developers can create API keys",
+                "NOT_A_DEVELOPER",
+            ));
+        }
+
+        let (key, key_prefix) = db::generate_api_key();
+
+        let record = repo
+            .create_api_key(
+                &developer_id,
+                req.name.trim(),
+                &req.scopes,
+                &key_prefix,
+                &db::hash_api_key(&key),
+            )
+            .await
+            .map_err(user_service_error_to_status)?;
+
+        Ok(Response::new(user::CreateApiKeyResponse {
+            api_key: Some(db_api_key_to_proto(record)),
+            key,
+        }))
+    }
+
+    async fn list_api_keys(
+        &self,
+        request: Request<user::ListApiKeysRequest>,
+    ) -> Result<Response<user::ListApiKeysResponse>, Status> {
+        let developer_id = parse_id(&request.into_inner().developer_id)?;
+
+        let records = self.repo.list_api_keys(&developer_id)
+            .await
+            .map_err(user_service_error_to_status)?;
+
+        Ok(Response::new(user::ListApiKeysResponse {
+            api_keys: records.into_iter().map(db_api_key_to_proto).collect(),
+        }))
+    }
+
+    async fn revoke_api_key(
+        &self,
+        request: Request<user::RevokeApiKeyRequest>,
+    ) -> Result<Response<user::RevokeApiKeyResponse>, Status> {
+        let req = request.into_inner();
+
+        let id = parse_id(&req.id)?;
+        let developer_id = parse_id(&req.developer_id)?;
+
+        self.repo.revoke_api_key(&id, &developer_id)
+            .await
+            .map_err(user_service_error_to_status)?;
+
+        Ok(Response::new(user::RevokeApiKeyResponse { success: true }))
+    }
+
+    async fn verify_api_key(
+        &self,
+        request: Request<user::VerifyApiKeyRequest>,
+    ) -> Result<Response<user::VerifyApiKeyResponse>, Status> {
+        let key = request.into_inner().key;
+
+        let invalid_key = || {
+            error_status(
+                tonic::Code::Unauthenticated,
+                "Invalid or revoked API key",
+                "INVALID_API_KEY",
+            )
+        };
+
+        let record = self.repo.find_active_api_key(&db::hash_api_key(&key))
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(invalid_key)?;
+        // A deleted developer's keys die with the account
+        let token_version = self.repo.get_token_version(&record.developer_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(invalid_key)?;
+
+        Ok(Response::new(user::VerifyApiKeyResponse {
+            developer_id: record.developer_id.to_string(),
+            scopes: record.scopes,
+            token_version,
+        }))
+    }
+
+    async fn batch_create_users(
+        &self,
+        request: Request<user::BatchCreateUsersRequest>,
+    ) -> Result<Response<user::BatchCreateUsersResponse>, Status> {
+        let deadline = Deadline::from_metadata(request.metadata());
+        let mut req = request.into_inner();
+        let all_or_nothing = req.mode() == user::BatchConflictMode::AllOrNothing;
+
+        if let Err(e) = validation::validate_batch_create_users_request(&req) {
+            return Err(validation_failed(e));
+        }
+
+        // Validate and hash everything up front so the transaction only
+        // holds its locks for the inserts.
+        let mut results = Vec::with_capacity(req.users.len());
+        let mut prepared = Vec::new();
+        for (index, user) in req.users.iter_mut().enumerate() {
+            let checked = validation::validate_create_user_request(user, &self.limits)
+                .map_err(|e| (e, "VALIDATION_FAILED"))
+                .and_then(|()| self.resolve_role(user.role).map_err(|e| (e, "UNKNOWN_ENUM_VALUE")));
+            match checked {
+                Ok(role) => {
+                    user.role = role;
+                    let user: &user::CreateUserRequest = user;
+                    // Each hash is deliberately slow; stop once the caller has given up
+                    deadline.check()?;
+                    let password_hash = self.passwords.hash(&user.password)
+                        .map_err(|e| Status::internal(format!("Password hash failed: {}", e)))?;
+                    prepared.push((index, user, password_hash));
+                }
+                Err((e, code)) => results.push(batch_failure(index, e, code)),
+            }
+        }
+
+        // With ALL_OR_NOTHING there is no point inserting once a row has failed
+        let already_failed = all_or_nothing && !results.is_empty();
+        if !already_failed {
+            deadline.check()?;
+            let (indices, rows): (Vec<usize>, Vec<_>) = prepared
+                .into_iter()
+                .map(|(index, user, password_hash)| (index, (user, password_hash)))
+                .unzip();
+            let outcomes = self
+                .repo
+                .insert_users(&rows, all_or_nothing)
+                .await
+                .map_err(user_service_error_to_status)?;
+
+            for (index, outcome) in indices.into_iter().zip(outcomes) {
+                match outcome {
+                    Ok(record) => results.push(user::BatchCreateUserResult {
+                        index: index as u32,
+                        user: Some(db_user_to_proto(record)),
+                        error: None,
+                        error_code: None,
+                    }),
+                    Err(field) => results.push(batch_failure(
+                        index,
+                        format!("User with this {} already exists", field),
+                        conflict_reason(field),
+                    )),
+                }
+            }
+        }
+
+        let failed = results.iter().any(|r| r.error.is_some());
+
+        let mut created_count = 0;
+        for result in results.iter_mut() {
+            if result.user.is_some() {
+                if all_or_nothing && failed {
+                    *result = batch_failure(
+                        result.index as usize,
+                        "Not created because another row in the batch failed".to_string(),
+                        "BATCH_ROLLED_BACK",
+                    );
+                } else {
+                    created_count += 1;
+                }
+            }
+        }
+        results.sort_by_key(|r| r.index);
+
+        Ok(Response::new(user::BatchCreateUsersResponse {
+            results,
+            created_count,
+        }))
+    }
+
+    async fn impersonate(
+        &self,
+        request: Request<user::ImpersonateRequest>,
+    ) -> Result<Response<user::ImpersonateResponse>, Status> {
+        let caller = auth::require_caller(&request)?;
+        let req = request.into_inner();
+        let user_id = parse_id(&req.user_id)?;
+
+        if let Err(e) = validation::validate_impersonate_request(&req) {
+            return Err(validation_failed(e));
+        }
+        if user_id == caller.user_id {
+            return Err(validation_failed("Admins cannot impersonate themselves".to_string()));
+        }
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+        let admin_id = require_admin(repo.as_ref(), &caller, "Only admins can impersonate users").await?;
+
+        let target = repo
+            .get_user_by_id(&user_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
+
+        // An impersonation token carries the target's role, so allowing
+        // admin targets would let one admin act with another's identity.
+        if matches!(target.role, db::DbUserRole::Admin) {
+            return Err(error_status(
+                tonic::Code::PermissionDenied,
+                "Admins cannot be impersonated",
+                "TARGET_IS_ADMIN",
+            ));
+        }
+
+        let expires_at = self.impersonation.expires_at();
+        let recorded = repo
+            .record_impersonation(
+                &admin_id,
+                &user_id,
+                req.reason.trim(),
+                expires_at,
+                self.impersonation.max_per_hour,
+            )
+            .await
+            .map_err(user_service_error_to_status)?;
+        if !recorded {
+            return Err(error_status(
+                tonic::Code::ResourceExhausted,
+                "Too many impersonations in the last hour",
+                "IMPERSONATION_RATE_LIMITED",
+            ));
+        }
+
+        // Carry the target's current version so the gateway accepts the
+        // token until the next revocation.
+        let token_version = repo
+            .get_token_version(&user_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
+
+        let token = self
+            .impersonation
+            .mint(&target, token_version, &admin_id, expires_at)
+            .map_err(|e| Status::internal(format!("Token signing failed: {}", e)))?;
+
+        tracing::info!(target: "audit", %admin_id, %user_id, reason = req.reason.trim(), "impersonation started");
+
+        Ok(Response::new(user::ImpersonateResponse {
+            token,
+            expires_at: Some(datetime_to_timestamp(expires_at)),
+            user: Some(db_user_to_proto(target)),
+        }))
+    }
+
+    async fn revoke_sessions(
+        &self,
+        request: Request<user::RevokeSessionsRequest>,
+    ) -> Result<Response<user::RevokeSessionsResponse>, Status> {
+        let caller = auth::require_caller(&request)?;
+        let user_id = parse_id(&request.into_inner().user_id)?;
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+        let admin_id = require_admin(repo.as_ref(), &caller, "Only admins can revoke sessions").await?;
+
+        let token_version = repo
+            .revoke_sessions(&user_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
+
+        tracing::info!(target: "audit", %admin_id, %user_id, token_version, "sessions revoked");
+
+        Ok(Response::new(user::RevokeSessionsResponse { token_version }))
+    }
+
+    async fn get_token_version(
+        &self,
+        request: Request<user::GetTokenVersionRequest>,
+    ) -> Result<Response<user::GetTokenVersionResponse>, Status> {
+        let user_id = parse_id(&request.into_inner().user_id)?;
+
+        let token_version = self
+            .repo
+            .get_token_version(&user_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
+
+        Ok(Response::new(user::GetTokenVersionResponse { token_version }))
+    }
+
+    async fn set_developer_verified(
+        &self,
+        request: Request<user::SetDeveloperVerifiedRequest>,
+    ) -> Result<Response<user::SetDeveloperVerifiedResponse>, Status> {
+        let caller = auth::require_caller(&request)?;
+        let req = request.into_inner();
+        let user_id = parse_id(&req.user_id)?;
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+        let admin_id = require_admin(repo.as_ref(), &caller, "Only admins can verify developers").await?;
+
+        let target = repo
+            .get_user_by_id(&user_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(user_not_found)?;
+        if !matches!(target.role, db::DbUserRole::Developer) {
+            return Err(error_status(
+                tonic::Code::FailedPrecondition,
+                "Only developers can be verified",
+                "NOT_A_DEVELOPER",
+            ));
+        }
+
+        // The role could have changed since the read above
+        let user_record = repo
+            .set_developer_verified(&user_id, req.verified)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(|| {
+                error_status(tonic::Code::FailedPrecondition, "Only developers can be verified", "NOT_A_DEVELOPER")
+            })?;
+
+        tracing::info!(target: "audit", %admin_id, %user_id, verified = req.verified, "developer verification changed");
+
+        Ok(Response::new(user::SetDeveloperVerifiedResponse {
+            user: Some(db_user_to_proto(user_record)),
+        }))
+    }
+
+    async fn create_developer_profile(
+        &self,
+        request: Request<user::CreateDeveloperProfileRequest>,
+    ) -> Result<Response<user::DeveloperProfileResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = parse_id(&req.user_id)?;
+
+        if let Err(e) = validation::validate_create_developer_profile_request(&req) {
+            return Err(validation_failed(e));
+        }
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+        require_developer(repo.as_ref(), &user_id).await?;
+
+        // None here is either an existing profile or a role change since
+        // the check above
+        let record = repo
+            .create_developer_profile(&user_id, req.studio_name.trim(), &req.bio, req.website.as_deref())
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(|| {
+                error_status(
+                    tonic::Code::AlreadyExists,
+                    "Developer profile already exists",
+                    "PROFILE_EXISTS",
+                )
+            })?;
+
+        Ok(Response::new(user::DeveloperProfileResponse {
+            profile: Some(db_developer_profile_to_proto(record)),
+        }))
+    }
+
+    async fn get_developer_profile(
+        &self,
+        request: Request<user::GetDeveloperProfileRequest>,
+    ) -> Result<Response<user::DeveloperProfileResponse>, Status> {
+        let user_id = parse_id(&request.into_inner().user_id)?;
+
+        let record = self
+            .repo
+            .get_developer_profile(&user_id)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(profile_not_found)?;
+
+        Ok(Response::new(user::DeveloperProfileResponse {
+            profile: Some(db_developer_profile_to_proto(record)),
+        }))
+    }
+
+    async fn update_developer_profile(
+        &self,
+        request: Request<user::UpdateDeveloperProfileRequest>,
+    ) -> Result<Response<user::DeveloperProfileResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = parse_id(&req.user_id)?;
+
+        if let Err(e) = validation::validate_update_developer_profile_request(&req) {
+            return Err(validation_failed(e));
+        }
+
+        let repo = self.repo.clone().scoped().await.map_err(user_service_error_to_status)?;
+        require_developer(repo.as_ref(), &user_id).await?;
+
+        let changes = db::DeveloperProfileChanges {
+            studio_name: req.studio_name.as_deref().map(str::trim),
+            bio: req.bio.as_deref(),
+            website: req.website.as_deref().map(|w| Some(w).filter(|w| !w.is_empty())),
+        };
+        let record = repo
+            .update_developer_profile(&user_id, &changes)
+            .await
+            .map_err(user_service_error_to_status)?
+            .ok_or_else(profile_not_found)?;
+
+        Ok(Response::new(user::DeveloperProfileResponse {
+            profile: Some(db_developer_profile_to_proto(record)),
+        }))
+    }
+
+    async fn authenticate(
+        &self,
+        request: Request<user::AuthenticateRequest>,
+    ) -> Result<Response<user::AuthenticateResponse>, Status> {
+        let req = request.into_inner();
+        let invalid = || error_status(tonic::Code::Unauthenticated, "Invalid email or password", "INVALID_CREDENTIALS");
+
+        let Some((user_record, stored_hash)) = self
+            .repo
+            .get_credentials_by_email(req.email.trim())
+            .await
+            .map_err(user_service_error_to_status)?
+        else {
+            // Hash anyway so an unknown email takes as long as a wrong password
+            let _ = self.passwords.hash(&req.password);
+            return Err(invalid());
+        };
+
+        let verified = self
+            .passwords
+            .verify(&req.password, &stored_hash)
+            .map_err(|e| user_service_error_to_status(e.into()))?;
+        if !verified {
+            return Err(invalid());
+        }
+
+        // The plaintext is only at hand right now, so this is the one chance
+        // to move an old hash onto the current parameters. A failure here
+        // must not fail the login.
+        if self.passwords.rehash_on_login && self.passwords.needs_rehash(&stored_hash) {
+            match self.passwords.hash(&req.password) {
+                Ok(new_hash) => match self.repo.replace_password_hash(&user_record.id, &stored_hash, &new_hash).await {
+                    Ok(true) => tracing::info!(user_id = %user_record.id, "upgraded password hash"),
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!(user_id = %user_record.id, "storing upgraded password hash failed: {}", e),
+                },
+                Err(e) => tracing::warn!(user_id = %user_record.id, "rehashing password failed: {}", e),
+            }
+        }
+
+        Ok(Response::new(user::AuthenticateResponse {
+            user: Some(db_user_to_proto(user_record)),
         }))
     }
 }
 
-pub fn user_service_error_to_status(err: UserServiceError) -> Status {
+fn batch_failure(index: usize, error: String, code: &str) -> user::BatchCreateUserResult {
+    user::BatchCreateUserResult {
+        index: index as u32,
+        user: None,
+        error: Some(error),
+        error_code: Some(code.to_string()),
+    }
+}
+
+fn db_user_to_proto(record: db::DbUser) -> user::UserMessage {
+    user::UserMessage {
+        id: record.id.to_string(),
+        email: record.email,
+        username: record.username,
+        role: db_role_to_proto(record.role),
+        created_at: Some(datetime_to_timestamp(record.created_at)),
+        verified: record.verified,
+    }
+}
+
+fn db_api_key_to_proto(record: db::DbApiKey) -> user::ApiKey {
+    user::ApiKey {
+        id: record.id.to_string(),
+        developer_id: record.developer_id.to_string(),
+        name: record.name,
+        prefix: record.key_prefix,
+        scopes: record.scopes,
+        created_at: Some(datetime_to_timestamp(record.created_at)),
+        revoked: record.revoked,
+    }
+}
+
+fn db_developer_profile_to_proto(record: db::DbDeveloperProfile) -> user::DeveloperProfile {
+    user::DeveloperProfile {
+        user_id: record.user_id.to_string(),
+        studio_name: record.studio_name,
+        bio: record.bio,
+        website: record.website,
+        created_at: Some(datetime_to_timestamp(record.created_at)),
+        updated_at: Some(datetime_to_timestamp(record.updated_at)),
+    }
+}
+
+const ERROR_DOMAIN: &str = "user-service";
+
+fn error_status(code: tonic::Code, message: impl Into<String>, reason: &str) -> Status {
+    status_with_error_info(code, message, ErrorInfo::new(reason, ERROR_DOMAIN))
+}
+
+/// Every RPC parses its ids up front so a malformed one is rejected before
+/// it reaches the database.
+#[allow(clippy::result_large_err)]
+fn parse_id(value: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value)
+        .map_err(|_| error_status(tonic::Code::InvalidArgument, "invalid id", "INVALID_ID"))
+}
+
+fn validation_failed(message: String) -> Status {
+    error_status(tonic::Code::InvalidArgument, message, "VALIDATION_FAILED")
+}
+
+fn unknown_enum_value(message: String) -> Status {
+    error_status(tonic::Code::InvalidArgument, message, "UNKNOWN_ENUM_VALUE")
+}
+
+fn user_not_found() -> Status {
+    error_status(tonic::Code::NotFound, "User not found", "USER_NOT_FOUND")
+}
+
+fn profile_not_found() -> Status {
+    error_status(tonic::Code::NotFound, "Developer profile not found", "PROFILE_NOT_FOUND")
+}
+
+/// The admin behind `caller`, for admin-only RPCs. Impersonation tokens
+/// never qualify, and the role is checked against the stored user rather
+/// than the token, which may predate a demotion or a session revocation.
+async fn require_admin(repo: &dyn UserRepository, caller: &Caller, message: &str) -> Result<Uuid, Status> {
+    let token_version = repo
+        .get_token_version(&caller.user_id)
+        .await
+        .map_err(user_service_error_to_status)?;
+    if token_version.is_some_and(|current| caller.token_version < current) {
+        return Err(Status::unauthenticated("Token has been revoked"));
+    }
+
+    let admin = repo
+        .get_user_by_id(&caller.user_id)
+        .await
+        .map_err(user_service_error_to_status)?;
+    if caller.act_as.is_some() || !admin.is_some_and(|a| matches!(a.role, db::DbUserRole::Admin)) {
+        return Err(error_status(tonic::Code::PermissionDenied, message, "NOT_AN_ADMIN"));
+    }
+    Ok(caller.user_id)
+}
+
+/// Profiles belong to developers only: a missing user is a 404 and any
+/// other role fails the precondition.
+async fn require_developer(repo: &dyn UserRepository, user_id: &Uuid) -> Result<(), Status> {
+    let owner = repo
+        .get_user_by_id(user_id)
+        .await
+        .map_err(user_service_error_to_status)?
+        .ok_or_else(user_not_found)?;
+    if !matches!(owner.role, db::DbUserRole::Developer) {
+        return Err(error_status(
+            tonic::Code::FailedPrecondition,
+            "Only developers can have a developer profile",
+            "NOT_A_DEVELOPER",
+        ));
+    }
+    Ok(())
+}
+
+fn conflict_reason(field: &str) -> &'static str {
+    match field {
+        "email" => "EMAIL_TAKEN",
+        "username" => "USERNAME_TAKEN",
+        _ => "USER_CONFLICT",
+    }
+}
+
+fn user_conflict(field: &str) -> Status {
+    status_with_error_info(
+        tonic::Code::AlreadyExists,
+        format!("User with this {} already exists", field),
+        ErrorInfo::new(conflict_reason(field), ERROR_DOMAIN).with_metadata("field", field),
+    )
+}
+
+/// Maps the default Postgres names of the `UNIQUE` column constraints on
+/// `users` back to the column that caused the violation.
+fn unique_violation_field(err: &sqlx::Error) -> Option<&'static str> {
+    match err.as_database_error()?.constraint()? {
+        "users_email_key" => Some("email"),
+        "users_username_key" => Some("username"),
+        _ => None,
+    }
+}
+
+/// The field a failed insert collided on, whether the repository reported
+/// it directly or Postgres raised the unique violation.
+fn conflict_field(err: &UserServiceError) -> Option<&'static str> {
     match err {
-        UserServiceError::Database(sqlx_err) => match sqlx_err {
-            sqlx::Error::RowNotFound => Status::not_found("User not found"),
-            _ => Status::internal(format!("Database error: {}", sqlx_err)),
-        },
-        UserServiceError::InvalidUuid(_) => Status::invalid_argument("Invalid user ID format"),
-        UserServiceError::PasswordHash(_) => Status::internal("Password processing failed"),
-        UserServiceError::UserNotFound => Status::not_found("User not found"),
-        UserServiceError::ValidationError(msg) => Status::invalid_argument(msg),
+        UserServiceError::Conflict(field) => Some(field),
+        UserServiceError::Database(e) => unique_violation_field(e),
+        _ => None,
     }
 }
 
-pub fn datetime_to_timestamp(datetime: DateTime<Utc>) -> Timestamp {
-    Timestamp {
-        seconds: datetime.timestamp(),
-        nanos: datetime.timestamp_subsec_nanos() as i32,
+/// Postgres `query_canceled`, raised when `statement_timeout` elapses.
+fn is_statement_timeout(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .and_then(|e| e.code())
+        .is_some_and(|code| code == "57014")
+}
+
+pub fn user_service_error_to_status(err: UserServiceError) -> Status {
+    match err {
+        UserServiceError::Database(sqlx_err) => {
+            if is_statement_timeout(&sqlx_err) {
+                return error_status(
+                    tonic::Code::DeadlineExceeded,
+                    "Database query timed out",
+                    "DB_TIMEOUT",
+                );
+            }
+            match unique_violation_field(&sqlx_err) {
+                Some(field) => user_conflict(field),
+                None => Status::internal(format!("Database error: {}", sqlx_err)),
+            }
+        }
+        UserServiceError::InvalidUuid(_) => error_status(
+            tonic::Code::InvalidArgument,
+            "Invalid user ID format",
+            "INVALID_USER_ID",
+        ),
+        UserServiceError::PasswordHash(_) => Status::internal("Password processing failed"),
+        UserServiceError::ApiKeyNotFound => error_status(
+            tonic::Code::NotFound,
+            "API key not found",
+            "API_KEY_NOT_FOUND",
+        ),
+        UserServiceError::ValidationError(msg) => validation_failed(msg),
+        UserServiceError::Conflict(field) => user_conflict(field),
     }
 }
 
@@ -192,27 +994,876 @@ fn db_role_to_proto(role: db::DbUserRole) -> i32 {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
 
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env");
 
+    let statement_timeout_ms: u64 = match env::var("DB_STATEMENT_TIMEOUT_MS") {
+        Ok(value) => value.parse().expect("Invalid DB_STATEMENT_TIMEOUT_MS configuration"),
+        Err(_) => 30_000,
+    };
+
+    let max_connections: u32 = match env::var("DB_MAX_CONNECTIONS") {
+        Ok(value) => value
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .expect("Invalid DB_MAX_CONNECTIONS configuration"),
+        Err(_) => 5,
+    };
+
+    // Bounds every query so a slow or blocked statement can't hold a handler
+    // forever; 0 disables the limit.
     let pool = PgPoolOptions::new()
-        .max_connections(5)
+        .max_connections(max_connections)
+        .after_connect(move |conn, _meta| {
+            Box::pin(async move {
+                sqlx::query(&format!("SET statement_timeout = {}", statement_timeout_ms))
+                    .execute(conn)
+                    .await?;
+                Ok(())
+            })
+        })
         .connect(&database_url)
         .await?;
 
     sqlx::migrate!("./migrations").run(&pool).await?;
 
     let addr = "[::1]:50051".parse()?;
-    let user_service = UserServiceImpl::new(pool);
+    let page_size = PageSizeConfig::from_env().expect("Invalid page size configuration");
+    let impersonation =
+        ImpersonationConfig::from_env().expect("Invalid impersonation configuration");
+    let passwords = PasswordConfig::from_env().expect("Invalid password configuration");
+    let enums = EnumPolicy::from_env().expect("Invalid enum policy configuration");
+    let limits = FieldLimits::from_env().expect("Invalid field length configuration");
+    let callers = CallerVerifier::from_env().expect("Invalid JWT configuration");
+    let user_service = UserServiceImpl::new(
+        Arc::new(PgUserRepository::new(pool)),
+        page_size,
+        impersonation,
+        passwords,
+        enums,
+        limits,
+    );
 
     println!("UserService listening on {}", addr);
 
     Server::builder()
-        .add_service(user::user_service_server::UserServiceServer::new(
+        .layer(RpcLogLayer)
+        .layer(DeadlineLayer)
+        .add_service(user::user_service_server::UserServiceServer::with_interceptor(
             user_service,
+            callers,
         ))
         .serve(addr)
         .await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use memory_repository::InMemoryUserRepository;
+    use user::user_service_server::UserService;
+
+    const SECRET: &str = "test-secret";
+
+    fn service(repo: Arc<InMemoryUserRepository>) -> UserServiceImpl {
+        service_with_passwords(repo, PasswordConfig::from_env().unwrap())
+    }
+
+    fn service_with_passwords(repo: Arc<InMemoryUserRepository>, passwords: PasswordConfig) -> UserServiceImpl {
+        UserServiceImpl::new(
+            repo,
+            PageSizeConfig::default(),
+            ImpersonationConfig::new(SECRET, Duration::minutes(15), 10),
+            passwords,
+            EnumPolicy::default(),
+            FieldLimits::default(),
+        )
+    }
+
+    /// `message` as the interceptor hands it over for a token with these
+    /// claims.
+    fn request_as<T>(message: T, claims: serde_json::Value) -> Request<T> {
+        let token = jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap();
+        let mut request = Request::new(message);
+        request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        CallerVerifier::new(SECRET, 0).authenticate(&mut request).unwrap();
+        request
+    }
+
+    fn claims(user: &user::UserMessage, role: &str) -> serde_json::Value {
+        serde_json::json!({
+            "sub": user.id,
+            "role": role,
+            "exp": jsonwebtoken::get_current_timestamp() + 300,
+        })
+    }
+
+    fn new_user(name: &str, role: user::UserRole) -> user::CreateUserRequest {
+        user::CreateUserRequest {
+            email: format!("{}@example.com", name),
+            username: name.to_string(),
+            password: "correct horse battery".to_string(),
+            role: role as i32,
+            dry_run: false,
+        }
+    }
+
+    async fn create(service: &UserServiceImpl, name: &str, role: user::UserRole) -> user::UserMessage {
+        service.create_user(Request::new(new_user(name, role))).await.unwrap().into_inner()
+    }
+
+    #[tokio::test]
+    async fn unknown_roles_become_players_unless_enums_are_strict() {
+        let unknown_role = || user::CreateUserRequest { role: 99, ..new_user("alice", user::UserRole::Player) };
+
+        let lenient = service(Arc::new(InMemoryUserRepository::new()));
+        let created = lenient.create_user(Request::new(unknown_role())).await.unwrap().into_inner();
+        assert_eq!(created.role, user::UserRole::Player as i32);
+
+        let mut strict = service(Arc::new(InMemoryUserRepository::new()));
+        strict.enums = EnumPolicy { strict: true };
+        let err = strict.create_user(Request::new(unknown_role())).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert_eq!(err.message(), "Unknown role value: 99");
+        assert_eq!(common::error_details::error_info(&err).unwrap().reason, "UNKNOWN_ENUM_VALUE");
+    }
+
+    #[tokio::test]
+    async fn created_user_can_be_fetched() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let created = create(&service, "alice", user::UserRole::Player).await;
+
+        let fetched = service
+            .get_user(Request::new(user::GetUserRequest { id: created.id.clone() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .user
+            .unwrap();
+
+        assert_eq!(fetched.email, "alice@example.com");
+        assert_eq!(fetched.role, user::UserRole::Player as i32);
+    }
+
+    async fn stored_hash(repo: &InMemoryUserRepository, email: &str) -> String {
+        repo.get_credentials_by_email(email).await.unwrap().unwrap().1
+    }
+
+    async fn log_in(service: &UserServiceImpl, password: &str) -> Result<user::AuthenticateResponse, Status> {
+        let request = user::AuthenticateRequest {
+            email: "alice@example.com".to_string(),
+            password: password.to_string(),
+        };
+        service.authenticate(Request::new(request)).await.map(Response::into_inner)
+    }
+
+    #[tokio::test]
+    async fn logins_upgrade_hashes_made_with_weaker_parameters() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let weak = service_with_passwords(repo.clone(), password::tests::config(&[], password::tests::params(8, 1)));
+        create(&weak, "alice", user::UserRole::Player).await;
+        let old_hash = stored_hash(&repo, "alice@example.com").await;
+
+        let strong = service_with_passwords(repo.clone(), password::tests::config(&[], password::tests::params(16, 2)));
+        assert!(strong.passwords.needs_rehash(&old_hash));
+
+        log_in(&strong, "wrong password").await.unwrap_err();
+        assert_eq!(stored_hash(&repo, "alice@example.com").await, old_hash);
+
+        log_in(&strong, "correct horse battery").await.unwrap();
+        let new_hash = stored_hash(&repo, "alice@example.com").await;
+        assert_ne!(new_hash, old_hash);
+        assert!(!strong.passwords.needs_rehash(&new_hash));
+
+        log_in(&strong, "correct horse battery").await.unwrap();
+        assert_eq!(stored_hash(&repo, "alice@example.com").await, new_hash);
+    }
+
+    #[tokio::test]
+    async fn logins_leave_weaker_hashes_alone_when_rehashing_is_off() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let weak = service_with_passwords(repo.clone(), password::tests::config(&[], password::tests::params(8, 1)));
+        create(&weak, "alice", user::UserRole::Player).await;
+        let old_hash = stored_hash(&repo, "alice@example.com").await;
+
+        let mut passwords = password::tests::config(&[], password::tests::params(16, 2));
+        passwords.rehash_on_login = false;
+        let strong = service_with_passwords(repo.clone(), passwords);
+
+        log_in(&strong, "correct horse battery").await.unwrap();
+        assert_eq!(stored_hash(&repo, "alice@example.com").await, old_hash);
+    }
+
+    #[tokio::test]
+    async fn deleting_twice_reports_the_second_delete_as_already_absent() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let created = create(&service, "alice", user::UserRole::Player).await;
+        let delete = || user::DeleteUserRequest { id: created.id.clone() };
+
+        let first = service.delete_user(Request::new(delete())).await.unwrap().into_inner();
+        assert!(first.success);
+        assert!(!first.already_absent);
+
+        let second = service.delete_user(Request::new(delete())).await.unwrap().into_inner();
+        assert!(second.success);
+        assert!(second.already_absent);
+
+        let err = service
+            .get_user(Request::new(user::GetUserRequest { id: created.id.clone() }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn deleting_an_unknown_user_is_already_absent() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+
+        let request = user::DeleteUserRequest { id: uuid::Uuid::new_v4().to_string() };
+        let response = service.delete_user(Request::new(request)).await.unwrap().into_inner();
+
+        assert!(response.success);
+        assert!(response.already_absent);
+    }
+
+    #[tokio::test]
+    async fn missing_user_is_not_found() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+
+        let err = service
+            .get_user(Request::new(user::GetUserRequest { id: Uuid::new_v4().to_string() }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn duplicate_email_is_a_conflict() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        create(&service, "alice", user::UserRole::Player).await;
+
+        let mut again = new_user("alice2", user::UserRole::Player);
+        again.email = "alice@example.com".to_string();
+        let err = service.create_user(Request::new(again)).await.unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::AlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn ensure_user_creates_a_missing_user() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let service = service(repo.clone());
+
+        let response = service
+            .ensure_user(Request::new(new_user("alice", user::UserRole::Developer)))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(response.created);
+        let user = response.user.unwrap();
+        assert_eq!(user.username, "alice");
+        assert_eq!(user.role, user::UserRole::Developer as i32);
+        assert_eq!(repo.count_users().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn ensure_user_returns_the_existing_user_on_a_conflict() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let service = service(repo.clone());
+        let existing = create(&service, "alice", user::UserRole::Player).await;
+
+        let same_email = user::CreateUserRequest { username: "alice2".to_string(), ..new_user("alice", user::UserRole::Player) };
+        let same_username = user::CreateUserRequest { email: "other@example.com".to_string(), ..new_user("alice", user::UserRole::Player) };
+        for req in [new_user("alice", user::UserRole::Player), same_email, same_username] {
+            let response = service.ensure_user(Request::new(req)).await.unwrap().into_inner();
+            assert!(!response.created);
+            let user = response.user.unwrap();
+            assert_eq!(user.id, existing.id);
+            assert_eq!(user.email, "alice@example.com");
+        }
+        assert_eq!(repo.count_users().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn ensure_user_validates_like_create_user() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let service = service(repo.clone());
+
+        let dry_run = user::CreateUserRequest { dry_run: true, ..new_user("alice", user::UserRole::Player) };
+        let err = service.ensure_user(Request::new(dry_run)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        let invalid = user::CreateUserRequest { email: "not-an-email".to_string(), ..new_user("alice", user::UserRole::Player) };
+        let err = service.ensure_user(Request::new(invalid)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        assert_eq!(repo.count_users().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn deleted_users_free_their_email_and_username() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let first = create(&service, "alice", user::UserRole::Player).await;
+        service
+            .delete_user(Request::new(user::DeleteUserRequest { id: first.id.clone() }))
+            .await
+            .unwrap();
+
+        let second = create(&service, "alice", user::UserRole::Player).await;
+        assert_ne!(second.id, first.id);
+        assert_eq!(second.email, "alice@example.com");
+
+        let fetched = service
+            .get_user(Request::new(user::GetUserRequest { id: second.id.clone() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .user
+            .unwrap();
+        assert_eq!(fetched.id, second.id);
+        let err = service
+            .get_user(Request::new(user::GetUserRequest { id: first.id }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        // Only the deleted row steps aside; the new live one still counts.
+        let err = service.create_user(Request::new(new_user("alice", user::UserRole::Player))).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::AlreadyExists);
+    }
+
+    fn batch(users: Vec<user::CreateUserRequest>, mode: user::BatchConflictMode) -> Request<user::BatchCreateUsersRequest> {
+        Request::new(user::BatchCreateUsersRequest { users, mode: mode as i32 })
+    }
+
+    /// `(index, error_code)` of every row, `None` for created ones.
+    fn outcomes(response: &user::BatchCreateUsersResponse) -> Vec<(u32, Option<&str>)> {
+        response.results.iter().map(|r| (r.index, r.error_code.as_deref())).collect()
+    }
+
+    /// A batch with a valid row, one taken by `alice`, an invalid one and
+    /// another valid one.
+    fn mixed_batch() -> Vec<user::CreateUserRequest> {
+        let mut invalid = new_user("carol", user::UserRole::Player);
+        invalid.email = "not-an-email".to_string();
+        vec![
+            new_user("bob", user::UserRole::Player),
+            new_user("alice", user::UserRole::Player),
+            invalid,
+            new_user("dave", user::UserRole::Developer),
+        ]
+    }
+
+    #[tokio::test]
+    async fn batch_of_valid_users_creates_them_all() {
+        let users = vec![
+            new_user("alice", user::UserRole::Player),
+            new_user("bob", user::UserRole::Developer),
+            new_user("carol", user::UserRole::Player),
+        ];
+
+        for mode in [user::BatchConflictMode::AllOrNothing, user::BatchConflictMode::SkipFailed] {
+            let repo = Arc::new(InMemoryUserRepository::new());
+            let service = service(repo.clone());
+            let response = service.batch_create_users(batch(users.clone(), mode)).await.unwrap().into_inner();
+
+            assert_eq!(response.created_count, 3);
+            assert_eq!(outcomes(&response), vec![(0, None), (1, None), (2, None)]);
+            let names: Vec<_> = response.results.iter().map(|r| r.user.as_ref().unwrap().username.as_str()).collect();
+            assert_eq!(names, ["alice", "bob", "carol"]);
+            assert_eq!(repo.count_users().await.unwrap(), 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn skip_failed_batches_create_the_rows_that_can_be_created() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let service = service(repo.clone());
+        create(&service, "alice", user::UserRole::Player).await;
+
+        let response = service
+            .batch_create_users(batch(mixed_batch(), user::BatchConflictMode::SkipFailed))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.created_count, 2);
+        assert_eq!(
+            outcomes(&response),
+            vec![(0, None), (1, Some("EMAIL_TAKEN")), (2, Some("VALIDATION_FAILED")), (3, None)]
+        );
+        assert_eq!(repo.count_users().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn all_or_nothing_batches_create_nothing_when_a_row_fails() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let service = service(repo.clone());
+        create(&service, "alice", user::UserRole::Player).await;
+
+        // A conflict found while inserting rolls back the rows before it.
+        let mut conflicting = mixed_batch();
+        conflicting.remove(2);
+        let response = service
+            .batch_create_users(batch(conflicting, user::BatchConflictMode::AllOrNothing))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.created_count, 0);
+        assert_eq!(
+            outcomes(&response),
+            vec![(0, Some("BATCH_ROLLED_BACK")), (1, Some("EMAIL_TAKEN")), (2, Some("BATCH_ROLLED_BACK"))]
+        );
+        assert!(response.results.iter().all(|r| r.user.is_none()));
+        assert_eq!(repo.count_users().await.unwrap(), 1);
+
+        // An invalid row stops the batch before anything is inserted.
+        let mut invalid = mixed_batch();
+        invalid.remove(1);
+        let response = service
+            .batch_create_users(batch(invalid, user::BatchConflictMode::AllOrNothing))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.created_count, 0);
+        assert!(response.results.iter().all(|r| r.user.is_none()));
+        assert_eq!(repo.count_users().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn duplicates_within_one_batch_conflict_with_each_other() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let service = service(repo.clone());
+        let mut twin = new_user("alice2", user::UserRole::Player);
+        twin.email = "alice@example.com".to_string();
+
+        let response = service
+            .batch_create_users(batch(
+                vec![new_user("alice", user::UserRole::Player), twin],
+                user::BatchConflictMode::SkipFailed,
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.created_count, 1);
+        assert_eq!(outcomes(&response), vec![(0, None), (1, Some("EMAIL_TAKEN"))]);
+    }
+
+    #[tokio::test]
+    async fn malformed_ids_are_invalid_arguments_in_every_rpc() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let admin = create(&service, "root", user::UserRole::Admin).await;
+        let bad = || "not-a-uuid".to_string();
+
+        // Calls `rpc` as an admin, so only the id can be what's wrong.
+        macro_rules! call {
+            ($rpc:ident, $message:expr) => {
+                service.$rpc(request_as($message, claims(&admin, "admin"))).await.map(drop)
+            };
+        }
+
+        let outcomes: Vec<(&str, Result<(), Status>)> = vec![
+            ("GetUser", call!(get_user, user::GetUserRequest { id: bad() })),
+            ("BatchGetUsers", call!(batch_get_users, user::BatchGetUsersRequest { ids: vec![admin.id.clone(), bad()] })),
+            ("UpdateUser", call!(update_user, user::UpdateUserRequest { id: bad(), ..Default::default() })),
+            ("DeleteUser", call!(delete_user, user::DeleteUserRequest { id: bad() })),
+            ("CreateApiKey", call!(create_api_key, user::CreateApiKeyRequest { developer_id: bad(), ..Default::default() })),
+            ("ListApiKeys", call!(list_api_keys, user::ListApiKeysRequest { developer_id: bad() })),
+            ("RevokeApiKey.id", call!(revoke_api_key, user::RevokeApiKeyRequest { id: bad(), developer_id: admin.id.clone() })),
+            ("RevokeApiKey.developer_id", call!(revoke_api_key, user::RevokeApiKeyRequest { id: admin.id.clone(), developer_id: bad() })),
+            ("Impersonate", call!(impersonate, user::ImpersonateRequest { user_id: bad(), reason: "support ticket".to_string() })),
+            ("RevokeSessions", call!(revoke_sessions, user::RevokeSessionsRequest { user_id: bad() })),
+            ("GetTokenVersion", call!(get_token_version, user::GetTokenVersionRequest { user_id: bad() })),
+            ("SetDeveloperVerified", call!(set_developer_verified, user::SetDeveloperVerifiedRequest { user_id: bad(), verified: true })),
+            ("CreateDeveloperProfile", call!(create_developer_profile, user::CreateDeveloperProfileRequest { user_id: bad(), ..Default::default() })),
+            ("GetDeveloperProfile", call!(get_developer_profile, user::GetDeveloperProfileRequest { user_id: bad() })),
+            ("UpdateDeveloperProfile", call!(update_developer_profile, user::UpdateDeveloperProfileRequest { user_id: bad(), ..Default::default() })),
+        ];
+
+        for (rpc, outcome) in outcomes {
+            let err = outcome.expect_err(rpc);
+            assert_eq!(err.code(), tonic::Code::InvalidArgument, "{rpc}: {}", err.message());
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_run_stores_nothing() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+
+        let mut request = new_user("alice", user::UserRole::Player);
+        request.dry_run = true;
+        let checked = service.create_user(Request::new(request)).await.unwrap().into_inner();
+        assert!(checked.id.is_empty());
+
+        create(&service, "alice", user::UserRole::Player).await;
+    }
+
+    #[tokio::test]
+    async fn impersonation_is_admin_only_and_audited() {
+        let repo = Arc::new(InMemoryUserRepository::new());
+        let service = service(repo.clone());
+        let admin = create(&service, "root", user::UserRole::Admin).await;
+        let developer = create(&service, "dev", user::UserRole::Developer).await;
+        let player = create(&service, "alice", user::UserRole::Player).await;
+        let impersonate = || user::ImpersonateRequest {
+            user_id: player.id.clone(),
+            reason: "Support ticket 42".to_string(),
+        };
+
+        let err = service.impersonate(Request::new(impersonate())).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+
+        // The token's role alone doesn't make an admin
+        let request = request_as(impersonate(), claims(&developer, "admin"));
+        let err = service.impersonate(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(repo.impersonations().is_empty());
+
+        let request = request_as(impersonate(), claims(&admin, "admin"));
+        let response = service.impersonate(request).await.unwrap().into_inner();
+        assert_eq!(response.user.unwrap().id, player.id);
+        let expires_at = response.expires_at.unwrap();
+
+        let audit = repo.impersonations();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].admin_id.to_string(), admin.id);
+        assert_eq!(audit[0].target_user_id.to_string(), player.id);
+        assert_eq!(audit[0].reason, "Support ticket 42");
+        assert_eq!(audit[0].expires_at.timestamp(), expires_at.seconds);
+    }
+
+    #[tokio::test]
+    async fn impersonation_tokens_act_as_the_target_but_are_flagged() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let admin = create(&service, "root", user::UserRole::Admin).await;
+        let player = create(&service, "alice", user::UserRole::Player).await;
+        let request = request_as(
+            user::ImpersonateRequest {
+                user_id: player.id.clone(),
+                reason: "Support ticket 42".to_string(),
+            },
+            claims(&admin, "admin"),
+        );
+
+        let token = service.impersonate(request).await.unwrap().into_inner().token;
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        CallerVerifier::new(SECRET, 0).authenticate(&mut request).unwrap();
+        let caller = auth::require_caller(&request).unwrap();
+        assert_eq!(caller.user_id.to_string(), player.id);
+        assert_eq!(caller.role, "player");
+        assert_eq!(caller.act_as.map(|id| id.to_string()), Some(admin.id));
+    }
+
+    #[tokio::test]
+    async fn impersonating_admins_cannot_use_admin_rpcs() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let admin = create(&service, "root", user::UserRole::Admin).await;
+        let player = create(&service, "alice", user::UserRole::Player).await;
+        let mut claims = claims(&admin, "admin");
+        claims["act_as"] = serde_json::json!(Uuid::new_v4().to_string());
+
+        let request = request_as(user::RevokeSessionsRequest { user_id: player.id.clone() }, claims);
+        let err = service.revoke_sessions(request).await.unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn revoked_admin_tokens_lose_admin_rpcs() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let root = create(&service, "root", user::UserRole::Admin).await;
+        let admin = create(&service, "mallory", user::UserRole::Admin).await;
+        let player = create(&service, "alice", user::UserRole::Player).await;
+        let before = request_as(user::RevokeSessionsRequest { user_id: player.id.clone() }, claims(&admin, "admin"));
+
+        let request = request_as(user::RevokeSessionsRequest { user_id: admin.id.clone() }, claims(&root, "admin"));
+        assert_eq!(service.revoke_sessions(request).await.unwrap().into_inner().token_version, 1);
+
+        let err = service.revoke_sessions(before).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+        let mut reissued = claims(&admin, "admin");
+        reissued["token_version"] = serde_json::json!(1);
+        let request = request_as(user::RevokeSessionsRequest { user_id: player.id.clone() }, reissued);
+        assert!(service.revoke_sessions(request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn admins_cannot_impersonate_themselves() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let admin = create(&service, "root", user::UserRole::Admin).await;
+        let request = request_as(
+            user::ImpersonateRequest {
+                user_id: admin.id.clone(),
+                reason: "Testing".to_string(),
+            },
+            claims(&admin, "admin"),
+        );
+
+        let err = service.impersonate(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn admins_verify_and_unverify_developers_only() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let admin = create(&service, "root", user::UserRole::Admin).await;
+        let developer = create(&service, "dev", user::UserRole::Developer).await;
+        let player = create(&service, "alice", user::UserRole::Player).await;
+        assert!(!developer.verified);
+
+        let set = |user: &user::UserMessage, verified: bool, caller: serde_json::Value| {
+            let message = user::SetDeveloperVerifiedRequest { user_id: user.id.clone(), verified };
+            service.set_developer_verified(request_as(message, caller))
+        };
+        let fetch = |id: &str| service.get_user(Request::new(user::GetUserRequest { id: id.to_string() }));
+
+        for verified in [true, false] {
+            let response = set(&developer, verified, claims(&admin, "admin")).await.unwrap().into_inner();
+            assert_eq!(response.user.unwrap().verified, verified);
+            let fetched = fetch(&developer.id).await.unwrap().into_inner().user.unwrap();
+            assert_eq!(fetched.verified, verified);
+        }
+
+        let err = set(&player, true, claims(&admin, "admin")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert!(!fetch(&player.id).await.unwrap().into_inner().user.unwrap().verified);
+
+        let err = set(&developer, true, claims(&developer, "developer")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(!fetch(&developer.id).await.unwrap().into_inner().user.unwrap().verified);
+    }
+
+    #[tokio::test]
+    async fn api_keys_verify_until_revoked() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let developer = create(&service, "dev", user::UserRole::Developer).await;
+
+        let created = service
+            .create_api_key(Request::new(user::CreateApiKeyRequest {
+                developer_id: developer.id.clone(),
+                name: "CI".to_string(),
+                scopes: vec!["games:read".to_string()],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let verify = || Request::new(user::VerifyApiKeyRequest { key: created.key.clone() });
+
+        let verified = service.verify_api_key(verify()).await.unwrap().into_inner();
+        assert_eq!(verified.developer_id, developer.id);
+        assert_eq!(verified.scopes, ["games:read"]);
+        assert_eq!(verified.token_version, 0);
+
+        service
+            .revoke_api_key(Request::new(user::RevokeApiKeyRequest {
+                id: created.api_key.unwrap().id,
+                developer_id: developer.id.clone(),
+            }))
+            .await
+            .unwrap();
+
+        let err = service.verify_api_key(verify()).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn api_keys_need_a_developer_and_known_scopes() {
+        let service = service(Arc::new(InMemoryUserRepository::new()));
+        let player = create(&service, "alice", user::UserRole::Player).await;
+        let developer = create(&service, "dev", user::UserRole::Developer).await;
+        let request = |developer_id: &str, scope: &str| {
+            Request::new(user::CreateApiKeyRequest {
+                developer_id: developer_id.to_string(),
+                name: "CI".to_string(),
+                scopes: vec![scope.to_string()],
+            })
+        };
+
+        let err = service.create_api_key(request(&player.id, "games:read")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let err = service.create_api_key(request(&developer.id, "users:write")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    fn pg_service(pool: sqlx::PgPool) -> UserServiceImpl {
+        UserServiceImpl::new(
+            Arc::new(PgUserRepository::new(pool)),
+            PageSizeConfig::default(),
+            ImpersonationConfig::new(SECRET, Duration::minutes(15), 10),
+            PasswordConfig::from_env().unwrap(),
+            EnumPolicy::default(),
+            FieldLimits::default(),
+        )
+    }
+
+    /// A pool of one connection: a handler that went back to the pool while
+    /// holding a connection would wait out the acquire timeout and fail.
+    #[sqlx::test(migrations = "./migrations")]
+    async fn handlers_make_all_their_queries_on_one_connection(
+        pool_options: PgPoolOptions,
+        connect_options: sqlx::postgres::PgConnectOptions,
+    ) {
+        let pool = pool_options
+            .max_connections(1)
+            .acquire_timeout(std::time::Duration::from_secs(2))
+            .connect_with(connect_options)
+            .await
+            .unwrap();
+        let service = pg_service(pool);
+        let admin = create(&service, "root", user::UserRole::Admin).await;
+        let player = create(&service, "alice", user::UserRole::Player).await;
+
+        // Failed insert, then the lookup of the existing row
+        let ensured = service
+            .ensure_user(Request::new(new_user("alice", user::UserRole::Player)))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(!ensured.created);
+        assert_eq!(ensured.user.unwrap().id, player.id);
+
+        let listed = service
+            .list_users(Request::new(user::ListUsersRequest { limit: 10, offset: 0, ..Default::default() }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(listed.total, 2);
+
+        let request = request_as(
+            user::ImpersonateRequest { user_id: player.id.clone(), reason: "Support ticket 42".to_string() },
+            claims(&admin, "admin"),
+        );
+        let impersonated = service.impersonate(request).await.unwrap().into_inner();
+        assert_eq!(impersonated.user.unwrap().id, player.id);
+    }
+
+    /// Postgres reports the collision through the unique constraint's name;
+    /// the field must survive the trip to the status either way.
+    #[sqlx::test(migrations = "./migrations")]
+    async fn email_and_username_conflicts_name_their_field(pool: sqlx::PgPool) {
+        let service = pg_service(pool);
+        create(&service, "alice", user::UserRole::Player).await;
+        let bob = create(&service, "bob", user::UserRole::Player).await;
+
+        let conflict = |err: Status| {
+            assert_eq!(err.code(), tonic::Code::AlreadyExists);
+            let info = common::error_details::error_info(&err).unwrap();
+            (err.message().to_string(), info.reason, info.metadata["field"].clone())
+        };
+        let email_taken = (
+            "User with this email already exists".to_string(),
+            "EMAIL_TAKEN".to_string(),
+            "email".to_string(),
+        );
+        let username_taken = (
+            "User with this username already exists".to_string(),
+            "USERNAME_TAKEN".to_string(),
+            "username".to_string(),
+        );
+
+        let same_email = user::CreateUserRequest { username: "alice2".to_string(), ..new_user("alice", user::UserRole::Player) };
+        let err = service.create_user(Request::new(same_email)).await.unwrap_err();
+        assert_eq!(conflict(err), email_taken);
+
+        let same_username = user::CreateUserRequest { email: "other@example.com".to_string(), ..new_user("alice", user::UserRole::Player) };
+        let err = service.create_user(Request::new(same_username)).await.unwrap_err();
+        assert_eq!(conflict(err), username_taken);
+
+        let update = |email: Option<&str>, username: Option<&str>| user::UpdateUserRequest {
+            id: bob.id.clone(),
+            email: email.map(str::to_string),
+            username: username.map(str::to_string),
+            ..Default::default()
+        };
+        let err = service.update_user(Request::new(update(Some("alice@example.com"), None))).await.unwrap_err();
+        assert_eq!(conflict(err), email_taken);
+        let err = service.update_user(Request::new(update(None, Some("alice")))).await.unwrap_err();
+        assert_eq!(conflict(err), username_taken);
+    }
+
+    /// Onboards a developer and edits their profile; shared by the
+    /// in-memory and Postgres tests below.
+    async fn developer_profiles_are_created_read_and_updated(service: &UserServiceImpl) {
+        let developer = create(service, "studio", user::UserRole::Developer).await;
+        let player = create(service, "alice", user::UserRole::Player).await;
+        let profile_of = |user: &user::UserMessage| user::CreateDeveloperProfileRequest {
+            user_id: user.id.clone(),
+            studio_name: "  Stellar Games ".to_string(),
+            bio: "Space sims".to_string(),
+            website: Some("https://stellar.example.com".to_string()),
+        };
+        let get = |user_id: &str| {
+            service.get_developer_profile(Request::new(user::GetDeveloperProfileRequest { user_id: user_id.to_string() }))
+        };
+
+        let err = get(&developer.id).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        let created = service
+            .create_developer_profile(Request::new(profile_of(&developer)))
+            .await
+            .unwrap()
+            .into_inner()
+            .profile
+            .unwrap();
+        assert_eq!(created.studio_name, "Stellar Games");
+        assert_eq!(created.website.as_deref(), Some("https://stellar.example.com"));
+
+        let fetched = get(&developer.id).await.unwrap().into_inner().profile.unwrap();
+        assert_eq!((fetched.studio_name, fetched.bio), (created.studio_name, created.bio));
+
+        let err = service.create_developer_profile(Request::new(profile_of(&developer))).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::AlreadyExists);
+
+        let update = user::UpdateDeveloperProfileRequest {
+            user_id: developer.id.clone(),
+            bio: Some("Space and racing sims".to_string()),
+            website: Some(String::new()),
+            ..Default::default()
+        };
+        let updated = service.update_developer_profile(Request::new(update)).await.unwrap().into_inner().profile.unwrap();
+        assert_eq!(updated.studio_name, "Stellar Games");
+        assert_eq!(updated.bio, "Space and racing sims");
+        assert_eq!(updated.website, None);
+
+        // Only developers can onboard
+        let err = service.create_developer_profile(Request::new(profile_of(&player))).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(err.message(), "Only developers can have a developer profile");
+        let update = user::UpdateDeveloperProfileRequest { user_id: player.id.clone(), bio: Some("Hi".to_string()), ..Default::default() };
+        let err = service.update_developer_profile(Request::new(update)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        let missing = user::CreateDeveloperProfileRequest { user_id: Uuid::new_v4().to_string(), ..profile_of(&developer) };
+        let err = service.create_developer_profile(Request::new(missing)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn developer_profiles_in_memory() {
+        developer_profiles_are_created_read_and_updated(&service(Arc::new(InMemoryUserRepository::new()))).await;
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn developer_profiles_in_postgres(pool: sqlx::PgPool) {
+        developer_profiles_are_created_read_and_updated(&pg_service(pool)).await;
+    }
+}
@@ -0,0 +1,487 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::{DbApiKey, DbDeveloperProfile, DbUser, DbUserRole, DeveloperProfileChanges};
+use crate::error::UserServiceError;
+use crate::repository::UserRepository;
+use crate::user::{CreateUserRequest, UpdateUserRequest};
+
+struct StoredUser {
+    user: DbUser,
+    password_hash: String,
+    token_version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+struct StoredApiKey {
+    key: DbApiKey,
+    key_hash: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Impersonation {
+    pub admin_id: Uuid,
+    pub target_user_id: Uuid,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// `UserRepository` kept in process memory, for running handlers without
+/// Postgres. Uniqueness of email and username among live users is enforced
+/// the same way the partial unique indexes do.
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: Mutex<HashMap<Uuid, StoredUser>>,
+    api_keys: Mutex<Vec<StoredApiKey>>,
+    impersonations: Mutex<Vec<Impersonation>>,
+    developer_profiles: Mutex<HashMap<Uuid, DbDeveloperProfile>>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The audit entries written so far, oldest first.
+    pub fn impersonations(&self) -> Vec<Impersonation> {
+        self.impersonations.lock().unwrap().clone()
+    }
+}
+
+fn is_live_developer(users: &HashMap<Uuid, StoredUser>, id: &Uuid) -> bool {
+    users
+        .get(id)
+        .is_some_and(|s| s.deleted_at.is_none() && matches!(s.user.role, DbUserRole::Developer))
+}
+
+fn role_from_proto(role: i32) -> DbUserRole {
+    DbUserRole::from_proto(role).unwrap_or(DbUserRole::Player)
+}
+
+fn conflict(
+    users: &HashMap<Uuid, StoredUser>,
+    except: Option<Uuid>,
+    email: &str,
+    username: &str,
+) -> Option<&'static str> {
+    let live = users
+        .values()
+        .filter(|s| s.deleted_at.is_none() && Some(s.user.id) != except);
+
+    for stored in live {
+        if stored.user.email == email {
+            return Some("email");
+        }
+        if stored.user.username == username {
+            return Some("username");
+        }
+    }
+    None
+}
+
+fn insert(
+    users: &mut HashMap<Uuid, StoredUser>,
+    req: &CreateUserRequest,
+    password_hash: &str,
+) -> Result<DbUser, &'static str> {
+    if let Some(field) = conflict(users, None, &req.email, &req.username) {
+        return Err(field);
+    }
+
+    let user = DbUser {
+        id: Uuid::new_v4(),
+        email: req.email.clone(),
+        username: req.username.clone(),
+        created_at: Utc::now(),
+        role: role_from_proto(req.role),
+        verified: false,
+    };
+    users.insert(
+        user.id,
+        StoredUser {
+            user: user.clone(),
+            password_hash: password_hash.to_string(),
+            token_version: 0,
+            deleted_at: None,
+        },
+    );
+
+    Ok(user)
+}
+
+#[tonic::async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn scoped(self: Arc<Self>) -> Result<Arc<dyn UserRepository>, UserServiceError> {
+        Ok(self)
+    }
+
+    async fn get_user_by_id(&self, id: &Uuid) -> Result<Option<DbUser>, UserServiceError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .get(id)
+            .filter(|s| s.deleted_at.is_none())
+            .map(|s| s.user.clone()))
+    }
+
+    async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<DbUser>, UserServiceError> {
+        let users = self.users.lock().unwrap();
+        Ok(ids
+            .iter()
+            .filter_map(|id| users.get(id))
+            .filter(|s| s.deleted_at.is_none())
+            .map(|s| s.user.clone())
+            .collect())
+    }
+
+    async fn find_user_conflict(
+        &self,
+        email: &str,
+        username: &str,
+    ) -> Result<Option<&'static str>, UserServiceError> {
+        Ok(conflict(&self.users.lock().unwrap(), None, email, username))
+    }
+
+    async fn find_live_user(&self, email: &str, username: &str) -> Result<Option<DbUser>, UserServiceError> {
+        let users = self.users.lock().unwrap();
+        let live = || users.values().filter(|s| s.deleted_at.is_none());
+
+        Ok(live()
+            .find(|s| s.user.email == email)
+            .or_else(|| live().find(|s| s.user.username == username))
+            .map(|s| s.user.clone()))
+    }
+
+    async fn create_user(
+        &self,
+        req: &CreateUserRequest,
+        password_hash: &str,
+    ) -> Result<DbUser, UserServiceError> {
+        insert(&mut self.users.lock().unwrap(), req, password_hash).map_err(UserServiceError::Conflict)
+    }
+
+    async fn insert_users(
+        &self,
+        users: &[(&CreateUserRequest, String)],
+        rollback_on_conflict: bool,
+    ) -> Result<Vec<Result<DbUser, &'static str>>, UserServiceError> {
+        let mut stored = self.users.lock().unwrap();
+
+        let outcomes: Vec<_> = users
+            .iter()
+            .map(|(req, password_hash)| insert(&mut stored, req, password_hash))
+            .collect();
+
+        if rollback_on_conflict && outcomes.iter().any(Result::is_err) {
+            for user in outcomes.iter().flatten() {
+                stored.remove(&user.id);
+            }
+        }
+
+        Ok(outcomes)
+    }
+
+    async fn update_user(
+        &self,
+        req: &UpdateUserRequest,
+        password_hash: Option<&str>,
+    ) -> Result<Option<DbUser>, UserServiceError> {
+        let id = Uuid::parse_str(&req.id)?;
+
+        let mut users = self.users.lock().unwrap();
+        if users.get(&id).is_none_or(|s| s.deleted_at.is_some()) {
+            return Ok(None);
+        }
+
+        let email = req.email.as_deref().unwrap_or_default();
+        let username = req.username.as_deref().unwrap_or_default();
+        if let Some(field) = conflict(&users, Some(id), email, username) {
+            return Err(UserServiceError::Conflict(field));
+        }
+
+        let Some(stored) = users.get_mut(&id) else {
+            return Ok(None);
+        };
+        if let Some(email) = &req.email {
+            stored.user.email = email.clone();
+        }
+        if let Some(username) = &req.username {
+            stored.user.username = username.clone();
+        }
+        if let Some(password_hash) = password_hash {
+            stored.password_hash = password_hash.to_string();
+        }
+        if let Some(role) = req.role {
+            stored.user.role = role_from_proto(role);
+            stored.user.verified &= matches!(stored.user.role, DbUserRole::Developer);
+        }
+
+        Ok(Some(stored.user.clone()))
+    }
+
+    async fn delete_user(&self, id: &Uuid) -> Result<bool, UserServiceError> {
+        match self.users.lock().unwrap().get_mut(id) {
+            Some(stored) if stored.deleted_at.is_none() => {
+                stored.deleted_at = Some(Utc::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn list_users(&self, limit: i32, offset: i32) -> Result<Vec<DbUser>, UserServiceError> {
+        let mut users: Vec<DbUser> = self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|s| s.deleted_at.is_none())
+            .map(|s| s.user.clone())
+            .collect();
+        users.sort_by_key(|u| std::cmp::Reverse(u.created_at));
+
+        Ok(users
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn count_users(&self) -> Result<i64, UserServiceError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|s| s.deleted_at.is_none())
+            .count() as i64)
+    }
+
+    async fn search_users(&self, query: &str, limit: i32) -> Result<Vec<DbUser>, UserServiceError> {
+        let query = query.to_lowercase();
+        let mut users: Vec<DbUser> = self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|s| s.deleted_at.is_none())
+            .filter(|s| {
+                s.user.username.to_lowercase().contains(&query) || s.user.email.to_lowercase().contains(&query)
+            })
+            .map(|s| s.user.clone())
+            .collect();
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        users.truncate(limit.max(0) as usize);
+
+        Ok(users)
+    }
+
+    async fn create_api_key(
+        &self,
+        developer_id: &Uuid,
+        name: &str,
+        scopes: &[String],
+        key_prefix: &str,
+        key_hash: &str,
+    ) -> Result<DbApiKey, UserServiceError> {
+        let key = DbApiKey {
+            id: Uuid::new_v4(),
+            developer_id: *developer_id,
+            name: name.to_string(),
+            key_prefix: key_prefix.to_string(),
+            scopes: scopes.to_vec(),
+            created_at: Utc::now(),
+            revoked: false,
+        };
+        self.api_keys.lock().unwrap().push(StoredApiKey {
+            key: key.clone(),
+            key_hash: key_hash.to_string(),
+        });
+
+        Ok(key)
+    }
+
+    async fn list_api_keys(&self, developer_id: &Uuid) -> Result<Vec<DbApiKey>, UserServiceError> {
+        let mut keys: Vec<DbApiKey> = self
+            .api_keys
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|stored| stored.key.developer_id == *developer_id)
+            .map(|stored| stored.key.clone())
+            .collect();
+        keys.sort_by_key(|k| std::cmp::Reverse(k.created_at));
+
+        Ok(keys)
+    }
+
+    async fn revoke_api_key(&self, id: &Uuid, developer_id: &Uuid) -> Result<(), UserServiceError> {
+        let mut keys = self.api_keys.lock().unwrap();
+        let stored = keys
+            .iter_mut()
+            .find(|stored| stored.key.id == *id && stored.key.developer_id == *developer_id)
+            .ok_or(UserServiceError::ApiKeyNotFound)?;
+        stored.key.revoked = true;
+
+        Ok(())
+    }
+
+    async fn find_active_api_key(&self, key_hash: &str) -> Result<Option<DbApiKey>, UserServiceError> {
+        let users = self.users.lock().unwrap();
+        let owner_is_live = |id: &Uuid| users.get(id).is_some_and(|s| s.deleted_at.is_none());
+
+        Ok(self
+            .api_keys
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|stored| {
+                stored.key_hash == key_hash && !stored.key.revoked && owner_is_live(&stored.key.developer_id)
+            })
+            .map(|stored| stored.key.clone()))
+    }
+
+    async fn record_impersonation(
+        &self,
+        admin_id: &Uuid,
+        target_user_id: &Uuid,
+        reason: &str,
+        expires_at: DateTime<Utc>,
+        max_per_hour: i64,
+    ) -> Result<bool, UserServiceError> {
+        let now = Utc::now();
+        let mut entries = self.impersonations.lock().unwrap();
+
+        let recent = entries
+            .iter()
+            .filter(|e| e.admin_id == *admin_id && now - e.created_at < chrono::Duration::hours(1))
+            .count() as i64;
+        if recent >= max_per_hour {
+            return Ok(false);
+        }
+
+        entries.push(Impersonation {
+            admin_id: *admin_id,
+            target_user_id: *target_user_id,
+            reason: reason.to_string(),
+            created_at: now,
+            expires_at,
+        });
+
+        Ok(true)
+    }
+
+    async fn get_token_version(&self, id: &Uuid) -> Result<Option<i32>, UserServiceError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .get(id)
+            .filter(|s| s.deleted_at.is_none())
+            .map(|s| s.token_version))
+    }
+
+    async fn revoke_sessions(&self, id: &Uuid) -> Result<Option<i32>, UserServiceError> {
+        match self.users.lock().unwrap().get_mut(id) {
+            Some(stored) if stored.deleted_at.is_none() => {
+                stored.token_version += 1;
+                Ok(Some(stored.token_version))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn set_developer_verified(&self, id: &Uuid, verified: bool) -> Result<Option<DbUser>, UserServiceError> {
+        match self.users.lock().unwrap().get_mut(id) {
+            Some(stored) if stored.deleted_at.is_none() && matches!(stored.user.role, DbUserRole::Developer) => {
+                stored.user.verified = verified;
+                Ok(Some(stored.user.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn get_credentials_by_email(&self, email: &str) -> Result<Option<(DbUser, String)>, UserServiceError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .find(|s| s.deleted_at.is_none() && s.user.email == email)
+            .map(|s| (s.user.clone(), s.password_hash.clone())))
+    }
+
+    async fn replace_password_hash(&self, id: &Uuid, old_hash: &str, new_hash: &str) -> Result<bool, UserServiceError> {
+        match self.users.lock().unwrap().get_mut(id) {
+            Some(stored) if stored.deleted_at.is_none() && stored.password_hash == old_hash => {
+                stored.password_hash = new_hash.to_string();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn create_developer_profile(
+        &self,
+        user_id: &Uuid,
+        studio_name: &str,
+        bio: &str,
+        website: Option<&str>,
+    ) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+        if !is_live_developer(&self.users.lock().unwrap(), user_id) {
+            return Ok(None);
+        }
+        let mut profiles = self.developer_profiles.lock().unwrap();
+        if profiles.contains_key(user_id) {
+            return Ok(None);
+        }
+        let now = Utc::now();
+        let profile = DbDeveloperProfile {
+            user_id: *user_id,
+            studio_name: studio_name.to_string(),
+            bio: bio.to_string(),
+            website: website.map(str::to_string),
+            created_at: now,
+            updated_at: now,
+        };
+        profiles.insert(*user_id, profile.clone());
+        Ok(Some(profile))
+    }
+
+    async fn get_developer_profile(&self, user_id: &Uuid) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+        if !is_live_developer(&self.users.lock().unwrap(), user_id) {
+            return Ok(None);
+        }
+        Ok(self.developer_profiles.lock().unwrap().get(user_id).cloned())
+    }
+
+    async fn update_developer_profile(
+        &self,
+        user_id: &Uuid,
+        changes: &DeveloperProfileChanges<'_>,
+    ) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+        if !is_live_developer(&self.users.lock().unwrap(), user_id) {
+            return Ok(None);
+        }
+        let mut profiles = self.developer_profiles.lock().unwrap();
+        let Some(profile) = profiles.get_mut(user_id) else {
+            return Ok(None);
+        };
+        if let Some(studio_name) = changes.studio_name {
+            profile.studio_name = studio_name.to_string();
+        }
+        if let Some(bio) = changes.bio {
+            profile.bio = bio.to_string();
+        }
+        if let Some(website) = changes.website {
+            profile.website = website.map(str::to_string);
+        }
+        profile.updated_at = Utc::now();
+        Ok(Some(profile.clone()))
+    }
+}
@@ -0,0 +1,266 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, SaltString};
+use argon2::{Algorithm, Argon2, Params, PasswordHasher, PasswordVerifier, Version};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Marks a stored hash whose input was HMAC'd with a pepper, followed by the
+/// pepper version and a colon. Unmarked hashes are plain argon2 PHC strings
+/// from before peppering was enabled.
+const PEPPER_MARKER: &str = "pepper-v";
+
+/// How passwords are hashed: argon2id with the configured cost, plus
+/// application-side secrets mixed in first so a copy of the users table
+/// alone is not enough to start guessing.
+///
+/// `PASSWORD_PEPPERS` holds `version:secret` pairs separated by commas. New
+/// hashes always use the highest version; older versions stay listed only
+/// so existing hashes keep verifying until they are replaced. Leaving it
+/// unset disables peppering.
+///
+/// `ARGON2_MEMORY_KIB`, `ARGON2_ITERATIONS` and `ARGON2_PARALLELISM` set the
+/// cost of new hashes (argon2's defaults otherwise). With
+/// `PASSWORD_REHASH_ON_LOGIN` on, the default, a successful login replaces a
+/// hash made with a weaker cost or an older pepper.
+pub struct PasswordConfig {
+    peppers: Vec<(u32, Vec<u8>)>,
+    params: Params,
+    pub rehash_on_login: bool,
+}
+
+impl PasswordConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let raw = std::env::var("PASSWORD_PEPPERS").unwrap_or_default();
+
+        let mut peppers: Vec<(u32, Vec<u8>)> = Vec::new();
+        for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (version, secret) = entry
+                .split_once(':')
+                .ok_or_else(|| "PASSWORD_PEPPERS entries must look like version:secret".to_string())?;
+            let version = version
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or_else(|| format!("PASSWORD_PEPPERS has an invalid version: {}", version))?;
+            if secret.is_empty() {
+                return Err(format!("PASSWORD_PEPPERS version {} has an empty secret", version));
+            }
+            if peppers.iter().any(|(v, _)| *v == version) {
+                return Err(format!("PASSWORD_PEPPERS lists version {} twice", version));
+            }
+            peppers.push((version, secret.as_bytes().to_vec()));
+        }
+        peppers.sort_by_key(|(version, _)| *version);
+
+        let defaults = Params::default();
+        let memory_kib = cost_from_env("ARGON2_MEMORY_KIB", defaults.m_cost())?;
+        let iterations = cost_from_env("ARGON2_ITERATIONS", defaults.t_cost())?;
+        let parallelism = cost_from_env("ARGON2_PARALLELISM", defaults.p_cost())?;
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|e| format!("Invalid argon2 parameters: {}", e))?;
+
+        let rehash_on_login = match std::env::var("PASSWORD_REHASH_ON_LOGIN") {
+            Ok(value) => value
+                .trim()
+                .parse::<bool>()
+                .map_err(|_| "PASSWORD_REHASH_ON_LOGIN must be true or false".to_string())?,
+            Err(_) => true,
+        };
+
+        Ok(Self {
+            peppers,
+            params,
+            rehash_on_login,
+        })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    fn current(&self) -> Option<&(u32, Vec<u8>)> {
+        self.peppers.last()
+    }
+
+    fn secret(&self, version: u32) -> Option<&[u8]> {
+        self.peppers
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(_, secret)| secret.as_slice())
+    }
+
+    pub fn hash(&self, password: &str) -> Result<String, argon2::password_hash::Error> {
+        match self.current() {
+            Some((version, secret)) => {
+                let phc = argon2_hash(&self.argon2(), &peppered(secret, password))?;
+                Ok(format!("{}{}:{}", PEPPER_MARKER, version, phc))
+            }
+            None => argon2_hash(&self.argon2(), password.as_bytes()),
+        }
+    }
+
+    /// Accepts both peppered and legacy hashes. A hash peppered with a
+    /// version that is no longer configured cannot match anything.
+    pub fn verify(&self, password: &str, stored: &str) -> Result<bool, argon2::password_hash::Error> {
+        let Some(rest) = stored.strip_prefix(PEPPER_MARKER) else {
+            return argon2_verify(password.as_bytes(), stored);
+        };
+
+        let (version, phc) = rest
+            .split_once(':')
+            .ok_or(argon2::password_hash::Error::PhcStringField)?;
+        let version = version
+            .parse::<u32>()
+            .map_err(|_| argon2::password_hash::Error::PhcStringField)?;
+
+        match self.secret(version) {
+            Some(secret) => argon2_verify(&peppered(secret, password), phc),
+            None => {
+                tracing::warn!(version, "password hash uses a pepper that is no longer configured");
+                Ok(false)
+            }
+        }
+    }
+
+    /// Whether a hash that just verified falls short of what `hash` would
+    /// produce now: a missing or older pepper, another argon2 variant or
+    /// version, or any cost below the configured one. Hashes made with a
+    /// higher cost are left alone.
+    pub fn needs_rehash(&self, stored: &str) -> bool {
+        let (pepper_version, phc) = match stored.strip_prefix(PEPPER_MARKER) {
+            Some(rest) => match rest.split_once(':') {
+                Some((version, phc)) => (version.parse::<u32>().ok(), phc),
+                None => return true,
+            },
+            None => (None, stored),
+        };
+        if self.current().map(|(version, _)| *version) != pepper_version {
+            return true;
+        }
+
+        let Ok(parsed) = PasswordHash::new(phc) else {
+            return true;
+        };
+        if parsed.algorithm != Algorithm::Argon2id.ident() || parsed.version != Some(Version::V0x13.into()) {
+            return true;
+        }
+        match Params::try_from(&parsed) {
+            Ok(params) => {
+                params.m_cost() < self.params.m_cost()
+                    || params.t_cost() < self.params.t_cost()
+                    || params.p_cost() < self.params.p_cost()
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+fn cost_from_env(name: &str, default: u32) -> Result<u32, String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|v| *v > 0)
+            .ok_or_else(|| format!("{} has an invalid value: {}", name, value)),
+        Err(_) => Ok(default),
+    }
+}
+
+fn peppered(secret: &[u8], password: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(password.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn argon2_hash(argon2: &Argon2, input: &[u8]) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(argon2.hash_password(input, &salt)?.to_string())
+}
+
+fn argon2_verify(input: &[u8], phc: &str) -> Result<bool, argon2::password_hash::Error> {
+    let parsed = PasswordHash::new(phc)?;
+    match Argon2::default().verify_password(input, &parsed) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Cheap argon2 parameters so tests don't spend seconds hashing.
+    pub(crate) fn params(memory_kib: u32, iterations: u32) -> Params {
+        Params::new(memory_kib, iterations, 1, None).unwrap()
+    }
+
+    pub(crate) fn config(peppers: &[(u32, &str)], params: Params) -> PasswordConfig {
+        PasswordConfig {
+            peppers: peppers.iter().map(|(v, s)| (*v, s.as_bytes().to_vec())).collect(),
+            params,
+            rehash_on_login: true,
+        }
+    }
+
+    #[test]
+    fn peppered_hashes_verify_with_the_same_pepper() {
+        let config = config(&[(1, "pepper-a")], params(8, 1));
+
+        let hash = config.hash("hunter2").unwrap();
+
+        assert!(hash.starts_with("pepper-v1:$argon2id$"));
+        assert!(config.verify("hunter2", &hash).unwrap());
+        assert!(!config.verify("hunter3", &hash).unwrap());
+    }
+
+    #[test]
+    fn a_hash_made_with_one_pepper_fails_under_another() {
+        let hash = config(&[(1, "pepper-a")], params(8, 1)).hash("hunter2").unwrap();
+
+        let other = config(&[(1, "pepper-b")], params(8, 1));
+
+        assert!(!other.verify("hunter2", &hash).unwrap());
+    }
+
+    #[test]
+    fn a_hash_whose_pepper_was_dropped_no_longer_verifies() {
+        let hash = config(&[(1, "pepper-a")], params(8, 1)).hash("hunter2").unwrap();
+
+        let rotated = config(&[(2, "pepper-b")], params(8, 1));
+
+        assert!(!rotated.verify("hunter2", &hash).unwrap());
+    }
+
+    #[test]
+    fn hashes_made_without_a_pepper_still_verify() {
+        let legacy = config(&[], params(8, 1)).hash("hunter2").unwrap();
+        assert!(legacy.starts_with("$argon2id$"));
+
+        let peppered = config(&[(1, "pepper-a")], params(8, 1));
+
+        assert!(peppered.verify("hunter2", &legacy).unwrap());
+        assert!(!peppered.verify("hunter3", &legacy).unwrap());
+    }
+
+    #[test]
+    fn older_listed_peppers_keep_verifying_during_rotation() {
+        let old = config(&[(1, "pepper-a")], params(8, 1)).hash("hunter2").unwrap();
+
+        let rotated = config(&[(1, "pepper-a"), (2, "pepper-b")], params(8, 1));
+        let new = rotated.hash("hunter2").unwrap();
+
+        assert!(new.starts_with("pepper-v2:"));
+        assert!(rotated.verify("hunter2", &old).unwrap());
+        assert!(rotated.verify("hunter2", &new).unwrap());
+    }
+
+    #[test]
+    fn malformed_peppered_hashes_are_errors() {
+        let config = config(&[(1, "pepper-a")], params(8, 1));
+
+        assert!(config.verify("hunter2", "pepper-v1").is_err());
+        assert!(config.verify("hunter2", "pepper-vX:$argon2id$").is_err());
+    }
+}
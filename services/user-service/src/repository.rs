@@ -0,0 +1,310 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::{PgConnection, PgPool, Postgres};
+use tokio::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+use common::db_timing::timed;
+
+use crate::db::{self, DbApiKey, DbDeveloperProfile, DbUser, DeveloperProfileChanges};
+use crate::error::UserServiceError;
+use crate::user::{CreateUserRequest, UpdateUserRequest};
+
+/// Storage used by the gRPC handlers. `PgUserRepository` is the real one;
+/// the handler tests run against the in-process one in `memory_repository`.
+#[tonic::async_trait]
+pub trait UserRepository: Send + Sync {
+    /// The repository a handler making several calls should use. With
+    /// Postgres every call on it goes through one pooled connection, held
+    /// until it is dropped, so a request never waits on the pool for a
+    /// second connection while it already holds one.
+    async fn scoped(self: Arc<Self>) -> Result<Arc<dyn UserRepository>, UserServiceError>;
+
+    async fn get_user_by_id(&self, id: &Uuid) -> Result<Option<DbUser>, UserServiceError>;
+
+    /// Live users among `ids`, in no particular order.
+    async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<DbUser>, UserServiceError>;
+
+    async fn find_user_conflict(
+        &self,
+        email: &str,
+        username: &str,
+    ) -> Result<Option<&'static str>, UserServiceError>;
+
+    async fn find_live_user(&self, email: &str, username: &str) -> Result<Option<DbUser>, UserServiceError>;
+
+    async fn create_user(
+        &self,
+        req: &CreateUserRequest,
+        password_hash: &str,
+    ) -> Result<DbUser, UserServiceError>;
+
+    async fn insert_users(
+        &self,
+        users: &[(&CreateUserRequest, String)],
+        rollback_on_conflict: bool,
+    ) -> Result<Vec<Result<DbUser, &'static str>>, UserServiceError>;
+
+    /// `password_hash` is already hashed by the caller, as for `create_user`.
+    async fn update_user(
+        &self,
+        req: &UpdateUserRequest,
+        password_hash: Option<&str>,
+    ) -> Result<Option<DbUser>, UserServiceError>;
+
+    async fn delete_user(&self, id: &Uuid) -> Result<bool, UserServiceError>;
+
+    async fn list_users(&self, limit: i32, offset: i32) -> Result<Vec<DbUser>, UserServiceError>;
+
+    async fn count_users(&self) -> Result<i64, UserServiceError>;
+
+    async fn search_users(&self, query: &str, limit: i32) -> Result<Vec<DbUser>, UserServiceError>;
+
+    async fn create_api_key(
+        &self,
+        developer_id: &Uuid,
+        name: &str,
+        scopes: &[String],
+        key_prefix: &str,
+        key_hash: &str,
+    ) -> Result<DbApiKey, UserServiceError>;
+
+    async fn list_api_keys(&self, developer_id: &Uuid) -> Result<Vec<DbApiKey>, UserServiceError>;
+
+    async fn revoke_api_key(&self, id: &Uuid, developer_id: &Uuid) -> Result<(), UserServiceError>;
+
+    async fn find_active_api_key(&self, key_hash: &str) -> Result<Option<DbApiKey>, UserServiceError>;
+
+    async fn record_impersonation(
+        &self,
+        admin_id: &Uuid,
+        target_user_id: &Uuid,
+        reason: &str,
+        expires_at: DateTime<Utc>,
+        max_per_hour: i64,
+    ) -> Result<bool, UserServiceError>;
+
+    async fn get_token_version(&self, id: &Uuid) -> Result<Option<i32>, UserServiceError>;
+
+    async fn revoke_sessions(&self, id: &Uuid) -> Result<Option<i32>, UserServiceError>;
+
+    async fn set_developer_verified(&self, id: &Uuid, verified: bool) -> Result<Option<DbUser>, UserServiceError>;
+
+    async fn get_credentials_by_email(&self, email: &str) -> Result<Option<(DbUser, String)>, UserServiceError>;
+
+    /// Returns false when the stored hash is no longer `old_hash`.
+    async fn replace_password_hash(&self, id: &Uuid, old_hash: &str, new_hash: &str) -> Result<bool, UserServiceError>;
+
+    /// `None` if the user already has a profile or is not a live developer.
+    async fn create_developer_profile(
+        &self,
+        user_id: &Uuid,
+        studio_name: &str,
+        bio: &str,
+        website: Option<&str>,
+    ) -> Result<Option<DbDeveloperProfile>, UserServiceError>;
+
+    async fn get_developer_profile(&self, user_id: &Uuid) -> Result<Option<DbDeveloperProfile>, UserServiceError>;
+
+    async fn update_developer_profile(
+        &self,
+        user_id: &Uuid,
+        changes: &DeveloperProfileChanges<'_>,
+    ) -> Result<Option<DbDeveloperProfile>, UserServiceError>;
+}
+
+pub struct PgUserRepository {
+    pool: PgPool,
+    /// Set on a scoped repository; every query then runs on this connection.
+    held: Option<Mutex<PoolConnection<Postgres>>>,
+}
+
+impl PgUserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, held: None }
+    }
+
+    async fn conn(&self) -> Result<Conn<'_>, UserServiceError> {
+        match &self.held {
+            Some(held) => Ok(Conn::Held(held.lock().await)),
+            None => Ok(Conn::Pooled(Box::new(self.pool.acquire().await?))),
+        }
+    }
+}
+
+enum Conn<'a> {
+    Pooled(Box<PoolConnection<Postgres>>),
+    Held(MutexGuard<'a, PoolConnection<Postgres>>),
+}
+
+impl Deref for Conn<'_> {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            Conn::Pooled(conn) => conn,
+            Conn::Held(conn) => conn,
+        }
+    }
+}
+
+impl DerefMut for Conn<'_> {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            Conn::Pooled(conn) => conn,
+            Conn::Held(conn) => conn,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl UserRepository for PgUserRepository {
+    async fn scoped(self: Arc<Self>) -> Result<Arc<dyn UserRepository>, UserServiceError> {
+        if self.held.is_some() {
+            return Ok(self);
+        }
+        Ok(Arc::new(PgUserRepository {
+            pool: self.pool.clone(),
+            held: Some(Mutex::new(self.pool.acquire().await?)),
+        }))
+    }
+
+    async fn get_user_by_id(&self, id: &Uuid) -> Result<Option<DbUser>, UserServiceError> {
+        timed("get_user_by_id", db::get_user_by_id(&mut *self.conn().await?, id)).await
+    }
+
+    async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<DbUser>, UserServiceError> {
+        timed("get_users_by_ids", db::get_users_by_ids(&mut *self.conn().await?, ids)).await
+    }
+
+    async fn find_user_conflict(
+        &self,
+        email: &str,
+        username: &str,
+    ) -> Result<Option<&'static str>, UserServiceError> {
+        timed("find_user_conflict", db::find_user_conflict(&mut *self.conn().await?, email, username)).await
+    }
+
+    async fn find_live_user(&self, email: &str, username: &str) -> Result<Option<DbUser>, UserServiceError> {
+        timed("find_live_user", db::find_live_user(&mut *self.conn().await?, email, username)).await
+    }
+
+    async fn create_user(
+        &self,
+        req: &CreateUserRequest,
+        password_hash: &str,
+    ) -> Result<DbUser, UserServiceError> {
+        timed("create_user", db::create_user(&mut *self.conn().await?, req, password_hash)).await
+    }
+
+    async fn insert_users(
+        &self,
+        users: &[(&CreateUserRequest, String)],
+        rollback_on_conflict: bool,
+    ) -> Result<Vec<Result<DbUser, &'static str>>, UserServiceError> {
+        timed("insert_users", db::insert_users(&mut *self.conn().await?, users, rollback_on_conflict)).await
+    }
+
+    async fn update_user(
+        &self,
+        req: &UpdateUserRequest,
+        password_hash: Option<&str>,
+    ) -> Result<Option<DbUser>, UserServiceError> {
+        timed("update_user", db::update_user(&mut *self.conn().await?, req, password_hash)).await
+    }
+
+    async fn delete_user(&self, id: &Uuid) -> Result<bool, UserServiceError> {
+        timed("delete_user", db::delete_user(&mut *self.conn().await?, id)).await
+    }
+
+    async fn list_users(&self, limit: i32, offset: i32) -> Result<Vec<DbUser>, UserServiceError> {
+        timed("list_users", db::list_users(&mut *self.conn().await?, Some(limit), Some(offset))).await
+    }
+
+    async fn count_users(&self) -> Result<i64, UserServiceError> {
+        timed("count_users", db::count_users(&mut *self.conn().await?)).await
+    }
+
+    async fn search_users(&self, query: &str, limit: i32) -> Result<Vec<DbUser>, UserServiceError> {
+        timed("search_users", db::search_users(&mut *self.conn().await?, query, limit)).await
+    }
+
+    async fn create_api_key(
+        &self,
+        developer_id: &Uuid,
+        name: &str,
+        scopes: &[String],
+        key_prefix: &str,
+        key_hash: &str,
+    ) -> Result<DbApiKey, UserServiceError> {
+        timed("create_api_key", db::create_api_key(&mut *self.conn().await?, developer_id, name, scopes, key_prefix, key_hash)).await
+    }
+
+    async fn list_api_keys(&self, developer_id: &Uuid) -> Result<Vec<DbApiKey>, UserServiceError> {
+        timed("list_api_keys", db::list_api_keys(&mut *self.conn().await?, developer_id)).await
+    }
+
+    async fn revoke_api_key(&self, id: &Uuid, developer_id: &Uuid) -> Result<(), UserServiceError> {
+        timed("revoke_api_key", db::revoke_api_key(&mut *self.conn().await?, id, developer_id)).await
+    }
+
+    async fn find_active_api_key(&self, key_hash: &str) -> Result<Option<DbApiKey>, UserServiceError> {
+        timed("find_active_api_key", db::find_active_api_key(&mut *self.conn().await?, key_hash)).await
+    }
+
+    async fn record_impersonation(
+        &self,
+        admin_id: &Uuid,
+        target_user_id: &Uuid,
+        reason: &str,
+        expires_at: DateTime<Utc>,
+        max_per_hour: i64,
+    ) -> Result<bool, UserServiceError> {
+        timed("record_impersonation", db::record_impersonation(&mut *self.conn().await?, admin_id, target_user_id, reason, expires_at, max_per_hour)).await
+    }
+
+    async fn get_token_version(&self, id: &Uuid) -> Result<Option<i32>, UserServiceError> {
+        timed("get_token_version", db::get_token_version(&mut *self.conn().await?, id)).await
+    }
+
+    async fn revoke_sessions(&self, id: &Uuid) -> Result<Option<i32>, UserServiceError> {
+        timed("revoke_sessions", db::revoke_sessions(&mut *self.conn().await?, id)).await
+    }
+
+    async fn set_developer_verified(&self, id: &Uuid, verified: bool) -> Result<Option<DbUser>, UserServiceError> {
+        timed("set_developer_verified", db::set_developer_verified(&mut *self.conn().await?, id, verified)).await
+    }
+
+    async fn create_developer_profile(
+        &self,
+        user_id: &Uuid,
+        studio_name: &str,
+        bio: &str,
+        website: Option<&str>,
+    ) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+        timed("create_developer_profile", db::create_developer_profile(&mut *self.conn().await?, user_id, studio_name, bio, website)).await
+    }
+
+    async fn get_developer_profile(&self, user_id: &Uuid) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+        timed("get_developer_profile", db::get_developer_profile(&mut *self.conn().await?, user_id)).await
+    }
+
+    async fn update_developer_profile(
+        &self,
+        user_id: &Uuid,
+        changes: &DeveloperProfileChanges<'_>,
+    ) -> Result<Option<DbDeveloperProfile>, UserServiceError> {
+        timed("update_developer_profile", db::update_developer_profile(&mut *self.conn().await?, user_id, changes)).await
+    }
+
+    async fn get_credentials_by_email(&self, email: &str) -> Result<Option<(DbUser, String)>, UserServiceError> {
+        timed("get_credentials_by_email", db::get_credentials_by_email(&mut *self.conn().await?, email)).await
+    }
+
+    async fn replace_password_hash(&self, id: &Uuid, old_hash: &str, new_hash: &str) -> Result<bool, UserServiceError> {
+        timed("replace_password_hash", db::replace_password_hash(&mut *self.conn().await?, id, old_hash, new_hash)).await
+    }
+}
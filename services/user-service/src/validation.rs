@@ -1,8 +1,83 @@
+use crate::user::BatchCreateUsersRequest;
+use crate::user::BatchGetUsersRequest;
+use crate::user::CreateApiKeyRequest;
+use crate::user::CreateDeveloperProfileRequest;
 use crate::user::CreateUserRequest;
+use crate::user::ImpersonateRequest;
+use crate::user::UpdateDeveloperProfileRequest;
 use crate::user::UpdateUserRequest;
+use common::config::env_or;
 use regex::Regex;
 
-pub fn validate_email(email: &str) -> Result<(), String> {
+/// `games:read` covers listing and fetching games, `games:write` covers
+/// creating, updating and deleting the key owner's games.
+pub const API_KEY_SCOPES: &[&str] = &["games:read", "games:write"];
+
+pub const MAX_BATCH_CREATE_USERS: usize = 500;
+pub const MAX_BATCH_GET_USERS: usize = 200;
+
+pub const MAX_STUDIO_NAME_LENGTH: usize = 100;
+pub const MAX_BIO_LENGTH: usize = 2000;
+/// Matches `developer_profiles.website`'s VARCHAR(255).
+pub const MAX_WEBSITE_LENGTH: usize = 255;
+
+/// The longest address RFC 5321 allows.
+pub const DEFAULT_MAX_EMAIL_LENGTH: usize = 254;
+pub const MIN_USERNAME_LENGTH: usize = 3;
+pub const DEFAULT_MAX_USERNAME_LENGTH: usize = 30;
+
+/// `users.email` is VARCHAR(255) and `users.username` VARCHAR(100); the
+/// configured limits can't go past them.
+const DB_MAX_EMAIL_LENGTH: usize = 255;
+const DB_MAX_USERNAME_LENGTH: usize = 100;
+
+/// Length limits for user fields, from `MAX_EMAIL_LENGTH` and
+/// `MAX_USERNAME_LENGTH`.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldLimits {
+    pub max_email_length: usize,
+    pub max_username_length: usize,
+}
+
+impl Default for FieldLimits {
+    fn default() -> Self {
+        Self {
+            max_email_length: DEFAULT_MAX_EMAIL_LENGTH,
+            max_username_length: DEFAULT_MAX_USERNAME_LENGTH,
+        }
+    }
+}
+
+impl FieldLimits {
+    pub fn from_env() -> Result<Self, String> {
+        Self::new(
+            env_or("MAX_EMAIL_LENGTH", DEFAULT_MAX_EMAIL_LENGTH)?,
+            env_or("MAX_USERNAME_LENGTH", DEFAULT_MAX_USERNAME_LENGTH)?,
+        )
+    }
+
+    pub fn new(max_email_length: usize, max_username_length: usize) -> Result<Self, String> {
+        if !(1..=DB_MAX_EMAIL_LENGTH).contains(&max_email_length) {
+            return Err(format!("MAX_EMAIL_LENGTH must be between 1 and {}", DB_MAX_EMAIL_LENGTH));
+        }
+        if !(MIN_USERNAME_LENGTH..=DB_MAX_USERNAME_LENGTH).contains(&max_username_length) {
+            return Err(format!(
+                "MAX_USERNAME_LENGTH must be between {} and {}",
+                MIN_USERNAME_LENGTH, DB_MAX_USERNAME_LENGTH
+            ));
+        }
+
+        Ok(Self {
+            max_email_length,
+            max_username_length,
+        })
+    }
+}
+
+pub fn validate_email(email: &str, limits: &FieldLimits) -> Result<(), String> {
+    if email.len() > limits.max_email_length {
+        return Err(format!("Email must be at most {} characters", limits.max_email_length));
+    }
     let email_regex = Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$").unwrap();
     if !email_regex.is_match(email) {
         return Err("Invalid email format".to_string());
@@ -26,9 +101,12 @@ pub fn validate_password(password: &str) -> Result<(), String> {
     Ok(())
 }
 
-pub fn validate_username(username: &str) -> Result<(), String> {
-    if username.len() < 3 || username.len() > 30 {
-        return Err("Username must be between 3 and 30 characters".to_string());
+pub fn validate_username(username: &str, limits: &FieldLimits) -> Result<(), String> {
+    if username.len() < MIN_USERNAME_LENGTH || username.len() > limits.max_username_length {
+        return Err(format!(
+            "Username must be between {} and {} characters",
+            MIN_USERNAME_LENGTH, limits.max_username_length
+        ));
     }
     if !username.chars().all(|c| c.is_alphanumeric() || c == '_') {
         return Err("Username can only contain letters, numbers and underscore".to_string());
@@ -36,17 +114,17 @@ pub fn validate_username(username: &str) -> Result<(), String> {
     Ok(())
 }
 
-pub fn validate_create_user_request(req: &CreateUserRequest) -> Result<(), String> {
-    validate_email(&req.email)?;
+pub fn validate_create_user_request(req: &CreateUserRequest, limits: &FieldLimits) -> Result<(), String> {
+    validate_email(&req.email, limits)?;
     validate_password(&req.password)?;
-    validate_username(&req.username)?;
+    validate_username(&req.username, limits)?;
     Ok(())
 }
 
-pub fn validate_update_user_request(req: &UpdateUserRequest) -> Result<(), String> {
+pub fn validate_update_user_request(req: &UpdateUserRequest, limits: &FieldLimits) -> Result<(), String> {
     if let Some(email) = req.email.as_ref() {
         if !email.is_empty() {
-            validate_email(email)?;
+            validate_email(email, limits)?;
         }
     }
 
@@ -58,17 +136,169 @@ pub fn validate_update_user_request(req: &UpdateUserRequest) -> Result<(), Strin
 
     if let Some(username) = req.username.as_ref() {
         if !username.is_empty() {
-            validate_username(username)?;
+            validate_username(username, limits)?;
         }
     }
 
-    if req.email.as_ref().map_or(true, |s| s.is_empty())
-        && req.password.as_ref().map_or(true, |s| s.is_empty())
-        && req.username.as_ref().map_or(true, |s| s.is_empty())
-        && req.role == Some(0)
+    if req.role.is_some_and(|role| !(0..=2).contains(&role)) {
+        return Err("Invalid role".to_string());
+    }
+
+    if req.email.as_ref().is_none_or(|s| s.is_empty())
+        && req.password.as_ref().is_none_or(|s| s.is_empty())
+        && req.username.as_ref().is_none_or(|s| s.is_empty())
+        && req.role.is_none()
     {
-        return Err("At least one field must be non-empty and role must not be 0".to_string());
+        return Err("At least one field must be non-empty".to_string());
     }
 
     Ok(())
 }
+
+pub fn validate_batch_create_users_request(req: &BatchCreateUsersRequest) -> Result<(), String> {
+    if req.users.is_empty() || req.users.len() > MAX_BATCH_CREATE_USERS {
+        return Err(format!(
+            "A batch must contain between 1 and {} users",
+            MAX_BATCH_CREATE_USERS
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_batch_get_users_request(req: &BatchGetUsersRequest) -> Result<(), String> {
+    if req.ids.len() > MAX_BATCH_GET_USERS {
+        return Err(format!("At most {} users can be fetched at once", MAX_BATCH_GET_USERS));
+    }
+    Ok(())
+}
+
+pub fn validate_create_api_key_request(req: &CreateApiKeyRequest) -> Result<(), String> {
+    if req.name.trim().is_empty() || req.name.len() > 100 {
+        return Err("API key name must be between 1 and 100 characters".to_string());
+    }
+
+    if req.scopes.is_empty() {
+        return Err("At least one scope is required".to_string());
+    }
+
+    if let Some(scope) = req
+        .scopes
+        .iter()
+        .find(|scope| !API_KEY_SCOPES.contains(&scope.as_str()))
+    {
+        return Err(format!(
+            "Unknown scope '{}'. Must be one of: {}",
+            scope,
+            API_KEY_SCOPES.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_impersonate_request(req: &ImpersonateRequest) -> Result<(), String> {
+    if req.reason.trim().is_empty() || req.reason.len() > 500 {
+        return Err("Impersonation reason must be between 1 and 500 characters".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_studio_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() || name.chars().count() > MAX_STUDIO_NAME_LENGTH {
+        return Err(format!("Studio name must be between 1 and {} characters", MAX_STUDIO_NAME_LENGTH));
+    }
+    Ok(())
+}
+
+fn validate_bio(bio: &str) -> Result<(), String> {
+    if bio.chars().count() > MAX_BIO_LENGTH {
+        return Err(format!("Bio must be at most {} characters", MAX_BIO_LENGTH));
+    }
+    Ok(())
+}
+
+fn validate_website(website: &str) -> Result<(), String> {
+    if website.len() > MAX_WEBSITE_LENGTH {
+        return Err(format!("Website must be at most {} characters", MAX_WEBSITE_LENGTH));
+    }
+    let rest = website
+        .strip_prefix("https://")
+        .or_else(|| website.strip_prefix("http://"))
+        .ok_or_else(|| "Website must start with http:// or https://".to_string())?;
+    if rest.is_empty() || rest.chars().any(char::is_whitespace) {
+        return Err("Website must be a valid URL".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_create_developer_profile_request(req: &CreateDeveloperProfileRequest) -> Result<(), String> {
+    validate_studio_name(&req.studio_name)?;
+    validate_bio(&req.bio)?;
+    if let Some(website) = &req.website {
+        validate_website(website)?;
+    }
+    Ok(())
+}
+
+/// An empty `website` clears it, so only non-empty values are checked.
+pub fn validate_update_developer_profile_request(req: &UpdateDeveloperProfileRequest) -> Result<(), String> {
+    if let Some(name) = &req.studio_name {
+        validate_studio_name(name)?;
+    }
+    if let Some(bio) = &req.bio {
+        validate_bio(bio)?;
+    }
+    if let Some(website) = req.website.as_deref().filter(|w| !w.is_empty()) {
+        validate_website(website)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An address of exactly `len` bytes.
+    fn email_of_length(len: usize) -> String {
+        let domain = "@example.com";
+        format!("{}{}", "a".repeat(len - domain.len()), domain)
+    }
+
+    #[test]
+    fn emails_are_limited_to_254_characters_by_default() {
+        let limits = FieldLimits::default();
+
+        assert_eq!(validate_email(&email_of_length(254), &limits), Ok(()));
+        assert!(validate_email(&email_of_length(255), &limits).is_err());
+    }
+
+    #[test]
+    fn the_email_limit_can_be_raised_to_the_column_size() {
+        let limits = FieldLimits::new(255, DEFAULT_MAX_USERNAME_LENGTH).unwrap();
+
+        assert_eq!(validate_email(&email_of_length(255), &limits), Ok(()));
+        assert!(validate_email(&email_of_length(256), &limits).is_err());
+    }
+
+    #[test]
+    fn limits_beyond_the_columns_are_refused() {
+        assert!(FieldLimits::new(256, DEFAULT_MAX_USERNAME_LENGTH).is_err());
+        assert!(FieldLimits::new(0, DEFAULT_MAX_USERNAME_LENGTH).is_err());
+        assert!(FieldLimits::new(DEFAULT_MAX_EMAIL_LENGTH, 101).is_err());
+        assert!(FieldLimits::new(DEFAULT_MAX_EMAIL_LENGTH, MIN_USERNAME_LENGTH - 1).is_err());
+        assert!(FieldLimits::new(DEFAULT_MAX_EMAIL_LENGTH, 100).is_ok());
+    }
+
+    #[test]
+    fn usernames_follow_the_configured_limit() {
+        let default = FieldLimits::default();
+        assert_eq!(validate_username(&"a".repeat(30), &default), Ok(()));
+        assert!(validate_username(&"a".repeat(31), &default).is_err());
+        assert!(validate_username("ab", &default).is_err());
+
+        let raised = FieldLimits::new(DEFAULT_MAX_EMAIL_LENGTH, 100).unwrap();
+        assert_eq!(validate_username(&"a".repeat(100), &raised), Ok(()));
+        assert!(validate_username(&"a".repeat(101), &raised).is_err());
+    }
+}